@@ -111,7 +111,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("4. Sort by: match count (DESC), then depth (ASC)");
     println!();
 
-    let search_results = graph.search_content("beatles abbey road", Some(10))?;
+    let search_results = graph.search_content("beatles abbey road", Some(10), None)?;
     let json_result: serde_json::Value = serde_json::from_str(&search_results)?;
     let bindings = json_result["results"]["bindings"].as_array().unwrap();
 
@@ -155,7 +155,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("2. Find content containing 'beatles' OR the exact phrase 'abbey road'");
     println!();
 
-    let quoted_search_results = graph.search_content(r#"beatles "abbey road""#, Some(10))?;
+    let quoted_search_results = graph.search_content(r#"beatles "abbey road""#, Some(10), None)?;
     let quoted_json_result: serde_json::Value = serde_json::from_str(&quoted_search_results)?;
     let quoted_bindings = quoted_json_result["results"]["bindings"]
         .as_array()
@@ -0,0 +1,64 @@
+use chrono::{DateTime, Duration, Utc};
+use std::sync::{Arc, Mutex};
+
+/// Source of the current time for timestamps written into pods.
+///
+/// The modified dates in the graph, the timestamp comments at the start of each scratchpad and
+/// the last refreshed times checked by the refresh TTL are taken from a `Clock`. Fork resolution picks the scratchpad with the newest timestamp
+/// comment, so swapping in a [`FakeClock`] makes that behavior deterministic in tests.
+/// Pointer and scratchpad counters always use the system time, because the network requires
+/// them to increase.
+pub trait Clock: Send + Sync {
+    /// The current time
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// Clock that reads the system time, used by default
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// Clock that only moves when told to, for tests.
+/// Clones share the same time, so a test can keep one clone and advance the time seen by the
+/// `Graph` and `PodManager` holding the others.
+#[derive(Debug, Clone)]
+pub struct FakeClock {
+    now: Arc<Mutex<DateTime<Utc>>>,
+}
+
+impl FakeClock {
+    /// Create a clock frozen at `now`
+    pub fn new(now: DateTime<Utc>) -> Self {
+        FakeClock {
+            now: Arc::new(Mutex::new(now)),
+        }
+    }
+
+    /// Jump to a specific time
+    pub fn set(&self, now: DateTime<Utc>) {
+        match self.now.lock() {
+            Ok(mut current) => *current = now,
+            Err(poisoned) => *poisoned.into_inner() = now,
+        }
+    }
+
+    /// Move the time forward by `duration`
+    pub fn advance(&self, duration: Duration) {
+        let now = self.now() + duration;
+        self.set(now);
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> DateTime<Utc> {
+        match self.now.lock() {
+            Ok(current) => *current,
+            Err(poisoned) => *poisoned.into_inner(),
+        }
+    }
+}
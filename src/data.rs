@@ -1,15 +1,19 @@
+use cocoon::Cocoon;
 use dirs;
 use serde;
 use serde_json;
-use std::fs::{File, create_dir_all, read_to_string, remove_file, write};
+use std::collections::{HashMap, HashSet};
+use std::fs::{
+    File, create_dir_all, read, read_dir, read_to_string, remove_file, symlink_metadata, write,
+};
 use std::io::Error as IoError;
-use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 use thiserror;
 use tracing::{error, info};
 
 // Import UpdateList from pod module
-use crate::pod::UpdateList;
+use crate::pod::{RemovalSection, UpdateList};
 
 // Error handling
 #[derive(Debug, thiserror::Error)]
@@ -42,11 +46,50 @@ impl serde::Serialize for Error {
     }
 }
 
+// Password for encrypting the local pointer and scratchpad files, never printed
+#[derive(Clone)]
+struct LocalEncryptionKey(Vec<u8>);
+
+impl std::fmt::Debug for LocalEncryptionKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("LocalEncryptionKey(..)")
+    }
+}
+
+/// Bytes used on disk by each part of the local store
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DiskUsage {
+    /// Local pointer files
+    pub pointers: u64,
+    /// Local scratchpad files holding the pod data
+    pub scratchpads: u64,
+    /// The oxigraph graph database directory
+    pub graph: u64,
+    /// The downloads directory
+    pub downloads: u64,
+    /// Sum of all of the above
+    pub total: u64,
+}
+
+/// Number of local pod files and the bytes they use
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct StorageStats {
+    /// Number of local pointer files
+    pub num_pointers: u64,
+    /// Number of local scratchpad files
+    pub num_scratchpads: u64,
+    /// Bytes used by the scratchpad files
+    pub total_scratchpad_bytes: u64,
+    /// Bytes used by the downloads directory
+    pub downloads_bytes: u64,
+}
+
 #[derive(Clone, Debug)]
 pub struct DataStore {
     data_dir: PathBuf,
     pods_dir: PathBuf,
     downloads_dir: PathBuf,
+    local_encryption_key: Option<LocalEncryptionKey>,
 }
 
 impl DataStore {
@@ -96,6 +139,7 @@ impl DataStore {
             data_dir,
             pods_dir,
             downloads_dir,
+            local_encryption_key: None,
         })
     }
 
@@ -122,6 +166,19 @@ impl DataStore {
         scratchpads_dir
     }
 
+    pub fn get_history_dir(&self, pod_address: &str) -> PathBuf {
+        let mut history_dir = self.pods_dir.clone();
+        history_dir.push("history");
+        history_dir.push(pod_address);
+        history_dir
+    }
+
+    pub fn get_checksums_dir(&self) -> PathBuf {
+        let mut checksums_dir = self.pods_dir.clone();
+        checksums_dir.push("checksums");
+        checksums_dir
+    }
+
     pub fn get_downloads_path(&self) -> PathBuf {
         self.downloads_dir.clone()
     }
@@ -148,6 +205,36 @@ impl DataStore {
         update_list_path
     }
 
+    pub fn get_refresh_times_path(&self) -> PathBuf {
+        let mut refresh_times_path = self.get_data_path();
+        refresh_times_path.push("refresh_times.json");
+        refresh_times_path
+    }
+
+    pub fn get_subject_versions_path(&self) -> PathBuf {
+        let mut subject_versions_path = self.get_data_path();
+        subject_versions_path.push("subject_versions.json");
+        subject_versions_path
+    }
+
+    pub fn get_removed_pods_path(&self) -> PathBuf {
+        let mut removed_pods_path = self.get_data_path();
+        removed_pods_path.push("removed_pods.json");
+        removed_pods_path
+    }
+
+    pub fn get_hidden_pods_path(&self) -> PathBuf {
+        let mut hidden_pods_path = self.get_data_path();
+        hidden_pods_path.push("hidden_pods.json");
+        hidden_pods_path
+    }
+
+    pub fn get_configuration_pending_path(&self) -> PathBuf {
+        let mut configuration_pending_path = self.get_data_path();
+        configuration_pending_path.push("configuration_pending");
+        configuration_pending_path
+    }
+
     pub fn set_active_wallet(&self, name: &str, address: &str) -> Result<(), Error> {
         let mut active_wallet_path = self.get_data_path();
         active_wallet_path.push("active_wallet.json");
@@ -235,8 +322,42 @@ impl DataStore {
         Ok(())
     }
 
+    /// Add a pod to the update list along with the exact set of scratchpads that should be uploaded for it.
+    /// Replaces any scratchpads previously recorded for the pod
+    pub fn append_update_list_with_scratchpads(
+        &self,
+        pod_address: &str,
+        scratchpads: &[String],
+    ) -> Result<(), Error> {
+        let mut update_list = self.read_update_list()?;
+
+        // Remove the pod and its scratchpads from the removal list (cross-removal behavior)
+        update_list
+            .remove
+            .pointers
+            .retain(|address| address != pod_address);
+        update_list
+            .remove
+            .scratchpads
+            .retain(|address| !scratchpads.contains(address));
+
+        info!(
+            "Recording {} scratchpads for pod {} in update list",
+            scratchpads.len(),
+            pod_address
+        );
+        update_list
+            .pods
+            .insert(pod_address.to_string(), scratchpads.to_vec());
+
+        self.write_update_list(&update_list)?;
+        Ok(())
+    }
+
     pub fn append_removal_list(&self, address: &str, address_type: &str) -> Result<(), Error> {
         let mut update_list = self.read_update_list()?;
+        // A removed key may be reused later, by then it is no longer known to be new
+        update_list.known_new.retain(|known| known != address);
 
         match address_type {
             "pointer" => {
@@ -292,6 +413,16 @@ impl DataStore {
         Ok(())
     }
 
+    /// Record a pointer or scratchpad address that is known not to exist on the network yet
+    pub fn mark_known_new(&self, address: &str) -> Result<(), Error> {
+        let mut update_list = self.read_update_list()?;
+        if !update_list.known_new.iter().any(|known| known == address) {
+            update_list.known_new.push(address.to_string());
+            self.write_update_list(&update_list)?;
+        }
+        Ok(())
+    }
+
     /// Add a scratchpad address to a pod's scratchpad list in the update list
     pub fn add_scratchpad_to_pod(
         &self,
@@ -329,6 +460,32 @@ impl DataStore {
         Ok(())
     }
 
+    /// Remove the given pod, pointer, and scratchpad addresses from the update list, keeping everything else
+    pub fn remove_from_update_list(&self, addresses: &HashSet<String>) -> Result<(), Error> {
+        let mut update_list = self.read_update_list()?;
+        update_list
+            .remove
+            .pointers
+            .retain(|address| !addresses.contains(address));
+        update_list
+            .remove
+            .scratchpads
+            .retain(|address| !addresses.contains(address));
+        update_list
+            .pods
+            .retain(|pod_address, _| !addresses.contains(pod_address));
+        update_list
+            .known_new
+            .retain(|address| !addresses.contains(address));
+        self.write_update_list(&update_list)?;
+        Ok(())
+    }
+
+    /// Replace the entire update list, for restoring a copy taken with `get_update_list`
+    pub fn replace_update_list(&self, update_list: &UpdateList) -> Result<(), Error> {
+        self.write_update_list(update_list)
+    }
+
     /// Clear the entire update list
     pub fn clear_update_list(&self) -> Result<(), Error> {
         let empty_list = UpdateList::default();
@@ -341,6 +498,261 @@ impl DataStore {
         self.read_update_list()
     }
 
+    /// Get the pointer and scratchpad addresses queued for removal from the network
+    pub fn get_pending_removals(&self) -> Result<RemovalSection, Error> {
+        Ok(self.read_update_list()?.remove)
+    }
+
+    /// Read the last refreshed times (Unix milliseconds) of all pods
+    fn read_refresh_times(&self) -> Result<HashMap<String, i64>, Error> {
+        let refresh_times_path = self.get_refresh_times_path();
+        if !refresh_times_path.exists() {
+            return Ok(HashMap::new());
+        }
+
+        let contents = read_to_string(&refresh_times_path)?;
+        match serde_json::from_str(&contents) {
+            Ok(refresh_times) => Ok(refresh_times),
+            Err(_) => {
+                info!("Failed to parse refresh times, starting with empty list");
+                Ok(HashMap::new())
+            }
+        }
+    }
+
+    /// Get the time a pod was last refreshed from the network in Unix milliseconds
+    pub fn get_last_refreshed(&self, pod_address: &str) -> Result<Option<i64>, Error> {
+        Ok(self.read_refresh_times()?.get(pod_address).copied())
+    }
+
+    /// Record that a pod was refreshed from the network at `now`
+    pub fn update_last_refreshed(
+        &self,
+        pod_address: &str,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), Error> {
+        let mut refresh_times = self.read_refresh_times()?;
+        refresh_times.insert(pod_address.to_string(), now.timestamp_millis());
+        write(
+            self.get_refresh_times_path(),
+            serde_json::to_string_pretty(&refresh_times)?,
+        )?;
+        Ok(())
+    }
+
+    /// Check if a pod was refreshed from the network less than `ttl` before `now`
+    pub fn refreshed_within(
+        &self,
+        pod_address: &str,
+        ttl: Duration,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<bool, Error> {
+        let Some(last_refreshed) = self.get_last_refreshed(pod_address)? else {
+            return Ok(false);
+        };
+        let elapsed = now.timestamp_millis() - last_refreshed;
+        Ok(elapsed >= 0 && (elapsed as u128) < ttl.as_millis())
+    }
+
+    /// Read the pod version (pointer counter) in which each subject of each pod last changed
+    fn read_subject_versions(&self) -> Result<HashMap<String, HashMap<String, u64>>, Error> {
+        let subject_versions_path = self.get_subject_versions_path();
+        if !subject_versions_path.exists() {
+            return Ok(HashMap::new());
+        }
+
+        let contents = read_to_string(&subject_versions_path)?;
+        match serde_json::from_str(&contents) {
+            Ok(subject_versions) => Ok(subject_versions),
+            Err(_) => {
+                info!("Failed to parse subject versions, starting with empty list");
+                Ok(HashMap::new())
+            }
+        }
+    }
+
+    /// Record that the given subjects of a pod were added, modified or removed in `version`
+    pub fn record_subject_changes(
+        &self,
+        pod_address: &str,
+        version: u64,
+        subjects: &[String],
+    ) -> Result<(), Error> {
+        if subjects.is_empty() {
+            return Ok(());
+        }
+        let mut subject_versions = self.read_subject_versions()?;
+        let pod_versions = subject_versions.entry(pod_address.to_string()).or_default();
+        for subject in subjects {
+            pod_versions.insert(subject.clone(), version);
+        }
+        write(
+            self.get_subject_versions_path(),
+            serde_json::to_string_pretty(&subject_versions)?,
+        )?;
+        Ok(())
+    }
+
+    /// Get the subjects of a pod that changed in a version newer than `since_version`, sorted
+    pub fn get_subjects_changed_since(
+        &self,
+        pod_address: &str,
+        since_version: u64,
+    ) -> Result<Vec<String>, Error> {
+        let subject_versions = self.read_subject_versions()?;
+        let mut subjects: Vec<String> = subject_versions
+            .get(pod_address)
+            .map(|pod_versions| {
+                pod_versions
+                    .iter()
+                    .filter(|(_, version)| **version > since_version)
+                    .map(|(subject, _)| subject.clone())
+                    .collect()
+            })
+            .unwrap_or_default();
+        subjects.sort();
+        Ok(subjects)
+    }
+
+    /// Read the scratchpads each removed pod held when it was removed
+    fn read_removed_pods(&self) -> Result<HashMap<String, Vec<String>>, Error> {
+        let removed_pods_path = self.get_removed_pods_path();
+        if !removed_pods_path.exists() {
+            return Ok(HashMap::new());
+        }
+
+        let contents = read_to_string(&removed_pods_path)?;
+        match serde_json::from_str(&contents) {
+            Ok(removed_pods) => Ok(removed_pods),
+            Err(_) => {
+                info!("Failed to parse removed pods, starting with empty list");
+                Ok(HashMap::new())
+            }
+        }
+    }
+
+    /// Record the scratchpads a pod held when it was removed, so the removal can be verified later
+    pub fn record_removed_pod(
+        &self,
+        pod_address: &str,
+        scratchpads: &[String],
+    ) -> Result<(), Error> {
+        let mut removed_pods = self.read_removed_pods()?;
+        removed_pods.insert(pod_address.to_string(), scratchpads.to_vec());
+        write(
+            self.get_removed_pods_path(),
+            serde_json::to_string_pretty(&removed_pods)?,
+        )?;
+        Ok(())
+    }
+
+    /// Get the scratchpads a removed pod held, or `None` if the removal was not recorded
+    pub fn get_removed_pod_scratchpads(
+        &self,
+        pod_address: &str,
+    ) -> Result<Option<Vec<String>>, Error> {
+        Ok(self.read_removed_pods()?.get(pod_address).cloned())
+    }
+
+    /// Get the pods hidden from browse and search, sorted by address
+    pub fn get_hidden_pods(&self) -> Result<Vec<String>, Error> {
+        let hidden_pods_path = self.get_hidden_pods_path();
+        if !hidden_pods_path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let contents = read_to_string(&hidden_pods_path)?;
+        match serde_json::from_str(&contents) {
+            Ok(hidden_pods) => Ok(hidden_pods),
+            Err(_) => {
+                info!("Failed to parse hidden pods, starting with empty list");
+                Ok(Vec::new())
+            }
+        }
+    }
+
+    /// Hide a pod from browse and search, or show it again. Only stored locally
+    pub fn set_pod_hidden(&self, pod_address: &str, hidden: bool) -> Result<(), Error> {
+        let mut hidden_pods = self.get_hidden_pods()?;
+        hidden_pods.retain(|address| address != pod_address);
+        if hidden {
+            hidden_pods.push(pod_address.to_string());
+            hidden_pods.sort();
+        }
+        write(
+            self.get_hidden_pods_path(),
+            serde_json::to_string_pretty(&hidden_pods)?,
+        )?;
+        Ok(())
+    }
+
+    /// Mark the configuration pod as changed in the graph but not yet written to its scratchpads
+    pub fn set_configuration_pending(&self, pending: bool) -> Result<(), Error> {
+        let configuration_pending_path = self.get_configuration_pending_path();
+        if pending {
+            write(configuration_pending_path, "")?;
+        } else if configuration_pending_path.exists() {
+            remove_file(configuration_pending_path)?;
+        }
+        Ok(())
+    }
+
+    /// Check if the configuration pod has changes that still need to be written to its scratchpads
+    pub fn configuration_pending(&self) -> bool {
+        self.get_configuration_pending_path().exists()
+    }
+
+    /// Encrypt pointer and scratchpad files on disk with the given password, or store them as plaintext if `None`.
+    /// This only protects the local cache, data uploaded to the network is not affected.
+    pub fn set_local_encryption(&mut self, key: Option<&str>) {
+        self.local_encryption_key = key.map(|key| LocalEncryptionKey(key.as_bytes().to_vec()));
+    }
+
+    pub fn local_encryption_enabled(&self) -> bool {
+        self.local_encryption_key.is_some()
+    }
+
+    /// Read a pointer or scratchpad file, decrypting it if local encryption is enabled
+    fn read_local_file(&self, path: &Path) -> Result<String, Error> {
+        let Some(LocalEncryptionKey(key)) = &self.local_encryption_key else {
+            return Ok(read_to_string(path)?);
+        };
+
+        let contents = read(path)?;
+        let cocoon = Cocoon::new(key);
+        match cocoon.unwrap(&contents) {
+            Ok(decrypted) => String::from_utf8(decrypted)
+                .map_err(|e| Error::Io(std::io::Error::new(std::io::ErrorKind::InvalidData, e))),
+            // Files written before encryption was enabled are still plaintext,
+            // they get encrypted the next time they are written
+            Err(_) => String::from_utf8(contents).map_err(|_| {
+                Error::Io(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("Failed to decrypt local file {path:?}"),
+                ))
+            }),
+        }
+    }
+
+    /// Write a pointer or scratchpad file, encrypting it if local encryption is enabled.
+    /// Every file gets its own salt and nonce from the cocoon container header
+    fn write_local_file(&self, path: &Path, contents: &str) -> Result<(), Error> {
+        let Some(LocalEncryptionKey(key)) = &self.local_encryption_key else {
+            write(path, contents.as_bytes())?;
+            return Ok(());
+        };
+
+        let mut cocoon = Cocoon::new(key);
+        let encrypted = cocoon.wrap(contents.as_bytes()).map_err(|e| {
+            Error::Io(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Failed to encrypt local file {path:?}: {e:?}"),
+            ))
+        })?;
+        write(path, encrypted)?;
+        Ok(())
+    }
+
     pub fn update_pointer_target(
         &self,
         pointer_address: &str,
@@ -352,16 +764,18 @@ impl DataStore {
 
         let mut contents = String::new();
         if pointer_path.exists() {
-            contents = read_to_string(&pointer_path)?;
+            contents = self.read_local_file(&pointer_path)?;
         }
 
         let mut lines = contents.lines();
         let _ = lines.next(); // Skip the first line
         let second_line = lines.next().unwrap_or("0").to_string(); // Get the second line or default to an empty string
 
-        let mut pointer_file = File::create(&pointer_path)?; // Overwrite the file
-        writeln!(pointer_file, "{scratchpad_address}")?; // Write the new first line
-        writeln!(pointer_file, "{second_line}")?; // Write the second line back
+        // Overwrite the file with the new first line and the second line written back
+        self.write_local_file(
+            &pointer_path,
+            &format!("{scratchpad_address}\n{second_line}\n"),
+        )?;
 
         Ok(())
     }
@@ -373,15 +787,14 @@ impl DataStore {
 
         let mut contents = String::new();
         if pointer_path.exists() {
-            contents = read_to_string(&pointer_path)?;
+            contents = self.read_local_file(&pointer_path)?;
         }
 
         let mut lines = contents.lines();
         let first_line = lines.next().unwrap_or("").to_string(); // Get the first line or default to an empty string
 
-        let mut pointer_file = File::create(&pointer_path)?; // Overwrite the file
-        writeln!(pointer_file, "{first_line}")?; // Write the first line back
-        writeln!(pointer_file, "{count}")?; // Write the new second line
+        // Overwrite the file with the first line written back and the new second line
+        self.write_local_file(&pointer_path, &format!("{first_line}\n{count}\n"))?;
 
         Ok(())
     }
@@ -389,14 +802,14 @@ impl DataStore {
     pub fn get_pointer_target(&self, pointer_address: &str) -> Result<String, Error> {
         let mut pointer_path = self.get_pointers_dir();
         pointer_path.push(pointer_address);
-        let data = read_to_string(pointer_path)?;
+        let data = self.read_local_file(&pointer_path)?;
         let target = data.lines().next().unwrap_or("").to_string(); // Get the first line or an empty string
         Ok(target)
     }
     pub fn get_pointer_count(&self, pointer_address: &str) -> Result<u64, Error> {
         let mut pointer_path = self.get_pointers_dir();
         pointer_path.push(pointer_address);
-        let data = read_to_string(pointer_path)?;
+        let data = self.read_local_file(&pointer_path)?;
         // get the second line of the file
         let count_line = data.lines().nth(1).unwrap_or("0");
         let count: u64 = count_line.parse().unwrap_or(0);
@@ -406,17 +819,59 @@ impl DataStore {
     pub fn get_scratchpad_data(&self, address: &str) -> Result<String, Error> {
         let mut scratchpad_path = self.get_scratchpads_dir();
         scratchpad_path.push(address);
-        let data = read_to_string(scratchpad_path)?;
+        let data = self.read_local_file(&scratchpad_path)?;
         Ok(data)
     }
 
     pub fn update_scratchpad_data(&self, address: &str, data: &str) -> Result<(), Error> {
         let mut scratchpad_path = self.get_scratchpads_dir();
         scratchpad_path.push(address);
-        write(scratchpad_path, data.as_bytes())?;
+        self.write_local_file(&scratchpad_path, data)?;
+
+        // Record the checksum of the file as written, to detect local corruption later
+        let checksums_dir = self.get_checksums_dir();
+        create_dir_all(&checksums_dir)?;
+        write(
+            self.get_checksum_path(address),
+            Self::file_checksum(&scratchpad_path)?,
+        )?;
         Ok(())
     }
 
+    /// Check a scratchpad file against the checksum recorded when it was last written.
+    /// Files written before checksums were recorded have nothing to compare against and pass,
+    /// a file that is missing while its checksum is still recorded fails
+    pub fn verify_scratchpad(&self, address: &str) -> Result<bool, Error> {
+        let checksum_path = self.get_checksum_path(address);
+        if !checksum_path.exists() {
+            return Ok(true);
+        }
+        let mut scratchpad_path = self.get_scratchpads_dir();
+        scratchpad_path.push(address);
+        if !scratchpad_path.exists() {
+            return Ok(false);
+        }
+
+        let expected = read_to_string(&checksum_path)?;
+        let valid = Self::file_checksum(&scratchpad_path)? == expected.trim();
+        if !valid {
+            error!("Scratchpad file {} does not match its checksum", address);
+        }
+        Ok(valid)
+    }
+
+    fn get_checksum_path(&self, address: &str) -> PathBuf {
+        let mut checksum_path = self.get_checksums_dir();
+        checksum_path.push(format!("{address}.sha3"));
+        checksum_path
+    }
+
+    /// Hex encoded SHA3-256 digest of a file's bytes on disk
+    fn file_checksum(path: &Path) -> Result<String, Error> {
+        use sha3::{Digest, Sha3_256};
+        Ok(hex::encode(Sha3_256::digest(read(path)?)))
+    }
+
     pub fn create_pointer_file(&self, address: &str) -> Result<(), Error> {
         let mut pointer_path = self.get_pointers_dir();
         pointer_path.push(address);
@@ -454,6 +909,10 @@ impl DataStore {
             remove_file(&scratchpad_path)?;
             info!("Removed scratchpad file: {:?}", scratchpad_path);
         }
+        let checksum_path = self.get_checksum_path(address);
+        if checksum_path.exists() {
+            remove_file(&checksum_path)?;
+        }
         Ok(())
     }
 
@@ -468,6 +927,172 @@ impl DataStore {
         Ok(false) // Address is not a directory (pointer)
     }
 
+    /// List the addresses of all local pointer files, sorted
+    pub fn list_pointer_addresses(&self) -> Result<Vec<String>, Error> {
+        Self::list_dir_entries(&self.get_pointers_dir())
+    }
+
+    /// List the addresses of all local scratchpad files, sorted
+    pub fn list_scratchpad_files(&self) -> Result<Vec<String>, Error> {
+        Self::list_dir_entries(&self.get_scratchpads_dir())
+    }
+
+    /// Find pointer and scratchpad files whose address is not in the set of known addresses
+    pub fn find_orphan_files(
+        &self,
+        known_addresses: &HashSet<String>,
+    ) -> Result<Vec<PathBuf>, Error> {
+        let mut orphans = Vec::new();
+        for dir in [self.get_pointers_dir(), self.get_scratchpads_dir()] {
+            for address in Self::list_dir_entries(&dir)? {
+                if !known_addresses.contains(&address) {
+                    let path = dir.join(address);
+                    info!("Found orphaned file: {:?}", path);
+                    orphans.push(path);
+                }
+            }
+        }
+        Ok(orphans)
+    }
+
+    /// Save a snapshot of a pod's TriG data, keyed by the pod pointer count it was taken at
+    pub fn save_pod_version(
+        &self,
+        pod_address: &str,
+        version: u64,
+        data: &str,
+    ) -> Result<(), Error> {
+        let mut version_path = self.get_history_dir(pod_address);
+        create_dir_all(&version_path)?;
+        version_path.push(format!("{version}.trig"));
+        self.write_local_file(&version_path, data)?;
+        info!("Saved version {} of pod {}", version, pod_address);
+        Ok(())
+    }
+
+    /// List the saved versions of a pod, oldest first
+    pub fn list_pod_versions(&self, pod_address: &str) -> Result<Vec<u64>, Error> {
+        let history_dir = self.get_history_dir(pod_address);
+        if !history_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut versions = Vec::new();
+        for entry in read_dir(&history_dir)? {
+            let path = entry?.path();
+            if !path.is_file() || path.extension().and_then(|ext| ext.to_str()) != Some("trig") {
+                continue;
+            }
+            if let Some(version) = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| stem.parse::<u64>().ok())
+            {
+                versions.push(version);
+            }
+        }
+        versions.sort_unstable();
+        Ok(versions)
+    }
+
+    /// Read a saved version of a pod
+    pub fn get_pod_version(&self, pod_address: &str, version: u64) -> Result<String, Error> {
+        let mut version_path = self.get_history_dir(pod_address);
+        version_path.push(format!("{version}.trig"));
+        self.read_local_file(&version_path)
+    }
+
+    /// Delete all but the newest `keep` versions of a pod, returning the versions removed
+    pub fn prune_pod_versions(&self, pod_address: &str, keep: usize) -> Result<Vec<u64>, Error> {
+        let versions = self.list_pod_versions(pod_address)?;
+        let excess = versions.len().saturating_sub(keep);
+        let mut removed = Vec::new();
+        for version in versions.into_iter().take(excess) {
+            let mut version_path = self.get_history_dir(pod_address);
+            version_path.push(format!("{version}.trig"));
+            remove_file(&version_path)?;
+            info!("Pruned version {} of pod {}", version, pod_address);
+            removed.push(version);
+        }
+        Ok(removed)
+    }
+
+    /// Sum the size of the pointer files, scratchpad files, graph database and downloads directory
+    pub fn disk_usage(&self) -> Result<DiskUsage, Error> {
+        let mut usage = DiskUsage {
+            pointers: Self::path_size(&self.get_pointers_dir())?,
+            scratchpads: Self::path_size(&self.get_scratchpads_dir())?,
+            graph: Self::path_size(&self.get_graph_path())?,
+            downloads: Self::path_size(&self.get_downloads_path())?,
+            total: 0,
+        };
+        usage.total = usage.pointers + usage.scratchpads + usage.graph + usage.downloads;
+        info!("Local storage usage: {:?}", usage);
+        Ok(usage)
+    }
+
+    /// Count the pointer and scratchpad files and sum the scratchpad and downloads sizes
+    pub fn storage_stats(&self) -> Result<StorageStats, Error> {
+        let stats = StorageStats {
+            num_pointers: Self::file_count(&self.get_pointers_dir())?,
+            num_scratchpads: Self::file_count(&self.get_scratchpads_dir())?,
+            total_scratchpad_bytes: Self::path_size(&self.get_scratchpads_dir())?,
+            downloads_bytes: Self::path_size(&self.get_downloads_path())?,
+        };
+        info!("Local storage stats: {:?}", stats);
+        Ok(stats)
+    }
+
+    /// Names of the files directly inside a directory, sorted
+    fn list_dir_entries(dir: &Path) -> Result<Vec<String>, Error> {
+        let mut names = Vec::new();
+        for entry in read_dir(dir)? {
+            let path = entry?.path();
+            if !path.is_file() {
+                continue;
+            }
+            if let Some(name) = path.file_name().and_then(|name| name.to_str()) {
+                names.push(name.to_string());
+            }
+        }
+        names.sort();
+        Ok(names)
+    }
+
+    /// Number of files directly inside a directory, 0 if the directory doesn't exist
+    fn file_count(dir: &Path) -> Result<u64, Error> {
+        let entries = match read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(e.into()),
+        };
+        let mut count = 0;
+        for entry in entries {
+            if entry?.file_type()?.is_file() {
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+
+    /// Total size in bytes of a file or everything below a directory, without following symlinks
+    fn path_size(path: &Path) -> Result<u64, Error> {
+        let metadata = match symlink_metadata(path) {
+            Ok(metadata) => metadata,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(e.into()),
+        };
+        if !metadata.is_dir() {
+            return Ok(metadata.len());
+        }
+
+        let mut size = 0;
+        for entry in read_dir(path)? {
+            size += Self::path_size(&entry?.path())?;
+        }
+        Ok(size)
+    }
+
     pub fn address_is_scratchpad(&self, address: &str) -> Result<bool, Error> {
         let mut pod_path = self.get_scratchpads_dir();
         pod_path.push(address); // Append the address to the base directory path
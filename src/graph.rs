@@ -1,6 +1,7 @@
 use alloc::string::FromUtf8Error;
-use chrono::Utc;
-use oxigraph::io::{RdfFormat, RdfParseError, RdfParser};
+use oxigraph::io::{RdfParseError, RdfParser, RdfSerializer};
+// Re-exported so callers of construct_query can pick a format without depending on oxigraph
+pub use oxigraph::io::RdfFormat;
 use oxigraph::model::{
     GraphNameRef, IriParseError, LiteralRef, NamedNodeRef, Quad, QuadRef, TermRef,
 };
@@ -10,12 +11,15 @@ use oxigraph::store::{LoaderError, SerializerError, StorageError, Store};
 use oxjsonld::{JsonLdProfile, JsonLdProfileSet};
 use oxttl::TriGParser;
 use serde;
-use serde_json::Error as SerdeError;
-use std::collections::HashMap;
+use serde_json::{Error as SerdeError, Value};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use std::io::Cursor;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use thiserror;
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
+
+use crate::clock::{Clock, SystemClock};
 
 //////////////////////////////////////////////
 // Vocabulary
@@ -74,6 +78,24 @@ pub const HAS_CREATION_DATE: &str = PREDICATE!("creation");
 /// Object is a literal representing the date
 pub const HAS_MODIFIED_DATE: &str = PREDICATE!("modified");
 
+/// Tag
+/// A free-form label used to classify a subject
+/// A subject can have any number of tags
+/// Object is a string literal
+pub const HAS_TAG: &str = PREDICATE!("tag");
+
+/// Icon
+/// An image representing the pod, shown as its avatar in pod listings
+/// Only valid for POD address types
+/// Object is the IRI of the Autonomi address holding the image
+pub const HAS_ICON: &str = PREDICATE!("icon");
+
+/// References
+/// A pod references another pod
+/// Only used in exported reference graphs, never stored in pod data
+/// Object is the IRI of the referenced pod
+pub const REFERENCES: &str = PREDICATE!("references");
+
 //////////////////////////////////////////////
 // Objects
 //////////////////////////////////////////////
@@ -87,6 +109,40 @@ pub const FREED_POD: &str = OBJECT!("free_pod"); //Unused pod pointer or scratch
 pub const FREED_DATA: &str = OBJECT!("free_data"); //Unused pod pointer or scratchpad
 pub const ABANDONED: &str = OBJECT!("abandoned"); //Abandoned pod pointer or scratchpad address, never use again
 
+/// Depth that search results from pods with no known depth are ranked at, after every real depth
+pub const UNKNOWN_DEPTH_RANK: u64 = 999999;
+
+// Lowercase accented letters and the plain letters they fold to in fuzzy text search
+const DIACRITIC_FOLDS: &[(&str, &str)] = &[
+    ("àáâãäåāăą", "a"),
+    ("æ", "ae"),
+    ("çćĉċč", "c"),
+    ("ďđ", "d"),
+    ("èéêëēĕėęě", "e"),
+    ("ĝğġģ", "g"),
+    ("ĥħ", "h"),
+    ("ìíîïĩīĭįı", "i"),
+    ("ĵ", "j"),
+    ("ķ", "k"),
+    ("ĺļľŀł", "l"),
+    ("ñńņňŉ", "n"),
+    ("òóôõöøōŏő", "o"),
+    ("œ", "oe"),
+    ("ŕŗř", "r"),
+    ("śŝşš", "s"),
+    ("ß", "ss"),
+    ("ţťŧ", "t"),
+    ("ùúûüũūŭůűų", "u"),
+    ("ŵ", "w"),
+    ("ýÿŷ", "y"),
+    ("źżž", "z"),
+];
+
+/// On-disk format version of the graph database written by this version of colonylib.
+/// Bump this when an oxigraph upgrade or a change in how pods are stored makes existing
+/// databases unreadable, so old databases are rejected with a clear error
+pub const STORE_FORMAT_VERSION: u32 = 1;
+
 // Error handling
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -106,6 +162,16 @@ pub enum Error {
     Loader(#[from] LoaderError),
     #[error(transparent)]
     RdfParse(#[from] RdfParseError),
+    #[error("Data written to the default graph: {0}")]
+    DefaultGraph(String),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("Incompatible graph database: {0}")]
+    StoreVersion(String),
+    #[error(transparent)]
+    Regex(#[from] regex::Error),
+    #[error("{0}")]
+    InvalidInput(String),
 }
 
 #[derive(serde::Serialize)]
@@ -120,6 +186,11 @@ pub enum ErrorKind {
     FromUtf8(String),
     Loader(String),
     RdfParse(String),
+    DefaultGraph(String),
+    Io(String),
+    StoreVersion(String),
+    Regex(String),
+    InvalidInput(String),
 }
 
 impl serde::Serialize for Error {
@@ -137,21 +208,276 @@ impl serde::Serialize for Error {
             Self::FromUtf8(_) => ErrorKind::FromUtf8(error_message),
             Self::Loader(_) => ErrorKind::Loader(error_message),
             Self::RdfParse(_) => ErrorKind::RdfParse(error_message),
+            Self::DefaultGraph(_) => ErrorKind::DefaultGraph(error_message),
+            Self::Io(_) => ErrorKind::Io(error_message),
+            Self::StoreVersion(_) => ErrorKind::StoreVersion(error_message),
+            Self::Regex(_) => ErrorKind::Regex(error_message),
+            Self::InvalidInput(_) => ErrorKind::InvalidInput(error_message),
         };
         error_kind.serialize(serializer)
     }
 }
 
+/// Preview of what a JSON-LD document would add to a pod
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+pub struct ValidationResult {
+    /// True if the document parsed without any errors
+    pub valid: bool,
+    /// Number of triples the document produces
+    pub triple_count: usize,
+    /// Distinct rdf:type IRIs found in the document
+    pub types: Vec<String>,
+    /// Parse errors and warnings encountered
+    pub warnings: Vec<String>,
+}
+
+/// Summary of a pod for listings
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PodInfo {
+    /// Address of the pod pointer
+    pub address: String,
+    /// Name of the pod, if it has one
+    pub name: Option<String>,
+    /// Creation date in RFC 3339 format, if recorded
+    pub created: Option<String>,
+    /// Last modified date in RFC 3339 format, if recorded
+    pub modified: Option<String>,
+    /// Depth of the pod in the reference tree, 0 for the user's own pods
+    pub depth: Option<u64>,
+    /// Autonomi address of the pod's icon image, if it has one
+    pub icon: Option<String>,
+    /// Number of scratchpads holding the pod data, `None` in listings. Only counted by
+    /// [`Graph::get_pod_info`]
+    pub scratchpad_count: Option<usize>,
+    /// Number of subjects described in the pod, not counting the pod itself and its
+    /// scratchpads, `None` in listings. Only counted by [`Graph::get_pod_info`]
+    pub subject_count: Option<usize>,
+}
+
+/// A pod whose stored depth differs from its shortest reference path from the user's own pods
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DepthAnomaly {
+    /// Address of the pod pointer
+    pub pod_address: String,
+    /// Depth recorded in the configuration graph, `None` if the pod has no depth
+    pub stored_depth: Option<u64>,
+    /// Length of the shortest reference path from a local pod, `None` if no local pod leads to it
+    pub computed_depth: Option<u64>,
+}
+
+/// Direction of the pod references between two pods
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub enum Relationship {
+    /// Neither pod references the other
+    None,
+    /// The first pod references the second
+    AtoB,
+    /// The second pod references the first
+    BtoA,
+    /// Both pods reference each other
+    Mutual,
+}
+
+/// How the text of a content search is matched against literal values
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum TextMatchMode {
+    /// Each word or quoted phrase is matched as a case-insensitive substring
+    #[default]
+    Contains,
+    /// The whole search text is a case-insensitive regular expression
+    Regex,
+    /// Like `Contains`, but accented letters also match their unaccented form
+    Fuzzy,
+}
+
+/// Which pods a query covers
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum SearchScope {
+    /// Every pod in the graph, local and referenced
+    All,
+    /// Only the user's own pods (depth 0)
+    Local,
+    /// A single pod, given by its address
+    Pod(String),
+}
+
+/// One value of a predicate in a [`SubjectView`] and the pods that supply it
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SubjectValue {
+    /// The object, an IRI or the lexical form of a literal
+    pub value: String,
+    /// Addresses of the pods that state this value, sorted
+    pub pods: Vec<String>,
+}
+
+/// All values of one predicate of a subject, merged across pods
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SubjectProperty {
+    /// The predicate IRI
+    pub predicate: String,
+    /// Every value of the predicate, sorted
+    pub values: Vec<SubjectValue>,
+    /// True if the pods that state this predicate do not all give it the same values
+    pub conflict: bool,
+}
+
+/// Merged view of a subject across the user's own pods, with the disagreements between pods
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SubjectView {
+    /// Address of the subject
+    pub subject: String,
+    /// Addresses of the local pods that describe the subject, sorted
+    pub pods: Vec<String>,
+    /// The subject's predicates, sorted by predicate IRI
+    pub properties: Vec<SubjectProperty>,
+}
+
+impl SubjectView {
+    /// The properties whose values differ between pods
+    pub fn conflicts(&self) -> impl Iterator<Item = &SubjectProperty> {
+        self.properties.iter().filter(|property| property.conflict)
+    }
+}
+
 #[derive(Clone)]
 pub struct Graph {
     store: Store,
+    hidden_configuration: Option<String>,
+    hidden_pods: Vec<String>,
+    clock: Arc<dyn Clock>,
 }
 
 impl Graph {
     pub fn open(db: &PathBuf) -> Result<Self, Error> {
+        // Check the format version before touching the store, an incompatible store
+        // would otherwise fail with a cryptic storage error or be misread
+        let existing_store = db.exists();
+        let version_path = Self::store_version_path(db);
+        match Self::read_store_format_version(db)? {
+            Some(version) if version == STORE_FORMAT_VERSION => {}
+            Some(version) if version > STORE_FORMAT_VERSION => {
+                return Err(Error::StoreVersion(format!(
+                    "{db:?} uses format version {version} but this version of colonylib only supports version {STORE_FORMAT_VERSION}. \
+                     Upgrade colonylib, or remove the graph database and refresh the cache to rebuild it"
+                )));
+            }
+            Some(version) => {
+                return Err(Error::StoreVersion(format!(
+                    "{db:?} uses format version {version} but this version of colonylib requires version {STORE_FORMAT_VERSION}. \
+                     Remove the graph database and refresh the cache to rebuild it from the network"
+                )));
+            }
+            None => {
+                if existing_store {
+                    // Stores created before the version marker existed use the first format
+                    info!("No format version found for graph store at {:?}", db);
+                }
+            }
+        }
+
         let store = Store::open(db)?;
+        if !version_path.exists() {
+            std::fs::write(&version_path, STORE_FORMAT_VERSION.to_string())?;
+            debug!("Wrote graph store format version to {:?}", version_path);
+        }
         info!("Opened graph store at {:?}", db);
-        Ok(Graph { store })
+        Ok(Graph {
+            store,
+            hidden_configuration: None,
+            hidden_pods: Vec::new(),
+            clock: Arc::new(SystemClock),
+        })
+    }
+
+    // Create a read-only handle to this graph that can be moved into other tasks or threads.
+    // The handle shares the underlying store, so it sees writes made through this Graph
+    pub fn reader(&self) -> GraphReader {
+        GraphReader {
+            graph: self.clone(),
+        }
+    }
+
+    // The format version this version of colonylib reads and writes
+    pub fn store_format_version() -> u32 {
+        STORE_FORMAT_VERSION
+    }
+
+    // Read the format version recorded for a graph database, None if there is no version marker
+    pub fn read_store_format_version(db: &Path) -> Result<Option<u32>, Error> {
+        let version_path = Self::store_version_path(db);
+        if !version_path.exists() {
+            return Ok(None);
+        }
+        let contents = std::fs::read_to_string(&version_path)?;
+        match contents.trim().parse::<u32>() {
+            Ok(version) => Ok(Some(version)),
+            Err(_) => Err(Error::StoreVersion(format!(
+                "unreadable format version {:?} in {version_path:?}",
+                contents.trim()
+            ))),
+        }
+    }
+
+    // The version marker sits next to the graph database directory
+    fn store_version_path(db: &Path) -> PathBuf {
+        let mut version_path = db.as_os_str().to_owned();
+        version_path.push(".version");
+        PathBuf::from(version_path)
+    }
+
+    // Replace the clock used for creation and modified dates
+    pub fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+        self.clock = clock;
+    }
+
+    // Get the clock used for creation and modified dates
+    pub fn clock(&self) -> Arc<dyn Clock> {
+        self.clock.clone()
+    }
+
+    // Hide the configuration pod from get_my_pods, browse and search results,
+    // or show it again by passing None
+    pub fn set_hidden_configuration(&mut self, configuration_address: Option<&str>) {
+        self.hidden_configuration = configuration_address.map(|address| format!("ant://{address}"));
+    }
+
+    // Whether a configuration pod is currently hidden
+    pub fn configuration_is_hidden(&self) -> bool {
+        self.hidden_configuration.is_some()
+    }
+
+    // Filter out the hidden configuration pod from a graph or subject variable
+    fn hidden_configuration_clause(&self, var: &str) -> String {
+        match &self.hidden_configuration {
+            Some(configuration_iri) => format!("FILTER(?{var} != <{configuration_iri}>)"),
+            None => String::new(),
+        }
+    }
+
+    // Hide pods from browse, search and pod listings, replacing any previously hidden pods.
+    // Pass an empty list to show every pod again
+    pub fn set_hidden_pods(&mut self, pod_addresses: &[String]) {
+        self.hidden_pods = pod_addresses
+            .iter()
+            .map(|address| format!("ant://{address}"))
+            .collect();
+    }
+
+    // Filter out the hidden configuration pod and the hidden pods from a graph or pod variable
+    fn hidden_pods_clause(&self, var: &str) -> String {
+        let mut clause = self.hidden_configuration_clause(var);
+        if !self.hidden_pods.is_empty() {
+            let hidden_pods: Vec<String> = self
+                .hidden_pods
+                .iter()
+                .map(|iri| format!("<{iri}>"))
+                .collect();
+            clause.push_str(&format!(
+                " FILTER(?{var} NOT IN ({}))",
+                hidden_pods.join(", ")
+            ));
+        }
+        clause
     }
 
     pub fn put_quad(
@@ -214,7 +540,7 @@ impl Graph {
         let scratchpad_iri = scratchpad_iri.as_str();
         let configuration_scratchpad_iri = format!("ant://{configuration_scratchpad_address}");
         let configuration_scratchpad_iri = configuration_scratchpad_iri.as_str();
-        let date = Utc::now().to_rfc3339();
+        let date = self.clock.now().to_rfc3339();
         let date = date.as_str();
         // Pod metadata
         let _quad = self.put_quad(pod_iri, HAS_ADDR_TYPE, POD, Some(configuration_iri))?;
@@ -365,6 +691,174 @@ impl Graph {
         Ok(configuration)
     }
 
+    // Move a pod to a new pointer and scratchpad address. The pod data is copied into a new graph
+    // with the pod IRI rewritten, the old scratchpad bookkeeping is replaced by the new scratchpad,
+    // and the user's pods referencing the old address are pointed at the new one. The old pointer
+    // and scratchpads are marked ABANDONED in the configuration graph so they are never reused.
+    // Returns the new pod graph and the configuration graph in TriG format, and the user's pods
+    // whose references were rewritten
+    #[allow(clippy::type_complexity)]
+    pub fn rotate_pod_entry(
+        &mut self,
+        pod_address: &str,
+        pod_scratchpads: &[String],
+        new_pod_address: &str,
+        new_scratchpad_address: &str,
+        configuration_address: &str,
+        num_keys: u64,
+    ) -> Result<(Vec<u8>, Vec<u8>, Vec<String>), Error> {
+        let pod_iri = format!("ant://{pod_address}");
+        let pod_iri = pod_iri.as_str();
+        let pod = NamedNodeRef::new(pod_iri)?;
+        let new_pod_iri = format!("ant://{new_pod_address}");
+        let new_pod_iri = new_pod_iri.as_str();
+        let new_pod = NamedNodeRef::new(new_pod_iri)?;
+        let new_scratchpad_iri = format!("ant://{new_scratchpad_address}");
+        let new_scratchpad_iri = new_scratchpad_iri.as_str();
+        let configuration_iri = format!("ant://{configuration_address}");
+        let configuration_iri = configuration_iri.as_str();
+        let config = NamedNodeRef::new(configuration_iri)?;
+        let default_graph_count = self.default_graph_triple_count()?;
+
+        // Look up the referencing pods before anything changes
+        let referencing_pods = self.get_pods_referencing(pod_address, true)?;
+
+        // Copy the pod data, leaving out the old scratchpad bookkeeping
+        self.store.insert_named_graph(new_pod)?;
+        let update = format!(
+            r#"
+            INSERT {{ GRAPH <{new_pod_iri}> {{ ?new_subject ?p ?new_object . }} }}
+            WHERE {{
+                GRAPH <{pod_iri}> {{ ?subject ?p ?object . }}
+                FILTER NOT EXISTS {{ GRAPH <{pod_iri}> {{ ?subject <{HAS_INDEX}> ?index . }} }}
+                BIND(IF(?subject = <{pod_iri}>, <{new_pod_iri}>, ?subject) AS ?new_subject)
+                BIND(IF(?object = <{pod_iri}>, <{new_pod_iri}>, ?object) AS ?new_object)
+            }}
+            "#
+        );
+        debug!("Copy pod graph string: {}", update);
+        self.store.update(update.as_str())?;
+        self.store.clear_graph(pod)?;
+
+        let date = self.clock.now().to_rfc3339();
+        let date = date.as_str();
+        let _quad = self.put_quad(new_scratchpad_iri, HAS_INDEX, "0", Some(new_pod_iri))?;
+        let _quad = self.put_quad(
+            new_scratchpad_iri,
+            HAS_MODIFIED_DATE,
+            date,
+            Some(new_pod_iri),
+        )?;
+
+        // Point the user's own pods at the new address
+        for referencing_pod in &referencing_pods {
+            let referencing_iri = format!("ant://{referencing_pod}");
+            let update = format!(
+                r#"
+                DELETE {{ GRAPH <{referencing_iri}> {{ <{pod_iri}> ?p ?o . }} }}
+                INSERT {{ GRAPH <{referencing_iri}> {{ <{new_pod_iri}> ?p ?o . }} }}
+                WHERE {{ GRAPH <{referencing_iri}> {{ <{pod_iri}> ?p ?o . }} }}
+                "#
+            );
+            self.store.update(update.as_str())?;
+        }
+
+        // Move the pod's configuration entries (type and depth) to the new address
+        let update = format!(
+            r#"
+            DELETE {{ GRAPH <{configuration_iri}> {{ <{pod_iri}> ?p ?o . }} }}
+            INSERT {{ GRAPH <{configuration_iri}> {{ <{new_pod_iri}> ?p ?o . }} }}
+            WHERE {{ GRAPH <{configuration_iri}> {{ <{pod_iri}> ?p ?o . }} }}
+            "#
+        );
+        self.store.update(update.as_str())?;
+        let _quad = self.put_quad(
+            new_scratchpad_iri,
+            HAS_ADDR_TYPE,
+            DATA,
+            Some(configuration_iri),
+        )?;
+
+        // The old addresses must never be handed out again
+        let _quad = self.put_quad(pod_iri, HAS_ADDR_TYPE, ABANDONED, Some(configuration_iri))?;
+        for scratchpad in pod_scratchpads {
+            let scratchpad_iri = format!("ant://{scratchpad}");
+            let scratchpad_iri = scratchpad_iri.as_str();
+            let update = format!(
+                "DELETE WHERE {{ GRAPH <{configuration_iri}> {{ <{scratchpad_iri}> ?p ?o . }} }}"
+            );
+            self.store.update(update.as_str())?;
+            let _quad = self.put_quad(
+                scratchpad_iri,
+                HAS_ADDR_TYPE,
+                ABANDONED,
+                Some(configuration_iri),
+            )?;
+        }
+
+        self.update_key_count(configuration_address, num_keys)?;
+
+        let mut buffer = Vec::new();
+        self.store
+            .dump_graph_to_writer(new_pod, RdfFormat::TriG, &mut buffer)?;
+        let mut configuration = Vec::new();
+        self.store
+            .dump_graph_to_writer(config, RdfFormat::TriG, &mut configuration)?;
+
+        // Pod data must only land in named graphs, never in the default graph
+        self.ensure_default_graph_unchanged(default_graph_count, "rotate_pod_entry")?;
+
+        Ok((buffer, configuration, referencing_pods))
+    }
+
+    // Set the icon of a pod to the Autonomi address holding the image, replacing any previous icon
+    pub fn pod_icon_entry(
+        &mut self,
+        pod_address: &str,
+        icon_address: &str,
+    ) -> Result<Vec<u8>, Error> {
+        let pod_iri = format!("ant://{pod_address}");
+        let pod_iri = pod_iri.as_str();
+        let pod = NamedNodeRef::new(pod_iri)?;
+
+        let update =
+            format!("DELETE WHERE {{ GRAPH <{pod_iri}> {{ <{pod_iri}> <{HAS_ICON}> ?o . }} }}");
+        debug!("Delete existing pod icon string: {}", update);
+        self.store.update(update.as_str())?;
+
+        let icon_iri = format!("ant://{icon_address}");
+        let _quad = self.put_quad(pod_iri, HAS_ICON, &icon_iri, Some(pod_iri))?;
+        debug!("Pod icon updated to {}", icon_address);
+
+        // Dump the updated graph in TriG format
+        let mut buffer = Vec::new();
+        self.store
+            .dump_graph_to_writer(pod, RdfFormat::TriG, &mut buffer)?;
+
+        Ok(buffer)
+    }
+
+    // Get the address of a pod's icon, if it has one
+    pub fn get_pod_icon(&self, pod_address: &str) -> Result<Option<String>, Error> {
+        let pod_iri = format!("ant://{pod_address}");
+        let query = format!(
+            "SELECT ?icon WHERE {{ GRAPH <{pod_iri}> {{ <{pod_iri}> <{HAS_ICON}> ?icon . }} }} LIMIT 1"
+        );
+        debug!("Pod icon query: {}", query);
+
+        let results = self.store.query(query.as_str())?;
+        if let QueryResults::Solutions(solutions) = results {
+            for solution in solutions.flatten() {
+                if let Some(oxigraph::model::Term::NamedNode(icon_node)) = solution.get("icon")
+                    && let Some(address) = icon_node.as_str().strip_prefix("ant://")
+                {
+                    return Ok(Some(address.to_string()));
+                }
+            }
+        }
+        Ok(None)
+    }
+
     pub fn rename_pod_entry(
         &mut self,
         pod_address: &str,
@@ -392,6 +886,52 @@ impl Graph {
         Ok(buffer)
     }
 
+    // Resolve the pods of a batch rename to their addresses and check the new names before
+    // anything is renamed. Names must be non-empty, each pod may appear only once, and no two pods
+    // may end up with the same name
+    pub fn resolve_pod_renames(
+        &self,
+        renames: &[(String, String)],
+    ) -> Result<Vec<(String, String)>, Error> {
+        let mut resolved: Vec<(String, String)> = Vec::new();
+        for (pod, new_name) in renames {
+            if new_name.trim().is_empty() {
+                return Err(Error::InvalidInput(format!(
+                    "New name for pod {pod} is empty"
+                )));
+            }
+            let address = self.check_pod_exists(pod.trim())?;
+            if resolved.iter().any(|(other, _)| *other == address) {
+                return Err(Error::InvalidInput(format!(
+                    "Pod {address} is renamed more than once"
+                )));
+            }
+            if resolved
+                .iter()
+                .any(|(_, other_name)| other_name == new_name)
+            {
+                return Err(Error::InvalidInput(format!(
+                    "Name \"{new_name}\" is given to more than one pod"
+                )));
+            }
+            resolved.push((address, new_name.clone()));
+        }
+
+        // Names of pods that are not being renamed stay taken
+        for (name, address) in self.get_pod_names()? {
+            if resolved.iter().any(|(renamed, _)| *renamed == address) {
+                continue;
+            }
+            if resolved.iter().any(|(_, new_name)| *new_name == name) {
+                return Err(Error::InvalidInput(format!(
+                    "Name \"{name}\" is already used by pod {address}"
+                )));
+            }
+        }
+
+        Ok(resolved)
+    }
+
     pub fn remove_scratchpad_entry(
         &mut self,
         pod_address: &str,
@@ -500,7 +1040,7 @@ impl Graph {
         debug!("Delete existing modified date query: {}", delete_query);
         self.store.update(delete_query.as_str())?;
 
-        let date = Utc::now().to_rfc3339();
+        let date = self.clock.now().to_rfc3339();
         let date = date.as_str();
         let _quad = self.put_quad(
             configuration_iri,
@@ -523,11 +1063,7 @@ impl Graph {
         }
 
         // Update the modified date in the pod
-        let delete_query = format!(
-            "DELETE WHERE {{ GRAPH <{pod_iri}> {{ ?subject <{HAS_MODIFIED_DATE}> ?date . }} }}"
-        );
-        debug!("Delete existing modified date query: {}", delete_query);
-        self.store.update(delete_query.as_str())?;
+        self.delete_pod_modified_dates(pod_iri)?;
 
         let _quad = self.put_quad(pod_iri, HAS_MODIFIED_DATE, date, Some(pod_iri))?;
 
@@ -578,6 +1114,52 @@ impl Graph {
         Ok(())
     }
 
+    // Record keys found on the network beyond the known key count in the configuration graph, so
+    // the key count covers them and later refreshes derive them again. Pods are added as local
+    // pods at depth 0
+    pub fn recovered_keys_entry(
+        &mut self,
+        configuration_address: &str,
+        pods: &[String],
+        scratchpads: &[String],
+        free_pointers: &[String],
+        free_scratchpads: &[String],
+        num_keys: u64,
+    ) -> Result<Vec<u8>, Error> {
+        let configuration_iri = format!("ant://{configuration_address}");
+        let configuration_iri = configuration_iri.as_str();
+        let config = NamedNodeRef::new(configuration_iri)?;
+
+        for (addresses, address_type) in [
+            (pods, POD),
+            (scratchpads, DATA),
+            (free_pointers, FREED_POD),
+            (free_scratchpads, FREED_DATA),
+        ] {
+            for address in addresses {
+                let address_iri = format!("ant://{address}");
+                let _quad = self.put_quad(
+                    &address_iri,
+                    HAS_ADDR_TYPE,
+                    address_type,
+                    Some(configuration_iri),
+                )?;
+            }
+        }
+        for pod in pods {
+            let pod_iri = format!("ant://{pod}");
+            let _quad = self.put_quad(&pod_iri, HAS_DEPTH, "0", Some(configuration_iri))?;
+        }
+        self.update_key_count(configuration_address, num_keys)?;
+
+        // Dump the updated configuration graph in TriG format
+        let mut configuration = Vec::new();
+        self.store
+            .dump_graph_to_writer(config, RdfFormat::TriG, &mut configuration)?;
+
+        Ok(configuration)
+    }
+
     // Input is a JSON-LD string
     pub fn put_subject_data(
         &mut self,
@@ -585,76 +1167,347 @@ impl Graph {
         subject_address: &str,
         configuration_address: &str,
         data: &str,
+    ) -> Result<(Vec<u8>, Vec<u8>), Error> {
+        self.put_subjects_data(
+            pod_address,
+            configuration_address,
+            &[(subject_address.to_string(), data.to_string())],
+        )
+    }
+
+    // Replace the data of several subjects in a pod at once. Input is a list of subject
+    // addresses with their JSON-LD strings. The pod is only touched and dumped once
+    pub fn put_subjects_data(
+        &mut self,
+        pod_address: &str,
+        configuration_address: &str,
+        subjects: &[(String, String)],
     ) -> Result<(Vec<u8>, Vec<u8>), Error> {
         let pod_iri = format!("ant://{pod_address}");
         let pod_iri = pod_iri.as_str();
         let pod = NamedNodeRef::new(pod_iri)?;
-        let subject_iri = format!("ant://{subject_address}");
-        let subject_iri = subject_iri.as_str();
+        let default_graph_count = self.default_graph_triple_count()?;
 
-        // Get the configuration IRI
-        let configuration_iri = format!("ant://{configuration_address}");
-        let configuration_iri = configuration_iri.as_str();
-        let config = NamedNodeRef::new(configuration_iri)?;
-
-        // Delete existing data for the subject in the pod graph
-        // This query deletes all triples for the subject in the specified pod graph
-        let update = format!("DELETE WHERE {{ GRAPH <{pod_iri}> {{ <{subject_iri}> ?p ?o . }} }}");
-        debug!("Delete string: {}", update);
-
-        self.store.update(update.as_str())?;
-
-        // Convert the data &str to a Reader
-        let data_reader = Cursor::new(data);
-
-        // Insert the new data using the Reader
         //FIXME: may need the streaming profile option here?
         let mut profile = JsonLdProfileSet::empty();
         profile |= JsonLdProfile::Compacted;
         profile |= JsonLdProfile::Context;
-        // Load the data into the pod graph
-        self.store.load_from_reader(
-            RdfParser::from_format(RdfFormat::JsonLd { profile })
-                //.with_base_iri("https://schema.org/")? // don't need this
-                .without_named_graphs() // No named graphs allowed in the input
-                .with_default_graph(pod), // we put the file default graph inside of a named graph
-            data_reader,
-        )?;
 
-        // Update modified date
-        let delete_query = format!(
-            "DELETE WHERE {{ GRAPH <{pod_iri}> {{ ?subject <{HAS_MODIFIED_DATE}> ?date . }} }}"
-        );
-        debug!("Delete existing modified date query: {}", delete_query);
-        self.store.update(delete_query.as_str())?;
+        // Check every document before the pod is changed, so a bad one leaves the pod untouched
+        for (subject_address, data) in subjects {
+            self.check_subject_jsonld(subject_address, data)?;
+        }
 
-        let date = Utc::now().to_rfc3339();
-        let date = date.as_str();
-        let _quad = self.put_quad(pod_iri, HAS_MODIFIED_DATE, date, Some(pod_iri))?;
+        let date = self.clock.now().to_rfc3339();
+        for (subject_address, data) in subjects {
+            let subject_iri = format!("ant://{subject_address}");
 
-        // Update all of the scratchpads within the pod to have the same modified date
-        let scratchpads = self.get_pod_scratchpads(pod_address)?;
-        for scratchpad in scratchpads {
-            let scratchpad_iri = format!("ant://{scratchpad}");
-            let scratchpad_iri = scratchpad_iri.as_str();
-            let _quad = self.put_quad(scratchpad_iri, HAS_MODIFIED_DATE, date, Some(pod_iri))?;
+            // Delete existing data for the subject in the pod graph
+            // This query deletes all triples for the subject in the specified pod graph
+            let update =
+                format!("DELETE WHERE {{ GRAPH <{pod_iri}> {{ <{subject_iri}> ?p ?o . }} }}");
+            debug!("Delete string: {}", update);
+
+            self.store.update(update.as_str())?;
+
+            // Load the data into the pod graph
+            self.store.load_from_reader(
+                RdfParser::from_format(RdfFormat::JsonLd { profile })
+                    .without_named_graphs() // No named graphs allowed in the input
+                    .with_default_graph(pod), // we put the file default graph inside of a named graph
+                Cursor::new(data.as_str()),
+            )?;
+            self.stamp_subject_modified(pod_iri, &subject_iri, &date)?;
         }
 
-        // Dump newly created graph in TriG format
-        let mut buffer = Vec::new();
-        self.store
-            .dump_graph_to_writer(pod, RdfFormat::TriG, &mut buffer)?;
+        let (buffer, configuration) =
+            self.touch_pod_modified(pod_address, configuration_address)?;
 
-        // Update the modified date in the configuration pod
-        let delete_query = format!(
-            "DELETE WHERE {{ GRAPH <{configuration_iri}> {{ ?subject <{HAS_MODIFIED_DATE}> ?date . }} }}"
-        );
-        debug!("Delete existing modified date query: {}", delete_query);
-        self.store.update(delete_query.as_str())?;
+        // Subject data must only land in the pod graph, never in the default graph
+        self.ensure_default_graph_unchanged(default_graph_count, "put_subjects_data")?;
 
-        let _quad = self.put_quad(
-            configuration_iri,
-            HAS_MODIFIED_DATE,
+        Ok((buffer, configuration))
+    }
+
+    // Delete all data of a subject in a pod, including its modified date, and update the pod's
+    // modified date. Returns an error if the pod has no data for the subject
+    pub fn remove_subject_data(
+        &mut self,
+        pod_address: &str,
+        subject_address: &str,
+        configuration_address: &str,
+    ) -> Result<(Vec<u8>, Vec<u8>), Error> {
+        let pod_iri = format!("ant://{pod_address}");
+        let pod = NamedNodeRef::new(pod_iri.as_str())?;
+        let subject_iri = format!("ant://{subject_address}");
+        let subject = NamedNodeRef::new(subject_iri.as_str())?;
+
+        if self
+            .store
+            .quads_for_pattern(Some(subject.into()), None, None, Some(pod.into()))
+            .next()
+            .is_none()
+        {
+            return Err(Error::Graph(StorageError::Io(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("Subject {subject_address} not found in pod {pod_address}"),
+            ))));
+        }
+
+        let update = format!("DELETE WHERE {{ GRAPH <{pod_iri}> {{ <{subject_iri}> ?p ?o . }} }}");
+        debug!("Delete string: {}", update);
+        self.store.update(update.as_str())?;
+
+        self.touch_pod_modified(pod_address, configuration_address)
+    }
+
+    // Input is a JSON-LD string. Only the predicates present in the input are replaced,
+    // all other existing triples of the subject are kept
+    pub fn merge_subject_data(
+        &mut self,
+        pod_address: &str,
+        subject_address: &str,
+        configuration_address: &str,
+        data: &str,
+    ) -> Result<(Vec<u8>, Vec<u8>), Error> {
+        let pod_iri = format!("ant://{pod_address}");
+        let pod = NamedNodeRef::new(pod_iri.as_str())?;
+        let default_graph_count = self.default_graph_triple_count()?;
+        debug!("Merging data for subject {}", subject_address);
+        self.check_subject_jsonld(subject_address, data)?;
+
+        // Parse the data into the pod graph without touching the store yet
+        let mut profile = JsonLdProfileSet::empty();
+        profile |= JsonLdProfile::Compacted;
+        profile |= JsonLdProfile::Context;
+        let mut quads = Vec::new();
+        for quad in RdfParser::from_format(RdfFormat::JsonLd { profile })
+            .without_named_graphs() // No named graphs allowed in the input
+            .with_default_graph(pod) // we put the file default graph inside of a named graph
+            .for_reader(Cursor::new(data))
+        {
+            quads.push(quad?);
+        }
+
+        // Remove the existing values of every subject/predicate pair being merged
+        let mut merged_predicates = std::collections::HashSet::new();
+        for quad in &quads {
+            if merged_predicates.insert((quad.subject.clone(), quad.predicate.clone())) {
+                let existing: Vec<Quad> = self
+                    .store
+                    .quads_for_pattern(
+                        Some(quad.subject.as_ref()),
+                        Some(quad.predicate.as_ref()),
+                        None,
+                        Some(GraphNameRef::NamedNode(pod)),
+                    )
+                    .collect::<Result<_, _>>()?;
+                for existing_quad in existing {
+                    self.store.remove(&existing_quad)?;
+                }
+            }
+        }
+
+        for quad in &quads {
+            self.store.insert(quad)?;
+        }
+        let date = self.clock.now().to_rfc3339();
+        let subject_iri = format!("ant://{subject_address}");
+        self.stamp_subject_modified(pod_iri.as_str(), &subject_iri, &date)?;
+
+        let (buffer, configuration) =
+            self.touch_pod_modified(pod_address, configuration_address)?;
+
+        // Subject data must only land in the pod graph, never in the default graph
+        self.ensure_default_graph_unchanged(default_graph_count, "merge_subject_data")?;
+
+        Ok((buffer, configuration))
+    }
+
+    // Validate subject JSON-LD before it is loaded into a pod, so a malformed document fails
+    // with the line it broke on instead of a bare parser error. Documents with named graphs
+    // are rejected, the subject data must land in the pod graph
+    fn check_subject_jsonld(&self, subject_address: &str, data: &str) -> Result<(), Error> {
+        let result = self.validate_jsonld(data)?;
+        if let Some(warning) = result.warnings.first() {
+            warn!(
+                "Invalid JSON-LD for subject {}: {}",
+                subject_address, warning
+            );
+            return Err(Error::InvalidInput(format!(
+                "Invalid JSON-LD for subject {subject_address}: {warning}"
+            )));
+        }
+
+        debug!(
+            "JSON-LD for subject {} holds {} triples",
+            subject_address, result.triple_count
+        );
+        Ok(())
+    }
+
+    // Parse JSON-LD into a temporary in-memory graph to preview the triples it produces.
+    // The persistent store is never touched
+    pub fn validate_jsonld(&self, jsonld: &str) -> Result<ValidationResult, Error> {
+        let mut result = ValidationResult::default();
+        let temp_store = Store::new()?;
+
+        // Same profile options as put_subject_data
+        let mut profile = JsonLdProfileSet::empty();
+        profile |= JsonLdProfile::Compacted;
+        profile |= JsonLdProfile::Context;
+        for quad in
+            RdfParser::from_format(RdfFormat::JsonLd { profile }).for_reader(Cursor::new(jsonld))
+        {
+            match quad {
+                // No named graphs allowed in the input, the data must land in the pod graph
+                Ok(quad) if !quad.graph_name.is_default_graph() => {
+                    result.warnings.push(format!(
+                        "JSON-LD puts data in the named graph {}, subject data is stored in \
+                         the pod graph and cannot contain named graphs",
+                        quad.graph_name
+                    ));
+                    break;
+                }
+                Ok(quad) => {
+                    temp_store.insert(&quad)?;
+                }
+                Err(e) => {
+                    debug!("JSON-LD validation error: {}", e);
+                    let location = match &e {
+                        RdfParseError::Syntax(syntax_error) => syntax_error.location(),
+                        RdfParseError::Io(_) => None,
+                    };
+                    // Point at the line the parser broke on
+                    let warning = match location {
+                        Some(location) => {
+                            let line = jsonld
+                                .lines()
+                                .nth(location.start.line as usize)
+                                .unwrap_or_default()
+                                .trim();
+                            format!(
+                                "{e} at line {} column {}, near `{line}`",
+                                location.start.line + 1,
+                                location.start.column + 1
+                            )
+                        }
+                        None => e.to_string(),
+                    };
+                    result.warnings.push(warning);
+                    // Syntax errors may be recoverable, but anything else stops the parser
+                    if matches!(e, RdfParseError::Io(_)) {
+                        break;
+                    }
+                }
+            }
+        }
+
+        result.triple_count = temp_store.len()?;
+
+        let query = format!(
+            "SELECT DISTINCT ?type WHERE {{ ?subject <{HAS_ADDR_TYPE}> ?type . }} ORDER BY ?type"
+        );
+        if let QueryResults::Solutions(solutions) = temp_store.query(query.as_str())? {
+            for solution in solutions.flatten() {
+                if let Some(oxigraph::model::Term::NamedNode(type_node)) = solution.get("type") {
+                    result.types.push(type_node.as_str().to_string());
+                }
+            }
+        }
+
+        result.valid = result.warnings.is_empty();
+        Ok(result)
+    }
+
+    // Copy a pod graph into a temporary in-memory store, apply a batch of subject updates
+    // the same way put_subject_data would, and return the result in TriG format.
+    // The persistent store is never touched
+    pub fn pod_trig_with_subjects(
+        &self,
+        pod_address: &str,
+        subjects: &[(String, String)],
+    ) -> Result<Vec<u8>, Error> {
+        let pod_iri = format!("ant://{pod_address}");
+        let pod_iri = pod_iri.as_str();
+        let pod = NamedNodeRef::new(pod_iri)?;
+        let temp_store = Store::new()?;
+
+        for quad in
+            self.store
+                .quads_for_pattern(None, None, None, Some(GraphNameRef::NamedNode(pod)))
+        {
+            temp_store.insert(&quad?)?;
+        }
+
+        // Same profile options as put_subject_data
+        let mut profile = JsonLdProfileSet::empty();
+        profile |= JsonLdProfile::Compacted;
+        profile |= JsonLdProfile::Context;
+        for (subject_address, data) in subjects {
+            let subject_iri = format!("ant://{subject_address}");
+            let update =
+                format!("DELETE WHERE {{ GRAPH <{pod_iri}> {{ <{subject_iri}> ?p ?o . }} }}");
+            temp_store.update(update.as_str())?;
+            temp_store.load_from_reader(
+                RdfParser::from_format(RdfFormat::JsonLd { profile })
+                    .without_named_graphs() // No named graphs allowed in the input
+                    .with_default_graph(pod),
+                Cursor::new(data.as_str()),
+            )?;
+        }
+
+        let mut buffer = Vec::new();
+        temp_store.dump_graph_to_writer(pod, RdfFormat::TriG, &mut buffer)?;
+        Ok(buffer)
+    }
+
+    // Update the modified dates of a pod and the configuration pod after a change,
+    // then return both graphs in TriG format
+    fn touch_pod_modified(
+        &mut self,
+        pod_address: &str,
+        configuration_address: &str,
+    ) -> Result<(Vec<u8>, Vec<u8>), Error> {
+        let pod_iri = format!("ant://{pod_address}");
+        let pod_iri = pod_iri.as_str();
+        let pod = NamedNodeRef::new(pod_iri)?;
+
+        // Get the configuration IRI
+        let configuration_iri = format!("ant://{configuration_address}");
+        let configuration_iri = configuration_iri.as_str();
+        let config = NamedNodeRef::new(configuration_iri)?;
+
+        // Update modified date
+        self.delete_pod_modified_dates(pod_iri)?;
+
+        let date = self.clock.now().to_rfc3339();
+        let date = date.as_str();
+        let _quad = self.put_quad(pod_iri, HAS_MODIFIED_DATE, date, Some(pod_iri))?;
+
+        // Update all of the scratchpads within the pod to have the same modified date
+        let scratchpads = self.get_pod_scratchpads(pod_address)?;
+        for scratchpad in scratchpads {
+            let scratchpad_iri = format!("ant://{scratchpad}");
+            let scratchpad_iri = scratchpad_iri.as_str();
+            let _quad = self.put_quad(scratchpad_iri, HAS_MODIFIED_DATE, date, Some(pod_iri))?;
+        }
+
+        // Dump newly created graph in TriG format
+        let mut buffer = Vec::new();
+        self.store
+            .dump_graph_to_writer(pod, RdfFormat::TriG, &mut buffer)?;
+
+        // Update the modified date in the configuration pod
+        let delete_query = format!(
+            "DELETE WHERE {{ GRAPH <{configuration_iri}> {{ ?subject <{HAS_MODIFIED_DATE}> ?date . }} }}"
+        );
+        debug!("Delete existing modified date query: {}", delete_query);
+        self.store.update(delete_query.as_str())?;
+
+        let _quad = self.put_quad(
+            configuration_iri,
+            HAS_MODIFIED_DATE,
             date,
             Some(configuration_iri),
         )?;
@@ -680,6 +1533,74 @@ impl Graph {
         Ok((buffer, configuration))
     }
 
+    // Delete the modified dates of a pod and its scratchpads, keeping the dates of the subjects
+    fn delete_pod_modified_dates(&self, pod_iri: &str) -> Result<(), Error> {
+        let delete_query = format!(
+            r#"
+            DELETE {{ GRAPH <{pod_iri}> {{ ?subject <{HAS_MODIFIED_DATE}> ?date . }} }}
+            WHERE {{
+                GRAPH <{pod_iri}> {{
+                    ?subject <{HAS_MODIFIED_DATE}> ?date .
+                    FILTER(?subject = <{pod_iri}> || EXISTS {{ ?subject <{HAS_INDEX}> ?index . }})
+                }}
+            }}
+            "#
+        );
+        debug!("Delete existing modified date query: {}", delete_query);
+        self.store.update(delete_query.as_str())?;
+        Ok(())
+    }
+
+    // Set the modified date of a subject within a pod, replacing any previous date
+    fn stamp_subject_modified(
+        &self,
+        pod_iri: &str,
+        subject_iri: &str,
+        date: &str,
+    ) -> Result<(), Error> {
+        let delete_query = format!(
+            "DELETE WHERE {{ GRAPH <{pod_iri}> {{ <{subject_iri}> <{HAS_MODIFIED_DATE}> ?date . }} }}"
+        );
+        self.store.update(delete_query.as_str())?;
+        let _quad = self.put_quad(subject_iri, HAS_MODIFIED_DATE, date, Some(pod_iri))?;
+        Ok(())
+    }
+
+    // Count the triples in the default (unnamed) graph. Pod data always lives in named graphs,
+    // so anything found here is stray data that is not associated with a pod and won't be uploaded
+    pub fn default_graph_triple_count(&self) -> Result<usize, Error> {
+        let mut count = 0;
+        for quad in self
+            .store
+            .quads_for_pattern(None, None, None, Some(GraphNameRef::DefaultGraph))
+        {
+            quad?;
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    // Return an error if the default graph grew since `count_before` was taken
+    pub fn ensure_default_graph_unchanged(
+        &self,
+        count_before: usize,
+        operation: &str,
+    ) -> Result<(), Error> {
+        let count_after = self.default_graph_triple_count()?;
+        if count_after > count_before {
+            error!(
+                "{} wrote {} triples to the default graph",
+                operation,
+                count_after - count_before
+            );
+            return Err(Error::DefaultGraph(format!(
+                "{operation} wrote {} triples outside of a pod graph",
+                count_after - count_before
+            )));
+        }
+        Ok(())
+    }
+
     pub fn get_subject_data(&self, subject_address: &str) -> Result<String, Error> {
         let subject_iri = format!("ant://{subject_address}");
 
@@ -700,6 +1621,248 @@ impl Graph {
         Ok(json_str)
     }
 
+    // Get everything known about a subject across all graphs as compacted JSON-LD, the same shape
+    // of document that put_subject_data accepts. Nested objects are stored as blank nodes, so the
+    // blank nodes reachable from the subject are included as well
+    pub fn get_subject_data_jsonld(&self, subject_address: &str) -> Result<String, Error> {
+        let subject_iri = format!("ant://{subject_address}");
+
+        let query = format!(
+            r#"
+            CONSTRUCT {{ ?node ?predicate ?object }} WHERE {{
+                GRAPH ?graph {{
+                    <{subject_iri}> (!<ant://colonylib/none>)* ?node .
+                    ?node ?predicate ?object .
+                }}
+                FILTER(?node = <{subject_iri}> || isBlank(?node))
+            }}
+            "#
+        );
+        debug!("Subject JSON-LD query: {}", query);
+
+        let mut profile = JsonLdProfileSet::empty();
+        profile |= JsonLdProfile::Compacted;
+        let serializer = RdfSerializer::from_format(RdfFormat::JsonLd { profile })
+            .with_prefix("schema", "http://schema.org/")?;
+        let buffer = self
+            .store
+            .query(query.as_str())?
+            .write_graph(Vec::new(), serializer)?;
+
+        let json_str = String::from_utf8(buffer)?;
+        debug!("Subject JSON-LD: {}", json_str);
+        Ok(json_str)
+    }
+
+    // Get the distinct rdf:type IRIs of a subject across all graphs
+    pub fn get_subject_types(&self, subject_address: &str) -> Result<Vec<String>, Error> {
+        let subject_iri = format!("ant://{subject_address}");
+
+        let query = format!(
+            "SELECT DISTINCT ?type WHERE {{ GRAPH ?graph {{ <{subject_iri}> <{HAS_ADDR_TYPE}> ?type . }} }} ORDER BY ?type"
+        );
+        debug!("Subject types query: {}", query);
+
+        let mut types = Vec::new();
+        let results = self.store.query(query.as_str())?;
+        if let QueryResults::Solutions(solutions) = results {
+            for solution in solutions.flatten() {
+                if let Some(oxigraph::model::Term::NamedNode(type_node)) = solution.get("type") {
+                    types.push(type_node.as_str().to_string());
+                }
+            }
+        }
+
+        debug!(
+            "Found {} types for subject {}",
+            types.len(),
+            subject_address
+        );
+        Ok(types)
+    }
+
+    // Get the pods whose graphs describe a subject, with the ant:// prefix stripped. Configuration
+    // graphs, which declare themselves as pods, are left out
+    pub fn get_subject_pods(&self, subject_address: &str) -> Result<Vec<String>, Error> {
+        let subject_iri = format!("ant://{subject_address}");
+        let subject = NamedNodeRef::new(&subject_iri)?;
+
+        let query = format!(
+            r#"
+            SELECT DISTINCT ?graph WHERE {{
+                GRAPH ?graph {{ {subject} ?predicate ?object . }}
+                FILTER NOT EXISTS {{ GRAPH ?graph {{ ?graph <{HAS_ADDR_TYPE}> <{POD}> . }} }}
+            }} ORDER BY ?graph
+            "#
+        );
+        debug!("Subject pods query: {}", query);
+
+        let mut pods = Vec::new();
+        let results = self.store.query(query.as_str())?;
+        if let QueryResults::Solutions(solutions) = results {
+            for solution in solutions.flatten() {
+                if let Some(oxigraph::model::Term::NamedNode(graph_node)) = solution.get("graph") {
+                    let graph_iri = graph_node.as_str();
+                    let pod_address = graph_iri.strip_prefix("ant://").unwrap_or(graph_iri);
+                    pods.push(pod_address.to_string());
+                }
+            }
+        }
+
+        debug!("Found {} pods for subject {}", pods.len(), subject_address);
+        Ok(pods)
+    }
+
+    // Get the merged data of a subject from the user's own pods (depth 0), keeping the pods
+    // each value comes from. A predicate is flagged as a conflict when the pods that state it
+    // give it different sets of values. Modified dates are stamped per pod, so they never count
+    // as a conflict
+    pub fn get_subject_data_local_with_conflicts(
+        &self,
+        subject_address: &str,
+    ) -> Result<SubjectView, Error> {
+        let subject_iri = format!("ant://{subject_address}");
+        let subject = NamedNodeRef::new(&subject_iri)?;
+        let scope_clause = Self::local_scope_clause("graph", true);
+
+        let query = format!(
+            "SELECT ?graph ?predicate ?object WHERE {{ GRAPH ?graph {{ <{}> ?predicate ?object . }} {scope_clause} }}",
+            subject.as_str()
+        );
+        debug!("Local subject data query: {}", query);
+
+        // predicate -> value -> pods
+        let mut predicates: BTreeMap<String, BTreeMap<String, BTreeSet<String>>> = BTreeMap::new();
+        let mut pods: BTreeSet<String> = BTreeSet::new();
+        let results = self.store.query(query.as_str())?;
+        if let QueryResults::Solutions(solutions) = results {
+            for solution in solutions.flatten() {
+                let (
+                    Some(oxigraph::model::Term::NamedNode(graph)),
+                    Some(oxigraph::model::Term::NamedNode(predicate)),
+                    Some(object),
+                ) = (
+                    solution.get("graph"),
+                    solution.get("predicate"),
+                    solution.get("object"),
+                )
+                else {
+                    continue;
+                };
+                let pod = graph
+                    .as_str()
+                    .strip_prefix("ant://")
+                    .unwrap_or(graph.as_str())
+                    .to_string();
+                let value = match object {
+                    oxigraph::model::Term::NamedNode(node) => node.as_str().to_string(),
+                    oxigraph::model::Term::Literal(literal) => literal.value().to_string(),
+                    other => other.to_string(),
+                };
+                pods.insert(pod.clone());
+                predicates
+                    .entry(predicate.as_str().to_string())
+                    .or_default()
+                    .entry(value)
+                    .or_default()
+                    .insert(pod);
+            }
+        }
+
+        let mut properties = Vec::new();
+        for (predicate, values) in predicates {
+            // Collect the values each pod gives the predicate and compare them
+            let mut pod_values: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+            for (value, value_pods) in &values {
+                for pod in value_pods {
+                    pod_values.entry(pod).or_default().insert(value);
+                }
+            }
+            let distinct: HashSet<&BTreeSet<&str>> = pod_values.values().collect();
+            let conflict = predicate != HAS_MODIFIED_DATE && distinct.len() > 1;
+
+            properties.push(SubjectProperty {
+                values: values
+                    .into_iter()
+                    .map(|(value, value_pods)| SubjectValue {
+                        value,
+                        pods: value_pods.into_iter().collect(),
+                    })
+                    .collect(),
+                predicate,
+                conflict,
+            });
+        }
+
+        debug!(
+            "Subject {} has {} properties in {} local pods",
+            subject_address,
+            properties.len(),
+            pods.len()
+        );
+        Ok(SubjectView {
+            subject: subject_address.to_string(),
+            pods: pods.into_iter().collect(),
+            properties,
+        })
+    }
+
+    // Get data for many subjects with a single query, keyed by subject address.
+    // Each entry uses the same SPARQL JSON layout as get_subject_data, subjects with no data get empty bindings
+    pub fn get_subjects_data(
+        &self,
+        subject_addresses: &[String],
+    ) -> Result<HashMap<String, Value>, Error> {
+        let mut subjects_data: HashMap<String, Value> = HashMap::new();
+        let mut values = String::new();
+        for subject_address in subject_addresses {
+            let subject_iri = format!("ant://{subject_address}");
+            let subject = NamedNodeRef::new(&subject_iri)?;
+            values.push_str(&format!("<{}> ", subject.as_str()));
+            subjects_data.insert(
+                subject_address.clone(),
+                serde_json::json!({
+                    "head": { "vars": ["graph", "predicate", "object"] },
+                    "results": { "bindings": [] }
+                }),
+            );
+        }
+
+        if subject_addresses.is_empty() {
+            return Ok(subjects_data);
+        }
+
+        let query = format!(
+            "SELECT ?subject ?graph ?predicate ?object WHERE {{ VALUES ?subject {{ {values}}} GRAPH ?graph {{ ?subject ?predicate ?object . }} }}"
+        );
+        debug!("Query string: {}", query);
+
+        let results = self.store.query(query.as_str())?;
+        let buffer = results.write(Vec::new(), QueryResultsFormat::Json)?;
+        let results: Value = serde_json::from_slice(&buffer)?;
+
+        // Group the bindings by subject
+        if let Some(bindings) = results["results"]["bindings"].as_array() {
+            for binding in bindings {
+                let mut binding = binding.clone();
+                let subject = binding
+                    .as_object_mut()
+                    .and_then(|b| b.remove("subject"))
+                    .and_then(|subject| subject["value"].as_str().map(|v| v.to_string()));
+                if let Some(subject) = subject
+                    && let Some(subject_address) = subject.strip_prefix("ant://")
+                    && let Some(entry) = subjects_data.get_mut(subject_address)
+                    && let Some(entry_bindings) = entry["results"]["bindings"].as_array_mut()
+                {
+                    entry_bindings.push(binding);
+                }
+            }
+        }
+
+        debug!("Fetched data for {} subjects", subjects_data.len());
+        Ok(subjects_data)
+    }
+
     // Get the depth of a pod from the graph database
     pub fn get_pod_depth(&self, pod_address: &str) -> Result<u64, Error> {
         let pod_iri = format!("ant://{pod_address}");
@@ -790,6 +1953,67 @@ impl Graph {
         Ok(())
     }
 
+    // Compare the stored depth of every pod to its shortest reference path from the local pods
+    // listed in the configuration graph. Only references in pod graphs that are loaded locally
+    // can be followed. Mismatches are returned sorted by pod address
+    pub fn audit_depths(&self, configuration_address: &str) -> Result<Vec<DepthAnomaly>, Error> {
+        // Breadth first from the local pods gives the shortest path to each reachable pod
+        let mut computed: HashMap<String, u64> = HashMap::new();
+        let mut queue = VecDeque::new();
+        for pod in self.get_pointers(configuration_address)? {
+            if pod != configuration_address && !computed.contains_key(&pod) {
+                computed.insert(pod.clone(), 0);
+                queue.push_back(pod);
+            }
+        }
+        while let Some(pod) = queue.pop_front() {
+            let depth = computed[&pod];
+            for reference in self.get_pod_references(&pod)? {
+                if !computed.contains_key(&reference) {
+                    computed.insert(reference.clone(), depth + 1);
+                    queue.push_back(reference);
+                }
+            }
+        }
+
+        let mut pods: HashSet<String> = self.get_all_pods()?.into_iter().collect();
+        pods.extend(computed.keys().cloned());
+        pods.remove(configuration_address);
+
+        let mut anomalies = Vec::new();
+        for pod in pods {
+            let stored_depth = Some(self.get_pod_depth(&pod)?).filter(|depth| *depth != u64::MAX);
+            let computed_depth = computed.get(&pod).copied();
+            if stored_depth != computed_depth {
+                anomalies.push(DepthAnomaly {
+                    pod_address: pod,
+                    stored_depth,
+                    computed_depth,
+                });
+            }
+        }
+        anomalies.sort_by(|a, b| a.pod_address.cmp(&b.pod_address));
+
+        debug!("Found {} depth anomalies", anomalies.len());
+        Ok(anomalies)
+    }
+
+    // Set the stored depth of every reachable pod to its shortest reference path from the local
+    // pods and return the anomalies that were fixed. Unreachable pods are left as they are
+    pub fn recompute_depths(
+        &mut self,
+        configuration_address: &str,
+    ) -> Result<Vec<DepthAnomaly>, Error> {
+        let mut fixed = Vec::new();
+        for anomaly in self.audit_depths(configuration_address)? {
+            if let Some(depth) = anomaly.computed_depth {
+                self.force_set_pod_depth(&anomaly.pod_address, configuration_address, depth)?;
+                fixed.push(anomaly);
+            }
+        }
+        Ok(fixed)
+    }
+
     // Get the largest pod depth in the graph database
     pub fn get_max_pod_depth(&self) -> Result<u64, Error> {
         let query = format!(
@@ -834,40 +2058,619 @@ impl Graph {
             }
         }
 
-        debug!("Found {} pods at depth {}", pods.len(), depth);
-        Ok(pods)
+        debug!("Found {} pods at depth {}", pods.len(), depth);
+        Ok(pods)
+    }
+
+    // Get all pods that have a depth, local and referenced
+    pub fn get_all_pods(&self) -> Result<Vec<String>, Error> {
+        let query = format!(
+            "SELECT DISTINCT ?pod WHERE {{ GRAPH ?graph {{ ?pod <{HAS_DEPTH}> ?depth . }} }}"
+        );
+        debug!("All pods query: {}", query);
+
+        let mut pods = Vec::new();
+        let results = self.store.query(query.as_str())?;
+        if let QueryResults::Solutions(solutions) = results {
+            for solution in solutions.flatten() {
+                if let Some(oxigraph::model::Term::NamedNode(pod_node)) = solution.get("pod")
+                    && let Some(address) = pod_node.as_str().strip_prefix("ant://")
+                {
+                    pods.push(address.to_string());
+                }
+            }
+        }
+
+        debug!("Found {} pods", pods.len());
+        Ok(pods)
+    }
+
+    // Get the name of every named graph holding data, with the ant:// prefix stripped. Unlike
+    // get_my_pods this includes the configuration graph and graphs no configuration refers to
+    pub fn list_named_graphs(&self) -> Result<Vec<String>, Error> {
+        let query = "SELECT DISTINCT ?graph WHERE { GRAPH ?graph { ?subject ?predicate ?object . } } ORDER BY ?graph";
+        debug!("Named graphs query: {}", query);
+
+        let mut graphs = Vec::new();
+        let results = self.store.query(query)?;
+        if let QueryResults::Solutions(solutions) = results {
+            for solution in solutions.flatten() {
+                if let Some(oxigraph::model::Term::NamedNode(graph_node)) = solution.get("graph") {
+                    let graph_iri = graph_node.as_str();
+                    let name = graph_iri.strip_prefix("ant://").unwrap_or(graph_iri);
+                    graphs.push(name.to_string());
+                }
+            }
+        }
+
+        debug!("Found {} named graphs", graphs.len());
+        Ok(graphs)
+    }
+
+    // Get all pod references from the graph data
+    pub fn get_pod_references(&self, pod_address: &str) -> Result<Vec<String>, Error> {
+        let pod_iri = format!("ant://{pod_address}");
+
+        // Query for all objects in the pod's named graph that are ant:// URIs
+        let query = format!(
+            "SELECT DISTINCT ?pod_ref WHERE {{ GRAPH <{pod_iri}> {{ ?pod_ref <{HAS_ADDR_TYPE}> <{POD_REF}> . }} }}"
+        );
+        debug!("Pod references query: {}", query);
+
+        let mut references = Vec::new();
+        let results = self.store.query(query.as_str())?;
+        if let QueryResults::Solutions(solutions) = results {
+            for solution in solutions.flatten() {
+                if let Some(oxigraph::model::Term::NamedNode(ref_node)) = solution.get("pod_ref") {
+                    let ref_iri = ref_node.as_str();
+                    // Extract the address from the ant:// URI
+                    if let Some(address) = ref_iri.strip_prefix("ant://") {
+                        references.push(address.to_string());
+                    }
+                }
+            }
+        }
+
+        debug!(
+            "Found {} references in pod {}",
+            references.len(),
+            pod_address
+        );
+        Ok(references)
+    }
+
+    // Get the pods that reference the given pod, sorted. With local_only set, only the user's
+    // own pods (depth 0) are returned
+    pub fn get_pods_referencing(
+        &self,
+        pod_address: &str,
+        local_only: bool,
+    ) -> Result<Vec<String>, Error> {
+        let pod_iri = format!("ant://{pod_address}");
+        let scope_clause = Self::local_scope_clause("pod", local_only);
+
+        let query = format!(
+            r#"
+            SELECT DISTINCT ?pod WHERE {{
+                GRAPH ?pod {{ <{pod_iri}> <{HAS_ADDR_TYPE}> <{POD_REF}> . }}
+                {scope_clause}
+            }}
+            ORDER BY ?pod
+            "#
+        );
+        debug!("Pods referencing query: {}", query);
+
+        let mut pods = Vec::new();
+        let results = self.store.query(query.as_str())?;
+        if let QueryResults::Solutions(solutions) = results {
+            for solution in solutions.flatten() {
+                if let Some(oxigraph::model::Term::NamedNode(pod_node)) = solution.get("pod")
+                    && let Some(address) = pod_node.as_str().strip_prefix("ant://")
+                    && address != pod_address
+                {
+                    pods.push(address.to_string());
+                }
+            }
+        }
+
+        debug!("Found {} pods referencing {}", pods.len(), pod_address);
+        Ok(pods)
+    }
+
+    // Dump a pod's named graph in TriG format
+    pub fn get_pod_graph_data(&self, pod_address: &str) -> Result<Vec<u8>, Error> {
+        let pod_iri = format!("ant://{pod_address}");
+        let pod = NamedNodeRef::new(pod_iri.as_str())?;
+
+        let mut buffer = Vec::new();
+        self.store
+            .dump_graph_to_writer(pod, RdfFormat::TriG, &mut buffer)?;
+        Ok(buffer)
+    }
+
+    // Dump every named graph in the store to a single file for backups. Unlike the per pod TriG
+    // dumps this keeps the graph names, so only dataset formats (TriG, N-Quads) are accepted
+    pub fn export_all(&self, writer: impl std::io::Write, format: RdfFormat) -> Result<(), Error> {
+        Self::check_dataset_format(format)?;
+        self.store.dump_to_writer(format, writer)?;
+        info!("Exported {} quads from the graph store", self.store.len()?);
+        Ok(())
+    }
+
+    // Load a file written by export_all, putting each quad back in the named graph it was
+    // exported from. Quads are added to what is already in the store, so import into a fresh
+    // Graph to restore a backup as it was
+    pub fn import_all(
+        &mut self,
+        reader: impl std::io::Read,
+        format: RdfFormat,
+    ) -> Result<(), Error> {
+        Self::check_dataset_format(format)?;
+        self.store.load_from_reader(format, reader)?;
+        info!(
+            "Imported quads, graph store now holds {}",
+            self.store.len()?
+        );
+        Ok(())
+    }
+
+    // Formats without named graphs would merge every pod into the default graph
+    fn check_dataset_format(format: RdfFormat) -> Result<(), Error> {
+        if !format.supports_datasets() {
+            return Err(Error::InvalidInput(format!(
+                "{} cannot hold named graphs, use TriG or N-Quads",
+                format.name()
+            )));
+        }
+        Ok(())
+    }
+
+    // Check if a pod's graph contains a reference to another pod
+    pub fn has_pod_reference(
+        &self,
+        pod_address: &str,
+        pod_ref_address: &str,
+    ) -> Result<bool, Error> {
+        let pod_iri = format!("ant://{pod_address}");
+        let pod_ref_iri = format!("ant://{pod_ref_address}");
+
+        let query = format!(
+            "ASK {{ GRAPH <{pod_iri}> {{ <{pod_ref_iri}> <{HAS_ADDR_TYPE}> <{POD_REF}> . }} }}"
+        );
+        debug!("Pod reference query: {}", query);
+
+        match self.store.query(query.as_str())? {
+            QueryResults::Boolean(found) => Ok(found),
+            _ => Ok(false),
+        }
+    }
+
+    // Work out which way the references between two pods point
+    pub fn reference_relationship(&self, pod_a: &str, pod_b: &str) -> Result<Relationship, Error> {
+        let a_to_b = self.has_pod_reference(pod_a, pod_b)?;
+        let b_to_a = self.has_pod_reference(pod_b, pod_a)?;
+
+        Ok(match (a_to_b, b_to_a) {
+            (true, true) => Relationship::Mutual,
+            (true, false) => Relationship::AtoB,
+            (false, true) => Relationship::BtoA,
+            (false, false) => Relationship::None,
+        })
+    }
+
+    // Get the (name, address) of every named pod, sorted by name.
+    // A pod's name is stored in its own graph, so only pods named there are returned
+    pub fn get_pod_names(&self) -> Result<Vec<(String, String)>, Error> {
+        let hidden_clause = self.hidden_configuration_clause("pod");
+        let query = format!(
+            "SELECT ?pod ?name WHERE {{ GRAPH ?pod {{ ?pod <{HAS_NAME}> ?name . }} {hidden_clause} }} ORDER BY ?name ?pod"
+        );
+        debug!("Pod names query: {}", query);
+
+        let mut names = Vec::new();
+        let results = self.store.query(query.as_str())?;
+        if let QueryResults::Solutions(solutions) = results {
+            for solution in solutions.flatten() {
+                if let (
+                    Some(oxigraph::model::Term::NamedNode(pod_node)),
+                    Some(oxigraph::model::Term::Literal(name)),
+                ) = (solution.get("pod"), solution.get("name"))
+                    && let Some(address) = pod_node.as_str().strip_prefix("ant://")
+                {
+                    names.push((name.value().to_string(), address.to_string()));
+                }
+            }
+        }
+
+        debug!("Found {} pod names", names.len());
+        Ok(names)
+    }
+
+    // Get all pods ordered by modified date, most recent first. Pods without a modified date
+    // sort last
+    pub fn get_pods_by_modified(&self, limit: Option<u64>) -> Result<Vec<PodInfo>, Error> {
+        let limit_clause = if let Some(l) = limit {
+            format!("LIMIT {l}")
+        } else {
+            String::new()
+        };
+        let hidden_clause = self.hidden_pods_clause("pod");
+
+        let query = format!(
+            r#"
+            SELECT ?pod (SAMPLE(?name) AS ?pod_name) (MIN(?created) AS ?creation) (MAX(?modified) AS ?last_modified) (SAMPLE(?depth) AS ?pod_depth) (SAMPLE(?icon) AS ?pod_icon) WHERE {{
+                GRAPH ?config {{ ?pod <{HAS_DEPTH}> ?depth . }}
+                OPTIONAL {{ GRAPH ?pod {{ ?pod <{HAS_NAME}> ?name . }} }}
+                OPTIONAL {{ GRAPH ?pod {{ ?pod <{HAS_CREATION_DATE}> ?created . }} }}
+                OPTIONAL {{ GRAPH ?pod {{ ?pod <{HAS_MODIFIED_DATE}> ?modified . }} }}
+                OPTIONAL {{ GRAPH ?pod {{ ?pod <{HAS_ICON}> ?icon . }} }}
+                {hidden_clause}
+            }}
+            GROUP BY ?pod
+            ORDER BY DESC(?last_modified) ?pod
+            {limit_clause}
+            "#
+        );
+        debug!("Pods by modified date query: {}", query);
+
+        let mut pods = Vec::new();
+        let results = self.store.query(query.as_str())?;
+        if let QueryResults::Solutions(solutions) = results {
+            for solution in solutions.flatten() {
+                let Some(oxigraph::model::Term::NamedNode(pod_node)) = solution.get("pod") else {
+                    continue;
+                };
+                let Some(address) = pod_node.as_str().strip_prefix("ant://") else {
+                    continue;
+                };
+                let literal = |var: &str| match solution.get(var) {
+                    Some(oxigraph::model::Term::Literal(literal)) => {
+                        Some(literal.value().to_string())
+                    }
+                    _ => None,
+                };
+                pods.push(PodInfo {
+                    address: address.to_string(),
+                    name: literal("pod_name"),
+                    created: literal("creation"),
+                    modified: literal("last_modified"),
+                    depth: literal("pod_depth").and_then(|depth| depth.parse().ok()),
+                    icon: match solution.get("pod_icon") {
+                        Some(oxigraph::model::Term::NamedNode(icon_node)) => icon_node
+                            .as_str()
+                            .strip_prefix("ant://")
+                            .map(str::to_string),
+                        _ => None,
+                    },
+                    ..Default::default()
+                });
+            }
+        }
+
+        debug!("Found {} pods", pods.len());
+        Ok(pods)
+    }
+
+    // Get the summary of a single pod: its name, dates, depth and icon plus the number of
+    // scratchpads and subjects in it. The pod graph must exist
+    pub fn get_pod_info(&self, pod_address: &str) -> Result<PodInfo, Error> {
+        let pod_iri = format!("ant://{pod_address}");
+        let pod = NamedNodeRef::new(&pod_iri)?;
+        if !self.store.contains_named_graph(pod)? {
+            return Err(Error::Graph(StorageError::Io(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("Pod {pod_address} not found"),
+            ))));
+        }
+
+        let query = format!(
+            r#"
+            SELECT (SAMPLE(?name) AS ?pod_name) (MIN(?created) AS ?creation) (MAX(?modified) AS ?last_modified) (SAMPLE(?depth) AS ?pod_depth) (SAMPLE(?icon) AS ?pod_icon) WHERE {{
+                OPTIONAL {{ GRAPH ?config {{ {pod} <{HAS_DEPTH}> ?depth . }} }}
+                OPTIONAL {{ GRAPH {pod} {{ {pod} <{HAS_NAME}> ?name . }} }}
+                OPTIONAL {{ GRAPH {pod} {{ {pod} <{HAS_CREATION_DATE}> ?created . }} }}
+                OPTIONAL {{ GRAPH {pod} {{ {pod} <{HAS_MODIFIED_DATE}> ?modified . }} }}
+                OPTIONAL {{ GRAPH {pod} {{ {pod} <{HAS_ICON}> ?icon . }} }}
+            }}
+            "#
+        );
+        debug!("Pod info query: {}", query);
+
+        let mut info = PodInfo {
+            address: pod_address.to_string(),
+            ..Default::default()
+        };
+        let results = self.store.query(query.as_str())?;
+        if let QueryResults::Solutions(solutions) = results {
+            for solution in solutions.flatten() {
+                let literal = |var: &str| match solution.get(var) {
+                    Some(oxigraph::model::Term::Literal(literal)) => {
+                        Some(literal.value().to_string())
+                    }
+                    _ => None,
+                };
+                info.name = literal("pod_name");
+                info.created = literal("creation");
+                info.modified = literal("last_modified");
+                info.depth = literal("pod_depth").and_then(|depth| depth.parse().ok());
+                info.icon = match solution.get("pod_icon") {
+                    Some(oxigraph::model::Term::NamedNode(icon_node)) => icon_node
+                        .as_str()
+                        .strip_prefix("ant://")
+                        .map(str::to_string),
+                    _ => None,
+                };
+            }
+        }
+
+        info.scratchpad_count = Some(self.get_pod_scratchpads(pod_address)?.len());
+
+        // Same notion of a subject as get_pod_subject_digests
+        let query = format!(
+            r#"
+            SELECT (COUNT(DISTINCT ?subject) AS ?n) WHERE {{
+                GRAPH {pod} {{
+                    ?subject ?predicate ?object .
+                    FILTER(isIRI(?subject) && ?subject != {pod})
+                    FILTER NOT EXISTS {{ ?subject <{HAS_INDEX}> ?index . }}
+                }}
+            }}
+            "#
+        );
+        debug!("Pod subject count query: {}", query);
+
+        let results = self.store.query(query.as_str())?;
+        if let QueryResults::Solutions(solutions) = results {
+            for solution in solutions.flatten() {
+                if let Some(oxigraph::model::Term::Literal(literal)) = solution.get("n") {
+                    info.subject_count = literal.value().parse().ok();
+                }
+            }
+        }
+
+        debug!("Pod info for {}: {:?}", pod_address, info);
+        Ok(info)
+    }
+
+    // Get the subjects whose modified date is within [start, end), newest first. Pods and
+    // scratchpads are not included
+    pub fn subjects_modified_between(
+        &self,
+        start: &chrono::DateTime<chrono::Utc>,
+        end: &chrono::DateTime<chrono::Utc>,
+        scope: &SearchScope,
+    ) -> Result<Vec<String>, Error> {
+        let start = start.to_rfc3339();
+        let end = end.to_rfc3339();
+        let scope_clause = Self::search_scope_clause("graph", scope)?;
+        let hidden_clause = self.hidden_pods_clause("graph");
+
+        let query = format!(
+            r#"
+            PREFIX xsd: <http://www.w3.org/2001/XMLSchema#>
+            SELECT ?subject (MAX(?date) AS ?last_modified) WHERE {{
+                GRAPH ?graph {{
+                    ?subject <{HAS_MODIFIED_DATE}> ?modified .
+                    FILTER(?subject != ?graph)
+                    FILTER NOT EXISTS {{ ?subject <{HAS_INDEX}> ?index . }}
+                }}
+                {scope_clause}
+                {hidden_clause}
+                BIND(xsd:dateTime(?modified) AS ?date)
+                FILTER(?date >= "{start}"^^xsd:dateTime && ?date < "{end}"^^xsd:dateTime)
+            }}
+            GROUP BY ?subject
+            ORDER BY DESC(?last_modified) ?subject
+            "#
+        );
+        debug!("Subjects modified between query: {}", query);
+
+        let mut subjects = Vec::new();
+        if let QueryResults::Solutions(solutions) = self.store.query(query.as_str())? {
+            for solution in solutions.flatten() {
+                if let Some(oxigraph::model::Term::NamedNode(node)) = solution.get("subject") {
+                    let address = node.as_str();
+                    subjects.push(
+                        address
+                            .strip_prefix("ant://")
+                            .unwrap_or(address)
+                            .to_string(),
+                    );
+                }
+            }
+        }
+
+        debug!("Found {} subjects modified in the window", subjects.len());
+        Ok(subjects)
+    }
+
+    // Get the name of a pod, if it has one
+    pub fn get_pod_name(&self, pod_address: &str) -> Result<Option<String>, Error> {
+        let pod_iri = format!("ant://{pod_address}");
+
+        let query = format!(
+            "SELECT ?name WHERE {{ GRAPH ?graph {{ <{pod_iri}> <{HAS_NAME}> ?name . }} }} LIMIT 1"
+        );
+        debug!("Pod name query: {}", query);
+
+        let results = self.store.query(query.as_str())?;
+        if let QueryResults::Solutions(solutions) = results {
+            for solution in solutions.flatten() {
+                if let Some(oxigraph::model::Term::Literal(literal)) = solution.get("name") {
+                    return Ok(Some(literal.value().to_string()));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    // Export a subject and the subjects it references, up to max_depth hops away, as a flattened
+    // JSON-LD document. Blank nodes are always followed since they are part of their parent
+    // subject. Triples using the colonylib vocabulary are pod bookkeeping and are left out
+    pub fn export_subject_bundle(
+        &self,
+        subject_address: &str,
+        max_depth: u8,
+    ) -> Result<Value, Error> {
+        let root = oxigraph::model::Subject::NamedNode(oxigraph::model::NamedNode::new(format!(
+            "ant://{subject_address}"
+        ))?);
+
+        let mut visited: HashSet<oxigraph::model::Subject> = HashSet::from([root.clone()]);
+        let mut queue = VecDeque::from([(root, 0u8)]);
+        let mut nodes = Vec::new();
+        while let Some((subject, depth)) = queue.pop_front() {
+            let mut types: Vec<String> = Vec::new();
+            let mut properties: serde_json::Map<String, Value> = serde_json::Map::new();
+            let mut seen: HashSet<(String, oxigraph::model::Term)> = HashSet::new();
+
+            for quad in self
+                .store
+                .quads_for_pattern(Some(subject.as_ref()), None, None, None)
+            {
+                let quad = quad?;
+                let predicate = quad.predicate.as_str();
+                if predicate.starts_with("ant://colonylib/")
+                    || !seen.insert((predicate.to_string(), quad.object.clone()))
+                {
+                    continue;
+                }
+
+                // Queue referenced subjects that have data in the store
+                let next = match &quad.object {
+                    oxigraph::model::Term::NamedNode(node) if depth < max_depth => {
+                        Some((oxigraph::model::Subject::NamedNode(node.clone()), depth + 1))
+                    }
+                    oxigraph::model::Term::BlankNode(node) => {
+                        Some((oxigraph::model::Subject::BlankNode(node.clone()), depth))
+                    }
+                    _ => None,
+                };
+                if let Some((next_subject, next_depth)) = next
+                    && !visited.contains(&next_subject)
+                    && self
+                        .store
+                        .quads_for_pattern(Some(next_subject.as_ref()), None, None, None)
+                        .next()
+                        .is_some()
+                {
+                    visited.insert(next_subject.clone());
+                    queue.push_back((next_subject, next_depth));
+                }
+
+                if predicate == HAS_ADDR_TYPE
+                    && let oxigraph::model::Term::NamedNode(type_node) = &quad.object
+                {
+                    types.push(type_node.as_str().to_string());
+                    continue;
+                }
+                let value = Self::jsonld_value(&quad.object);
+                if let Value::Array(values) = properties
+                    .entry(predicate)
+                    .or_insert_with(|| Value::Array(Vec::new()))
+                {
+                    values.push(value);
+                }
+            }
+
+            if types.is_empty() && properties.is_empty() {
+                continue;
+            }
+            let mut node = serde_json::Map::new();
+            node.insert("@id".to_string(), Value::String(Self::jsonld_id(&subject)));
+            if !types.is_empty() {
+                node.insert(
+                    "@type".to_string(),
+                    Value::Array(types.into_iter().map(Value::String).collect()),
+                );
+            }
+            node.extend(properties);
+            nodes.push(Value::Object(node));
+        }
+
+        debug!(
+            "Exported {} nodes for subject {}",
+            nodes.len(),
+            subject_address
+        );
+        Ok(serde_json::json!({ "@graph": nodes }))
     }
 
-    // Get all pod references from the graph data
-    pub fn get_pod_references(&self, pod_address: &str) -> Result<Vec<String>, Error> {
-        let pod_iri = format!("ant://{pod_address}");
+    // JSON-LD node identifier of a subject
+    fn jsonld_id(subject: &oxigraph::model::Subject) -> String {
+        match subject {
+            oxigraph::model::Subject::NamedNode(node) => node.as_str().to_string(),
+            other => other.to_string(),
+        }
+    }
 
-        // Query for all objects in the pod's named graph that are ant:// URIs
-        let query = format!(
-            "SELECT DISTINCT ?pod_ref WHERE {{ GRAPH <{pod_iri}> {{ ?pod_ref <{HAS_ADDR_TYPE}> <{POD_REF}> . }} }}"
-        );
-        debug!("Pod references query: {}", query);
+    // JSON-LD value object of an RDF term
+    fn jsonld_value(term: &oxigraph::model::Term) -> Value {
+        if let oxigraph::model::Term::Literal(literal) = term {
+            if let Some(language) = literal.language() {
+                serde_json::json!({ "@value": literal.value(), "@language": language })
+            } else if literal.datatype() == oxigraph::model::vocab::xsd::STRING {
+                serde_json::json!({ "@value": literal.value() })
+            } else {
+                serde_json::json!({ "@value": literal.value(), "@type": literal.datatype().as_str() })
+            }
+        } else if let oxigraph::model::Term::NamedNode(node) = term {
+            serde_json::json!({ "@id": node.as_str() })
+        } else {
+            serde_json::json!({ "@id": term.to_string() })
+        }
+    }
 
-        let mut references = Vec::new();
-        let results = self.store.query(query.as_str())?;
-        if let QueryResults::Solutions(solutions) = results {
-            for solution in solutions.flatten() {
-                if let Some(oxigraph::model::Term::NamedNode(ref_node)) = solution.get("pod_ref") {
-                    let ref_iri = ref_node.as_str();
-                    // Extract the address from the ant:// URI
-                    if let Some(address) = ref_iri.strip_prefix("ant://") {
-                        references.push(address.to_string());
-                    }
-                }
+    // Export the pod reference network as a small Turtle document. Each pod gets its name,
+    // depth and a REFERENCES edge to every pod it references. Subject data is left out
+    pub fn export_reference_graph(&self) -> Result<String, Error> {
+        let export_store = Store::new()?;
+        let name_predicate = NamedNodeRef::new(HAS_NAME)?;
+        let depth_predicate = NamedNodeRef::new(HAS_DEPTH)?;
+        let references_predicate = NamedNodeRef::new(REFERENCES)?;
+
+        let pods = self.get_all_pods()?;
+        for pod_address in &pods {
+            let pod_iri = format!("ant://{pod_address}");
+            let pod = NamedNodeRef::new(pod_iri.as_str())?;
+
+            if let Some(name) = self.get_pod_name(pod_address)? {
+                export_store.insert(QuadRef::new(
+                    pod,
+                    name_predicate,
+                    LiteralRef::new_simple_literal(name.as_str()),
+                    GraphNameRef::DefaultGraph,
+                ))?;
+            }
+
+            let depth = self.get_pod_depth(pod_address)?.to_string();
+            export_store.insert(QuadRef::new(
+                pod,
+                depth_predicate,
+                LiteralRef::new_simple_literal(depth.as_str()),
+                GraphNameRef::DefaultGraph,
+            ))?;
+
+            for reference in self.get_pod_references(pod_address)? {
+                let reference_iri = format!("ant://{reference}");
+                export_store.insert(QuadRef::new(
+                    pod,
+                    references_predicate,
+                    NamedNodeRef::new(reference_iri.as_str())?,
+                    GraphNameRef::DefaultGraph,
+                ))?;
             }
         }
+        debug!("Exported reference graph for {} pods", pods.len());
 
-        debug!(
-            "Found {} references in pod {}",
-            references.len(),
-            pod_address
-        );
-        Ok(references)
+        let mut buffer = Vec::new();
+        export_store.dump_graph_to_writer(
+            GraphNameRef::DefaultGraph,
+            RdfFormat::Turtle,
+            &mut buffer,
+        )?;
+        Ok(String::from_utf8(buffer)?)
     }
 
     // Get all free pointers from the graph data
@@ -934,6 +2737,35 @@ impl Graph {
         Ok(scratchpads)
     }
 
+    // Get all abandoned pointers and scratchpads from the graph data
+    pub fn get_abandoned(&self, pod_address: &str) -> Result<Vec<String>, Error> {
+        let pod_iri = format!("ant://{pod_address}");
+
+        let query = format!(
+            "SELECT DISTINCT ?address WHERE {{ GRAPH <{pod_iri}> {{ ?address <{HAS_ADDR_TYPE}> <{ABANDONED}> . }} }}"
+        );
+        debug!("Abandoned addresses query: {}", query);
+
+        let mut addresses = Vec::new();
+        let results = self.store.query(query.as_str())?;
+        if let QueryResults::Solutions(solutions) = results {
+            for solution in solutions.flatten() {
+                if let Some(oxigraph::model::Term::NamedNode(node)) = solution.get("address")
+                    && let Some(address) = node.as_str().strip_prefix("ant://")
+                {
+                    addresses.push(address.to_string());
+                }
+            }
+        }
+
+        debug!(
+            "Found {} abandoned addresses in pod {}",
+            addresses.len(),
+            pod_address
+        );
+        Ok(addresses)
+    }
+
     // Get all pointers from the graph data
     pub fn get_pointers(&self, pod_address: &str) -> Result<Vec<String>, Error> {
         let pod_iri = format!("ant://{pod_address}");
@@ -1053,11 +2885,235 @@ impl Graph {
         Ok(subjects)
     }
 
+    // Digest of each subject's data in a pod, used to find the subjects that changed between two
+    // versions of the pod. Nested blank nodes are folded into their parent subject so the digest
+    // doesn't depend on the blank node labels picked at load time. The pod itself and its
+    // scratchpads are left out
+    pub fn get_pod_subject_digests(
+        &self,
+        pod_address: &str,
+    ) -> Result<HashMap<String, String>, Error> {
+        use sha3::{Digest, Sha3_256};
+
+        let pod_iri = format!("ant://{pod_address}");
+        let pod = NamedNodeRef::new(&pod_iri)?;
+
+        let mut subjects: HashMap<String, Vec<(String, oxigraph::model::Term)>> = HashMap::new();
+        let mut blank_nodes: HashMap<String, Vec<(String, oxigraph::model::Term)>> = HashMap::new();
+        let mut scratchpads = HashSet::new();
+        for quad in
+            self.store
+                .quads_for_pattern(None, None, None, Some(GraphNameRef::NamedNode(pod)))
+        {
+            let quad = quad?;
+            let predicate = quad.predicate.as_str().to_string();
+            if let oxigraph::model::Subject::NamedNode(subject) = &quad.subject {
+                if subject.as_str() == pod_iri {
+                    continue;
+                }
+                if let Some(address) = subject.as_str().strip_prefix("ant://") {
+                    if predicate == HAS_INDEX {
+                        scratchpads.insert(address.to_string());
+                    }
+                    subjects
+                        .entry(address.to_string())
+                        .or_default()
+                        .push((predicate, quad.object));
+                }
+            } else if let oxigraph::model::Subject::BlankNode(blank_node) = &quad.subject {
+                blank_nodes
+                    .entry(blank_node.as_str().to_string())
+                    .or_default()
+                    .push((predicate, quad.object));
+            }
+        }
+
+        let mut digests = HashMap::new();
+        for (address, triples) in subjects {
+            if scratchpads.contains(&address) {
+                continue;
+            }
+            let canonical = Self::canonical_triples(&triples, &blank_nodes, 0);
+            let digest = hex::encode(Sha3_256::digest(canonical.as_bytes()));
+            digests.insert(address, digest);
+        }
+        Ok(digests)
+    }
+
+    // Sorted text form of a subject's triples with blank node objects expanded in place
+    fn canonical_triples(
+        triples: &[(String, oxigraph::model::Term)],
+        blank_nodes: &HashMap<String, Vec<(String, oxigraph::model::Term)>>,
+        depth: usize,
+    ) -> String {
+        let mut lines: Vec<String> = triples
+            .iter()
+            .map(|(predicate, object)| match object {
+                oxigraph::model::Term::BlankNode(blank_node) if depth < 16 => {
+                    let nested = blank_nodes
+                        .get(blank_node.as_str())
+                        .map(|nested| Self::canonical_triples(nested, blank_nodes, depth + 1))
+                        .unwrap_or_default();
+                    format!("<{predicate}> [{nested}]")
+                }
+                oxigraph::model::Term::BlankNode(_) => format!("<{predicate}> []"),
+                _ => format!("<{predicate}> {object}"),
+            })
+            .collect();
+        lines.sort();
+        lines.join(" ; ")
+    }
+
+    // Find all subjects that do not have the given predicate, optionally scoped to a single pod.
+    // Pod, scratchpad, and pod reference bookkeeping subjects are excluded from the report.
+    pub fn find_subjects_missing(
+        &self,
+        predicate_uri: &str,
+        within_pod: Option<&str>,
+    ) -> Result<Vec<String>, Error> {
+        // Validate the predicate before building the query
+        let predicate = NamedNodeRef::new(predicate_uri)?;
+        let predicate_uri = predicate.as_str();
+
+        let (graph_clause, missing_clause) = match within_pod {
+            Some(pod_address) => {
+                let pod_iri = format!("ant://{pod_address}");
+                let pod = NamedNodeRef::new(&pod_iri)?;
+                let pod_iri = pod.as_str();
+                (
+                    format!("VALUES ?graph {{ <{pod_iri}> }}"),
+                    format!("GRAPH <{pod_iri}> {{ ?subject <{predicate_uri}> ?missing . }}"),
+                )
+            }
+            None => (
+                String::new(),
+                format!("GRAPH ?missing_graph {{ ?subject <{predicate_uri}> ?missing . }}"),
+            ),
+        };
+
+        let query = format!(
+            r#"
+            SELECT DISTINCT ?subject WHERE {{
+                {graph_clause}
+                GRAPH ?graph {{
+                    ?subject ?p ?o .
+                    FILTER(STRSTARTS(STR(?subject), "ant://"))
+                }}
+                FILTER NOT EXISTS {{ {missing_clause} }}
+                FILTER NOT EXISTS {{ GRAPH ?index_graph {{ ?subject <{HAS_INDEX}> ?index . }} }}
+                FILTER NOT EXISTS {{
+                    GRAPH ?type_graph {{
+                        ?subject <{HAS_ADDR_TYPE}> ?addr_type .
+                        FILTER(STRSTARTS(STR(?addr_type), "ant://colonylib/"))
+                    }}
+                }}
+            }}
+            ORDER BY ?subject
+            "#
+        );
+        debug!("Subjects missing predicate query: {}", query);
+
+        let mut subjects = Vec::new();
+        let results = self.store.query(query.as_str())?;
+        if let QueryResults::Solutions(solutions) = results {
+            for solution in solutions.flatten() {
+                if let Some(oxigraph::model::Term::NamedNode(subject_node)) =
+                    solution.get("subject")
+                    && let Some(address) = subject_node.as_str().strip_prefix("ant://")
+                {
+                    subjects.push(address.to_string());
+                }
+            }
+        }
+
+        debug!(
+            "Found {} subjects missing predicate {}",
+            subjects.len(),
+            predicate_uri
+        );
+        Ok(subjects)
+    }
+
+    // Find all subjects that only carry colonylib bookkeeping predicates (type, index, depth, key
+    // count and dates) and no user metadata, optionally scoped to a single pod. Pod, scratchpad,
+    // and pod reference bookkeeping subjects are excluded from the report.
+    pub fn find_empty_subjects(&self, within_pod: Option<&str>) -> Result<Vec<String>, Error> {
+        let internal_predicates = [
+            HAS_ADDR_TYPE,
+            HAS_INDEX,
+            HAS_DEPTH,
+            KEY_COUNT,
+            HAS_CREATION_DATE,
+            HAS_MODIFIED_DATE,
+        ]
+        .iter()
+        .map(|predicate| format!("<{predicate}>"))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+        let (graph_clause, data_graph) = match within_pod {
+            Some(pod_address) => {
+                let pod_iri = format!("ant://{pod_address}");
+                let pod = NamedNodeRef::new(&pod_iri)?;
+                (
+                    format!("VALUES ?graph {{ <{}> }}", pod.as_str()),
+                    format!("<{}>", pod.as_str()),
+                )
+            }
+            None => (String::new(), "?data_graph".to_string()),
+        };
+
+        let query = format!(
+            r#"
+            SELECT DISTINCT ?subject WHERE {{
+                {graph_clause}
+                GRAPH ?graph {{
+                    ?subject ?p ?o .
+                    FILTER(STRSTARTS(STR(?subject), "ant://"))
+                }}
+                FILTER NOT EXISTS {{
+                    GRAPH {data_graph} {{
+                        ?subject ?data_predicate ?data .
+                        FILTER(?data_predicate NOT IN ({internal_predicates}))
+                    }}
+                }}
+                FILTER NOT EXISTS {{ GRAPH ?index_graph {{ ?subject <{HAS_INDEX}> ?index . }} }}
+                FILTER NOT EXISTS {{
+                    GRAPH ?type_graph {{
+                        ?subject <{HAS_ADDR_TYPE}> ?addr_type .
+                        FILTER(STRSTARTS(STR(?addr_type), "ant://colonylib/"))
+                    }}
+                }}
+            }}
+            ORDER BY ?subject
+            "#
+        );
+        debug!("Empty subjects query: {}", query);
+
+        let mut subjects = Vec::new();
+        let results = self.store.query(query.as_str())?;
+        if let QueryResults::Solutions(solutions) = results {
+            for solution in solutions.flatten() {
+                if let Some(oxigraph::model::Term::NamedNode(subject_node)) =
+                    solution.get("subject")
+                    && let Some(address) = subject_node.as_str().strip_prefix("ant://")
+                {
+                    subjects.push(address.to_string());
+                }
+            }
+        }
+
+        debug!("Found {} empty subjects", subjects.len());
+        Ok(subjects)
+    }
+
     // Get all of the user's pods
     pub fn get_my_pods(&self, configuration_address: &str) -> Result<String, Error> {
         let configuration_iri = format!("ant://{configuration_address}");
         let configuration_iri = configuration_iri.as_str();
 
+        let hidden_clause = self.hidden_configuration_clause("subject");
+
         // Query for all subjects that have HAS_ADDR_TYPE predicate with POD object
         // Returns all information stored in all graphs for these subjects
         let query = format!(
@@ -1068,6 +3124,7 @@ impl Graph {
                         GRAPH <{configuration_iri}> {{
                             ?subject <{HAS_ADDR_TYPE}> <{POD}> .
                         }}
+                        {hidden_clause}
                     }}
                 }}
                 GRAPH ?graph {{
@@ -1093,6 +3150,137 @@ impl Graph {
         Ok(json_str)
     }
 
+    // Add or remove a tag on a subject in a pod graph.
+    // Returns the updated pod and configuration graphs in TriG format
+    pub fn subject_tag_entry(
+        &mut self,
+        pod_address: &str,
+        subject_address: &str,
+        configuration_address: &str,
+        tag: &str,
+        add: bool,
+    ) -> Result<(Vec<u8>, Vec<u8>), Error> {
+        let pod_iri = format!("ant://{pod_address}");
+        let subject_iri = format!("ant://{subject_address}");
+        let default_graph_count = self.default_graph_triple_count()?;
+
+        // The tag is always a literal, even if it looks like a URI
+        let quad = QuadRef::new(
+            NamedNodeRef::new(subject_iri.as_str())?,
+            NamedNodeRef::new(HAS_TAG)?,
+            LiteralRef::new_simple_literal(tag),
+            GraphNameRef::NamedNode(NamedNodeRef::new(pod_iri.as_str())?),
+        );
+        if add {
+            self.store.insert(quad)?;
+            debug!("Tag {} added to subject {}", tag, subject_address);
+        } else {
+            self.store.remove(quad)?;
+            debug!("Tag {} removed from subject {}", tag, subject_address);
+        }
+
+        let (buffer, configuration) =
+            self.touch_pod_modified(pod_address, configuration_address)?;
+
+        self.ensure_default_graph_unchanged(default_graph_count, "subject_tag_entry")?;
+
+        Ok((buffer, configuration))
+    }
+
+    // Get the subjects with a tag, sorted by address. Uses the store index for the
+    // (predicate, object) pair instead of a SPARQL scan
+    pub fn get_subjects_by_tag(&self, tag: &str, local_only: bool) -> Result<Vec<String>, Error> {
+        let mut graph_in_scope: HashMap<String, bool> = HashMap::new();
+        let mut subjects = Vec::new();
+        for quad in self.store.quads_for_pattern(
+            None,
+            Some(NamedNodeRef::new(HAS_TAG)?),
+            Some(TermRef::Literal(LiteralRef::new_simple_literal(tag))),
+            None,
+        ) {
+            let quad = quad?;
+            let oxigraph::model::GraphName::NamedNode(graph) = &quad.graph_name else {
+                continue;
+            };
+            let graph_iri = graph.as_str();
+
+            let in_scope = match graph_in_scope.get(graph_iri) {
+                Some(in_scope) => *in_scope,
+                None => {
+                    let in_scope = if self.hidden_configuration.as_deref() == Some(graph_iri) {
+                        false
+                    } else if local_only {
+                        // Only the user's own pods (depth 0)
+                        match graph_iri.strip_prefix("ant://") {
+                            Some(pod_address) => self.get_pod_depth(pod_address)? == 0,
+                            None => false,
+                        }
+                    } else {
+                        true
+                    };
+                    graph_in_scope.insert(graph_iri.to_string(), in_scope);
+                    in_scope
+                }
+            };
+
+            if in_scope
+                && let oxigraph::model::Subject::NamedNode(subject) = &quad.subject
+                && let Some(address) = subject.as_str().strip_prefix("ant://")
+            {
+                subjects.push(address.to_string());
+            }
+        }
+
+        subjects.sort();
+        subjects.dedup();
+        debug!("Found {} subjects with tag {}", subjects.len(), tag);
+        Ok(subjects)
+    }
+
+    // Get every tag in use with the number of subjects carrying it, sorted by tag
+    pub fn get_all_tags(&self) -> Result<Vec<(String, usize)>, Error> {
+        let mut tagged_subjects: HashMap<String, HashSet<String>> = HashMap::new();
+        for quad in
+            self.store
+                .quads_for_pattern(None, Some(NamedNodeRef::new(HAS_TAG)?), None, None)
+        {
+            let quad = quad?;
+            if let oxigraph::model::GraphName::NamedNode(graph) = &quad.graph_name
+                && self.hidden_configuration.as_deref() == Some(graph.as_str())
+            {
+                continue;
+            }
+            if let oxigraph::model::Term::Literal(tag) = &quad.object {
+                tagged_subjects
+                    .entry(tag.value().to_string())
+                    .or_default()
+                    .insert(quad.subject.to_string());
+            }
+        }
+
+        let mut tags: Vec<(String, usize)> = tagged_subjects
+            .into_iter()
+            .map(|(tag, subjects)| (tag, subjects.len()))
+            .collect();
+        tags.sort();
+        Ok(tags)
+    }
+
+    // Check that TriG pod data parses completely, returning the first parse error.
+    // Uses the same parser settings as load_pod_into_graph
+    pub fn validate_trig(&self, trig_data: &str) -> Result<usize, Error> {
+        let mut count = 0;
+        for quad in RdfParser::from_format(RdfFormat::TriG)
+            .without_named_graphs()
+            .for_reader(Cursor::new(trig_data))
+        {
+            quad?;
+            count += 1;
+        }
+        debug!("Validated {} statements of TriG data", count);
+        Ok(count)
+    }
+
     // Load TriG data into the graph database
     pub fn load_pod_into_graph(&mut self, pod_address: &str, trig_data: &str) -> Result<(), Error> {
         if !trig_data.trim().is_empty() {
@@ -1122,15 +3310,58 @@ impl Graph {
         Ok(())
     }
 
+    // Replace a pod graph with a saved version of its TriG data. The saved version may list
+    // scratchpads that the pod no longer owns, so the scratchpad entries are reset to the
+    // current scratchpads before the modified dates are updated
+    pub fn restore_pod_graph(
+        &mut self,
+        pod_address: &str,
+        trig_data: &str,
+        current_scratchpads: &[String],
+        configuration_address: &str,
+    ) -> Result<(Vec<u8>, Vec<u8>), Error> {
+        let pod_iri = format!("ant://{pod_address}");
+        let pod_iri = pod_iri.as_str();
+
+        self.load_pod_into_graph(pod_address, trig_data)?;
+
+        for scratchpad in self.get_pod_scratchpads(pod_address)? {
+            self.remove_scratchpad_entry(pod_address, &scratchpad)?;
+        }
+        for (index, scratchpad) in current_scratchpads.iter().enumerate() {
+            let scratchpad_iri = format!("ant://{}", scratchpad.trim());
+            self.put_quad(
+                &scratchpad_iri,
+                HAS_INDEX,
+                &index.to_string(),
+                Some(pod_iri),
+            )?;
+        }
+
+        self.touch_pod_modified(pod_address, configuration_address)
+    }
+
     // Browse all subjects on the network and return their name, @type, and description
     // ordered by pod depth
     pub fn browse(&self, limit: Option<u64>) -> Result<String, Error> {
+        self.browse_scoped(limit, false)
+    }
+
+    // Browse only the subjects stored in the user's own pods (depth 0)
+    pub fn browse_local(&self, limit: Option<u64>) -> Result<String, Error> {
+        self.browse_scoped(limit, true)
+    }
+
+    fn browse_scoped(&self, limit: Option<u64>, local_only: bool) -> Result<String, Error> {
         let limit_clause = if let Some(l) = limit {
             format!("LIMIT {l}")
         } else {
             String::new()
         };
 
+        let scope_clause = Self::local_scope_clause("graph", local_only);
+        let hidden_clause = self.hidden_pods_clause("graph");
+
         let query = format!(
             r#"
             SELECT DISTINCT ?subject ?name ?type ?description ?size ?graph ?depth WHERE {{
@@ -1140,6 +3371,8 @@ impl Graph {
                     OPTIONAL {{ ?subject <http://schema.org/description> ?description . }}
                     OPTIONAL {{ ?subject <http://schema.org/contentSize> ?size . }}
                 }}
+                {scope_clause}
+                {hidden_clause}
                 OPTIONAL {{
                     # Look for depth in any graph (typically configuration graphs)
                     GRAPH ?config_graph {{
@@ -1147,31 +3380,207 @@ impl Graph {
                     }}
                 }}
             }}
-            ORDER BY ASC(COALESCE(?depth, 999999)) ?graph ?subject
+            ORDER BY ASC(COALESCE(?depth, {UNKNOWN_DEPTH_RANK})) ?graph ?subject
             {limit_clause}
             "#
         );
 
-        debug!("Browse query: {}", query);
+        debug!("Browse query: {}", query);
+
+        let results = self.store.query(query.as_str())?;
+        let buffer = results.write(Vec::new(), QueryResultsFormat::Json)?;
+        let json_str = String::from_utf8(buffer)?;
+
+        debug!("Browse results: {}", json_str);
+        Ok(json_str)
+    }
+
+    // Search for content across all graphs. Results are returned one row per subject triple,
+    // skipping `offset` rows and returning at most `limit` rows, so a UI can page through them
+    pub fn search_content(
+        &self,
+        search_text: &str,
+        limit: Option<u64>,
+        offset: Option<u64>,
+    ) -> Result<String, Error> {
+        self.search_content_scoped(
+            search_text,
+            TextMatchMode::Contains,
+            limit,
+            offset,
+            false,
+            &[],
+        )
+    }
+
+    // Search for content only within the user's own pods (depth 0)
+    pub fn search_content_local(
+        &self,
+        search_text: &str,
+        limit: Option<u64>,
+        offset: Option<u64>,
+    ) -> Result<String, Error> {
+        self.search_content_scoped(
+            search_text,
+            TextMatchMode::Contains,
+            limit,
+            offset,
+            true,
+            &[],
+        )
+    }
+
+    // Search for content with a choice of how the text is matched. Regex mode uses the whole
+    // search text as one case-insensitive pattern and returns an error if it doesn't compile.
+    // Results have the same shape as search_content
+    pub fn search_content_with_mode(
+        &self,
+        search_text: &str,
+        mode: TextMatchMode,
+        limit: Option<u64>,
+        offset: Option<u64>,
+        local_only: bool,
+    ) -> Result<String, Error> {
+        self.search_content_scoped(search_text, mode, limit, offset, local_only, &[])
+    }
+
+    // Search for content only within the given pods. Both the matching values and the returned
+    // subject data are taken from those pods. Results have the same shape as search_content
+    pub fn search_content_in_pods(
+        &self,
+        search_text: &str,
+        mode: TextMatchMode,
+        limit: Option<u64>,
+        offset: Option<u64>,
+        local_only: bool,
+        pod_addresses: &[String],
+    ) -> Result<String, Error> {
+        self.search_content_scoped(search_text, mode, limit, offset, local_only, pod_addresses)
+    }
+
+    // Escape text for use inside a double quoted SPARQL string literal
+    fn escape_sparql_string(text: &str) -> String {
+        text.replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n")
+            .replace('\r', "\\r")
+    }
+
+    // Lowercase text and replace accented letters with their unaccented form, matching what
+    // fold_diacritics_expr does inside a query
+    fn fold_diacritics(text: &str) -> String {
+        let mut folded = String::new();
+        for ch in text.to_lowercase().chars() {
+            match DIACRITIC_FOLDS
+                .iter()
+                .find(|(accented, _)| accented.contains(ch))
+            {
+                Some((_, plain)) => folded.push_str(plain),
+                None => folded.push(ch),
+            }
+        }
+        folded
+    }
+
+    // Wrap a SPARQL string expression so it is lowercased with the accents stripped
+    fn fold_diacritics_expr(expr: &str) -> String {
+        let mut folded = format!("LCASE({expr})");
+        for (accented, plain) in DIACRITIC_FOLDS {
+            folded = format!("REPLACE({folded}, \"[{accented}]\", \"{plain}\")");
+        }
+        folded
+    }
 
-        let results = self.store.query(query.as_str())?;
-        let buffer = results.write(Vec::new(), QueryResultsFormat::Json)?;
-        let json_str = String::from_utf8(buffer)?;
+    // SPARQL expression that is true when the string form of a variable matches a search term
+    fn text_match_expr(var: &str, term: &str, mode: TextMatchMode) -> String {
+        match mode {
+            TextMatchMode::Contains => {
+                let escaped_term = Self::escape_sparql_string(term);
+                format!("CONTAINS(LCASE(STR(?{var})), LCASE(\"{escaped_term}\"))")
+            }
+            TextMatchMode::Regex => {
+                let escaped_term = Self::escape_sparql_string(term);
+                format!("REGEX(STR(?{var}), \"{escaped_term}\", \"i\")")
+            }
+            TextMatchMode::Fuzzy => {
+                let escaped_term = Self::escape_sparql_string(&Self::fold_diacritics(term));
+                let folded = Self::fold_diacritics_expr(&format!("STR(?{var})"));
+                format!("CONTAINS({folded}, \"{escaped_term}\")")
+            }
+        }
+    }
 
-        debug!("Browse results: {}", json_str);
-        Ok(json_str)
+    // Build the LIMIT and OFFSET clauses for a paged query. Paging is only stable when the query
+    // orders its rows completely, so every paged query must ORDER BY all of its result variables
+    fn page_clause(limit: Option<u64>, offset: Option<u64>) -> String {
+        let mut clause = String::new();
+        if let Some(l) = limit {
+            clause.push_str(&format!("LIMIT {l}"));
+        }
+        if let Some(o) = offset.filter(|o| *o > 0) {
+            if !clause.is_empty() {
+                clause.push(' ');
+            }
+            clause.push_str(&format!("OFFSET {o}"));
+        }
+        clause
     }
 
-    // Search for content across all graphs
-    pub fn search_content(&self, search_text: &str, limit: Option<u64>) -> Result<String, Error> {
-        let limit_clause = if let Some(l) = limit {
-            format!("LIMIT {l}")
+    // Restrict a graph variable to pods at depth 0 when local_only is set
+    fn local_scope_clause(graph_var: &str, local_only: bool) -> String {
+        if local_only {
+            format!("GRAPH ?{graph_var}_scope {{ ?{graph_var} <{HAS_DEPTH}> \"0\" . }}")
         } else {
             String::new()
-        };
+        }
+    }
+
+    fn search_scope_clause(graph_var: &str, scope: &SearchScope) -> Result<String, Error> {
+        match scope {
+            SearchScope::All => Ok(String::new()),
+            SearchScope::Local => Ok(Self::local_scope_clause(graph_var, true)),
+            SearchScope::Pod(pod_address) => {
+                let pod_iri = format!("ant://{}", pod_address.trim_start_matches("ant://"));
+                let pod_iri = NamedNodeRef::new(&pod_iri)?;
+                Ok(format!("FILTER(?{graph_var} = <{}>)", pod_iri.as_str()))
+            }
+        }
+    }
+
+    // Restrict a graph variable to the given pods. An empty list leaves the graph unrestricted
+    fn pods_values_clause(graph_var: &str, pod_addresses: &[String]) -> Result<String, Error> {
+        if pod_addresses.is_empty() {
+            return Ok(String::new());
+        }
+        let mut pod_iris = Vec::new();
+        for pod_address in pod_addresses {
+            let pod_iri = format!("ant://{}", pod_address.trim_start_matches("ant://"));
+            pod_iris.push(format!("<{}>", NamedNodeRef::new(&pod_iri)?.as_str()));
+        }
+        Ok(format!("VALUES ?{graph_var} {{ {} }}", pod_iris.join(" ")))
+    }
 
-        // Parse search text to handle quoted phrases and individual words
-        let search_terms = Self::parse_search_terms(search_text);
+    fn search_content_scoped(
+        &self,
+        search_text: &str,
+        mode: TextMatchMode,
+        limit: Option<u64>,
+        offset: Option<u64>,
+        local_only: bool,
+        pod_addresses: &[String],
+    ) -> Result<String, Error> {
+        let limit_clause = Self::page_clause(limit, offset);
+
+        // Parse search text to handle quoted phrases and individual words.
+        // A regular expression is always a single term, and must compile before it is used
+        let search_terms = match mode {
+            TextMatchMode::Regex if search_text.trim().is_empty() => Vec::new(),
+            TextMatchMode::Regex => {
+                regex::Regex::new(search_text)?;
+                vec![search_text.to_string()]
+            }
+            TextMatchMode::Contains | TextMatchMode::Fuzzy => Self::parse_search_terms(search_text),
+        };
 
         if search_terms.is_empty() {
             return Ok("[]".to_string()); // Return empty results for empty search
@@ -1180,23 +3589,25 @@ impl Graph {
         // Create filter conditions for subquery (OR logic)
         let mut subquery_term_filters = Vec::new();
         for term in &search_terms {
-            let escaped_term = term.replace("\"", "\\\"");
-            subquery_term_filters.push(format!(
-                "CONTAINS(LCASE(STR(?filter_object)), LCASE(\"{escaped_term}\"))"
-            ));
+            subquery_term_filters.push(Self::text_match_expr("filter_object", term, mode));
         }
         let subquery_combined_filter = subquery_term_filters.join(" || ");
 
         // Create individual term match expressions for counting
         let mut match_expressions = Vec::new();
         for term in &search_terms {
-            let escaped_term = term.replace("\"", "\\\"");
-            match_expressions.push(format!(
-                "IF(CONTAINS(LCASE(STR(?object)), LCASE(\"{escaped_term}\")), 1, 0)"
-            ));
+            let term_match = Self::text_match_expr("object", term, mode);
+            match_expressions.push(format!("IF({term_match}, 1, 0)"));
         }
         let match_count_expr = match_expressions.join(" + ");
 
+        let filter_scope_clause = Self::local_scope_clause("filter_graph", local_only);
+        let scope_clause = Self::local_scope_clause("graph", local_only);
+        let filter_hidden_clause = self.hidden_pods_clause("filter_graph");
+        let hidden_clause = self.hidden_pods_clause("graph");
+        let filter_pods_clause = Self::pods_values_clause("filter_graph", pod_addresses)?;
+        let pods_clause = Self::pods_values_clause("graph", pod_addresses)?;
+
         let query = format!(
             r#"
             SELECT ?subject ?predicate ?object ?graph ?depth
@@ -1207,11 +3618,17 @@ impl Graph {
                             ?subject ?filter_predicate ?filter_object .
                             FILTER(isLiteral(?filter_object) && ({subquery_combined_filter}))
                         }}
+                        {filter_pods_clause}
+                        {filter_scope_clause}
+                        {filter_hidden_clause}
                     }}
                 }}
+                {pods_clause}
                 GRAPH ?graph {{
                     ?subject ?predicate ?object .
                 }}
+                {scope_clause}
+                {hidden_clause}
                 OPTIONAL {{
                     # Look for depth in any graph (typically configuration graphs)
                     GRAPH ?config_graph {{
@@ -1219,7 +3636,7 @@ impl Graph {
                     }}
                 }}
             }}
-            ORDER BY DESC(?match_count) ASC(COALESCE(?depth, 999999)) ?graph ?subject
+            ORDER BY DESC(?match_count) ASC(COALESCE(?depth, {UNKNOWN_DEPTH_RANK})) ?graph ?subject ?predicate ?object
             {limit_clause}
             "#
         );
@@ -1240,6 +3657,87 @@ impl Graph {
         Ok(json_str)
     }
 
+    // Search for subjects with a literal value equal to the given text, optionally only through
+    // one predicate. Results have the same shape as search_content
+    pub fn search_exact(
+        &self,
+        value: &str,
+        predicate: Option<&str>,
+        limit: Option<u64>,
+        offset: Option<u64>,
+        local_only: bool,
+    ) -> Result<String, Error> {
+        self.search_exact_in_pods(value, predicate, limit, offset, local_only, &[])
+    }
+
+    // Exact search only within the given pods, see search_exact
+    pub fn search_exact_in_pods(
+        &self,
+        value: &str,
+        predicate: Option<&str>,
+        limit: Option<u64>,
+        offset: Option<u64>,
+        local_only: bool,
+        pod_addresses: &[String],
+    ) -> Result<String, Error> {
+        let limit_clause = Self::page_clause(limit, offset);
+
+        let escaped_value = Self::escape_sparql_string(value);
+        let filter_predicate = match predicate {
+            Some(predicate) => format!("<{}>", NamedNodeRef::new(predicate)?.as_str()),
+            None => "?filter_predicate".to_string(),
+        };
+
+        let filter_scope_clause = Self::local_scope_clause("filter_graph", local_only);
+        let scope_clause = Self::local_scope_clause("graph", local_only);
+        let filter_hidden_clause = self.hidden_pods_clause("filter_graph");
+        let hidden_clause = self.hidden_pods_clause("graph");
+        let filter_pods_clause = Self::pods_values_clause("filter_graph", pod_addresses)?;
+        let pods_clause = Self::pods_values_clause("graph", pod_addresses)?;
+
+        let query = format!(
+            r#"
+            SELECT ?subject ?predicate ?object ?graph ?depth
+                   (IF(isLiteral(?object) && STR(?object) = "{escaped_value}", 1, 0) AS ?match_count) WHERE {{
+                {{
+                    SELECT DISTINCT ?subject WHERE {{
+                        GRAPH ?filter_graph {{
+                            ?subject {filter_predicate} ?filter_object .
+                            FILTER(isLiteral(?filter_object) && STR(?filter_object) = "{escaped_value}")
+                        }}
+                        {filter_pods_clause}
+                        {filter_scope_clause}
+                        {filter_hidden_clause}
+                    }}
+                }}
+                {pods_clause}
+                GRAPH ?graph {{
+                    ?subject ?predicate ?object .
+                }}
+                {scope_clause}
+                {hidden_clause}
+                OPTIONAL {{
+                    # Look for depth in any graph (typically configuration graphs)
+                    GRAPH ?config_graph {{
+                        ?graph <{HAS_DEPTH}> ?depth .
+                    }}
+                }}
+            }}
+            ORDER BY DESC(?match_count) ASC(COALESCE(?depth, {UNKNOWN_DEPTH_RANK})) ?graph ?subject ?predicate ?object
+            {limit_clause}
+            "#
+        );
+
+        debug!("Exact search query: {}", query);
+
+        let results = self.store.query(query.as_str())?;
+        let buffer = results.write(Vec::new(), QueryResultsFormat::Json)?;
+        let json_str = String::from_utf8(buffer)?;
+
+        debug!("Exact search results: {}", json_str);
+        Ok(json_str)
+    }
+
     // Helper function to parse search terms, handling quoted phrases
     fn parse_search_terms(search_text: &str) -> Vec<String> {
         let mut terms = Vec::new();
@@ -1309,19 +3807,36 @@ impl Graph {
 
     // Search for subjects by type
     //FIXME: order the results by pod depth
-    pub fn search_by_type(&self, type_uri: &str, limit: Option<u64>) -> Result<String, Error> {
-        let limit_clause = if let Some(l) = limit {
-            format!("LIMIT {l}")
-        } else {
-            String::new()
-        };
+    pub fn search_by_type(
+        &self,
+        type_uri: &str,
+        limit: Option<u64>,
+        offset: Option<u64>,
+    ) -> Result<String, Error> {
+        self.search_by_type_in_pods(type_uri, limit, offset, &[])
+    }
+
+    // Search for subjects by type only within the given pods
+    pub fn search_by_type_in_pods(
+        &self,
+        type_uri: &str,
+        limit: Option<u64>,
+        offset: Option<u64>,
+        pod_addresses: &[String],
+    ) -> Result<String, Error> {
+        let limit_clause = Self::page_clause(limit, offset);
+
+        let hidden_clause = self.hidden_pods_clause("graph");
+        let pods_clause = Self::pods_values_clause("graph", pod_addresses)?;
 
         let query = format!(
             r#"
             SELECT DISTINCT ?subject ?graph WHERE {{
+                {pods_clause}
                 GRAPH ?graph {{
                     ?subject <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <{type_uri}> .
                 }}
+                {hidden_clause}
             }}
             ORDER BY ?graph ?subject
             {limit_clause}
@@ -1344,26 +3859,115 @@ impl Graph {
         Ok(json_str)
     }
 
+    // Count the distinct subjects of each rdf:type within a scope, most common type first.
+    // The types colonylib uses internally for pod bookkeeping are left out
+    pub fn type_facets(&self, scope: &SearchScope) -> Result<Vec<(String, u64)>, Error> {
+        let scope_clause = Self::search_scope_clause("graph", scope)?;
+        let hidden_clause = self.hidden_pods_clause("graph");
+
+        let query = format!(
+            r#"
+            SELECT ?type (COUNT(DISTINCT ?subject) AS ?n) WHERE {{
+                GRAPH ?graph {{
+                    ?subject <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> ?type .
+                }}
+                FILTER(!STRSTARTS(STR(?type), "ant://colonylib/"))
+                {scope_clause}
+                {hidden_clause}
+            }}
+            GROUP BY ?type
+            ORDER BY DESC(?n) ?type
+            "#
+        );
+        debug!("Type facets query: {}", query);
+
+        let mut facets = Vec::new();
+        if let QueryResults::Solutions(solutions) = self.store.query(query.as_str())? {
+            for solution in solutions.flatten() {
+                if let Some(oxigraph::model::Term::NamedNode(type_node)) = solution.get("type")
+                    && let Some(oxigraph::model::Term::Literal(count)) = solution.get("n")
+                    && let Ok(count) = count.value().parse::<u64>()
+                {
+                    facets.push((type_node.as_str().to_string(), count));
+                }
+            }
+        }
+        Ok(facets)
+    }
+
+    // Get the distinct object values of a predicate within a scope, the value used by the most
+    // subjects first. IRIs are returned as is and literals by their lexical value
+    pub fn distinct_objects_for_predicate(
+        &self,
+        predicate_uri: &str,
+        scope: &SearchScope,
+    ) -> Result<Vec<String>, Error> {
+        let predicate = NamedNodeRef::new(predicate_uri)?;
+        let scope_clause = Self::search_scope_clause("graph", scope)?;
+        let hidden_clause = self.hidden_pods_clause("graph");
+
+        let query = format!(
+            r#"
+            SELECT ?object (COUNT(DISTINCT ?subject) AS ?n) WHERE {{
+                GRAPH ?graph {{
+                    ?subject {predicate} ?object .
+                }}
+                {scope_clause}
+                {hidden_clause}
+            }}
+            GROUP BY ?object
+            ORDER BY DESC(?n) ?object
+            "#
+        );
+        debug!("Distinct objects query: {}", query);
+
+        let mut objects = Vec::new();
+        if let QueryResults::Solutions(solutions) = self.store.query(query.as_str())? {
+            for solution in solutions.flatten() {
+                match solution.get("object") {
+                    Some(oxigraph::model::Term::NamedNode(node)) => {
+                        objects.push(node.as_str().to_string())
+                    }
+                    Some(oxigraph::model::Term::Literal(literal)) => {
+                        objects.push(literal.value().to_string())
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Ok(objects)
+    }
+
     // Search for subjects with a specific predicate
     pub fn search_by_predicate(
         &self,
         predicate_uri: &str,
         limit: Option<u64>,
+        offset: Option<u64>,
     ) -> Result<String, Error> {
-        let limit_clause = if let Some(l) = limit {
-            format!("LIMIT {l}")
-        } else {
-            String::new()
-        };
+        self.search_by_predicate_in_pods(predicate_uri, limit, offset, &[])
+    }
+
+    // Search for subjects with a specific predicate only within the given pods
+    pub fn search_by_predicate_in_pods(
+        &self,
+        predicate_uri: &str,
+        limit: Option<u64>,
+        offset: Option<u64>,
+        pod_addresses: &[String],
+    ) -> Result<String, Error> {
+        let limit_clause = Self::page_clause(limit, offset);
+        let pods_clause = Self::pods_values_clause("graph", pod_addresses)?;
 
         let query = format!(
             r#"
             SELECT DISTINCT ?subject ?object ?graph WHERE {{
+                {pods_clause}
                 GRAPH ?graph {{
                     ?subject <{predicate_uri}> ?object .
                 }}
             }}
-            ORDER BY ?graph ?subject
+            ORDER BY ?graph ?subject ?object
             {limit_clause}
             "#
         );
@@ -1402,8 +4006,40 @@ impl Graph {
         Ok(json_str)
     }
 
+    // Run a CONSTRUCT or DESCRIBE query and serialize the resulting triples in the given RDF
+    // format. Other query forms return an error, use advanced_search for SELECT and ASK
+    pub fn construct_query(&self, sparql: &str, format: RdfFormat) -> Result<Vec<u8>, Error> {
+        debug!("Construct query: {}", sparql);
+
+        let results = self.store.query(sparql)?;
+        if !matches!(results, QueryResults::Graph(_)) {
+            return Err(Error::InvalidInput(
+                "Only CONSTRUCT and DESCRIBE queries produce RDF output".to_string(),
+            ));
+        }
+        let buffer = results.write_graph(Vec::new(), format)?;
+
+        debug!("Construct query produced {} bytes", buffer.len());
+        Ok(buffer)
+    }
+
+    // Run an ASK query and return its answer. Other query forms return an error, use
+    // advanced_search for SELECT queries
+    pub fn ask_query(&self, sparql: &str) -> Result<bool, Error> {
+        debug!("Ask query: {}", sparql);
+
+        match self.store.query(sparql)? {
+            QueryResults::Boolean(answer) => {
+                debug!("Ask query answered {}", answer);
+                Ok(answer)
+            }
+            _ => Err(Error::InvalidInput(
+                "Only ASK queries produce a boolean answer".to_string(),
+            )),
+        }
+    }
+
     // Advanced search with multiple criteria
-    // FIXME: will need something like this for text search to handle fuzzy search and regexes
     pub fn query_builder(&self, criteria: &serde_json::Value) -> Result<String, Error> {
         // Build SPARQL query based on criteria
         let mut where_clauses = Vec::new();
@@ -1437,16 +4073,13 @@ impl Graph {
         }
 
         // Handle pod filter (specific graph)
+        let mut pod_addresses = Vec::new();
         if let Some(pod_address) = criteria.get("pod").and_then(|v| v.as_str())
             && !pod_address.is_empty()
         {
-            let _pod_iri = if pod_address.starts_with("ant://") {
-                pod_address.to_string()
-            } else {
-                format!("ant://{pod_address}")
-            };
-            // This will be used in the GRAPH clause
+            pod_addresses.push(pod_address.to_string());
         }
+        let pods_clause = Self::pods_values_clause("graph", &pod_addresses)?;
 
         // Default to basic search if no criteria
         if where_clauses.is_empty() {
@@ -1468,6 +4101,7 @@ impl Graph {
         let query = format!(
             r#"
             SELECT DISTINCT ?subject ?predicate ?object ?graph WHERE {{
+                {pods_clause}
                 GRAPH ?graph {{
                     {where_clause}
                     {filter_clause}
@@ -1494,11 +4128,12 @@ impl Graph {
 
         // Query for all scratchpad addresses in the pod's named graph
         let query = format!(
-            "SELECT DISTINCT ?scratchpad ?index WHERE {{ GRAPH <{pod_iri}> {{ ?scratchpad <{HAS_INDEX}> ?index . }} }}"
+            "SELECT DISTINCT ?scratchpad ?index ?modified WHERE {{ GRAPH <{pod_iri}> {{ ?scratchpad <{HAS_INDEX}> ?index . OPTIONAL {{ ?scratchpad <{HAS_MODIFIED_DATE}> ?modified . }} }} }}"
         );
         debug!("Pod scratchpads query: {}", query);
 
-        let mut triples = HashMap::new();
+        let mut indexed: HashMap<u64, Vec<String>> = HashMap::new();
+        let mut modified_dates: HashMap<String, String> = HashMap::new();
         let results = self.store.query(query.as_str())?;
         if let QueryResults::Solutions(solutions) = results {
             for solution in solutions.flatten() {
@@ -1511,22 +4146,22 @@ impl Graph {
                         && let Some(oxigraph::model::Term::Literal(literal)) = solution.get("index")
                         && let Ok(index) = literal.value().parse::<u64>()
                     {
-                        triples.insert(index, address.to_string());
+                        let addresses = indexed.entry(index).or_default();
+                        if !addresses.iter().any(|existing| existing == address) {
+                            addresses.push(address.to_string());
+                        }
+                        if let Some(oxigraph::model::Term::Literal(modified)) =
+                            solution.get("modified")
+                        {
+                            modified_dates
+                                .insert(address.to_string(), modified.value().to_string());
+                        }
                     }
                 }
             }
         }
 
-        // take the ordered addresses from the hashmap and map them to the scratchpads vector
-        let mut scratchpads = Vec::new();
-        for i in 0..triples.len() {
-            if let Some(scratchpad) = triples.get(&(i as u64)) {
-                // The address is already stripped of "ant://" prefix in line 661
-                scratchpads.push(scratchpad.clone());
-            } else {
-                error!("Missing scratchpad at index {}", i);
-            }
-        }
+        let scratchpads = Self::order_scratchpads(indexed, &modified_dates);
 
         debug!(
             "Found {} scratchpads for pod {}",
@@ -1537,8 +4172,9 @@ impl Graph {
     }
 
     pub fn get_pod_scratchpads_from_string(&self, data: &str) -> Result<Vec<String>, Error> {
-        // Parse the TriG data and return a hashmap of the scratchpad addresses and their pod index
-        let mut triples = HashMap::new();
+        // Parse the TriG data and collect the scratchpad addresses by their pod index
+        let mut indexed: HashMap<u64, Vec<String>> = HashMap::new();
+        let mut modified_dates: HashMap<String, String> = HashMap::new();
         for triple in TriGParser::new().for_reader(data.as_bytes()) {
             // The last line will be garbage, so we just ignore it by passing a default quad
             let triple = triple.unwrap_or_else(|_e| {
@@ -1556,26 +4192,73 @@ impl Graph {
                     && let Ok(index) = literal.value().parse::<u64>()
                     && let oxigraph::model::Subject::NamedNode(scratchpad) = triple.subject
                 {
-                    triples.insert(index, scratchpad.into_string());
+                    let address = scratchpad
+                        .as_str()
+                        .strip_prefix("ant://")
+                        .unwrap_or_default();
+                    let addresses = indexed.entry(index).or_default();
+                    if !addresses.iter().any(|existing| existing == address) {
+                        addresses.push(address.to_string());
+                    }
                 }
+            } else if triple.predicate == HAS_MODIFIED_DATE
+                && let oxigraph::model::Term::Literal(literal) = triple.object
+                && let oxigraph::model::Subject::NamedNode(subject) = triple.subject
+                && let Some(address) = subject.as_str().strip_prefix("ant://")
+            {
+                modified_dates.insert(address.to_string(), literal.value().to_string());
             }
         }
 
-        // take the ordered addresses from the hashmap and map them to the scratchpads vector
+        Ok(Self::order_scratchpads(indexed, &modified_dates))
+    }
+
+    // Put scratchpads in pod index order. A corrupt or forked pod can claim two scratchpads for
+    // the same index. Only one of them is kept so the pod data is always combined the same way:
+    // the one with the newest modified date, or the lowest address if the dates don't decide
+    fn order_scratchpads(
+        indexed: HashMap<u64, Vec<String>>,
+        modified_dates: &HashMap<String, String>,
+    ) -> Vec<String> {
+        let modified = |address: &str| {
+            modified_dates
+                .get(address)
+                .and_then(|date| chrono::DateTime::parse_from_rfc3339(date).ok())
+        };
+
         let mut scratchpads = Vec::new();
-        for i in 0..triples.len() {
-            if let Some(scratchpad) = triples.get(&(i as u64)) {
-                let address = scratchpad
-                    .as_str()
-                    .strip_prefix("ant://")
-                    .unwrap_or_default();
-                scratchpads.push(address.to_string());
-            } else {
+        for i in 0..indexed.len() {
+            let Some(candidates) = indexed.get(&(i as u64)) else {
                 error!("Missing scratchpad at index {}", i);
+                continue;
+            };
+            let mut candidates = candidates.clone();
+            candidates.sort_by(|a, b| modified(b).cmp(&modified(a)).then_with(|| a.cmp(b)));
+            if candidates.len() > 1 {
+                warn!(
+                    "Scratchpads {:?} all claim index {}, using {}",
+                    candidates, i, candidates[0]
+                );
             }
+            scratchpads.push(candidates[0].clone());
         }
+        scratchpads
+    }
 
-        Ok(scratchpads)
+    // Flush pending writes and compact the on-disk store, reclaiming the space left behind by
+    // removed data. This can take a while on a large store
+    pub fn compact(&self) -> Result<(), Error> {
+        self.store.flush()?;
+        self.store.optimize()?;
+        info!("Compacted the graph store");
+        Ok(())
+    }
+
+    // Remove every quad from every graph in the store
+    pub fn clear_all(&mut self) -> Result<(), Error> {
+        self.store.clear()?;
+        info!("Cleared all graphs from the graph store");
+        Ok(())
     }
 
     // Clear a specific pod graph
@@ -1625,3 +4308,178 @@ impl Graph {
         Ok(())
     }
 }
+
+/// Read-only handle to a [`Graph`] for serving queries concurrently.
+///
+/// Concurrency model: writes go through a `Graph`, and `PodManager` holds it as `&mut Graph`,
+/// so only one task modifies the graph at a time. A `GraphReader` is
+/// created with [`Graph::reader`], is `Clone + Send + Sync`, and only exposes queries. Every
+/// oxigraph query runs against a consistent snapshot of the store, so readers in other tasks
+/// never block a writer and never observe a half-applied pod update. Settings such as the
+/// hidden configuration pod and the clock are captured when the reader is created.
+#[derive(Clone)]
+pub struct GraphReader {
+    graph: Graph,
+}
+
+impl GraphReader {
+    /// Search for content across all graphs, see [`Graph::search_content`]
+    pub fn search_content(
+        &self,
+        search_text: &str,
+        limit: Option<u64>,
+        offset: Option<u64>,
+    ) -> Result<String, Error> {
+        self.graph.search_content(search_text, limit, offset)
+    }
+
+    /// Search for content in the user's own pods, see [`Graph::search_content_local`]
+    pub fn search_content_local(
+        &self,
+        search_text: &str,
+        limit: Option<u64>,
+        offset: Option<u64>,
+    ) -> Result<String, Error> {
+        self.graph.search_content_local(search_text, limit, offset)
+    }
+
+    /// Search for subjects of a type, see [`Graph::search_by_type`]
+    pub fn search_by_type(
+        &self,
+        type_uri: &str,
+        limit: Option<u64>,
+        offset: Option<u64>,
+    ) -> Result<String, Error> {
+        self.graph.search_by_type(type_uri, limit, offset)
+    }
+
+    /// Search for subjects of a type within some pods, see [`Graph::search_by_type_in_pods`]
+    pub fn search_by_type_in_pods(
+        &self,
+        type_uri: &str,
+        limit: Option<u64>,
+        offset: Option<u64>,
+        pod_addresses: &[String],
+    ) -> Result<String, Error> {
+        self.graph
+            .search_by_type_in_pods(type_uri, limit, offset, pod_addresses)
+    }
+
+    /// Count the subjects of each type, see [`Graph::type_facets`]
+    pub fn type_facets(&self, scope: &SearchScope) -> Result<Vec<(String, u64)>, Error> {
+        self.graph.type_facets(scope)
+    }
+
+    /// Distinct values of a predicate, see [`Graph::distinct_objects_for_predicate`]
+    pub fn distinct_objects_for_predicate(
+        &self,
+        predicate_uri: &str,
+        scope: &SearchScope,
+    ) -> Result<Vec<String>, Error> {
+        self.graph
+            .distinct_objects_for_predicate(predicate_uri, scope)
+    }
+
+    /// Get the subjects modified within a time window, see [`Graph::subjects_modified_between`]
+    pub fn subjects_modified_between(
+        &self,
+        start: &chrono::DateTime<chrono::Utc>,
+        end: &chrono::DateTime<chrono::Utc>,
+        scope: &SearchScope,
+    ) -> Result<Vec<String>, Error> {
+        self.graph.subjects_modified_between(start, end, scope)
+    }
+
+    /// Search for subjects with a predicate, see [`Graph::search_by_predicate`]
+    pub fn search_by_predicate(
+        &self,
+        predicate_uri: &str,
+        limit: Option<u64>,
+        offset: Option<u64>,
+    ) -> Result<String, Error> {
+        self.graph.search_by_predicate(predicate_uri, limit, offset)
+    }
+
+    /// Search for subjects with a predicate within some pods, see
+    /// [`Graph::search_by_predicate_in_pods`]
+    pub fn search_by_predicate_in_pods(
+        &self,
+        predicate_uri: &str,
+        limit: Option<u64>,
+        offset: Option<u64>,
+        pod_addresses: &[String],
+    ) -> Result<String, Error> {
+        self.graph
+            .search_by_predicate_in_pods(predicate_uri, limit, offset, pod_addresses)
+    }
+
+    /// Run a raw SPARQL query, see [`Graph::advanced_search`]
+    pub fn advanced_search(&self, query: &str) -> Result<String, Error> {
+        self.graph.advanced_search(query)
+    }
+
+    /// Run an ASK query, see [`Graph::ask_query`]
+    pub fn ask_query(&self, sparql: &str) -> Result<bool, Error> {
+        self.graph.ask_query(sparql)
+    }
+
+    /// Run a CONSTRUCT or DESCRIBE query, see [`Graph::construct_query`]
+    pub fn construct_query(&self, sparql: &str, format: RdfFormat) -> Result<Vec<u8>, Error> {
+        self.graph.construct_query(sparql, format)
+    }
+
+    /// Browse all subjects, see [`Graph::browse`]
+    pub fn browse(&self, limit: Option<u64>) -> Result<String, Error> {
+        self.graph.browse(limit)
+    }
+
+    /// Browse the subjects in the user's own pods, see [`Graph::browse_local`]
+    pub fn browse_local(&self, limit: Option<u64>) -> Result<String, Error> {
+        self.graph.browse_local(limit)
+    }
+
+    /// Get the data of a subject, see [`Graph::get_subject_data`]
+    pub fn get_subject_data(&self, subject_address: &str) -> Result<String, Error> {
+        self.graph.get_subject_data(subject_address)
+    }
+
+    /// Get the data of a subject from local pods with conflicts flagged, see
+    /// [`Graph::get_subject_data_local_with_conflicts`]
+    pub fn get_subject_data_local_with_conflicts(
+        &self,
+        subject_address: &str,
+    ) -> Result<SubjectView, Error> {
+        self.graph
+            .get_subject_data_local_with_conflicts(subject_address)
+    }
+
+    /// Get the data of a subject as JSON-LD, see [`Graph::get_subject_data_jsonld`]
+    pub fn get_subject_data_jsonld(&self, subject_address: &str) -> Result<String, Error> {
+        self.graph.get_subject_data_jsonld(subject_address)
+    }
+
+    /// Get the rdf:type values of a subject, see [`Graph::get_subject_types`]
+    pub fn get_subject_types(&self, subject_address: &str) -> Result<Vec<String>, Error> {
+        self.graph.get_subject_types(subject_address)
+    }
+
+    /// Get the pods describing a subject, see [`Graph::get_subject_pods`]
+    pub fn get_subject_pods(&self, subject_address: &str) -> Result<Vec<String>, Error> {
+        self.graph.get_subject_pods(subject_address)
+    }
+
+    /// Get the name of every named graph, see [`Graph::list_named_graphs`]
+    pub fn list_named_graphs(&self) -> Result<Vec<String>, Error> {
+        self.graph.list_named_graphs()
+    }
+
+    /// Get the name and address of every named pod, see [`Graph::get_pod_names`]
+    pub fn get_pod_names(&self) -> Result<Vec<(String, String)>, Error> {
+        self.graph.get_pod_names()
+    }
+
+    /// Get the subjects carrying a tag, see [`Graph::get_subjects_by_tag`]
+    pub fn get_subjects_by_tag(&self, tag: &str, local_only: bool) -> Result<Vec<String>, Error> {
+        self.graph.get_subjects_by_tag(tag, local_only)
+    }
+}
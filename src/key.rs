@@ -70,6 +70,35 @@ impl serde::Serialize for Error {
     }
 }
 
+/// What a derived key is used for, as reported by `KeyStore::derivation_map`
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyKind {
+    /// The configuration pod pointer or its first scratchpad
+    Configuration,
+    /// Pointer of a pod
+    Pointer,
+    /// Scratchpad of a pod
+    Scratchpad,
+    /// Pointer key that is free to be reused
+    FreePointer,
+    /// Scratchpad key that is free to be reused
+    FreeScratchpad,
+    /// Key that is never used, because it was abandoned or its use is unknown
+    Bad,
+    /// Derived index whose address is not held by the key store at all
+    Unassigned,
+}
+
+/// The public addresses of the pods a key store owns, safe to share with others.
+/// It never holds private keys, scratchpad addresses or wallet details
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct PublicManifest {
+    /// Address of the configuration pod pointer
+    pub configuration_address: String,
+    /// Addresses of the owned pod pointers, sorted, without the configuration pod
+    pub pod_addresses: Vec<String>,
+}
+
 #[derive(BorshDeserialize, BorshSerialize, Clone)]
 pub struct KeyStore {
     wallet_key: HashMap<String, Vec<u8>>,
@@ -80,6 +109,10 @@ pub struct KeyStore {
     bad_keys: HashMap<Vec<u8>, Vec<u8>>,
     free_pointers: HashMap<Vec<u8>, Vec<u8>>,
     free_scratchpads: HashMap<Vec<u8>, Vec<u8>>,
+    // Derivation index of every key held, by public key. Not saved in the key store file,
+    // it is rebuilt when the file is read
+    #[borsh(skip)]
+    address_indices: HashMap<Vec<u8>, u64>,
 }
 
 impl fmt::Debug for KeyStore {
@@ -109,7 +142,8 @@ impl KeyStore {
         let cocoon = Cocoon::new(password.as_bytes());
         let encoded = cocoon.parse(file).map_err(Error::Cocoon)?;
         debug!("Read from file: {:?}", file);
-        let key_store = KeyStore::try_from_slice(&encoded)?;
+        let mut key_store = KeyStore::try_from_slice(&encoded)?;
+        key_store.rebuild_address_indices()?;
         debug!("Parsed key store: {:?}", key_store);
         info!("Key store loaded successfully");
         Ok(key_store)
@@ -129,11 +163,46 @@ impl KeyStore {
         Ok(())
     }
 
+    // Re-encrypt the key store under a new password. The current key store file must open with
+    // the old password and hold this key store, then this key store is written to `updated`
+    // encrypted with the new password
+    #[instrument(skip(old_password, new_password))]
+    pub fn change_password<
+        R: std::io::Read + std::fmt::Debug,
+        W: std::io::Write + std::fmt::Debug,
+    >(
+        &self,
+        current: &mut R,
+        updated: &mut W,
+        old_password: &str,
+        new_password: &str,
+    ) -> Result<(), Error> {
+        let stored = KeyStore::from_file(current, old_password)?;
+        if stored.main_sk != self.main_sk {
+            error!("Key store file does not belong to this key store");
+            return Err(Error::Io(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Key store file does not belong to this key store",
+            )));
+        }
+        self.to_file(updated, new_password)?;
+        info!("Key store password changed");
+        Ok(())
+    }
+
     #[instrument]
     pub fn from_mnemonic(mnemonic: &str) -> Result<Self, Error> {
+        Self::from_mnemonic_with_passphrase(mnemonic, "")
+    }
+
+    // Create a key store from a mnemonic protected with an extra BIP39 passphrase (the "25th
+    // word"). The passphrase is part of the seed, so every passphrase derives a different set
+    // of keys and pod addresses from the same mnemonic. An empty passphrase matches from_mnemonic
+    #[instrument(skip(passphrase))]
+    pub fn from_mnemonic_with_passphrase(mnemonic: &str, passphrase: &str) -> Result<Self, Error> {
         // Generate a new mnemonic from the given phrase
         let mnemonic = Mnemonic::parse_in_normalized(Language::English, mnemonic)?;
-        let seed = mnemonic.to_seed_normalized("");
+        let seed = mnemonic.to_seed(passphrase);
 
         // Derive BLS12-381 master secret key from seed using EIP-2333 standard.
         // Guarantees a valid, non-zero scalar represented as 32 Big-Endian bytes.
@@ -165,6 +234,7 @@ impl KeyStore {
         let bad_keys: HashMap<PublicKey, SecretKey> = HashMap::new();
         let free_pointers: HashMap<PublicKey, SecretKey> = HashMap::new();
         let free_scratchpads: HashMap<PublicKey, SecretKey> = HashMap::new();
+        let mut address_indices: HashMap<Vec<u8>, u64> = HashMap::new();
         //let pod_key: SecretKey = main_sk.derive_key(&index(0)).into();
         //let pod_pubkey: PublicKey = pod_key.public_key();
         //pods.insert(pod_pubkey, pod_key.clone());
@@ -172,11 +242,13 @@ impl KeyStore {
         // Add configuratino pod pointer key
         let pointer_key: SecretKey = main_sk.derive_key(&index(0)).into();
         let pointer_pubkey: PublicKey = pointer_key.clone().public_key();
+        address_indices.insert(pointer_pubkey.to_bytes().to_vec(), 0);
         pointers.insert(pointer_pubkey, pointer_key);
 
         // Add configuration pod scratchpad key
         let scratchpad_key: SecretKey = main_sk.derive_key(&index(1)).into();
         let scratchpad_pubkey: PublicKey = scratchpad_key.clone().public_key();
+        address_indices.insert(scratchpad_pubkey.to_bytes().to_vec(), 1);
         scratchpads.insert(scratchpad_pubkey, scratchpad_key);
 
         Ok(KeyStore {
@@ -203,6 +275,7 @@ impl KeyStore {
                 .iter()
                 .map(|(k, v)| (k.to_bytes().to_vec(), v.to_bytes().to_vec()))
                 .collect(),
+            address_indices,
         })
     }
 
@@ -211,18 +284,17 @@ impl KeyStore {
         self.mnemonic.clone()
     }
 
+    // Check that a wallet key is a valid Ethereum private key and return the address it derives,
+    // without storing anything. The 0x prefix and surrounding whitespace are optional
+    pub fn validate_wallet_key(wallet_key: &str) -> Result<String, Error> {
+        let (_decoded_key, signing_key) = decode_wallet_key(wallet_key)?;
+        Ok(ethereum_address_from_public_key(
+            signing_key.verifying_key(),
+        ))
+    }
+
     pub fn add_wallet_key(&mut self, name: &str, wallet_key: &str) -> Result<(), Error> {
-        let wallet_key = remove_0x_prefix(wallet_key);
-        // Verify that the decoded key is a valid Ethereum private key (32 bytes)
-        let decoded_key = hex::decode(&wallet_key)?;
-        if decoded_key.len() != 32 {
-            return Err(Error::Io(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                "Ethereum private key must be exactly 32 bytes",
-            )));
-        }
-        // Verify it's a valid secp256k1 private key
-        let _signing_key = SigningKey::from_slice(&decoded_key)?;
+        let (decoded_key, _signing_key) = decode_wallet_key(wallet_key)?;
 
         self.wallet_key
             .insert(name.to_string(), decoded_key.clone());
@@ -318,6 +390,31 @@ impl KeyStore {
         }
     }
 
+    // Get the EIP-55 checksummed address of a wallet, for display and sharing
+    pub fn get_wallet_checksum_address(&self, name: &str) -> Result<String, Error> {
+        Ok(Self::checksum_address(&self.get_wallet_address(name)?))
+    }
+
+    // Apply EIP-55 mixed case checksum encoding to an Ethereum address
+    pub fn checksum_address(address: &str) -> String {
+        use sha3::{Digest, Keccak256};
+
+        let address = remove_0x_prefix(address.trim()).to_lowercase();
+        let hash = hex::encode(Keccak256::digest(address.as_bytes()));
+        let checksummed: String = address
+            .chars()
+            .zip(hash.chars())
+            .map(|(c, h)| {
+                if c.is_ascii_alphabetic() && h.to_digit(16).unwrap_or(0) >= 8 {
+                    c.to_ascii_uppercase()
+                } else {
+                    c
+                }
+            })
+            .collect();
+        format!("0x{checksummed}")
+    }
+
     pub fn get_wallet_addresses(&self) -> HashMap<String, String> {
         // Get the list of wallet keys
         let wallet_keys = self.get_wallet_keys();
@@ -350,6 +447,26 @@ impl KeyStore {
         Ok(pubkey.to_hex())
     }
 
+    // Check whether this key store derives the given configuration pod address, for confirming
+    // a restored mnemonic before refreshing from the network. An ant:// prefix, surrounding
+    // whitespace and letter case are ignored
+    pub fn verify_configuration_address(&self, expected_address: &str) -> Result<bool, Error> {
+        let expected_address = expected_address.trim();
+        let expected_address = expected_address
+            .strip_prefix("ant://")
+            .unwrap_or(expected_address);
+        let matches = self
+            .get_configuration_address()?
+            .eq_ignore_ascii_case(expected_address);
+        if !matches {
+            warn!(
+                "Configuration address {} does not match the key store",
+                expected_address
+            );
+        }
+        Ok(matches)
+    }
+
     pub fn get_configuration_scratchpad_address(&self) -> Result<String, Error> {
         // Get the first derived key
         let main_sk_array: [u8; 32] = self
@@ -455,24 +572,12 @@ impl KeyStore {
             return Ok((hex::encode(pubkey), hex::encode(key)));
         }
 
-        let main_sk_array: [u8; 32] = self
-            .main_sk
-            .clone()
-            .try_into()
-            .expect("main_sk must be 32 bytes");
-        let secret_key: SecretKey = SecretKey::from_bytes(main_sk_array)?;
-        let main_sk: MainSecretKey = MainSecretKey::new(secret_key);
-        let num_keys = self.get_num_keys();
-        let pod_key: SecretKey = main_sk.derive_key(&index(num_keys)).into();
-        let pod_pubkey: PublicKey = pod_key.clone().public_key();
-        self.pointers.insert(
-            pod_pubkey.to_bytes().to_vec(),
-            pod_key.clone().to_bytes().to_vec(),
-        );
-        Ok((
-            pod_pubkey.to_hex().to_string(),
-            pod_key.to_hex().to_string(),
-        ))
+        self.add_next_pointer_key()
+    }
+
+    // Derive the key at the next index as a pointer key, without reusing unused keys
+    pub fn add_next_pointer_key(&mut self) -> Result<(String, String), Error> {
+        self.add_key_at_index(self.get_num_derived_keys(), KeyKind::Pointer)
     }
 
     pub fn remove_pointer_key(&mut self, address: &str) -> Result<(), Error> {
@@ -506,24 +611,13 @@ impl KeyStore {
             );
             return Ok((hex::encode(pubkey), hex::encode(key)));
         }
-        let main_sk_array: [u8; 32] = self
-            .main_sk
-            .clone()
-            .try_into()
-            .expect("main_sk must be 32 bytes");
-        let secret_key: SecretKey = SecretKey::from_bytes(main_sk_array)?;
-        let main_sk: MainSecretKey = MainSecretKey::new(secret_key);
-        let num_keys = self.get_num_keys();
-        let pod_key: SecretKey = main_sk.derive_key(&index(num_keys)).into();
-        let pod_pubkey: PublicKey = pod_key.clone().public_key();
-        self.scratchpads.insert(
-            pod_pubkey.to_bytes().to_vec(),
-            pod_key.clone().to_bytes().to_vec(),
-        );
-        Ok((
-            pod_pubkey.to_hex().to_string(),
-            pod_key.to_hex().to_string(),
-        ))
+
+        self.add_next_scratchpad_key()
+    }
+
+    // Derive the key at the next index as a scratchpad key, without reusing unused keys
+    pub fn add_next_scratchpad_key(&mut self) -> Result<(String, String), Error> {
+        self.add_key_at_index(self.get_num_derived_keys(), KeyKind::Scratchpad)
     }
 
     pub fn remove_scratchpad_key(&mut self, address: &str) -> Result<(), Error> {
@@ -543,43 +637,54 @@ impl KeyStore {
         Ok(())
     }
 
+    // Move a pointer or scratchpad key, used or free, to the bad keys so it is never handed out
+    // again. Used when a key may be compromised
+    pub fn abandon_key(&mut self, address: &str) -> Result<(), Error> {
+        let pubkey = hex::decode(address)?;
+        let key = self
+            .pointers
+            .remove(&pubkey)
+            .or_else(|| self.scratchpads.remove(&pubkey))
+            .or_else(|| self.free_pointers.remove(&pubkey))
+            .or_else(|| self.free_scratchpads.remove(&pubkey));
+        match key {
+            Some(value) => {
+                self.bad_keys.insert(pubkey, value);
+            }
+            None => {
+                return Err(Error::Io(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    "Key not found",
+                )));
+            }
+        }
+        Ok(())
+    }
+
     pub fn add_bad_key(&mut self) -> Result<String, Error> {
-        let main_sk_array: [u8; 32] = self
-            .main_sk
-            .clone()
-            .try_into()
-            .expect("main_sk must be 32 bytes");
-        let secret_key: SecretKey = SecretKey::from_bytes(main_sk_array)?;
-        let main_sk: MainSecretKey = MainSecretKey::new(secret_key);
-        let num_keys = self.get_num_keys();
-        let pod_key: SecretKey = main_sk.derive_key(&index(num_keys)).into();
-        let pod_pubkey: PublicKey = pod_key.clone().public_key();
-        self.bad_keys.insert(
-            pod_pubkey.to_bytes().to_vec(),
-            pod_key.clone().to_bytes().to_vec(),
-        );
-        Ok(pod_key.to_hex().to_string())
+        let (_, key) = self.add_key_at_index(self.get_num_derived_keys(), KeyKind::Bad)?;
+        Ok(key)
     }
 
     pub fn add_free_pointer_key(&mut self) -> Result<String, Error> {
-        let main_sk_array: [u8; 32] = self
-            .main_sk
-            .clone()
-            .try_into()
-            .expect("main_sk must be 32 bytes");
-        let secret_key: SecretKey = SecretKey::from_bytes(main_sk_array)?;
-        let main_sk: MainSecretKey = MainSecretKey::new(secret_key);
-        let num_keys = self.get_num_keys();
-        let pod_key: SecretKey = main_sk.derive_key(&index(num_keys)).into();
-        let pod_pubkey: PublicKey = pod_key.clone().public_key();
-        self.free_pointers.insert(
-            pod_pubkey.to_bytes().to_vec(),
-            pod_key.clone().to_bytes().to_vec(),
-        );
-        Ok(pod_key.to_hex().to_string())
+        let (_, key) = self.add_key_at_index(self.get_num_derived_keys(), KeyKind::FreePointer)?;
+        Ok(key)
     }
 
     pub fn add_free_scratchpad_key(&mut self) -> Result<String, Error> {
+        let (_, key) =
+            self.add_key_at_index(self.get_num_derived_keys(), KeyKind::FreeScratchpad)?;
+        Ok(key)
+    }
+
+    // Derive the key at an explicit index and hold it as the given kind. Keys found on the network
+    // are recorded at the index they were derived at, which is not necessarily the next one.
+    // Returns the address and the secret key
+    pub fn add_key_at_index(
+        &mut self,
+        key_index: u64,
+        kind: KeyKind,
+    ) -> Result<(String, String), Error> {
         let main_sk_array: [u8; 32] = self
             .main_sk
             .clone()
@@ -587,14 +692,31 @@ impl KeyStore {
             .expect("main_sk must be 32 bytes");
         let secret_key: SecretKey = SecretKey::from_bytes(main_sk_array)?;
         let main_sk: MainSecretKey = MainSecretKey::new(secret_key);
-        let num_keys = self.get_num_keys();
-        let pod_key: SecretKey = main_sk.derive_key(&index(num_keys)).into();
+        let keys = match kind {
+            KeyKind::Pointer => &mut self.pointers,
+            KeyKind::Scratchpad => &mut self.scratchpads,
+            KeyKind::FreePointer => &mut self.free_pointers,
+            KeyKind::FreeScratchpad => &mut self.free_scratchpads,
+            KeyKind::Bad => &mut self.bad_keys,
+            KeyKind::Configuration | KeyKind::Unassigned => {
+                return Err(Error::Io(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!("Cannot add a key of kind {kind:?}"),
+                )));
+            }
+        };
+        let pod_key: SecretKey = main_sk.derive_key(&index(key_index)).into();
         let pod_pubkey: PublicKey = pod_key.clone().public_key();
-        self.free_scratchpads.insert(
+        keys.insert(
             pod_pubkey.to_bytes().to_vec(),
             pod_key.clone().to_bytes().to_vec(),
         );
-        Ok(pod_key.to_hex().to_string())
+        self.address_indices
+            .insert(pod_pubkey.to_bytes().to_vec(), key_index);
+        Ok((
+            pod_pubkey.to_hex().to_string(),
+            pod_key.to_hex().to_string(),
+        ))
     }
 
     pub fn clear_keys(&mut self) -> Result<(), Error> {
@@ -603,6 +725,7 @@ impl KeyStore {
         self.bad_keys.clear();
         self.free_pointers.clear();
         self.free_scratchpads.clear();
+        self.address_indices.clear();
         Ok(())
     }
 
@@ -625,6 +748,12 @@ impl KeyStore {
         self.get_num_pointer_keys() + self.get_num_scratchpad_keys() + self.get_num_bad_keys()
     }
 
+    // Number of derivation indices handed out, including the free keys. The next key is derived
+    // at this index
+    pub fn get_num_derived_keys(&self) -> u64 {
+        self.get_num_keys() + self.free_pointers.len() as u64 + self.free_scratchpads.len() as u64
+    }
+
     pub fn get_pointers(&self) -> HashMap<String, String> {
         self.pointers
             .iter()
@@ -660,6 +789,91 @@ impl KeyStore {
             .collect()
     }
 
+    // Collect the public pointer addresses of the owned pods. Only the map keys are read, so no
+    // secret key can end up in the manifest
+    pub fn export_public_manifest(&self) -> PublicManifest {
+        let configuration_address = self.get_configuration_address().unwrap_or_else(|e| {
+            error!("Failed to derive the configuration address: {}", e);
+            String::new()
+        });
+        let mut pod_addresses: Vec<String> = self
+            .pointers
+            .keys()
+            .map(hex::encode)
+            .filter(|address| *address != configuration_address)
+            .collect();
+        pod_addresses.sort();
+        PublicManifest {
+            configuration_address,
+            pod_addresses,
+        }
+    }
+
+    // List every derivation index up to the number of keys held, with the derived address and
+    // what the key store uses it for. Unassigned indices mean the key store and the derivation
+    // order are out of sync
+    pub fn derivation_map(&self) -> Result<Vec<(u64, String, KeyKind)>, Error> {
+        Ok(self
+            .derived_keys()?
+            .map(|(i, pubkey, kind)| (i, hex::encode(pubkey), kind))
+            .collect())
+    }
+
+    // Get the derivation index of a pointer or scratchpad address held by the key store, used,
+    // free or bad. Returns None for addresses the key store doesn't hold
+    pub fn index_of_address(&self, address: &str) -> Option<u64> {
+        let pubkey = hex::decode(address).ok()?;
+        self.address_indices.get(&pubkey).copied()
+    }
+
+    // Rebuild the reverse address to index map from the same derived keys derivation_map lists
+    fn rebuild_address_indices(&mut self) -> Result<(), Error> {
+        let address_indices: HashMap<Vec<u8>, u64> = self
+            .derived_keys()?
+            .filter(|(_, _, kind)| *kind != KeyKind::Unassigned)
+            .map(|(i, pubkey, _)| (pubkey, i))
+            .collect();
+        self.address_indices = address_indices;
+        debug!("Rebuilt {} address indices", self.address_indices.len());
+        Ok(())
+    }
+
+    // Derive every index up to the number of keys held, yielding the index, the derived public
+    // key and what the key store uses it for
+    fn derived_keys(&self) -> Result<impl Iterator<Item = (u64, Vec<u8>, KeyKind)> + '_, Error> {
+        let main_sk_array: [u8; 32] = self
+            .main_sk
+            .clone()
+            .try_into()
+            .expect("main_sk must be 32 bytes");
+        let secret_key: SecretKey = SecretKey::from_bytes(main_sk_array)?;
+        let main_sk: MainSecretKey = MainSecretKey::new(secret_key);
+
+        let count = self.get_num_derived_keys();
+        Ok((0..count).map(move |i| {
+            let key: SecretKey = main_sk.derive_key(&index(i)).into();
+            let pubkey = key.public_key().to_bytes().to_vec();
+            let kind = if i < 2
+                && (self.pointers.contains_key(&pubkey) || self.scratchpads.contains_key(&pubkey))
+            {
+                KeyKind::Configuration
+            } else if self.pointers.contains_key(&pubkey) {
+                KeyKind::Pointer
+            } else if self.scratchpads.contains_key(&pubkey) {
+                KeyKind::Scratchpad
+            } else if self.free_pointers.contains_key(&pubkey) {
+                KeyKind::FreePointer
+            } else if self.free_scratchpads.contains_key(&pubkey) {
+                KeyKind::FreeScratchpad
+            } else if self.bad_keys.contains_key(&pubkey) {
+                KeyKind::Bad
+            } else {
+                KeyKind::Unassigned
+            };
+            (i, pubkey, kind)
+        }))
+    }
+
     pub fn get_address_at_index(&self, count: u64) -> Result<String, Error> {
         let main_sk_array: [u8; 32] = self
             .main_sk
@@ -683,6 +897,22 @@ fn remove_0x_prefix(input: &str) -> String {
     input.strip_prefix("0x").unwrap_or(input).to_string()
 }
 
+// Decode a hex wallet key and verify it's a valid Ethereum (secp256k1) private key
+fn decode_wallet_key(wallet_key: &str) -> Result<(Vec<u8>, SigningKey), Error> {
+    let wallet_key = remove_0x_prefix(wallet_key.trim());
+    // Verify that the decoded key is a valid Ethereum private key (32 bytes)
+    let decoded_key = hex::decode(&wallet_key)?;
+    if decoded_key.len() != 32 {
+        return Err(Error::Io(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "Ethereum private key must be exactly 32 bytes",
+        )));
+    }
+    // Verify it's a valid secp256k1 private key
+    let signing_key = SigningKey::from_slice(&decoded_key)?;
+    Ok((decoded_key, signing_key))
+}
+
 fn ethereum_address_from_public_key(verifying_key: &VerifyingKey) -> String {
     use sha3::{Digest, Keccak256};
 
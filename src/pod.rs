@@ -8,22 +8,39 @@ use autonomi::{AddressParseError, Bytes, Chunk, Client, SecretKey, Wallet};
 use alloc::string::FromUtf8Error;
 use autonomi::client::analyze::{Analysis, AnalysisError};
 use blsttc::Error as BlsttcError;
-use chrono::Utc;
-use futures::future::{join_all, try_join_all};
+use futures::future::{AbortHandle, Abortable, BoxFuture};
+use futures::stream::StreamExt;
+use oxigraph::model::{Quad, Term};
+use oxttl::TriGParser;
 use serde::{Deserialize, Serialize};
 use serde_json::{Error as SerdeError, Value};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::io::Error as IoError;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use thiserror;
 use tracing::{debug, error, info, warn};
 
 use crate::DataStore;
 use crate::Graph;
 use crate::KeyStore;
+use crate::clock::Clock;
 use crate::data::Error as DataStoreError;
+use crate::data::{DiskUsage, StorageStats};
+use crate::graph::DepthAnomaly;
 use crate::graph::Error as GraphError;
+use crate::graph::GraphReader;
+use crate::graph::PodInfo;
+use crate::graph::RdfFormat;
+use crate::graph::Relationship;
+use crate::graph::SearchScope;
+use crate::graph::SubjectView;
+use crate::graph::TextMatchMode;
 use crate::key::Error as KeyStoreError;
+use crate::key::KeyKind;
 
 /// Structure representing the removal section of the update list
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
@@ -45,1362 +62,5559 @@ pub struct UpdateList {
     /// Pod addresses mapped to their associated scratchpad addresses for upload
     #[serde(default)]
     pub pods: std::collections::HashMap<String, Vec<String>>,
+    /// Pointer and scratchpad addresses derived locally that are known not to exist on the
+    /// network yet, so uploads can create them without checking first
+    #[serde(default)]
+    pub known_new: Vec<String>,
 }
 
-/// Batch operation types for concurrent network operations
-#[derive(Debug, Clone)]
-pub enum BatchOperation {
-    PointerGet { address: String },
-    PointerPut { address: String, target: String },
-    PointerUpdate { address: String, target: String },
-    ScratchpadGet { address: String },
-    ScratchpadPut { address: String, data: String },
-    ScratchpadUpdate { address: String, data: String },
+/// Report describing a forked scratchpad and which version was kept
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct ForkInfo {
+    /// Scratchpad address the versions were downloaded from
+    pub address: String,
+    /// Number of versions that existed on the network
+    pub version_count: usize,
+    /// Counter of each version, in the order returned by the network
+    pub counters: Vec<u64>,
+    /// Timestamp comment of each version, `None` if it could not be parsed
+    pub timestamps: Vec<Option<String>>,
+    /// Index of the version that was selected
+    pub selected_index: usize,
+    /// True if no version had a parseable timestamp and the selection was arbitrary
+    pub ambiguous: bool,
 }
 
-/// Results from batch operations
-#[derive(Debug, Clone)]
-pub enum BatchResult {
-    PointerGetResult {
-        address: String,
-        success: bool,
-        data: Option<String>,
-    },
-    PointerPutResult {
-        address: String,
-        success: bool,
-    },
-    PointerUpdateResult {
-        address: String,
-        success: bool,
-    },
-    ScratchpadGetResult {
-        address: String,
-        success: bool,
-        data: Option<String>,
-    },
-    ScratchpadPutResult {
-        address: String,
-        success: bool,
-    },
-    ScratchpadUpdateResult {
-        address: String,
-        success: bool,
-    },
+/// Summary of the referenced pods processed by `refresh_ref`
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct RefreshReport {
+    /// Referenced pods that were found on the network
+    pub downloaded: Vec<String>,
+    /// Referenced pods that could not be downloaded, with the reason
+    pub broken_references: Vec<(String, String)>,
 }
 
-/// Preprocessed batch of operations ready for concurrent execution
-#[derive(Debug, Clone, Default)]
-pub struct NetworkBatch {
-    pub pointer_gets: Vec<String>,
-    pub pointer_puts: Vec<(String, String)>, // (address, target)
-    pub pointer_updates: Vec<(String, String)>, // (address, target)
-    pub scratchpad_gets: Vec<String>,
-    pub scratchpad_puts: Vec<(String, String)>, // (address, data)
-    pub scratchpad_updates: Vec<(String, String)>, // (address, data)
+/// Summary of the pods downloaded by `fetch_pods`
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct FetchReport {
+    /// Pods that were found on the network, downloaded or already up to date
+    pub fetched: Vec<String>,
+    /// Pods that could not be downloaded, with the reason
+    pub failed: Vec<(String, String)>,
 }
 
-use crate::graph;
-
-// Error handling
-#[derive(Debug, thiserror::Error)]
-pub enum Error {
-    #[error(transparent)]
-    Connect(#[from] ConnectError),
-    #[error(transparent)]
-    Pointer(Box<PointerError>),
-    #[error(transparent)]
-    Scratchpad(Box<ScratchpadError>),
-    #[error(transparent)]
-    Blsttc(#[from] BlsttcError),
-    #[error(transparent)]
-    Address(#[from] AddressParseError),
-    #[error(transparent)]
-    FromUtf8(#[from] FromUtf8Error),
-    #[error(transparent)]
-    KeyStore(#[from] KeyStoreError),
-    #[error(transparent)]
-    DataStore(#[from] DataStoreError),
-    #[error(transparent)]
-    Io(#[from] IoError),
-    #[error(transparent)]
-    Serde(#[from] SerdeError),
-    #[error(transparent)]
-    Graph(#[from] GraphError),
-    #[error("{0}")]
-    Pod(String),
+/// Where the scratchpads of a pod are recorded, returned by `diff_pod_scratchpads`. All lists
+/// are sorted scratchpad addresses
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct ScratchpadDiff {
+    /// Scratchpads in the pod graph that have no local file
+    pub in_graph_only: Vec<String>,
+    /// Local scratchpad files reachable from the pod pointer that aren't in the pod graph
+    pub on_disk_only: Vec<String>,
+    /// Scratchpad keys in the key store that no pod or local file accounts for, which is what an
+    /// interrupted scratchpad allocation leaves behind. Only reported for the user's own pods
+    pub in_keystore_only: Vec<String>,
+    /// Scratchpads of one of the user's own pods, in the graph or on disk, without a key
+    pub missing_key: Vec<String>,
+    /// Scratchpads in the graph and on disk, and in the key store for the user's own pods
+    pub in_all: Vec<String>,
 }
 
-impl From<PointerError> for Error {
-    fn from(err: PointerError) -> Self {
-        Error::Pointer(Box::new(err))
-    }
+/// Summary of the pods reloaded by `rebuild_graph_from_local`
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct RebuildReport {
+    /// Pods that were reloaded into the graph from their local scratchpad files
+    pub rebuilt: Vec<String>,
+    /// Pods that could not be reloaded, with the reason
+    pub failed: Vec<(String, String)>,
 }
 
-impl From<ScratchpadError> for Error {
-    fn from(err: ScratchpadError) -> Self {
-        Error::Scratchpad(Box::new(err))
-    }
+/// What `remove_pod` would delete, as reported by `remove_pod_preview`
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct RemovalPlan {
+    /// Address of the pod that would be removed
+    pub pod_address: String,
+    /// Name of the pod, if it has one
+    pub name: Option<String>,
+    /// Scratchpads holding the pod data, which would be cleared on the network
+    pub scratchpads: Vec<String>,
+    /// Number of subjects described in the pod
+    pub subject_count: usize,
+    /// The user's other pods that reference this pod
+    pub referenced_by: Vec<String>,
+    /// Pointer and scratchpad addresses whose keys would be freed for reuse
+    pub freed_keys: Vec<String>,
 }
 
-#[derive(serde::Serialize)]
-#[serde(tag = "kind", content = "message")]
-#[serde(rename_all = "camelCase")]
-pub enum ErrorKind {
-    Connect(String),
-    Pointer(String),
-    Scratchpad(String),
-    Blsttc(String),
-    Address(String),
-    FromUtf8(String),
-    KeyStore(String),
-    DataStore(String),
-    Io(String),
-    Serde(String),
-    Graph(String),
-    Pod(String),
+/// Network state of a removed pod, as reported by `verify_removal`
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct RemovalStatus {
+    /// Address of the removed pod
+    pub pod_address: String,
+    /// The removal is still queued locally and has not been uploaded yet
+    pub pending_upload: bool,
+    /// The pod pointer on the network points to itself
+    pub pointer_removed: bool,
+    /// Scratchpads that are empty on the network
+    pub empty_scratchpads: Vec<String>,
+    /// Scratchpads that still hold data on the network
+    pub scratchpads_with_data: Vec<String>,
+    /// Scratchpads whose keys were reused by another pod since the removal, so they were not checked
+    pub reused_scratchpads: Vec<String>,
+    /// Addresses that could not be fetched from the network, with the reason
+    pub unreachable: Vec<(String, String)>,
 }
 
-impl serde::Serialize for Error {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::ser::Serializer,
-    {
-        let error_message = self.to_string();
-        let error_kind = match self {
-            Self::Connect(_) => ErrorKind::Connect(error_message),
-            Self::Pointer(_) => ErrorKind::Pointer(error_message),
-            Self::Scratchpad(_) => ErrorKind::Scratchpad(error_message),
-            Self::Blsttc(_) => ErrorKind::Blsttc(error_message),
-            Self::Address(_) => ErrorKind::Address(error_message),
-            Self::FromUtf8(_) => ErrorKind::FromUtf8(error_message),
-            Self::KeyStore(_) => ErrorKind::KeyStore(error_message),
-            Self::DataStore(_) => ErrorKind::DataStore(error_message),
-            Self::Io(_) => ErrorKind::Io(error_message),
-            Self::Serde(_) => ErrorKind::Serde(error_message),
-            Self::Graph(_) => ErrorKind::Graph(error_message),
-            Self::Pod(_) => ErrorKind::Pod(error_message),
-        };
-        error_kind.serialize(serializer)
+impl RemovalStatus {
+    /// The pointer points to itself and every checked scratchpad is empty
+    pub fn is_complete(&self) -> bool {
+        self.pointer_removed && self.scratchpads_with_data.is_empty() && self.unreachable.is_empty()
     }
 }
 
-//#[derive(Clone)]
-pub struct PodManager<'a> {
-    pub client: Client,
-    pub wallet: &'a Wallet,
-    pub data_store: &'a mut DataStore,
-    pub key_store: &'a mut KeyStore,
-    pub graph: &'a mut Graph,
+/// Local and network state of a pod pointer, as reported by `get_pointer_info`
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct PointerInfo {
+    /// Address of the pod pointer
+    pub pod_address: String,
+    /// Scratchpad the locally cached pointer targets, `None` if the pointer is not cached
+    pub local_target: Option<String>,
+    /// Counter of the locally cached pointer, `None` if the pointer is not cached
+    pub local_counter: Option<u64>,
+    /// The pod has local changes waiting for `upload_all`
+    pub pending_upload: bool,
+    /// Scratchpad the pointer on the network targets, `None` if the network was not checked
+    pub remote_target: Option<String>,
+    /// Counter of the pointer on the network, `None` if the network was not checked
+    pub remote_counter: Option<u64>,
+    /// Why the pointer could not be fetched from the network
+    pub remote_error: Option<String>,
 }
 
-impl<'a> fmt::Debug for PodManager<'a> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("PodManager")
-            .field("client", &"Client(Debug not implemented)")
-            .field("wallet", &self.wallet.address().to_string())
-            .field("data_store", &"DataStore(Debug not implemented)")
-            .field("key_store", &"KeyStore(Debug not implemented)")
-            .field("graph", &"Graph(Debug not implemented)")
-            .finish()
-    }
+/// How `refresh_cache` decides which pods to download, set with `PodManager::set_refresh_mode`
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RefreshMode {
+    /// Download the configuration pod on each refresh, ignoring its pointer counter, and the other
+    /// pods when their pointer counter on the network is newer than the local one. Safe on an
+    /// unreliable network, where a failed fetch of the configuration scratchpad can leave its
+    /// local counter ahead of the data actually cached, hiding new pods until the next change
+    #[default]
+    Always,
+    /// Download the configuration pod and the other pods only when their pointer counter on the
+    /// network is newer than the local one
+    CounterAware,
+    /// Like `CounterAware`, but also skip pods refreshed within the refresh TTL without contacting
+    /// the network
+    TtlAware,
 }
 
-impl<'a> PodManager<'a> {
-    /// Creates a new PodManager instance with the provided components.
-    ///
-    /// This constructor initializes a PodManager that coordinates between the Autonomi network client,
-    /// wallet for payments, local data storage, cryptographic key management, and RDF graph database.
-    /// The PodManager serves as the main interface for pod operations including creation, modification,
-    /// synchronization, and querying.
-    ///
-    /// # Parameters
-    ///
-    /// * `client` - An Autonomi network client for communicating with the decentralized network
-    /// * `wallet` - A reference to a wallet for handling network transaction payments
-    /// * `data_store` - A mutable reference to the local data storage system
-    /// * `key_store` - A mutable reference to the cryptographic key management system
-    /// * `graph` - A mutable reference to the RDF graph database for semantic data storage
-    ///
-    /// # Returns
-    ///
-    /// Returns `Ok(PodManager)` on successful initialization, or an `Error` if any component
-    /// fails to initialize properly.
-    ///
-    /// # Example
-    ///
-    /// ```ignore
-    /// use autonomi::{Client, Wallet};
-    /// use colonylib::{PodManager, DataStore, KeyStore, Graph};
-    ///
-    /// let client = Client::init_local().await?;
-    /// let evm_network = client.evm_network();
-    /// let wallet = &Wallet::new_from_private_key(evm_network.clone(), PRIVATE_KEY)?;
-    /// let data_store = &mut DataStore::create()?;
-    /// let key_store_file = data_store.get_keystore_path();
-    /// let key_store: &mut KeyStore = if key_store_file.exists() {
-    ///     let mut file = std::fs::File::open(key_store_file)?;
-    ///     &mut KeyStore::from_file(&mut file, "password")?
-    /// } else {
-    ///     let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
-    ///     &mut KeyStore::from_mnemonic(mnemonic)?
-    /// };
-    /// let _ = key_store.add_wallet_key("main", PRIVATE_KEY)?;
-    /// let graph_path = data_store.get_graph_path();
-    /// let graph = &mut Graph::open(&graph_path)?;
-    /// let pod_manager = PodManager::new(client, wallet, data_store, key_store, graph).await?;
-    /// ```
-    pub async fn new(
-        client: Client,
-        wallet: &'a Wallet,
-        data_store: &'a mut DataStore,
-        key_store: &'a mut KeyStore,
-        graph: &'a mut Graph,
-    ) -> Result<Self, Error> {
-        Ok(Self {
-            client,
-            wallet,
-            data_store,
-            key_store,
-            graph,
-        })
+impl RefreshMode {
+    /// Whether a pod with the given network and local pointer counters should be downloaded.
+    /// A missing local counter means the pod has never been cached. Every mode compares the
+    /// counters of the user's pods, `Always` only downloads the configuration pod regardless
+    pub fn should_download(
+        &self,
+        remote_counter: u64,
+        local_counter: Option<u64>,
+        configuration: bool,
+    ) -> bool {
+        match (self, local_counter) {
+            (RefreshMode::Always, _) if configuration => true,
+            (_, None) => true,
+            (_, Some(local_counter)) => remote_counter > local_counter,
+        }
     }
 
-    // Create a new pointer key, make sure it is empty, and add it to the key store
-    async fn create_pointer_key(&mut self) -> Result<SecretKey, Error> {
-        // Derive a new key
-        info!("Deriving or using a free key");
-        let (pubkey, key) = self.key_store.add_pointer_key()?;
+    /// Whether pods refreshed within the refresh TTL are skipped
+    pub fn uses_ttl(&self) -> bool {
+        *self == RefreshMode::TtlAware
+    }
+}
 
-        // If the address is being freed, unset the FREE attribute in the configuration graph
-        let configuration_address = self.key_store.get_configuration_address()?;
-        let configuration_address = configuration_address.as_str();
-        self.graph
-            .use_free_pointer(pubkey.as_str(), configuration_address)?;
+/// What `refresh_cache` does with a pod that has local changes waiting for `upload_all`, set with
+/// `PodManager::set_refresh_conflict_policy`
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RefreshConflictPolicy {
+    /// Keep the local copy and skip downloading the pod, so pending edits are not lost
+    #[default]
+    PreferLocal,
+    /// Download the pod anyway, overwriting the pending edits in the local copy
+    PreferRemote,
+    /// Stop the refresh with an error naming the pod
+    Error,
+}
 
-        info!("New key: {}", key);
-        let derived_key: SecretKey = SecretKey::from_hex(key.trim())?;
-        Ok(derived_key)
+impl RefreshConflictPolicy {
+    /// Whether a pod that would otherwise be downloaded should be. Pods without local changes are
+    /// always downloaded, and `Error` fails for pods with local changes
+    pub fn should_download(
+        &self,
+        pod_address: &str,
+        has_local_changes: bool,
+    ) -> Result<bool, Error> {
+        match (self, has_local_changes) {
+            (_, false) | (RefreshConflictPolicy::PreferRemote, true) => Ok(true),
+            (RefreshConflictPolicy::PreferLocal, true) => {
+                info!(
+                    "Pod {} has changes that were not uploaded, keeping the local copy",
+                    pod_address
+                );
+                Ok(false)
+            }
+            (RefreshConflictPolicy::Error, true) => Err(Error::Pod(format!(
+                "Pod {pod_address} has changes that were not uploaded, upload or discard them before refreshing"
+            ))),
+        }
     }
+}
 
-    // Create a new scratchpad key, make sure it is empty, and add it to the key store
-    async fn create_scratchpad_key(&mut self) -> Result<SecretKey, Error> {
-        // Derive a new key
-        info!("Deriving or using a free key");
-        let (pubkey, key) = self.key_store.add_scratchpad_key()?;
+/// Stage of a refresh reported to the progress callback
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RefreshPhase {
+    /// Downloading the configuration pod
+    Configuration,
+    /// Fetching the pointers of the user's pods
+    Pointers,
+    /// Downloading the scratchpads of the pods that changed
+    Scratchpads,
+    /// Following pod references in `refresh_ref`
+    References,
+}
 
-        // If the address is being freed, unset the FREE attribute in the configuration graph
-        let configuration_address = self.key_store.get_configuration_address()?;
-        let configuration_address = configuration_address.as_str();
-        self.graph
-            .use_free_scratchpad(pubkey.as_str(), configuration_address)?;
+/// Progress of `refresh_cache` and `refresh_ref`, passed to the callback set with
+/// `PodManager::set_progress_callback`. Counts start over at each phase
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RefreshProgress {
+    /// The stage of the refresh being worked on
+    pub phase: RefreshPhase,
+    /// Pods finished so far in this phase
+    pub pods_processed: usize,
+    /// Pods known to this phase so far. Following references can discover more pods, so the
+    /// total can grow during the `References` phase
+    pub pods_total: usize,
+    /// Reference depth being processed, 0 outside of the `References` phase
+    pub current_depth: u64,
+}
 
-        info!("New key: {}", key);
-        let derived_key: SecretKey = SecretKey::from_hex(key.trim())?;
-        Ok(derived_key)
+/// Callback receiving refresh progress, see `PodManager::set_progress_callback`
+pub type ProgressCallback = Box<dyn Fn(RefreshProgress) + Send + Sync>;
+
+/// A single row of a search, as returned by `search_typed`
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct SearchHit {
+    /// Subject IRI
+    pub subject: String,
+    /// Predicate IRI, `None` for searches that don't return one such as browse
+    pub predicate: Option<String>,
+    /// Object value, either an IRI or a literal
+    pub object: Option<String>,
+    /// Address of the pod the triple was found in
+    pub pod: Option<String>,
+    /// Depth of that pod, `None` if the search doesn't report it
+    pub depth: Option<u64>,
+    /// How many search terms matched the subject, `None` if the search doesn't report it
+    pub match_count: Option<u64>,
+}
+
+/// Results of a search, parsed from the SPARQL JSON returned by `search`
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct SearchResults {
+    pub results: Vec<SearchHit>,
+    pub result_count: usize,
+    /// Addresses of the pods that contain matching data, sorted
+    pub pods_found: Vec<String>,
+    /// RFC 3339 timestamp of when the search was performed
+    pub timestamp: String,
+}
+
+impl SearchResults {
+    /// Parse SPARQL JSON results. Bindings without a subject are skipped
+    pub fn from_sparql_json(results: &Value, timestamp: &str) -> Self {
+        let mut hits = Vec::new();
+        let mut pods = HashSet::new();
+        for binding in sparql_bindings(results) {
+            let Some(subject) = binding_value(binding, "subject") else {
+                continue;
+            };
+            let pod = binding_value(binding, "graph")
+                .map(|graph| graph.trim_start_matches("ant://").to_string());
+            if let Some(pod) = &pod {
+                pods.insert(pod.clone());
+            }
+            hits.push(SearchHit {
+                subject,
+                predicate: binding_value(binding, "predicate"),
+                object: binding_value(binding, "object"),
+                pod,
+                depth: binding_number(binding, "depth"),
+                match_count: binding_number(binding, "match_count"),
+            });
+        }
+
+        let mut pods_found: Vec<String> = pods.into_iter().collect();
+        pods_found.sort();
+        SearchResults {
+            result_count: hits.len(),
+            results: hits,
+            pods_found,
+            timestamp: timestamp.to_string(),
+        }
     }
+}
 
-    ///////////////////////////////////////////
-    // Graph operations
-    ///////////////////////////////////////////
+/// How a single text search result was ranked, returned by `search` when `"explain": true` is
+/// set. Results are ordered by `match_count` (highest first), then `sort_depth` (lowest first),
+/// then pod and subject
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct SearchRanking {
+    /// 1-based position of the result in the search results
+    pub rank: usize,
+    /// Subject IRI
+    pub subject: String,
+    /// Address of the pod the result was found in
+    pub pod: Option<String>,
+    /// How many search terms matched the result's value
+    pub match_count: u64,
+    /// Depth of the pod, `None` if it is unknown
+    pub depth: Option<u64>,
+    /// Depth used for ordering, `UNKNOWN_DEPTH_RANK` when the pod depth is unknown
+    pub sort_depth: u64,
+}
 
-    /// Performs a comprehensive search across all pod data using various search strategies.
-    ///
-    /// This function provides a flexible search interface that supports multiple search types
-    /// including text search, type-based search, predicate-based search, browse functionality,
+impl SearchRanking {
+    /// Explain the ranking of each binding of text search SPARQL JSON results, in result order
+    pub fn from_sparql_json(results: &Value) -> Vec<Self> {
+        sparql_bindings(results)
+            .iter()
+            .enumerate()
+            .map(|(position, binding)| {
+                let depth = binding_number(binding, "depth");
+                SearchRanking {
+                    rank: position + 1,
+                    subject: binding_value(binding, "subject").unwrap_or_default(),
+                    pod: binding_value(binding, "graph")
+                        .map(|graph| graph.trim_start_matches("ant://").to_string()),
+                    match_count: binding_number(binding, "match_count").unwrap_or(0),
+                    depth,
+                    sort_depth: depth.unwrap_or(graph::UNKNOWN_DEPTH_RANK),
+                }
+            })
+            .collect()
+    }
+}
+
+// Get the bindings of SPARQL JSON results, empty if there are none
+fn sparql_bindings(results: &Value) -> &[Value] {
+    results
+        .get("results")
+        .and_then(|r| r.get("bindings"))
+        .and_then(|b| b.as_array())
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+// Get the value of a variable in a SPARQL JSON binding
+fn binding_value(binding: &Value, name: &str) -> Option<String> {
+    binding
+        .get(name)
+        .and_then(|v| v.get("value"))
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+}
+
+// Get the value of a variable in a SPARQL JSON binding as a number
+fn binding_number(binding: &Value, name: &str) -> Option<u64> {
+    binding_value(binding, name).and_then(|v| v.trim().parse::<u64>().ok())
+}
+
+/// Describes how the columns of a CSV file map to subject data, see
+/// `PodManager::import_csv_into_pod`. Columns are identified by their header in the first row
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct CsvMapping {
+    /// Column holding the subject address, with or without the `ant://` prefix
+    pub subject_column: String,
+    /// Columns mapped to the predicate IRI their values are stored under. Several columns may
+    /// map to the same predicate
+    pub predicates: Vec<(String, String)>,
+    /// rdf:type IRI given to every imported subject
+    pub type_uri: Option<String>,
+    /// Column holding an rdf:type IRI per row, used instead of `type_uri` when the cell is filled
+    pub type_column: Option<String>,
+}
+
+impl CsvMapping {
+    /// Parse the CSV and build the JSON-LD for each row, returned as `(subject_address, jsonld)`
+    /// pairs in row order. Empty cells are left out. Fails if a mapped column is missing from the
+    /// header or a row has no subject address
+    pub fn to_subjects(&self, csv: &str) -> Result<Vec<(String, String)>, Error> {
+        let mut rows = parse_csv(csv)?.into_iter();
+        let Some(header) = rows.next() else {
+            return Ok(Vec::new());
+        };
+        let column = |name: &str| -> Result<usize, Error> {
+            header
+                .iter()
+                .position(|heading| heading.trim() == name.trim())
+                .ok_or_else(|| Error::Pod(format!("CSV has no column named \"{name}\"")))
+        };
+
+        let subject_column = column(&self.subject_column)?;
+        let type_column = match &self.type_column {
+            Some(name) => Some(column(name)?),
+            None => None,
+        };
+        let mut predicate_columns = Vec::new();
+        for (name, predicate) in &self.predicates {
+            predicate_columns.push((column(name)?, predicate.as_str()));
+        }
+
+        let mut subjects = Vec::new();
+        // Row numbers count the header as row 1, as a spreadsheet would
+        for (row_number, row) in rows.enumerate().map(|(i, row)| (i + 2, row)) {
+            let cell = |index: usize| row.get(index).map(|v| v.trim()).unwrap_or_default();
+            let subject = cell(subject_column);
+            let subject = subject.strip_prefix("ant://").unwrap_or(subject);
+            if subject.is_empty() {
+                return Err(Error::Pod(format!(
+                    "CSV row {row_number} has no subject address"
+                )));
+            }
+
+            let mut object = serde_json::Map::new();
+            object.insert("@id".to_string(), Value::String(format!("ant://{subject}")));
+            let type_uri = type_column
+                .map(cell)
+                .filter(|t| !t.is_empty())
+                .or(self.type_uri.as_deref());
+            if let Some(type_uri) = type_uri {
+                object.insert("@type".to_string(), Value::String(type_uri.to_string()));
+            }
+            for (index, predicate) in &predicate_columns {
+                let value = cell(*index);
+                if value.is_empty() {
+                    continue;
+                }
+                let value = Value::String(value.to_string());
+                match object.get_mut(*predicate) {
+                    Some(Value::Array(values)) => values.push(value),
+                    Some(existing) => *existing = Value::Array(vec![existing.take(), value]),
+                    None => {
+                        object.insert(predicate.to_string(), value);
+                    }
+                }
+            }
+
+            subjects.push((subject.to_string(), Value::Object(object).to_string()));
+        }
+
+        Ok(subjects)
+    }
+}
+
+/// Default maximum size in bytes of the data `PodManager` writes to a single scratchpad
+pub const DEFAULT_SCRATCHPAD_SIZE_LIMIT: usize = 4 * 1024 * 1024;
+
+// Smallest scratchpad size limit accepted, leaving room for the timestamp comment and some data
+const MIN_SCRATCHPAD_SIZE_LIMIT: usize = 64;
+
+/// Default number of network operations `PodManager` runs at the same time
+pub const DEFAULT_MAX_CONCURRENCY: usize = 32;
+
+/// Default number of attempts made for each network operation before giving up
+pub const DEFAULT_RETRY_ATTEMPTS: u32 = 3;
+
+/// Default delay before the first retry of a failed network operation, doubled for each
+/// following retry
+pub const DEFAULT_RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+
+// Errors that tell transient network failures apart from permanent ones, see retry_with_backoff
+pub(crate) trait TransientError {
+    // True if the failed operation may succeed when tried again
+    fn is_transient(&self) -> bool;
+}
+
+// Only reads are retried. A missing record means the pointer has to be created (or skipped), so
+// only the other get failures are transient. Puts are paid for before they are verified, so a
+// failed put is never treated as transient, retrying it could pay twice
+impl TransientError for PointerError {
+    fn is_transient(&self) -> bool {
+        match self {
+            PointerError::GetError(GetError::RecordNotFound) => false,
+            PointerError::GetError(_) => true,
+            _ => false,
+        }
+    }
+}
+
+impl TransientError for ScratchpadError {
+    fn is_transient(&self) -> bool {
+        match self {
+            ScratchpadError::GetError(GetError::RecordNotFound) => false,
+            ScratchpadError::GetError(_) => true,
+            _ => false,
+        }
+    }
+}
+
+// Run a network read, retrying it while it fails with a transient error. `op` is called up to
+// `attempts` times (at least once). The delay before each retry starts at `base_delay` and
+// doubles every time. Permanent errors, like a record that does not exist, are returned right
+// away, as are the errors of the last attempt
+pub(crate) async fn retry_with_backoff<F, Fut, T, E>(
+    attempts: u32,
+    base_delay: Duration,
+    mut op: F,
+) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T, E>>,
+    E: TransientError + fmt::Display,
+{
+    let attempts = attempts.max(1);
+    let mut delay = base_delay;
+    let mut attempt = 1;
+    loop {
+        match op().await {
+            Err(e) if attempt < attempts && e.is_transient() => {
+                warn!(
+                    "Network operation failed (attempt {} of {}), retrying in {:?}: {}",
+                    attempt, attempts, delay, e
+                );
+                tokio::time::sleep(delay).await;
+                delay = delay.saturating_mul(2);
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+// Run the futures with at most `limit` of them in flight, returning the outputs in the order
+// the futures were given
+async fn join_limited<F: std::future::Future>(
+    futures: impl IntoIterator<Item = F>,
+    limit: usize,
+) -> Vec<F::Output> {
+    let mut results: Vec<(usize, F::Output)> = futures::stream::iter(
+        futures
+            .into_iter()
+            .enumerate()
+            .map(|(index, future)| async move { (index, future.await) }),
+    )
+    .buffer_unordered(limit.max(1))
+    .collect()
+    .await;
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, output)| output).collect()
+}
+
+// Network reads made while refreshing pods. Implemented by the autonomi client, and by a stub in
+// the unit tests so the refresh decisions can be checked without a network
+pub(crate) trait RefreshNetwork: Sync {
+    fn pointer_get<'a>(
+        &'a self,
+        address: &'a PointerAddress,
+    ) -> BoxFuture<'a, Result<Pointer, PointerError>>;
+
+    fn scratchpad_get<'a>(
+        &'a self,
+        address: &'a ScratchpadAddress,
+    ) -> BoxFuture<'a, Result<Scratchpad, ScratchpadError>>;
+}
+
+impl RefreshNetwork for Client {
+    fn pointer_get<'a>(
+        &'a self,
+        address: &'a PointerAddress,
+    ) -> BoxFuture<'a, Result<Pointer, PointerError>> {
+        Box::pin(Client::pointer_get(self, address))
+    }
+
+    fn scratchpad_get<'a>(
+        &'a self,
+        address: &'a ScratchpadAddress,
+    ) -> BoxFuture<'a, Result<Scratchpad, ScratchpadError>> {
+        Box::pin(Client::scratchpad_get(self, address))
+    }
+}
+
+// Fetch the pointers of the given pods, at most `limit` at a time, returning the result for each
+// pod in order. `on_fetched` is called with the number of pointers fetched so far
+async fn fetch_pointers<N: RefreshNetwork + ?Sized>(
+    network: &N,
+    pointer_addresses: &[(String, PointerAddress)],
+    attempts: u32,
+    base_delay: Duration,
+    limit: usize,
+    on_fetched: impl Fn(usize),
+) -> Vec<(String, Result<Pointer, PointerError>)> {
+    let pointers_fetched = AtomicUsize::new(0);
+    let pointer_futures: Vec<_> = pointer_addresses
+        .iter()
+        .map(|(address, pointer_address)| {
+            let pointers_fetched = &pointers_fetched;
+            let on_fetched = &on_fetched;
+            async move {
+                info!("Checking pointer: {}", address);
+                let result = retry_with_backoff(attempts, base_delay, || {
+                    network.pointer_get(pointer_address)
+                })
+                .await;
+                on_fetched(pointers_fetched.fetch_add(1, Ordering::SeqCst) + 1);
+                (address.clone(), result)
+            }
+        })
+        .collect();
+    join_limited(pointer_futures, limit).await
+}
+
+// Download the main scratchpad of each queued pod, at most `limit` at a time, returning
+// (pod address, scratchpad address, data) for each pod in order. A forked scratchpad resolves to
+// its newest version and is reported in `fork_reports`
+async fn fetch_main_scratchpads<N: RefreshNetwork + ?Sized>(
+    network: &N,
+    scratchpad_operations: &[(String, ScratchpadAddress, bool, u64)],
+    attempts: u32,
+    base_delay: Duration,
+    limit: usize,
+    fork_reports: &std::sync::Mutex<Vec<ForkInfo>>,
+) -> Vec<Result<(String, String, String), Error>> {
+    let main_scratchpad_futures: Vec<_> = scratchpad_operations
+        .iter()
+        .map(
+            |(pod_address, scratchpad_address, _pointer_exists, _counter)| async move {
+                match retry_with_backoff(attempts, base_delay, || {
+                    network.scratchpad_get(scratchpad_address)
+                })
+                .await
+                {
+                    Ok(scratchpad) => {
+                        let data = scratchpad.encrypted_data();
+                        let data_string = String::from_utf8(data.to_vec())?;
+                        Ok((
+                            pod_address.clone(),
+                            scratchpad_address.to_hex(),
+                            data_string,
+                        ))
+                    }
+                    Err(e) => match e {
+                        ScratchpadError::Fork(scratchpads) => {
+                            let (scratchpad, fork_info) = PodManager::select_newest_scratchpad(
+                                scratchpads,
+                            )
+                            .ok_or_else(|| PodManager::empty_fork_error(scratchpad_address))?;
+                            PodManager::record_fork(fork_reports, fork_info);
+                            let data = scratchpad.encrypted_data();
+                            let data_string = String::from_utf8(data.to_vec())?;
+                            Ok((
+                                pod_address.clone(),
+                                scratchpad_address.to_hex(),
+                                data_string,
+                            ))
+                        }
+                        _ => {
+                            info!(
+                                "Main scratchpad not found on network: {}",
+                                scratchpad_address.to_hex()
+                            );
+                            Err(Error::Scratchpad(Box::new(e)))
+                        }
+                    },
+                }
+            },
+        )
+        .collect();
+    join_limited(main_scratchpad_futures, limit).await
+}
+
+// Probe `count` derived keys from `first_index` on, at most `limit` at a time, and record every
+// key up to the last one used on the network in the key store at the index it was derived at.
+// Returns the index, address, kind and pod pointer of each recorded key, in index order
+async fn scan_owned_keys<N: RefreshNetwork + ?Sized>(
+    network: &N,
+    key_store: &mut KeyStore,
+    first_index: u64,
+    count: u64,
+    attempts: u32,
+    base_delay: Duration,
+    limit: usize,
+) -> Result<Vec<(u64, String, KeyKind, Option<Pointer>)>, Error> {
+    let mut addresses = Vec::new();
+    for i in first_index..first_index + count {
+        addresses.push((i, key_store.get_address_at_index(i)?));
+    }
+
+    // Fetch the pointer and scratchpad at each address concurrently
+    let probe_futures: Vec<_> = addresses
+        .into_iter()
+        .map(|(i, address)| async move {
+            let pointer_address = PointerAddress::from_hex(&address)?;
+            let pointer = match retry_with_backoff(attempts, base_delay, || {
+                network.pointer_get(&pointer_address)
+            })
+            .await
+            {
+                Ok(pointer) => Some(pointer),
+                Err(
+                    PointerError::CannotUpdateNewPointer
+                    | PointerError::GetError(GetError::RecordNotFound),
+                ) => None,
+                Err(e) => return Err(Error::Pointer(Box::new(e))),
+            };
+            // Some(true) if the scratchpad holds data, Some(false) if it was emptied
+            let scratchpad_address = ScratchpadAddress::from_hex(&address)?;
+            let scratchpad = match retry_with_backoff(attempts, base_delay, || {
+                network.scratchpad_get(&scratchpad_address)
+            })
+            .await
+            {
+                Ok(scratchpad) => Some(!scratchpad.encrypted_data().is_empty()),
+                // A fork without versions is as good as missing
+                Err(ScratchpadError::Fork(scratchpads)) => {
+                    PodManager::select_newest_scratchpad(scratchpads)
+                        .map(|(newest, _)| !newest.encrypted_data().is_empty())
+                }
+                Err(
+                    ScratchpadError::CannotUpdateNewScratchpad
+                    | ScratchpadError::GetError(GetError::RecordNotFound),
+                ) => None,
+                Err(e) => return Err(Error::Scratchpad(Box::new(e))),
+            };
+            Ok((i, address, pointer, scratchpad))
+        })
+        .collect();
+    let mut probes = Vec::new();
+    for result in join_limited(probe_futures, limit).await {
+        probes.push(result?);
+    }
+
+    // Keys after the last one used on the network stay underived
+    let last_used = probes
+        .iter()
+        .rposition(|(_, _, pointer, scratchpad)| pointer.is_some() || scratchpad.is_some());
+    probes.truncate(last_used.map_or(0, |last_used| last_used + 1));
+
+    let mut keys = Vec::new();
+    for (i, address, pointer, scratchpad) in probes {
+        let kind = match (&pointer, scratchpad) {
+            // A removed pod points to itself, its key can be reused
+            (Some(pointer), _) if PodManager::pointer_is_tombstone(pointer) => KeyKind::FreePointer,
+            (Some(_), _) => KeyKind::Pointer,
+            (None, Some(true)) => KeyKind::Scratchpad,
+            (None, Some(false)) | (None, None) => KeyKind::FreeScratchpad,
+        };
+        key_store.add_key_at_index(i, kind)?;
+        let pointer = pointer.filter(|_| kind == KeyKind::Pointer);
+        keys.push((i, address, kind, pointer));
+    }
+    Ok(keys)
+}
+
+// Fetch the configuration pod pointer and decide whether the configuration pod is downloaded.
+// Returns None when the configuration pod is not on the network. A current local copy only skips
+// the configuration download, the pods it lists are still checked one by one
+async fn check_configuration_pointer<N: RefreshNetwork + ?Sized>(
+    network: &N,
+    data_store: &DataStore,
+    configuration_address: &str,
+    mode: RefreshMode,
+    force: bool,
+) -> Result<Option<(Pointer, bool)>, Error> {
+    let pointer_address = PointerAddress::from_hex(configuration_address)?;
+    let pointer = match network.pointer_get(&pointer_address).await {
+        Ok(pointer) => pointer,
+        Err(e) => {
+            match e {
+                PointerError::CannotUpdateNewPointer => {
+                    warn!("Configuration pointer not found on network, skipping");
+                    return Ok(None);
+                }
+                // Catch Pointer(Network(GetRecordError(RecordNotFound))) error when there is nothing on the network
+                PointerError::GetError(GetError::RecordNotFound) => {
+                    warn!("Configuration pointer not found on network, skipping");
+                    return Ok(None);
+                }
+                _ => {
+                    error!("Error occurred: {:?}", e); // Log the error
+                    return Err(Error::Pointer(Box::new(e))); // Propagate the error to the higher-level function
+                }
+            }
+        }
+    };
+
+    debug!("Retrieved pointer. Update count: {}", pointer.counter());
+    // A missing pointer file means the configuration was never cached and is always refreshed
+    let local_pointer_count = data_store.get_pointer_count(configuration_address).ok();
+    let download = force || mode.should_download(pointer.counter(), local_pointer_count, true);
+    if !download {
+        info!("Local configuration pod is up to date, skipping its download");
+    }
+    Ok(Some((pointer, download)))
+}
+
+// Fetch the pointer of each pod and decide which pods to download, following the refresh mode
+// and conflict policy in `config`. Pointer files are created for pods not cached yet.
+// `on_fetched` is called with the number of pointers fetched so far and the number to fetch.
+// Returns the scratchpad operations to run and the pods found on the network
+async fn plan_pod_downloads<N: RefreshNetwork + ?Sized>(
+    network: &N,
+    data_store: &DataStore,
+    pointer_addresses: Vec<(String, PointerAddress)>,
+    pending_changes: &HashSet<String>,
+    config: &PodManagerConfig,
+    force: bool,
+    on_fetched: impl Fn(usize, usize),
+) -> Result<(Vec<(String, ScratchpadAddress, bool, u64)>, Vec<String>), Error> {
+    let mode = config.refresh_mode;
+    // Phase 1: Fetch all pointers concurrently
+    info!(
+        "Phase 1: Fetching {} pointers concurrently",
+        pointer_addresses.len()
+    );
+
+    let pointers_total = pointer_addresses.len();
+    on_fetched(0, pointers_total);
+    let pointer_results = fetch_pointers(
+        network,
+        &pointer_addresses,
+        config.retry_attempts,
+        config.retry_base_delay,
+        config.max_concurrency,
+        |fetched| on_fetched(fetched, pointers_total),
+    )
+    .await;
+
+    // Phase 2: Collect all scratchpad operations that need to be performed
+    let mut scratchpad_operations: Vec<(String, ScratchpadAddress, bool, u64)> = Vec::new(); // (address, scratchpad_addr, pointer_exists, counter)
+    let mut refreshed_pods: Vec<String> = Vec::new();
+
+    for (address, pointer_result) in pointer_results {
+        let pointer = match pointer_result {
+            Ok(pointer) => pointer,
+            Err(e) => match e {
+                PointerError::CannotUpdateNewPointer => {
+                    warn!("Pointer not found on network, skipping: {}", address);
+                    continue;
+                }
+                PointerError::GetError(GetError::RecordNotFound) => {
+                    warn!("Pointer not found on network, skipping: {}", address);
+                    continue;
+                }
+                _ => {
+                    error!("Error occurred: {:?}", e);
+                    return Err(Error::Pointer(Box::new(e)));
+                }
+            },
+        };
+
+        info!("Pointer found: {:?}", pointer);
+        refreshed_pods.push(address.clone());
+
+        // Check if the pointer file exists in the local data store
+        let pointer_exists = data_store.address_is_pointer(&address)?;
+        if !pointer_exists {
+            info!("Pointer file does not exist, creating it");
+            data_store.create_pointer_file(&address)?;
+            data_store.update_pointer_target(&address, pointer.target().to_hex().as_str())?;
+            data_store.update_pointer_count(&address, pointer.counter())?;
+        }
+
+        // Check if the pointer is newer than the local cache
+        let local_pointer_count = if pointer_exists {
+            Some(data_store.get_pointer_count(&address)?)
+        } else {
+            None
+        };
+        if (force || mode.should_download(pointer.counter(), local_pointer_count, false))
+            && config
+                .refresh_conflict_policy
+                .should_download(&address, pending_changes.contains(&address))?
+        {
+            info!("Pointer is newer or refresh is unconditional, queuing scratchpad update");
+            let target = match PodManager::pointer_scratchpad_target(&pointer) {
+                Ok(scratchpad_address) => scratchpad_address,
+                Err(reason) => {
+                    error!("Pod {} skipped: {}", address, reason);
+                    continue;
+                }
+            };
+
+            scratchpad_operations.push((address, target, pointer_exists, pointer.counter()));
+        } else {
+            info!("Pointer is up to date");
+        }
+    }
+
+    Ok((scratchpad_operations, refreshed_pods))
+}
+
+// Split CSV text into rows of fields. Fields may be quoted with double quotes, in which case they
+// can hold commas, line breaks and doubled quotes. Blank lines are skipped
+fn parse_csv(csv: &str) -> Result<Vec<Vec<String>>, Error> {
+    let mut rows = Vec::new();
+    let mut row: Vec<String> = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = csv.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' => in_quotes = true,
+            ',' => row.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' | '\r' => {
+                row.push(std::mem::take(&mut field));
+                if row.iter().any(|f| !f.is_empty()) {
+                    rows.push(std::mem::take(&mut row));
+                } else {
+                    row.clear();
+                }
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(Error::Pod(
+            "CSV has an unterminated quoted field".to_string(),
+        ));
+    }
+    row.push(field);
+    if row.iter().any(|f| !f.is_empty()) {
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+/// The `PodManager` settings that can be persisted and restored across sessions, see
+/// `PodManager::get_config` and `PodManager::apply_config`. Missing fields deserialize to their
+/// defaults, so settings saved by an older version still load
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct PodManagerConfig {
+    /// How `refresh_cache` decides which pods to download
+    pub refresh_mode: RefreshMode,
+    /// How long a refreshed pod is skipped when the refresh mode is `TtlAware`
+    pub refresh_ttl: Duration,
+    /// Batch configuration pod writes until `sync_configuration` or `upload_all`
+    pub defer_configuration: bool,
+    /// Include the configuration pod in pod listings and search results
+    pub show_configuration_pod: bool,
+    /// What `refresh_cache` does with pods that have changes waiting for upload
+    pub refresh_conflict_policy: RefreshConflictPolicy,
+    /// Number of earlier versions kept locally for each pod, 0 disables pod history
+    pub history_depth: usize,
+    /// Maximum number of network operations in flight at the same time
+    pub max_concurrency: usize,
+    /// Number of attempts made for each network operation before giving up
+    pub retry_attempts: u32,
+    /// Delay before the first retry of a failed network operation, doubled for each retry
+    pub retry_base_delay: Duration,
+    /// Maximum size in bytes of the data written to a single scratchpad
+    pub scratchpad_size_limit: usize,
+}
+
+impl Default for PodManagerConfig {
+    fn default() -> Self {
+        PodManagerConfig {
+            refresh_mode: RefreshMode::default(),
+            refresh_ttl: Duration::ZERO,
+            defer_configuration: false,
+            show_configuration_pod: false,
+            refresh_conflict_policy: RefreshConflictPolicy::default(),
+            history_depth: 0,
+            max_concurrency: DEFAULT_MAX_CONCURRENCY,
+            retry_attempts: DEFAULT_RETRY_ATTEMPTS,
+            retry_base_delay: DEFAULT_RETRY_BASE_DELAY,
+            scratchpad_size_limit: DEFAULT_SCRATCHPAD_SIZE_LIMIT,
+        }
+    }
+}
+
+/// Kind of network object touched by an upload operation
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperationKind {
+    Pointer,
+    Scratchpad,
+}
+
+/// What an upload operation did to the network object
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperationAction {
+    Create,
+    Update,
+    Remove,
+}
+
+/// Outcome of a single completed pointer or scratchpad operation
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct OperationOutcome {
+    /// Address of the pointer or scratchpad
+    pub address: String,
+    /// Whether the operation touched a pointer or a scratchpad
+    pub kind: OperationKind,
+    /// Whether the object was created, updated or removed
+    pub action: OperationAction,
+    /// Cost paid for the operation, `None` if nothing was written
+    pub cost: Option<String>,
+}
+
+/// Summary of the network operations performed by `upload_all_report` and `upload_pod_report`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UploadReport {
+    /// Pointers created on the network
+    pub created_pointers: usize,
+    /// Existing pointers that were updated
+    pub updated_pointers: usize,
+    /// Scratchpads created on the network
+    pub created_scratchpads: usize,
+    /// Existing scratchpads that were updated
+    pub updated_scratchpads: usize,
+    /// Pointers and scratchpads that were removed
+    pub removed: usize,
+    /// Total cost paid for all operations, in atto tokens
+    pub total_cost: String,
+}
+
+impl Default for UploadReport {
+    fn default() -> Self {
+        UploadReport {
+            created_pointers: 0,
+            updated_pointers: 0,
+            created_scratchpads: 0,
+            updated_scratchpads: 0,
+            removed: 0,
+            total_cost: "0".to_string(),
+        }
+    }
+}
+
+impl UploadReport {
+    // Count a completed operation and add its cost to the total
+    fn record(&mut self, kind: OperationKind, action: OperationAction, cost: Option<&str>) {
+        match (kind, action) {
+            (_, OperationAction::Remove) => self.removed += 1,
+            (OperationKind::Pointer, OperationAction::Create) => self.created_pointers += 1,
+            (OperationKind::Pointer, OperationAction::Update) => self.updated_pointers += 1,
+            (OperationKind::Scratchpad, OperationAction::Create) => self.created_scratchpads += 1,
+            (OperationKind::Scratchpad, OperationAction::Update) => self.updated_scratchpads += 1,
+        }
+        let Some(cost) = cost else {
+            return;
+        };
+        match (self.total_cost.parse::<u128>(), cost.trim().parse::<u128>()) {
+            (Ok(total), Ok(cost)) => self.total_cost = total.saturating_add(cost).to_string(),
+            _ => warn!("Could not add operation cost {} to the upload total", cost),
+        }
+    }
+}
+
+/// Funds held by the wallet a `PodManager` pays with
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct WalletBalance {
+    /// Balance of the token used to pay for uploads, in atto tokens
+    pub token_balance: String,
+    /// Balance of the gas token used for transaction fees, in wei
+    pub gas_balance: String,
+}
+
+/// Flag that cancels the running network operation when set, see
+/// `PodManager::set_cancellation_token`
+pub type CancellationToken = Arc<AtomicBool>;
+
+/// Handle for listing and cancelling the network operations started by a `PodManager`.
+///
+/// Obtained with `PodManager::operation_handle()` and safe to move to another task. Cancelling
+/// aborts the operations that are in flight and prevents the ones that have not started yet from
+/// starting. If nothing is running, the next operation is cancelled instead.
+#[derive(Clone, Debug, Default)]
+pub struct OperationHandle {
+    cancelled: Arc<AtomicBool>,
+    // Flag set with `PodManager::set_cancellation_token`, shared by every clone of the handle.
+    // It belongs to the application and is never cleared here
+    token: Arc<Mutex<Option<CancellationToken>>>,
+    next_id: Arc<AtomicU64>,
+    in_flight: Arc<Mutex<HashMap<u64, (String, AbortHandle)>>>,
+}
+
+impl OperationHandle {
+    /// Cancel all in-flight operations and any that have not started yet
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        for (address, abort_handle) in self.lock_in_flight().values() {
+            debug!("Cancelling operation for {}", address);
+            abort_handle.abort();
+        }
+    }
+
+    /// Check if cancellation has been requested, with [`OperationHandle::cancel`] or the
+    /// cancellation token
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+            || self
+                .lock_token()
+                .as_ref()
+                .is_some_and(|token| token.load(Ordering::SeqCst))
+    }
+
+    /// Addresses of the network operations currently in flight
+    pub fn in_flight(&self) -> Vec<String> {
+        let mut addresses: Vec<String> = self
+            .lock_in_flight()
+            .values()
+            .map(|(address, _)| address.clone())
+            .collect();
+        addresses.sort();
+        addresses
+    }
+
+    // Clear a cancellation request made with cancel() once the cancelled operation has finished.
+    // The cancellation token is left as the application set it
+    fn reset(&self) {
+        self.cancelled.store(false, Ordering::SeqCst);
+    }
+
+    fn set_token(&self, token: CancellationToken) {
+        *self.lock_token() = Some(token);
+    }
+
+    fn lock_token(&self) -> std::sync::MutexGuard<'_, Option<CancellationToken>> {
+        self.token
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    // Stop an operation between steps if cancellation was requested. A request made with cancel()
+    // is cleared, as the operation returns right away
+    fn check_cancelled(&self, operation: &str) -> Result<(), Error> {
+        if self.is_cancelled() {
+            self.reset();
+            info!("{} cancelled", operation);
+            return Err(Error::Pod(format!("{operation} cancelled")));
+        }
+        Ok(())
+    }
+
+    fn lock_in_flight(&self) -> std::sync::MutexGuard<'_, HashMap<u64, (String, AbortHandle)>> {
+        self.in_flight
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    // Wrap a network operation so it can be listed and cancelled. Operations cancelled
+    // before they start never touch the network
+    fn track<'f, T: Send + 'f>(
+        &self,
+        address: String,
+        future: BoxFuture<'f, Result<T, Error>>,
+    ) -> BoxFuture<'f, Result<T, Error>> {
+        let handle = self.clone();
+        Box::pin(async move {
+            if handle.is_cancelled() {
+                return Err(Error::Pod(format!(
+                    "Operation cancelled before it started: {address}"
+                )));
+            }
+
+            let (abort_handle, abort_registration) = AbortHandle::new_pair();
+            let id = handle.next_id.fetch_add(1, Ordering::SeqCst);
+            handle
+                .lock_in_flight()
+                .insert(id, (address.clone(), abort_handle.clone()));
+            // Cancelled between the check above and registering the operation
+            if handle.is_cancelled() {
+                abort_handle.abort();
+            }
+
+            let result = Abortable::new(future, abort_registration).await;
+            handle.lock_in_flight().remove(&id);
+            match result {
+                Ok(result) => result,
+                Err(_aborted) => Err(Error::Pod(format!("Operation cancelled: {address}"))),
+            }
+        })
+    }
+}
+
+// State carried between the items of `upload_all_stream`
+struct UploadStreamState<'f> {
+    operations: futures::stream::BufferUnordered<
+        futures::stream::Iter<std::vec::IntoIter<BoxFuture<'f, Result<OperationOutcome, Error>>>>,
+    >,
+    pointer_updates: HashMap<String, u64>,
+    pod_uploads: HashMap<String, Vec<String>>,
+    completed: HashSet<String>,
+    pending_error: Option<Error>,
+    failed: bool,
+}
+
+/// Batch operation types for concurrent network operations
+#[derive(Debug, Clone)]
+pub enum BatchOperation {
+    PointerGet { address: String },
+    PointerPut { address: String, target: String },
+    PointerUpdate { address: String, target: String },
+    ScratchpadGet { address: String },
+    ScratchpadPut { address: String, data: String },
+    ScratchpadUpdate { address: String, data: String },
+}
+
+/// Results from batch operations
+#[derive(Debug, Clone)]
+pub enum BatchResult {
+    PointerGetResult {
+        address: String,
+        success: bool,
+        data: Option<String>,
+    },
+    PointerPutResult {
+        address: String,
+        success: bool,
+    },
+    PointerUpdateResult {
+        address: String,
+        success: bool,
+    },
+    ScratchpadGetResult {
+        address: String,
+        success: bool,
+        data: Option<String>,
+    },
+    ScratchpadPutResult {
+        address: String,
+        success: bool,
+    },
+    ScratchpadUpdateResult {
+        address: String,
+        success: bool,
+    },
+}
+
+/// Preprocessed batch of operations ready for concurrent execution
+#[derive(Debug, Clone, Default)]
+pub struct NetworkBatch {
+    pub pointer_gets: Vec<String>,
+    pub pointer_puts: Vec<(String, String)>, // (address, target)
+    pub pointer_updates: Vec<(String, String)>, // (address, target)
+    pub scratchpad_gets: Vec<String>,
+    pub scratchpad_puts: Vec<(String, String)>, // (address, data)
+    pub scratchpad_updates: Vec<(String, String)>, // (address, data)
+}
+
+use crate::graph;
+
+// Error handling
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Connect(#[from] ConnectError),
+    #[error(transparent)]
+    Pointer(Box<PointerError>),
+    #[error(transparent)]
+    Scratchpad(Box<ScratchpadError>),
+    #[error(transparent)]
+    Blsttc(#[from] BlsttcError),
+    #[error(transparent)]
+    Address(#[from] AddressParseError),
+    #[error(transparent)]
+    FromUtf8(#[from] FromUtf8Error),
+    #[error(transparent)]
+    KeyStore(#[from] KeyStoreError),
+    #[error(transparent)]
+    DataStore(#[from] DataStoreError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+    #[error(transparent)]
+    Serde(#[from] SerdeError),
+    #[error(transparent)]
+    Graph(#[from] GraphError),
+    #[error("{0}")]
+    Pod(String),
+}
+
+impl From<PointerError> for Error {
+    fn from(err: PointerError) -> Self {
+        Error::Pointer(Box::new(err))
+    }
+}
+
+impl From<ScratchpadError> for Error {
+    fn from(err: ScratchpadError) -> Self {
+        Error::Scratchpad(Box::new(err))
+    }
+}
+
+#[derive(serde::Serialize)]
+#[serde(tag = "kind", content = "message")]
+#[serde(rename_all = "camelCase")]
+pub enum ErrorKind {
+    Connect(String),
+    Pointer(String),
+    Scratchpad(String),
+    Blsttc(String),
+    Address(String),
+    FromUtf8(String),
+    KeyStore(String),
+    DataStore(String),
+    Io(String),
+    Serde(String),
+    Graph(String),
+    Pod(String),
+}
+
+impl serde::Serialize for Error {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        let error_message = self.to_string();
+        let error_kind = match self {
+            Self::Connect(_) => ErrorKind::Connect(error_message),
+            Self::Pointer(_) => ErrorKind::Pointer(error_message),
+            Self::Scratchpad(_) => ErrorKind::Scratchpad(error_message),
+            Self::Blsttc(_) => ErrorKind::Blsttc(error_message),
+            Self::Address(_) => ErrorKind::Address(error_message),
+            Self::FromUtf8(_) => ErrorKind::FromUtf8(error_message),
+            Self::KeyStore(_) => ErrorKind::KeyStore(error_message),
+            Self::DataStore(_) => ErrorKind::DataStore(error_message),
+            Self::Io(_) => ErrorKind::Io(error_message),
+            Self::Serde(_) => ErrorKind::Serde(error_message),
+            Self::Graph(_) => ErrorKind::Graph(error_message),
+            Self::Pod(_) => ErrorKind::Pod(error_message),
+        };
+        error_kind.serialize(serializer)
+    }
+}
+
+//#[derive(Clone)]
+pub struct PodManager<'a> {
+    pub client: Client,
+    pub wallet: &'a Wallet,
+    pub data_store: &'a mut DataStore,
+    pub key_store: &'a mut KeyStore,
+    pub graph: &'a mut Graph,
+    payment_override: Option<PaymentOption>,
+    fork_reports: std::sync::Mutex<Vec<ForkInfo>>,
+    refresh_ttl: Duration,
+    refresh_mode: RefreshMode,
+    refresh_conflict_policy: RefreshConflictPolicy,
+    history_depth: usize,
+    scratchpad_size_limit: usize,
+    max_concurrency: usize,
+    retry_attempts: u32,
+    retry_base_delay: Duration,
+    progress_callback: Option<ProgressCallback>,
+    operation_handle: OperationHandle,
+    defer_configuration: bool,
+    clock: Arc<dyn Clock>,
+}
+
+impl<'a> fmt::Debug for PodManager<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PodManager")
+            .field("client", &"Client(Debug not implemented)")
+            .field("wallet", &self.wallet.address().to_string())
+            .field("data_store", &"DataStore(Debug not implemented)")
+            .field("key_store", &"KeyStore(Debug not implemented)")
+            .field("graph", &"Graph(Debug not implemented)")
+            .field("payment_override", &self.payment_override.is_some())
+            .field(
+                "fork_reports",
+                &self.fork_reports.lock().map(|r| r.len()).unwrap_or(0),
+            )
+            .field("refresh_ttl", &self.refresh_ttl)
+            .field("refresh_mode", &self.refresh_mode)
+            .field("refresh_conflict_policy", &self.refresh_conflict_policy)
+            .field("history_depth", &self.history_depth)
+            .field("scratchpad_size_limit", &self.scratchpad_size_limit)
+            .field("max_concurrency", &self.max_concurrency)
+            .field("retry_attempts", &self.retry_attempts)
+            .field("retry_base_delay", &self.retry_base_delay)
+            .field("progress_callback", &self.progress_callback.is_some())
+            .field(
+                "operations_in_flight",
+                &self.operation_handle.in_flight().len(),
+            )
+            .field("defer_configuration", &self.defer_configuration)
+            .finish()
+    }
+}
+
+impl<'a> PodManager<'a> {
+    /// Creates a new PodManager instance with the provided components.
+    ///
+    /// This constructor initializes a PodManager that coordinates between the Autonomi network client,
+    /// wallet for payments, local data storage, cryptographic key management, and RDF graph database.
+    /// The PodManager serves as the main interface for pod operations including creation, modification,
+    /// synchronization, and querying.
+    ///
+    /// # Parameters
+    ///
+    /// * `client` - An Autonomi network client for communicating with the decentralized network
+    /// * `wallet` - A reference to a wallet for handling network transaction payments
+    /// * `data_store` - A mutable reference to the local data storage system
+    /// * `key_store` - A mutable reference to the cryptographic key management system
+    /// * `graph` - A mutable reference to the RDF graph database for semantic data storage
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(PodManager)` on successful initialization, or an `Error` if any component
+    /// fails to initialize properly.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use autonomi::{Client, Wallet};
+    /// use colonylib::{PodManager, DataStore, KeyStore, Graph};
+    ///
+    /// let client = Client::init_local().await?;
+    /// let evm_network = client.evm_network();
+    /// let wallet = &Wallet::new_from_private_key(evm_network.clone(), PRIVATE_KEY)?;
+    /// let data_store = &mut DataStore::create()?;
+    /// let key_store_file = data_store.get_keystore_path();
+    /// let key_store: &mut KeyStore = if key_store_file.exists() {
+    ///     let mut file = std::fs::File::open(key_store_file)?;
+    ///     &mut KeyStore::from_file(&mut file, "password")?
+    /// } else {
+    ///     let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    ///     &mut KeyStore::from_mnemonic(mnemonic)?
+    /// };
+    /// let _ = key_store.add_wallet_key("main", PRIVATE_KEY)?;
+    /// let graph_path = data_store.get_graph_path();
+    /// let graph = &mut Graph::open(&graph_path)?;
+    /// let pod_manager = PodManager::new(client, wallet, data_store, key_store, graph).await?;
+    /// ```
+    pub async fn new(
+        client: Client,
+        wallet: &'a Wallet,
+        data_store: &'a mut DataStore,
+        key_store: &'a mut KeyStore,
+        graph: &'a mut Graph,
+    ) -> Result<Self, Error> {
+        // The configuration pod is bookkeeping data, keep it out of listings and search results
+        graph.set_hidden_configuration(key_store.get_configuration_address().ok().as_deref());
+        graph.set_hidden_pods(&data_store.get_hidden_pods()?);
+        let clock = graph.clock();
+
+        Ok(Self {
+            client,
+            wallet,
+            data_store,
+            key_store,
+            graph,
+            payment_override: None,
+            fork_reports: std::sync::Mutex::new(Vec::new()),
+            refresh_ttl: Duration::ZERO,
+            refresh_mode: RefreshMode::default(),
+            refresh_conflict_policy: RefreshConflictPolicy::default(),
+            history_depth: 0,
+            scratchpad_size_limit: DEFAULT_SCRATCHPAD_SIZE_LIMIT,
+            max_concurrency: DEFAULT_MAX_CONCURRENCY,
+            retry_attempts: DEFAULT_RETRY_ATTEMPTS,
+            retry_base_delay: DEFAULT_RETRY_BASE_DELAY,
+            progress_callback: None,
+            operation_handle: OperationHandle::default(),
+            defer_configuration: false,
+            clock,
+        })
+    }
+
+    /// Replaces the clock used for timestamps written into pods.
+    ///
+    /// Modified dates, the timestamp comments at the start of each scratchpad, and search
+    /// timestamps are all read from this clock, and the graph database is switched to the same
+    /// clock. The default is the system clock. Tests can pass a [`FakeClock`] to control "now" and
+    /// make timestamp-dependent behavior, such as picking the newest version of a forked scratchpad,
+    /// deterministic. Pointer and scratchpad counters always use the system time.
+    ///
+    /// # Parameters
+    ///
+    /// * `clock` - The clock to read the current time from
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// use colonylib::clock::FakeClock;
+    /// use std::sync::Arc;
+    ///
+    /// let clock = FakeClock::new(chrono::Utc::now());
+    /// pod_manager.set_clock(Arc::new(clock.clone()));
+    /// clock.advance(chrono::Duration::minutes(5));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`FakeClock`]: crate::clock::FakeClock
+    pub fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+        self.graph.set_clock(clock.clone());
+        self.clock = clock;
+    }
+
+    /// Shows or hides the configuration pod in pod listings and search results.
+    ///
+    /// The configuration pod is a real pod holding the bookkeeping data for all of the user's
+    /// pods. By default it is excluded from [`list_my_pods`] and from the `text`, `by_type` and
+    /// `browse` searches so it doesn't clutter results. Advanced SPARQL queries are never filtered.
+    ///
+    /// # Parameters
+    ///
+    /// * `show` - `true` to include the configuration pod in results, `false` to hide it (the default)
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success, or an `Error` if the configuration address cannot be retrieved.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// // Debug view that shows everything
+    /// pod_manager.set_show_configuration_pod(true)?;
+    /// let all_pods = pod_manager.list_my_pods()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`list_my_pods`] - List the user's pods
+    /// - [`search`] - Search across all pods
+    pub fn set_show_configuration_pod(&mut self, show: bool) -> Result<(), Error> {
+        if show {
+            self.graph.set_hidden_configuration(None);
+        } else {
+            let configuration_address = self.key_store.get_configuration_address()?;
+            self.graph
+                .set_hidden_configuration(Some(configuration_address.as_str()));
+        }
+        Ok(())
+    }
+
+    /// Hides a pod from browse and search results, or shows it again.
+    ///
+    /// Useful for muting a noisy or untrusted referenced pod without removing the reference or
+    /// its downloaded data. The flag is only stored locally, so hiding a pod never changes the
+    /// pod that references it and nothing is queued for upload. Hidden pods are excluded from
+    /// the `text`, `by_type` and `browse` searches, type facets and the pods listed by
+    /// modification date, unless the search query sets `"include_hidden": true`. Advanced SPARQL
+    /// queries are never filtered.
+    ///
+    /// # Parameters
+    ///
+    /// * `pod_address` - The hexadecimal address or name of the pod
+    /// * `hidden` - `true` to hide the pod, `false` to show it again
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success, or an `Error` if the pod doesn't exist or the hidden pod list
+    /// cannot be written.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use serde_json::json;
+    ///
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let pod_address = "80e79010a13e7eee779f799d99a20b418436828269b18192d92940bc9ddbfe295a7e1823d7bff75c59cbacbdea101a0d";
+    /// pod_manager.set_pod_hidden(pod_address, true)?;
+    ///
+    /// // Hidden pods can still be searched explicitly
+    /// let results = pod_manager
+    ///     .search(json!({"type": "browse", "include_hidden": true}))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`get_hidden_pods`] - List the hidden pods
+    /// - [`search`] - Search across all pods
+    pub fn set_pod_hidden(&mut self, pod_address: &str, hidden: bool) -> Result<(), Error> {
+        // A pod removed since it was hidden can still be shown again
+        let pod_address = match self.graph.check_pod_exists(pod_address) {
+            Ok(address) => address,
+            Err(_) if !hidden => pod_address.to_string(),
+            Err(e) => return Err(e.into()),
+        };
+        let pod_address = pod_address.trim();
+
+        self.data_store.set_pod_hidden(pod_address, hidden)?;
+        self.graph
+            .set_hidden_pods(&self.data_store.get_hidden_pods()?);
+        Ok(())
+    }
+
+    /// Returns the addresses of the pods hidden with [`set_pod_hidden`], sorted by address.
+    pub fn get_hidden_pods(&self) -> Result<Vec<String>, Error> {
+        Ok(self.data_store.get_hidden_pods()?)
+    }
+
+    /// Overrides the payment option used for network uploads.
+    ///
+    /// By default every upload is paid for by the wallet the PodManager was created with. This
+    /// function lets callers route payments through a different wallet or a pre-paid receipt,
+    /// for example to pay for different pods from different wallets. Passing `None` restores the
+    /// default wallet.
+    ///
+    /// # Parameters
+    ///
+    /// * `payment` - The payment option to use for subsequent uploads, or `None` for the default wallet
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>, other_wallet: &Wallet) -> Result<(), Box<dyn std::error::Error>> {
+    /// // Pay for this pod from a different wallet
+    /// pod_manager.set_payment_override(Some(PaymentOption::from(other_wallet)));
+    /// pod_manager.upload_pod("My Documents").await?;
+    ///
+    /// // Go back to paying with the default wallet
+    /// pod_manager.set_payment_override(None);
+    /// pod_manager.upload_all().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`get_payment_address`] - Report which wallet will pay for uploads
+    /// - [`upload_all`] - Upload all pending changes
+    /// - [`upload_pod`] - Upload a single pod
+    pub fn set_payment_override(&mut self, payment: Option<PaymentOption>) {
+        self.payment_override = payment;
+    }
+
+    /// Returns the address of the wallet that will pay for the next upload.
+    ///
+    /// Returns `None` when the payment override is a pre-paid receipt rather than a wallet.
+    ///
+    /// # Related Functions
+    ///
+    /// - [`set_payment_override`] - Choose a different payment option for uploads
+    pub fn get_payment_address(&self) -> Option<String> {
+        match &self.payment_override {
+            Some(PaymentOption::Wallet(wallet)) => Some(wallet.address().to_string()),
+            Some(_) => None,
+            None => Some(self.wallet.address().to_string()),
+        }
+    }
+
+    /// Sets how long a refreshed pod is considered fresh.
+    ///
+    /// Applications that refresh on a timer would otherwise re-fetch every pod on each call to
+    /// [`refresh_cache`]. In [`RefreshMode::TtlAware`] mode, pods that were refreshed less than
+    /// `ttl` ago are skipped without contacting the network. Pods outside the TTL still go through
+    /// the usual pointer counter check. The last refreshed time of each pod is persisted in the
+    /// data store, so the TTL also applies across restarts. A TTL of zero (the default) disables
+    /// the check. The TTL is ignored in the other refresh modes, including the default
+    /// [`RefreshMode::Always`], so setting it has no effect until [`set_refresh_mode`] selects
+    /// `TtlAware`; a warning is logged when that is the case.
+    ///
+    /// # Parameters
+    ///
+    /// * `ttl` - How long a refreshed pod is skipped by subsequent refreshes
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// use colonylib::pod::RefreshMode;
+    /// use std::time::Duration;
+    ///
+    /// pod_manager.set_refresh_mode(RefreshMode::TtlAware);
+    /// pod_manager.set_refresh_ttl(Duration::from_secs(300));
+    /// pod_manager.refresh_cache(false).await?; // fetches all pods
+    /// pod_manager.refresh_cache(false).await?; // skips pods refreshed in the last 5 minutes
+    /// pod_manager.refresh_cache(true).await?; // ignores the TTL
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`refresh_cache`] - Refresh local pods, honoring the TTL unless forced
+    /// - [`set_refresh_mode`] - Choose whether the TTL is used
+    pub fn set_refresh_ttl(&mut self, ttl: Duration) {
+        if !ttl.is_zero() && !self.refresh_mode.uses_ttl() {
+            warn!(
+                "Refresh TTL of {:?} has no effect in {:?} refresh mode, use RefreshMode::TtlAware",
+                ttl, self.refresh_mode
+            );
+        }
+        self.refresh_ttl = ttl;
+    }
+
+    /// Sets how [`refresh_cache`] decides which pods to download.
+    ///
+    /// In every mode, the user's pods are only downloaded when their pointer counter on the
+    /// network is newer than the local copy. The default, [`RefreshMode::Always`], downloads the
+    /// configuration pod on each refresh regardless of its counter. This is the safe choice while
+    /// the network is unreliable, because a counter that was stored for a configuration fetch that
+    /// failed would otherwise hide new pods until the configuration changes again. On a reliable
+    /// network, [`RefreshMode::CounterAware`] checks the configuration counter too, and
+    /// [`RefreshMode::TtlAware`] additionally skips pods refreshed within the TTL set by
+    /// [`set_refresh_ttl`]. Calling [`refresh_cache`] with `force` set downloads every pod
+    /// whatever the mode.
+    ///
+    /// # Parameters
+    ///
+    /// * `mode` - The refresh mode to use for subsequent refreshes
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// use colonylib::pod::RefreshMode;
+    ///
+    /// pod_manager.set_refresh_mode(RefreshMode::CounterAware);
+    /// pod_manager.refresh_cache(false).await?; // only downloads pods that changed
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`refresh_cache`] - Refresh local pods using the refresh mode
+    /// - [`set_refresh_ttl`] - Set the TTL used by `RefreshMode::TtlAware`
+    pub fn set_refresh_mode(&mut self, mode: RefreshMode) {
+        self.refresh_mode = mode;
+    }
+
+    /// Returns the refresh mode used by [`refresh_cache`]
+    pub fn refresh_mode(&self) -> RefreshMode {
+        self.refresh_mode
+    }
+
+    /// Sets what [`refresh_cache`] does with pods that have local changes waiting for upload.
+    ///
+    /// Refreshing overwrites the local copy of a pod with the version on the network, which would
+    /// lose edits that are queued for [`upload_all`] but not uploaded yet. A pod has pending local
+    /// changes while it is in the update list or queued for removal, and the configuration pod
+    /// also while its update is deferred. With the default, [`RefreshConflictPolicy::PreferLocal`],
+    /// those pods are skipped and keep their local edits. [`RefreshConflictPolicy::PreferRemote`]
+    /// downloads them anyway, and [`RefreshConflictPolicy::Error`] stops the refresh with an error.
+    ///
+    /// # Parameters
+    ///
+    /// * `policy` - The conflict policy to use for subsequent refreshes
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// use colonylib::pod::RefreshConflictPolicy;
+    ///
+    /// // Throw away local edits in favor of the network copy
+    /// pod_manager.set_refresh_conflict_policy(RefreshConflictPolicy::PreferRemote);
+    /// pod_manager.refresh_cache(false).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`refresh_cache`] - Refresh local pods using the conflict policy
+    /// - [`get_update_list`] - List the pods with pending changes
+    /// - [`upload_all`] - Upload the pending changes
+    pub fn set_refresh_conflict_policy(&mut self, policy: RefreshConflictPolicy) {
+        self.refresh_conflict_policy = policy;
+    }
+
+    /// Returns the conflict policy used by [`refresh_cache`]
+    pub fn refresh_conflict_policy(&self) -> RefreshConflictPolicy {
+        self.refresh_conflict_policy
+    }
+
+    /// Sets how many earlier versions of each pod are kept locally.
+    ///
+    /// Pod history is off by default. When enabled, the local copy of a pod is saved before an
+    /// edit overwrites it, so a mistaken edit can be undone with [`restore_pod_version`] even
+    /// after it was uploaded. Versions are numbered by the pod pointer count they were saved at,
+    /// so all local edits between two uploads share one version holding the state before the
+    /// first of them. Only the newest `depth` versions of each pod are kept, older ones are
+    /// deleted as new versions are saved and when the depth is lowered. Setting the depth to 0
+    /// stops saving versions but leaves the saved ones in place.
+    ///
+    /// # Parameters
+    ///
+    /// * `depth` - The number of versions to keep for each pod, 0 to disable pod history
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success, or an `Error` if old versions cannot be deleted.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// // Keep the last 5 versions of every pod
+    /// pod_manager.set_history_depth(5)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`list_pod_history`] - List the saved versions of a pod
+    /// - [`restore_pod_version`] - Roll a pod back to a saved version
+    pub fn set_history_depth(&mut self, depth: usize) -> Result<(), Error> {
+        self.history_depth = depth;
+        if depth == 0 {
+            return Ok(());
+        }
+        for pod_address in self.data_store.list_pointer_addresses()? {
+            self.data_store.prune_pod_versions(&pod_address, depth)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the number of pod versions kept locally, 0 when pod history is disabled
+    pub fn history_depth(&self) -> usize {
+        self.history_depth
+    }
+
+    /// Sets the maximum size of the data written to a single scratchpad.
+    ///
+    /// Pod data larger than the limit is split over several scratchpads. The default is
+    /// [`DEFAULT_SCRATCHPAD_SIZE_LIMIT`], the largest scratchpad the Autonomi network accepts.
+    /// A smaller limit is useful for exercising the multi-scratchpad logic in tests without
+    /// generating megabytes of data. Values below 64 bytes are treated as 64.
+    ///
+    /// # Parameters
+    ///
+    /// * `bytes` - The maximum number of bytes per scratchpad, including the timestamp comment
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// pod_manager.set_scratchpad_size_limit(1024);
+    /// let (pod_address, _) = pod_manager.add_pod("Small Scratchpads").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`split_data_into_chunks`] - Split pod data into scratchpad sized chunks
+    /// - [`estimate_subject_batch_scratchpads`] - Count the scratchpads a batch update needs
+    pub fn set_scratchpad_size_limit(&mut self, bytes: usize) {
+        self.scratchpad_size_limit = bytes.max(MIN_SCRATCHPAD_SIZE_LIMIT);
+    }
+
+    /// Returns the maximum size in bytes of the data written to a single scratchpad
+    pub fn scratchpad_size_limit(&self) -> usize {
+        self.scratchpad_size_limit
+    }
+
+    /// Sets how many network operations run at the same time.
+    ///
+    /// Uploads, refreshes and downloads start one pointer or scratchpad operation per address.
+    /// With many pods, starting all of them at once floods the network and leads to failed
+    /// requests, so at most `max` operations are in flight at any time and the rest wait for a
+    /// free slot. The default is [`DEFAULT_MAX_CONCURRENCY`]. A value of 0 is treated as 1.
+    ///
+    /// # Parameters
+    ///
+    /// * `max` - The maximum number of network operations in flight
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// // Go easy on a slow connection
+    /// pod_manager.set_max_concurrency(4);
+    /// pod_manager.refresh_cache(false).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`upload_all`] - Upload pending changes
+    /// - [`refresh_cache`] - Refresh local pods from the network
+    /// - [`refresh_ref`] - Download referenced pods
+    pub fn set_max_concurrency(&mut self, max: usize) {
+        self.max_concurrency = max.max(1);
+    }
+
+    /// Returns the maximum number of network operations run at the same time
+    pub fn max_concurrency(&self) -> usize {
+        self.max_concurrency
+    }
+
+    /// Sets how failed network operations are retried.
+    ///
+    /// The network is flaky, so pointer and scratchpad gets made while uploading, refreshing and
+    /// downloading pods are retried when they fail with a transient network error. Puts are
+    /// never retried, they are paid for before they are verified and a retry could pay twice.
+    /// The delay before the first retry is `base_delay` and doubles for each following retry.
+    /// Permanent errors are not retried: a pointer or scratchpad that does not exist is created
+    /// or skipped as before. The defaults are [`DEFAULT_RETRY_ATTEMPTS`] attempts and a
+    /// [`DEFAULT_RETRY_BASE_DELAY`] base delay.
+    ///
+    /// # Parameters
+    ///
+    /// * `attempts` - The number of times each operation is tried, 1 disables retries
+    /// * `base_delay` - The delay before the first retry
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// use std::time::Duration;
+    ///
+    /// // Try each operation up to 5 times, waiting 1, 2, 4 and 8 seconds between tries
+    /// pod_manager.set_retry_policy(5, Duration::from_secs(1));
+    /// pod_manager.refresh_cache(false).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`set_max_concurrency`] - Limit the network operations in flight
+    /// - [`upload_all`] - Upload pending changes
+    /// - [`refresh_cache`] - Refresh local pods from the network
+    pub fn set_retry_policy(&mut self, attempts: u32, base_delay: Duration) {
+        self.retry_attempts = attempts.max(1);
+        self.retry_base_delay = base_delay;
+    }
+
+    /// Returns the number of attempts and the base retry delay of network operations
+    pub fn retry_policy(&self) -> (u32, Duration) {
+        (self.retry_attempts, self.retry_base_delay)
+    }
+
+    /// Sets a callback that receives the progress of [`refresh_cache`] and [`refresh_ref`].
+    ///
+    /// Refreshing many pods can take a long time. The callback is called with a
+    /// [`RefreshProgress`] at the start and end of each phase of a refresh, and after each pod
+    /// pointer is fetched, so applications can show a progress bar or a spinner. The callback
+    /// runs on the task doing the refresh and should return quickly. Only one callback is kept,
+    /// setting a new one replaces the previous one.
+    ///
+    /// # Parameters
+    ///
+    /// * `callback` - Function called with each progress update
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// pod_manager.set_progress_callback(Box::new(|progress| {
+    ///     println!("{:?}: {}/{} pods at depth {}", progress.phase,
+    ///              progress.pods_processed, progress.pods_total, progress.current_depth);
+    /// }));
+    /// pod_manager.refresh_ref(2).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`clear_progress_callback`] - Stop reporting progress
+    /// - [`refresh_cache`] - Refresh local pods
+    /// - [`refresh_ref`] - Refresh local and referenced pods
+    pub fn set_progress_callback(&mut self, callback: ProgressCallback) {
+        self.progress_callback = Some(callback);
+    }
+
+    /// Removes the callback set with [`set_progress_callback`]
+    pub fn clear_progress_callback(&mut self) {
+        self.progress_callback = None;
+    }
+
+    // Pass the progress of a refresh to the progress callback, if one is set
+    fn report_progress(
+        &self,
+        phase: RefreshPhase,
+        pods_processed: usize,
+        pods_total: usize,
+        current_depth: u64,
+    ) {
+        if let Some(callback) = &self.progress_callback {
+            callback(RefreshProgress {
+                phase,
+                pods_processed,
+                pods_total,
+                current_depth,
+            });
+        }
+    }
+
+    /// Returns the current settings as a serializable [`PodManagerConfig`].
+    ///
+    /// Applications can save the returned value, for example as JSON next to their own
+    /// preferences, and restore it in a later session with [`apply_config`]. The payment override,
+    /// the clock and the local encryption password are not included.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # fn example(pod_manager: &PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = pod_manager.get_config();
+    /// std::fs::write("colony_settings.json", serde_json::to_string_pretty(&config)?)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`apply_config`] - Restore saved settings
+    pub fn get_config(&self) -> PodManagerConfig {
+        PodManagerConfig {
+            refresh_mode: self.refresh_mode,
+            refresh_ttl: self.refresh_ttl,
+            defer_configuration: self.defer_configuration,
+            show_configuration_pod: !self.graph.configuration_is_hidden(),
+            refresh_conflict_policy: self.refresh_conflict_policy,
+            history_depth: self.history_depth,
+            max_concurrency: self.max_concurrency,
+            retry_attempts: self.retry_attempts,
+            retry_base_delay: self.retry_base_delay,
+            scratchpad_size_limit: self.scratchpad_size_limit,
+        }
+    }
+
+    /// Applies settings saved with [`get_config`].
+    ///
+    /// Every setting in the [`PodManagerConfig`] replaces the current one, the same as calling
+    /// the individual setters. Use [`PodManagerConfig::default`] to go back to the defaults.
+    ///
+    /// # Parameters
+    ///
+    /// * `config` - The settings to apply
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success, or an `Error` if the configuration address cannot be retrieved
+    /// or the scratchpad size limit is smaller than the minimum accepted by
+    /// [`set_scratchpad_size_limit`]. Nothing is changed when the scratchpad size limit is rejected.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use colonylib::pod::PodManagerConfig;
+    ///
+    /// # fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let saved = std::fs::read_to_string("colony_settings.json")?;
+    /// let config: PodManagerConfig = serde_json::from_str(&saved)?;
+    /// pod_manager.apply_config(&config)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`get_config`] - Read the current settings
+    /// - [`set_refresh_mode`] - Set the refresh mode
+    /// - [`set_refresh_ttl`] - Set the refresh TTL
+    /// - [`set_refresh_conflict_policy`] - Protect pending local edits on refresh
+    /// - [`set_history_depth`] - Keep earlier pod versions locally
+    /// - [`set_max_concurrency`] - Limit the network operations in flight
+    /// - [`set_retry_policy`] - Retry failed network operations
+    /// - [`set_scratchpad_size_limit`] - Limit the data written to each scratchpad
+    /// - [`defer_configuration_sync`] - Batch configuration updates
+    /// - [`set_show_configuration_pod`] - Show or hide the configuration pod
+    pub fn apply_config(&mut self, config: &PodManagerConfig) -> Result<(), Error> {
+        // A saved limit this small is corrupt rather than a setting, so don't silently clamp it
+        if config.scratchpad_size_limit < MIN_SCRATCHPAD_SIZE_LIMIT {
+            return Err(Error::Pod(format!(
+                "Scratchpad size limit {} is below the minimum of {} bytes",
+                config.scratchpad_size_limit, MIN_SCRATCHPAD_SIZE_LIMIT
+            )));
+        }
+        self.set_show_configuration_pod(config.show_configuration_pod)?;
+        self.set_refresh_mode(config.refresh_mode);
+        self.set_refresh_ttl(config.refresh_ttl);
+        self.set_refresh_conflict_policy(config.refresh_conflict_policy);
+        self.set_history_depth(config.history_depth)?;
+        self.set_max_concurrency(config.max_concurrency);
+        self.set_retry_policy(config.retry_attempts, config.retry_base_delay);
+        self.set_scratchpad_size_limit(config.scratchpad_size_limit);
+        self.defer_configuration_sync(config.defer_configuration);
+        Ok(())
+    }
+
+    /// Defers writing the configuration pod until it is explicitly synced.
+    ///
+    /// Every subject or reference edit normally rewrites the configuration pod scratchpads to
+    /// record the new key count and modified date, so bulk cataloging produces constant
+    /// configuration rewrites. With deferral enabled, edits only update the configuration graph in
+    /// the local graph database and mark the configuration as pending. The configuration
+    /// scratchpads are written once, on the next call to [`sync_configuration`] or [`upload_all`].
+    ///
+    /// The pending state is persisted in the data store and the configuration pod is rebuilt
+    /// from the graph database when synced, so nothing is lost if the process exits before the
+    /// configuration is synced. Disabling deferral does not sync a pending configuration by itself.
+    ///
+    /// # Parameters
+    ///
+    /// * `defer` - `true` to batch configuration updates, `false` to write them on every change
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>, items: Vec<(String, String)>) -> Result<(), Box<dyn std::error::Error>> {
+    /// pod_manager.defer_configuration_sync(true);
+    /// for (subject, jsonld) in &items {
+    ///     pod_manager.put_subject_data("pod_address", subject, jsonld).await?;
+    /// }
+    /// pod_manager.sync_configuration().await?; // configuration written once
+    /// pod_manager.defer_configuration_sync(false);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`sync_configuration`] - Write a pending configuration pod
+    /// - [`upload_all`] - Syncs a pending configuration before uploading
+    pub fn defer_configuration_sync(&mut self, defer: bool) {
+        self.defer_configuration = defer;
+    }
+
+    /// Writes a pending configuration pod to its local scratchpads.
+    ///
+    /// When configuration sync is deferred with [`defer_configuration_sync`], configuration
+    /// changes are only recorded in the graph database. This function rebuilds the configuration
+    /// pod data from the graph, updates its key count, writes it to the configuration scratchpads
+    /// and adds it to the update list. It does nothing if no configuration update is pending.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success, or an `Error` if:
+    /// - The configuration address cannot be retrieved
+    /// - The graph database operations fail
+    /// - The scratchpad files cannot be written
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// pod_manager.sync_configuration().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`defer_configuration_sync`] - Batch configuration updates
+    /// - [`upload_all`] - Upload pending changes to the network
+    pub async fn sync_configuration(&mut self) -> Result<(), Error> {
+        if !self.data_store.configuration_pending() {
+            return Ok(());
+        }
+
+        let configuration_address = self.key_store.get_configuration_address()?;
+        let configuration_address = configuration_address.as_str();
+        info!(
+            "Syncing deferred configuration pod {}",
+            configuration_address
+        );
+
+        // Rebuild the configuration pod data from the graph
+        let num_keys = self.key_store.get_num_keys();
+        self.graph
+            .update_key_count(configuration_address, num_keys)?;
+        let configuration = self.graph.get_pod_graph_data(configuration_address)?;
+        self.process_pod_data(configuration_address, configuration)
+            .await?;
+
+        self.data_store.set_configuration_pending(false)?;
+        Ok(())
+    }
+
+    /// Enables or disables encryption of the local pointer and scratchpad files.
+    ///
+    /// Scratchpad files hold the pod data as plaintext TriG. On shared machines this lets users
+    /// keep the local cache encrypted with a password, using an authenticated cipher with a fresh
+    /// salt and nonce stored in each file. Files are encrypted when written and decrypted when
+    /// read. Files written before encryption was enabled are read as plaintext and encrypted the
+    /// next time they are written.
+    ///
+    /// This protects the local cache only. Scratchpads on the Autonomi network are public, and
+    /// uploads still send the plaintext TriG data.
+    ///
+    /// # Parameters
+    ///
+    /// * `key` - The password used to encrypt local files, or `None` to store them as plaintext
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// pod_manager.set_local_encryption(Some("my local cache password"));
+    /// pod_manager.refresh_cache(false).await?; // downloaded pods are now encrypted on disk
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`refresh_cache`] - Download pods into the local cache
+    /// - [`upload_all`] - Upload local pods to the network
+    pub fn set_local_encryption(&mut self, key: Option<&str>) {
+        self.data_store.set_local_encryption(key);
+    }
+
+    /// Changes the password the key store file is encrypted with.
+    ///
+    /// The key store file in the data directory must open with the old password. It is then
+    /// replaced with the current key store encrypted under the new password. The new file is
+    /// written next to the old one first and moved into place, so a failure part way leaves the
+    /// old file untouched.
+    ///
+    /// # Parameters
+    ///
+    /// * `old_password` - The password the key store file is currently encrypted with
+    /// * `new_password` - The password to encrypt the key store file with from now on
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success, or an `Error` if:
+    /// - The key store file does not exist or cannot be written
+    /// - The old password does not open the key store file
+    /// - The key store file belongs to a different key store
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # fn example(pod_manager: &PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// pod_manager.change_keystore_password("password", "a much better password")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`set_local_encryption`] - Encrypt the local pod files
+    pub fn change_keystore_password(
+        &self,
+        old_password: &str,
+        new_password: &str,
+    ) -> Result<(), Error> {
+        let key_store_path = self.data_store.get_keystore_path();
+        let updated_path = key_store_path.with_extension("db.tmp");
+
+        let mut current = std::fs::File::open(&key_store_path)?;
+        let mut updated = std::fs::File::create(&updated_path)?;
+        if let Err(e) =
+            self.key_store
+                .change_password(&mut current, &mut updated, old_password, new_password)
+        {
+            drop(updated);
+            let _ = std::fs::remove_file(&updated_path);
+            return Err(e.into());
+        }
+        updated.sync_all()?;
+        drop(updated);
+        std::fs::rename(&updated_path, &key_store_path)?;
+
+        info!("Changed the key store password");
+        Ok(())
+    }
+
+    /// Returns a handle for listing and cancelling in-flight network operations.
+    ///
+    /// Upload operations launch many concurrent network calls. The returned handle can be moved to
+    /// another task (for example a UI cancel button) while an upload is running. Calling
+    /// [`OperationHandle::cancel`] aborts the outstanding network calls and prevents the remaining
+    /// ones from starting. The update list is kept in sync with what actually reached the network,
+    /// so a cancelled upload can simply be retried later.
+    ///
+    /// # Returns
+    ///
+    /// Returns an [`OperationHandle`] shared with this PodManager.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let handle = pod_manager.operation_handle();
+    /// tokio::spawn(async move {
+    ///     tokio::time::sleep(std::time::Duration::from_secs(30)).await;
+    ///     println!("Cancelling {} operations", handle.in_flight().len());
+    ///     handle.cancel();
+    /// });
+    ///
+    /// if let Err(e) = pod_manager.upload_all().await {
+    ///     println!("Upload stopped: {}", e);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`upload_all`] - Upload all pending changes
+    /// - [`upload_all_stream`] - Upload all pending changes as a stream
+    /// - [`set_cancellation_token`] - Cancel operations with an application provided flag
+    pub fn operation_handle(&self) -> OperationHandle {
+        self.operation_handle.clone()
+    }
+
+    /// Uses an application provided flag to cancel long running network operations.
+    ///
+    /// Setting the flag to `true` has the same effect as [`OperationHandle::cancel`] on uploads.
+    /// [`refresh_ref`] checks the flag before each depth level and between the batches of
+    /// referenced pods it downloads, so following references with a depth of 0 can be stopped.
+    /// Downloads already in flight are allowed to finish and are stored locally before the
+    /// operation returns, so the local cache stays consistent. Handles returned by
+    /// [`operation_handle`] see the flag whether they were obtained before or after this call.
+    ///
+    /// The flag belongs to the application and is never cleared by the PodManager. While it is
+    /// `true`, every cancellable operation stops right away, so set it back to `false` before
+    /// starting the next one. Setting a new token replaces the previous one.
+    ///
+    /// # Parameters
+    ///
+    /// * `token` - Shared flag, set it to `true` to cancel the running operation
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// use std::sync::Arc;
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    ///
+    /// let token = Arc::new(AtomicBool::new(false));
+    /// pod_manager.set_cancellation_token(token.clone());
+    /// let stop = token.clone();
+    /// tokio::spawn(async move {
+    ///     tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+    ///     stop.store(true, Ordering::SeqCst);
+    /// });
+    ///
+    /// if let Err(e) = pod_manager.refresh_ref(0).await {
+    ///     println!("Refresh stopped: {}", e);
+    ///     token.store(false, Ordering::SeqCst);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`operation_handle`] - Cancel uploads and list in-flight operations
+    /// - [`refresh_ref`] - Follow pod references
+    pub fn set_cancellation_token(&mut self, token: CancellationToken) {
+        self.operation_handle.set_token(token);
+    }
+
+    // Select the payment option for network writes, preferring the caller supplied override
+    fn payment_option(&self) -> PaymentOption {
+        match self.get_payment_address() {
+            Some(address) => info!("Paying for uploads with wallet {}", address),
+            None => info!("Paying for uploads with a pre-paid receipt"),
+        }
+        match &self.payment_override {
+            Some(payment_option) => payment_option.clone(),
+            None => PaymentOption::from(self.wallet),
+        }
+    }
+
+    // Create a new pointer key, make sure it is empty, and add it to the key store
+    async fn create_pointer_key(&mut self) -> Result<SecretKey, Error> {
+        // Derive a new key
+        info!("Deriving or using a free key");
+        let free_pointers = self.key_store.get_free_pointers();
+        let (pubkey, key) = self.key_store.add_pointer_key()?;
+
+        // A newly derived key has never been written to the network, a reused one may have been
+        if !free_pointers.contains_key(&pubkey) {
+            self.data_store.mark_known_new(&pubkey)?;
+        }
+
+        // If the address is being freed, unset the FREE attribute in the configuration graph
+        let configuration_address = self.key_store.get_configuration_address()?;
+        let configuration_address = configuration_address.as_str();
+        self.graph
+            .use_free_pointer(pubkey.as_str(), configuration_address)?;
+
+        info!("New key: {}", key);
+        let derived_key: SecretKey = SecretKey::from_hex(key.trim())?;
+        Ok(derived_key)
+    }
+
+    // Create a new scratchpad key, make sure it is empty, and add it to the key store
+    async fn create_scratchpad_key(&mut self) -> Result<SecretKey, Error> {
+        // Derive a new key
+        info!("Deriving or using a free key");
+        let free_scratchpads = self.key_store.get_free_scratchpads();
+        let (pubkey, key) = self.key_store.add_scratchpad_key()?;
+
+        // A newly derived key has never been written to the network, a reused one may have been
+        if !free_scratchpads.contains_key(&pubkey) {
+            self.data_store.mark_known_new(&pubkey)?;
+        }
+
+        // If the address is being freed, unset the FREE attribute in the configuration graph
+        let configuration_address = self.key_store.get_configuration_address()?;
+        let configuration_address = configuration_address.as_str();
+        self.graph
+            .use_free_scratchpad(pubkey.as_str(), configuration_address)?;
+
+        info!("New key: {}", key);
+        let derived_key: SecretKey = SecretKey::from_hex(key.trim())?;
+        Ok(derived_key)
+    }
+
+    ///////////////////////////////////////////
+    // Graph operations
+    ///////////////////////////////////////////
+
+    /// Performs a comprehensive search across all pod data using various search strategies.
+    ///
+    /// This function provides a flexible search interface that supports multiple search types
+    /// including text search, type-based search, predicate-based search, browse functionality,
     /// and advanced SPARQL queries. The search operates across all loaded pods in the graph
     /// database and returns enhanced results with metadata.
     ///
     /// # Parameters
     ///
-    /// * `query` - A JSON value containing the search parameters. Can be either:
-    ///   - A simple string for basic text search
-    ///   - A structured object with specific search type and parameters
+    /// * `query` - A JSON value containing the search parameters. Can be either:
+    ///   - A simple string for basic text search
+    ///   - A structured object with specific search type and parameters
+    ///
+    /// # Supported Query Types
+    ///
+    /// ## Text Search
+    /// ```json
+    /// {
+    ///   "type": "text",
+    ///   "text": "search term",
+    ///   "limit": 50,
+    ///   "scope": "local"
+    /// }
+    /// ```
+    /// The optional `scope` parameter accepts `"all"` (default) to search every pod in the
+    /// graph database, or `"local"` to search only the user's own pods (depth 0).
+    ///
+    /// Set `"exact": true` to match literal values equal to `text` instead of containing it,
+    /// for looking up a subject by a known identifier or exact title. An exact search can be
+    /// restricted to a single property with `"predicate_uri"`:
+    /// ```json
+    /// {
+    ///   "type": "text",
+    ///   "text": "urn:isbn:9780141439518",
+    ///   "exact": true,
+    ///   "predicate_uri": "http://schema.org/identifier"
+    /// }
+    /// ```
+    ///
+    /// ## Type-based Search
+    /// ```json
+    /// {
+    ///   "type": "by_type",
+    ///   "type_uri": "http://example.org/MyType",
+    ///   "limit": 100
+    /// }
+    /// ```
+    ///
+    /// ## Predicate-based Search
+    /// ```json
+    /// {
+    ///   "type": "by_predicate",
+    ///   "predicate_uri": "http://example.org/hasProperty",
+    ///   "limit": 25
+    /// }
+    /// ```
+    ///
+    /// ## Advanced Search
+    /// ```json
+    /// {
+    ///   "type": "advanced",
+    ///   "sparql": "SELECT ?s ?p ?o WHERE { ?s ?p ?o }"
+    /// }
+    /// ```
+    ///
+    /// ## Ask
+    /// ```json
+    /// {
+    ///   "type": "ask",
+    ///   "sparql": "ASK { GRAPH ?g { <ant://c859818c623ce4fc0899c2ab43061b19caa0b0598eec35ef309dbe50c8af8d59> ?p ?o } }"
+    /// }
+    /// ```
+    /// Runs a SPARQL ASK query for a quick existence check. The result is a JSON object with a
+    /// single boolean `result` field instead of the usual search results.
+    ///
+    /// ## Construct
+    /// ```json
+    /// {
+    ///   "type": "construct",
+    ///   "sparql": "CONSTRUCT { ?s ?p ?o } WHERE { GRAPH ?g { ?s ?p ?o } }",
+    ///   "format": "turtle"
+    /// }
+    /// ```
+    /// Runs a SPARQL CONSTRUCT or DESCRIBE query and returns the resulting triples serialized
+    /// as RDF, for handing subgraphs to external tools. `format` accepts `"turtle"` (default),
+    /// `"trig"` or `"ntriples"`. The result is a JSON object with the `format` and the
+    /// serialized `data` string instead of the usual search results.
+    ///
+    /// ## Browse
+    /// ```json
+    /// {
+    ///   "type": "browse",
+    ///   "limit": 100,
+    ///   "scope": "local"
+    /// }
+    /// ```
+    /// Browse all subjects with their name, type, and description, ordered by pod depth.
+    /// The optional `scope` parameter works the same as for text search.
+    ///
+    /// Set `"explain": true` on a text search to add a `ranking` array to the results, with the
+    /// `match_count`, pod depth and final rank of each result (see [`SearchRanking`]), for
+    /// working out why one result is ranked above another.
+    ///
+    /// Set `"mode"` on a text search to change how `text` is matched. `"contains"` (default)
+    /// matches each word or quoted phrase as a case-insensitive substring, `"regex"` uses the
+    /// whole text as a case-insensitive regular expression, for example `"^Important"`, and
+    /// `"fuzzy"` works like `"contains"` but also ignores accents, so `"cafe"` finds `"Café"`.
+    /// An invalid regular expression returns an `Error`. The mode is ignored for exact searches.
+    ///
+    /// Text, type and predicate searches accept an `"offset"` alongside `"limit"` to page
+    /// through large result sets, for example `"limit": 10, "offset": 20` for the third page of
+    /// ten. Results keep a stable order across pages. Text search pages count result rows, one
+    /// per property of a matching subject, not subjects.
+    ///
+    /// Text, type and predicate searches accept a `"pods"` array of pod addresses to search only
+    /// within those pods, for example `"pods": ["<pod address>"]` to search a single
+    /// collection. An empty array searches every pod.
+    ///
+    /// Pods hidden with [`set_pod_hidden`] are left out of every structured search except
+    /// `advanced`. Set `"include_hidden": true` in the query to include them.
+    ///
+    /// # Returns
+    ///
+    /// Returns a JSON object containing:
+    /// - `sparql_results` - The raw SPARQL query results
+    /// - `result_count` - Number of results found
+    /// - `pods_found` - Array of pod addresses that contain matching data
+    /// - `search_timestamp` - ISO 8601 timestamp of when the search was performed
+    /// - `ranking` - Only for text searches with `"explain": true`, how each result was ranked
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The query format is invalid
+    /// - Required parameters are missing for the specified search type
+    /// - The underlying graph database query fails
+    /// - JSON parsing of results fails
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use serde_json::{json, Value};
+    ///
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// // Simple text search
+    /// let results = pod_manager.search(json!("my search term")).await?;
+    ///
+    /// // Structured search for specific type
+    /// let type_search = json!({
+    ///     "type": "by_type",
+    ///     "type_uri": "http://schema.org/Person",
+    ///     "limit": 10
+    /// });
+    /// let results = pod_manager.search(type_search).await?;
+    ///
+    /// // Browse all subjects ordered by pod depth
+    /// let browse_search = json!({
+    ///     "type": "browse",
+    ///     "limit": 50
+    /// });
+    /// let results = pod_manager.search(browse_search).await?;
+    ///
+    /// // Advanced SPARQL query
+    /// let advanced_search = json!({
+    ///     "type": "advanced",
+    ///     "sparql": "SELECT ?name WHERE { ?person <http://schema.org/name> ?name }"
+    /// });
+    /// let results = pod_manager.search(advanced_search).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn search(&mut self, query: Value) -> Result<Value, Error> {
+        info!("Performing search with query: {}", query);
+
+        // Explain the ranking of text search results if requested
+        let explain = query.get("type").and_then(|v| v.as_str()) == Some("text")
+            && query
+                .get("explain")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+
+        // Parse the query to determine search type and parameters
+        let search_results = if let Some(query_obj) = query.as_object() {
+            // Handle structured query
+            if let Some(search_type) = query_obj.get("type").and_then(|v| v.as_str()) {
+                // Restrict the search to the user's own pods (depth 0) if requested
+                let local_only = match query_obj.get("scope").and_then(|v| v.as_str()) {
+                    None | Some("all") => false,
+                    Some("local") => true,
+                    Some(scope) => {
+                        return Ok(Value::Object(serde_json::Map::from_iter([(
+                            "error".to_string(),
+                            Value::String(format!("Unknown search scope: {scope}")),
+                        )])));
+                    }
+                };
+                // Hidden pods are left out unless explicitly requested
+                let include_hidden = query_obj
+                    .get("include_hidden")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false);
+                let unfiltered_graph;
+                let graph: &Graph = if include_hidden {
+                    let mut graph = self.graph.clone();
+                    graph.set_hidden_pods(&[]);
+                    unfiltered_graph = graph;
+                    &unfiltered_graph
+                } else {
+                    &*self.graph
+                };
+                // Restrict text, type and predicate searches to specific pods if requested
+                let pods: Vec<String> = match query_obj.get("pods") {
+                    None | Some(Value::Null) => Vec::new(),
+                    Some(Value::Array(pods)) if pods.iter().all(|pod| pod.is_string()) => pods
+                        .iter()
+                        .filter_map(|pod| pod.as_str())
+                        .map(|pod| pod.to_string())
+                        .collect(),
+                    Some(_) => {
+                        return Ok(Value::Object(serde_json::Map::from_iter([(
+                            "error".to_string(),
+                            Value::String(
+                                "The 'pods' parameter must be an array of pod addresses"
+                                    .to_string(),
+                            ),
+                        )])));
+                    }
+                };
+                match search_type {
+                    "text" => {
+                        // Text search across all literal values
+                        if let Some(text) = query_obj.get("text").and_then(|v| v.as_str()) {
+                            let limit = query_obj.get("limit").and_then(|v| v.as_u64());
+                            let offset = query_obj.get("offset").and_then(|v| v.as_u64());
+                            let exact = query_obj
+                                .get("exact")
+                                .and_then(|v| v.as_bool())
+                                .unwrap_or(false);
+                            let mode = match query_obj.get("mode").and_then(|v| v.as_str()) {
+                                None | Some("contains") => TextMatchMode::Contains,
+                                Some("regex") => TextMatchMode::Regex,
+                                Some("fuzzy") => TextMatchMode::Fuzzy,
+                                Some(mode) => {
+                                    return Ok(Value::Object(serde_json::Map::from_iter([(
+                                        "error".to_string(),
+                                        Value::String(format!("Unknown text search mode: {mode}")),
+                                    )])));
+                                }
+                            };
+                            if exact {
+                                let predicate_uri =
+                                    query_obj.get("predicate_uri").and_then(|v| v.as_str());
+                                graph.search_exact_in_pods(
+                                    text,
+                                    predicate_uri,
+                                    limit,
+                                    offset,
+                                    local_only,
+                                    &pods,
+                                )?
+                            } else {
+                                graph.search_content_in_pods(
+                                    text, mode, limit, offset, local_only, &pods,
+                                )?
+                            }
+                        } else {
+                            return Ok(Value::Object(serde_json::Map::from_iter([(
+                                "error".to_string(),
+                                Value::String(
+                                    "Missing 'text' parameter for text search".to_string(),
+                                ),
+                            )])));
+                        }
+                    }
+                    "by_type" => {
+                        // Search by RDF type
+                        if let Some(type_uri) = query_obj.get("type_uri").and_then(|v| v.as_str()) {
+                            let limit = query_obj.get("limit").and_then(|v| v.as_u64());
+                            let offset = query_obj.get("offset").and_then(|v| v.as_u64());
+                            graph.search_by_type_in_pods(type_uri, limit, offset, &pods)?
+                        } else {
+                            return Ok(Value::Object(serde_json::Map::from_iter([(
+                                "error".to_string(),
+                                Value::String(
+                                    "Missing 'type_uri' parameter for type search".to_string(),
+                                ),
+                            )])));
+                        }
+                    }
+                    "by_predicate" => {
+                        // Search by predicate
+                        if let Some(predicate_uri) =
+                            query_obj.get("predicate_uri").and_then(|v| v.as_str())
+                        {
+                            let limit = query_obj.get("limit").and_then(|v| v.as_u64());
+                            let offset = query_obj.get("offset").and_then(|v| v.as_u64());
+                            graph.search_by_predicate_in_pods(
+                                predicate_uri,
+                                limit,
+                                offset,
+                                &pods,
+                            )?
+                        } else {
+                            return Ok(Value::Object(serde_json::Map::from_iter([(
+                                "error".to_string(),
+                                Value::String(
+                                    "Missing 'predicate_uri' parameter for predicate search"
+                                        .to_string(),
+                                ),
+                            )])));
+                        }
+                    }
+                    "advanced" => {
+                        // Advanced search with multiple criteria
+                        if let Some(sparql) = query_obj.get("sparql").and_then(|v| v.as_str()) {
+                            graph.advanced_search(sparql)?
+                        } else {
+                            return Ok(Value::Object(serde_json::Map::from_iter([(
+                                "error".to_string(),
+                                Value::String(
+                                    "Missing 'sparql' parameter for advanced search".to_string(),
+                                ),
+                            )])));
+                        }
+                    }
+                    "ask" => {
+                        // ASK query returning a boolean instead of SPARQL JSON
+                        let Some(sparql) = query_obj.get("sparql").and_then(|v| v.as_str()) else {
+                            return Ok(Value::Object(serde_json::Map::from_iter([(
+                                "error".to_string(),
+                                Value::String(
+                                    "Missing 'sparql' parameter for ask search".to_string(),
+                                ),
+                            )])));
+                        };
+                        let answer = graph.ask_query(sparql)?;
+                        info!("Ask search completed successfully");
+                        return Ok(serde_json::json!({ "result": answer }));
+                    }
+                    "construct" => {
+                        // CONSTRUCT or DESCRIBE query returning RDF instead of SPARQL JSON
+                        let Some(sparql) = query_obj.get("sparql").and_then(|v| v.as_str()) else {
+                            return Ok(Value::Object(serde_json::Map::from_iter([(
+                                "error".to_string(),
+                                Value::String(
+                                    "Missing 'sparql' parameter for construct search".to_string(),
+                                ),
+                            )])));
+                        };
+                        let format_name = query_obj
+                            .get("format")
+                            .and_then(|v| v.as_str())
+                            .unwrap_or("turtle");
+                        let format = match format_name {
+                            "turtle" => RdfFormat::Turtle,
+                            "trig" => RdfFormat::TriG,
+                            "ntriples" | "n-triples" => RdfFormat::NTriples,
+                            _ => {
+                                return Ok(Value::Object(serde_json::Map::from_iter([(
+                                    "error".to_string(),
+                                    Value::String(format!("Unknown RDF format: {format_name}")),
+                                )])));
+                            }
+                        };
+                        let data = graph.construct_query(sparql, format)?;
+                        info!("Construct search completed successfully");
+                        return Ok(serde_json::json!({
+                            "format": format_name,
+                            "data": String::from_utf8_lossy(&data),
+                        }));
+                    }
+                    "browse" => {
+                        // Browse all subjects ordered by pod depth
+                        let limit = query_obj.get("limit").and_then(|v| v.as_u64());
+                        if local_only {
+                            graph.browse_local(limit)?
+                        } else {
+                            graph.browse(limit)?
+                        }
+                    }
+                    _ => {
+                        return Ok(Value::Object(serde_json::Map::from_iter([(
+                            "error".to_string(),
+                            Value::String(format!("Unknown search type: {search_type}")),
+                        )])));
+                    }
+                }
+            } else {
+                // No explicit type, treat as advanced search
+                return Ok(Value::Object(serde_json::Map::from_iter([(
+                    "error".to_string(),
+                    Value::String("No search type provided: none".to_string()),
+                )])));
+            }
+        } else if let Some(text) = query.as_str() {
+            // Simple text search if query is just a string
+            self.graph.search_content(text, Some(50), None)?
+        } else {
+            return Ok(Value::Object(serde_json::Map::from_iter([(
+                "error".to_string(),
+                Value::String("Invalid query format. Expected object or string.".to_string()),
+            )])));
+        };
+
+        // Parse the SPARQL JSON results and return them
+        let results: Value = serde_json::from_str(&search_results)?;
+
+        // Enhance the results with additional metadata
+        let mut enhanced_results = self.enhance_search_results(results)?;
+        if explain
+            && let Some(enhanced) = enhanced_results.as_object_mut()
+            && let Some(sparql_results) = enhanced.get("sparql_results")
+        {
+            let ranking = SearchRanking::from_sparql_json(sparql_results);
+            enhanced.insert("ranking".to_string(), serde_json::to_value(ranking)?);
+        }
+
+        info!("Search completed successfully");
+        Ok(enhanced_results)
+    }
+
+    /// Performs a search and returns the results as typed structs instead of raw SPARQL JSON.
+    ///
+    /// Accepts the same query formats as [`search`] and parses the returned bindings into
+    /// [`SearchHit`] values, so callers don't have to dig through the nested JSON. Pod IRIs are
+    /// converted to plain pod addresses. Bindings without a `subject` variable, which can come
+    /// from custom advanced queries, are skipped.
+    ///
+    /// # Parameters
+    ///
+    /// * `query` - A search query in any of the formats accepted by [`search`]
+    ///
+    /// # Returns
+    ///
+    /// Returns a [`SearchResults`] with the hits, their count, the pods they were found in and
+    /// the search timestamp, or an `Error` if:
+    /// - The query format is invalid or required parameters are missing
+    /// - The underlying graph database query fails
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use serde_json::json;
+    ///
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let results = pod_manager.search_typed(json!("beethoven")).await?;
+    /// for hit in &results.results {
+    ///     println!("{} {:?} = {:?}", hit.subject, hit.predicate, hit.object);
+    /// }
+    /// println!("{} results in {} pods", results.result_count, results.pods_found.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`search`] - The same search returning SPARQL JSON
+    pub async fn search_typed(&mut self, query: Value) -> Result<SearchResults, Error> {
+        let enhanced = self.search(query).await?;
+
+        if let Some(error) = enhanced.get("error").and_then(|e| e.as_str()) {
+            return Err(Error::Pod(error.to_string()));
+        }
+
+        let timestamp = enhanced
+            .get("search_timestamp")
+            .and_then(|t| t.as_str())
+            .unwrap_or_default()
+            .to_string();
+        let sparql_results = enhanced.get("sparql_results").unwrap_or(&Value::Null);
+
+        Ok(SearchResults::from_sparql_json(sparql_results, &timestamp))
+    }
+
+    // Helper method to enhance search results with additional metadata
+    fn enhance_search_results(&self, results: Value) -> Result<Value, Error> {
+        let mut enhanced = serde_json::Map::new();
+
+        if let Some(results_obj) = results.as_object() {
+            // Copy the original results
+            enhanced.insert("sparql_results".to_string(), results.clone());
+
+            // Add metadata
+            enhanced.insert(
+                "search_timestamp".to_string(),
+                Value::String(self.clock.now().to_rfc3339()),
+            );
+
+            // Count results
+            if let Some(bindings) = results_obj
+                .get("results")
+                .and_then(|r| r.get("bindings"))
+                .and_then(|b| b.as_array())
+            {
+                enhanced.insert(
+                    "result_count".to_string(),
+                    Value::Number(serde_json::Number::from(bindings.len())),
+                );
+
+                // Extract unique graphs (pods) from results
+                let mut unique_graphs = std::collections::HashSet::new();
+                for binding in bindings {
+                    if let Some(graph_value) = binding
+                        .get("graph")
+                        .and_then(|g| g.get("value"))
+                        .and_then(|v| v.as_str())
+                    {
+                        unique_graphs.insert(graph_value.to_string());
+                    }
+                }
+
+                let graphs_vec: Vec<Value> = unique_graphs.into_iter().map(Value::String).collect();
+                enhanced.insert("pods_found".to_string(), Value::Array(graphs_vec));
+            } else {
+                enhanced.insert(
+                    "result_count".to_string(),
+                    Value::Number(serde_json::Number::from(0)),
+                );
+                enhanced.insert("pods_found".to_string(), Value::Array(vec![]));
+            }
+        } else {
+            // If results is not an object, just wrap it
+            enhanced.insert("sparql_results".to_string(), results);
+            enhanced.insert(
+                "result_count".to_string(),
+                Value::Number(serde_json::Number::from(0)),
+            );
+            enhanced.insert("pods_found".to_string(), Value::Array(vec![]));
+        }
+
+        Ok(Value::Object(enhanced))
+    }
+
+    /// Adds, modifies, or removes semantic data for a specific subject within a pod.
+    ///
+    /// This function updates the RDF graph data associated with a subject (identified by its Autonomi address)
+    /// within a specific pod. The data is stored in the pod's graph entry in the database and automatically
+    /// synchronized to the associated scratchpad(s) for network storage. The operation is queued
+    /// for upload to the Autonomi network.
+    ///
+    /// # Parameters
+    ///
+    /// * `pod_address` - The hexadecimal Autonomi address of the pod to update
+    /// * `subject_address` - The hexadecimal Autonomi address of the object whose metadata is being updated
+    /// * `subject_data` - JSON-LD structured RDF data describing the subject. Use empty string to remove data.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on successful update, or an `Error` if:
+    /// - The pod address is invalid or doesn't exist
+    /// - The subject data is malformed
+    /// - Graph database update fails
+    /// - Local storage update fails
+    ///
+    /// # Side Effects
+    ///
+    /// - Updates the local graph database with the new subject data
+    /// - Writes updated graph data to associated scratchpad files
+    /// - Adds the pod and scratchpad addresses to the upload queue
+    /// - The changes will be uploaded to the network on the next `upload_all()` call
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use serde_json::json;
+    ///
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let pod_address = "80e79010a13e7eee779f799d99a20b418436828269b18192d92940bc9ddbfe295a7e1823d7bff75c59cbacbdea101a0d"; // Pod Autonomi address
+    /// let subject_address = "c859818c623ce4fc0899c2ab43061b19caa0b0598eec35ef309dbe50c8af8d59"; // Subject Autonomi address
+    ///
+    /// // Add metadata for a document
+    /// let metadata = json!({
+    ///     "@context": "http://schema.org/",
+    ///     "@type": "TextDigitalDocument",
+    ///     "name": "Important Document",
+    ///     "author": "John Doe",
+    ///     "dateCreated": "2024-01-15",
+    ///     "description": "A document containing important information"
+    /// }).to_string();
+    ///
+    /// pod_manager.put_subject_data(pod_address, subject_address, &metadata).await?;
+    ///
+    /// // Remove metadata by providing empty data
+    /// pod_manager.put_subject_data(pod_address, subject_address, "").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`get_subject_data`] - Retrieve data for a specific subject
+    /// - [`upload_all`] - Upload pending changes to the network
+    /// - [`search`] - Search for subjects across pods
+    pub async fn put_subject_data(
+        &mut self,
+        pod_address: &str,
+        subject_address: &str,
+        subject_data: &str,
+    ) -> Result<(), Error> {
+        let pod_address = self.graph.check_pod_exists(pod_address)?;
+        let pod_address = pod_address.trim();
+        let configuration_address = self.key_store.get_configuration_address()?;
+        let configuration_address = configuration_address.as_str();
+
+        // Inject the JSON data into the graph using the pod address as the named graph
+        // And return the resulting graph data as a TriG formatted byte vector
+        let (graph, configuration) = self.graph.put_subject_data(
+            pod_address,
+            subject_address,
+            configuration_address,
+            subject_data,
+        )?;
+
+        // Process the pod data with proper scratchpad management
+        self.process_pod_data(pod_address, graph).await?;
+        // Update the configuration graph with the updated key count
+        let num_keys = self.key_store.get_num_keys();
+        self.graph
+            .update_key_count(configuration_address, num_keys)?;
+        self.process_configuration_data(configuration_address, configuration)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Adds or replaces the semantic data of many subjects in a pod at once.
+    ///
+    /// Every [`put_subject_data`] call sorts, splits and rewrites all scratchpads of the pod,
+    /// which adds up quickly when importing many subjects. This function applies all of the
+    /// graph updates first and then processes the pod data a single time. Each entry replaces
+    /// the existing data of its subject in the pod, the same way as [`put_subject_data`]. Every
+    /// JSON-LD document is checked before the pod is changed, so one bad entry leaves the pod
+    /// untouched.
+    ///
+    /// # Parameters
+    ///
+    /// * `pod_address` - The hexadecimal address or name of the pod to update
+    /// * `entries` - Pairs of subject address and the JSON-LD data describing that subject
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on successful update, or an `Error` if:
+    /// - The pod does not exist locally
+    /// - The data of any subject is malformed
+    /// - Graph database update fails
+    /// - Local storage update fails
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use serde_json::json;
+    ///
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let entries = vec![
+    ///     (
+    ///         "c859818c623ce4fc0899c2ab43061b19caa0b0598eec35ef309dbe50c8af8d59".to_string(),
+    ///         json!({"@context": "http://schema.org/", "name": "First Song"}).to_string(),
+    ///     ),
+    ///     (
+    ///         "a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f90".to_string(),
+    ///         json!({"@context": "http://schema.org/", "name": "Second Song"}).to_string(),
+    ///     ),
+    /// ];
+    /// pod_manager.put_subjects_data("My Music", entries).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`put_subject_data`] - Set the data of a single subject
+    /// - [`import_csv_into_pod`] - Import subjects from a CSV file
+    /// - [`upload_all`] - Upload pending changes to the network
+    pub async fn put_subjects_data(
+        &mut self,
+        pod_address: &str,
+        entries: Vec<(String, String)>,
+    ) -> Result<(), Error> {
+        let pod_address = self.graph.check_pod_exists(pod_address)?;
+        let pod_address = pod_address.trim();
+        if entries.is_empty() {
+            return Ok(());
+        }
+        let configuration_address = self.key_store.get_configuration_address()?;
+        let configuration_address = configuration_address.as_str();
+
+        let (graph, configuration) =
+            self.graph
+                .put_subjects_data(pod_address, configuration_address, &entries)?;
+
+        // Process the pod data with proper scratchpad management, once for all subjects
+        self.process_pod_data(pod_address, graph).await?;
+        // Update the configuration graph with the updated key count
+        let num_keys = self.key_store.get_num_keys();
+        self.graph
+            .update_key_count(configuration_address, num_keys)?;
+        self.process_configuration_data(configuration_address, configuration)
+            .await?;
+
+        info!(
+            "Put data for {} subjects into pod {}",
+            entries.len(),
+            pod_address
+        );
+        Ok(())
+    }
+
+    /// Removes all semantic data for a single subject from a pod.
+    ///
+    /// Unlike calling [`put_subject_data`] with an empty string, this also removes the subject's
+    /// modified date, so the subject no longer shows up in [`list_pod_subjects`], and it checks
+    /// that the pod actually holds data for the subject. The pod's modified date is updated and
+    /// the pod is queued for upload to the Autonomi network.
+    ///
+    /// # Parameters
+    ///
+    /// * `pod_address` - The hexadecimal address or name of the pod to remove the subject from
+    /// * `subject_address` - The hexadecimal Autonomi address of the subject to remove
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on successful removal, or an `Error` if:
+    /// - The pod does not exist locally
+    /// - The pod has no data for the subject
+    /// - Graph database update fails
+    /// - Local storage update fails
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let pod_address = "80e79010a13e7eee779f799d99a20b418436828269b18192d92940bc9ddbfe295a7e1823d7bff75c59cbacbdea101a0d";
+    /// let subject_address = "c859818c623ce4fc0899c2ab43061b19caa0b0598eec35ef309dbe50c8af8d59";
+    ///
+    /// pod_manager.remove_subject_data(pod_address, subject_address).await?;
+    /// pod_manager.upload_all().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`put_subject_data`] - Add or replace the data of a subject
+    /// - [`list_pod_subjects`] - List the subjects in a pod
+    /// - [`upload_all`] - Upload pending changes to the network
+    pub async fn remove_subject_data(
+        &mut self,
+        pod_address: &str,
+        subject_address: &str,
+    ) -> Result<(), Error> {
+        let pod_address = self.graph.check_pod_exists(pod_address)?;
+        let pod_address = pod_address.trim();
+        let configuration_address = self.key_store.get_configuration_address()?;
+        let configuration_address = configuration_address.as_str();
+
+        let (graph, configuration) =
+            self.graph
+                .remove_subject_data(pod_address, subject_address, configuration_address)?;
+
+        // Process the pod data with proper scratchpad management
+        self.process_pod_data(pod_address, graph).await?;
+        // Update the configuration graph with the updated key count
+        let num_keys = self.key_store.get_num_keys();
+        self.graph
+            .update_key_count(configuration_address, num_keys)?;
+        self.process_configuration_data(configuration_address, configuration)
+            .await?;
+
+        info!(
+            "Removed subject {} from pod {}",
+            subject_address, pod_address
+        );
+        Ok(())
+    }
+
+    /// Imports subjects into a pod from a CSV file, using a column mapping.
+    ///
+    /// This makes it easy to populate a pod from a spreadsheet catalog. The first row of the CSV
+    /// is the header, and the `mapping` says which column holds the subject address and which
+    /// columns are stored under which predicate IRIs and rdf:type. Each row becomes the complete
+    /// data of one subject, replacing any existing data for it in the pod the same way as
+    /// [`put_subject_data`]. Empty cells are skipped. All rows are written to the pod in one
+    /// batch with [`put_subjects_data`], which is queued for upload to the Autonomi network.
+    ///
+    /// # Parameters
+    ///
+    /// * `pod_address` - The hexadecimal address or name of the pod to import into
+    /// * `csv` - The CSV text, with a header row
+    /// * `mapping` - Which columns to read and how to store them
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of subjects imported, or an `Error` if:
+    /// - The pod does not exist locally
+    /// - The CSV is malformed, a mapped column is missing or a row has no subject address
+    /// - Graph database update fails
+    /// - Local storage update fails
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use colonylib::pod::CsvMapping;
+    ///
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let csv = "address,title,artist\n\
+    ///            c859818c623ce4fc0899c2ab43061b19caa0b0598eec35ef309dbe50c8af8d59,Moonlight Sonata,Beethoven\n";
+    /// let mapping = CsvMapping {
+    ///     subject_column: "address".to_string(),
+    ///     predicates: vec![
+    ///         ("title".to_string(), "http://schema.org/name".to_string()),
+    ///         ("artist".to_string(), "http://schema.org/byArtist".to_string()),
+    ///     ],
+    ///     type_uri: Some("http://schema.org/MusicRecording".to_string()),
+    ///     type_column: None,
+    /// };
+    /// let imported = pod_manager.import_csv_into_pod("My Music", csv, mapping).await?;
+    /// println!("Imported {} subjects", imported);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`put_subjects_data`] - Set the data of many subjects at once
+    /// - [`put_subject_data`] - Set the data of a single subject
+    /// - [`upload_all`] - Upload pending changes to the network
+    pub async fn import_csv_into_pod(
+        &mut self,
+        pod_address: &str,
+        csv: &str,
+        mapping: CsvMapping,
+    ) -> Result<usize, Error> {
+        let pod_address = self.graph.check_pod_exists(pod_address)?;
+        let pod_address = pod_address.trim();
+
+        let subjects = mapping.to_subjects(csv)?;
+        if subjects.is_empty() {
+            return Ok(0);
+        }
+        let imported = subjects
+            .iter()
+            .map(|(subject, _)| subject.as_str())
+            .collect::<HashSet<_>>()
+            .len();
+
+        self.put_subjects_data(pod_address, subjects).await?;
+
+        info!("Imported {} subjects into pod {}", imported, pod_address);
+        Ok(imported)
+    }
+
+    /// Merges semantic data into a subject within a pod, keeping the fields that are not supplied.
+    ///
+    /// Unlike [`put_subject_data`], which replaces everything known about the subject, this function
+    /// only overwrites the predicates present in the supplied JSON-LD. All other existing predicates
+    /// of the subject are left intact, so incremental edits such as adding a tag or fixing a
+    /// description don't require resending the whole record. The operation is queued for upload to
+    /// the Autonomi network.
+    ///
+    /// # Parameters
+    ///
+    /// * `pod_address` - The hexadecimal Autonomi address of the pod to update
+    /// * `subject_address` - The hexadecimal Autonomi address of the object whose metadata is being updated
+    /// * `jsonld` - JSON-LD structured RDF data with the predicates to add or overwrite
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on successful update, or an `Error` if:
+    /// - The pod address is invalid or doesn't exist
+    /// - The subject data is malformed
+    /// - Graph database update fails
+    /// - Local storage update fails
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use serde_json::json;
+    ///
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let pod_address = "80e79010a13e7eee779f799d99a20b418436828269b18192d92940bc9ddbfe295a7e1823d7bff75c59cbacbdea101a0d";
+    /// let subject_address = "c859818c623ce4fc0899c2ab43061b19caa0b0598eec35ef309dbe50c8af8d59";
+    ///
+    /// // Only update the description, the name and other fields are kept
+    /// let update = json!({
+    ///     "@context": "http://schema.org/",
+    ///     "@id": format!("ant://{}", subject_address),
+    ///     "description": "An updated description"
+    /// }).to_string();
+    ///
+    /// pod_manager.merge_subject_data(pod_address, subject_address, &update).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`put_subject_data`] - Replace all data for a subject
+    /// - [`get_subject_data`] - Retrieve data for a specific subject
+    /// - [`upload_all`] - Upload pending changes to the network
+    pub async fn merge_subject_data(
+        &mut self,
+        pod_address: &str,
+        subject_address: &str,
+        jsonld: &str,
+    ) -> Result<(), Error> {
+        let pod_address = self.graph.check_pod_exists(pod_address)?;
+        let pod_address = pod_address.trim();
+        let configuration_address = self.key_store.get_configuration_address()?;
+        let configuration_address = configuration_address.as_str();
+
+        // Merge the JSON data into the pod graph and return the resulting graph data as TriG
+        let (graph, configuration) = self.graph.merge_subject_data(
+            pod_address,
+            subject_address,
+            configuration_address,
+            jsonld,
+        )?;
+
+        // Process the pod data with proper scratchpad management
+        self.process_pod_data(pod_address, graph).await?;
+        // Update the configuration graph with the updated key count
+        let num_keys = self.key_store.get_num_keys();
+        self.graph
+            .update_key_count(configuration_address, num_keys)?;
+        self.process_configuration_data(configuration_address, configuration)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Estimates how many scratchpads a pod will need after applying a batch of subject updates.
+    ///
+    /// The pod graph is copied into a temporary in-memory store, each subject's JSON-LD is applied
+    /// the same way [`put_subject_data`] would, and the resulting TriG is sorted and split with the
+    /// same chunking as the real upload path. Neither the local graph nor the network is modified,
+    /// so this can be used to check the cost of a bulk import before committing to it.
+    ///
+    /// # Parameters
+    ///
+    /// * `pod_address` - The hexadecimal Autonomi address of the pod to update
+    /// * `subjects` - `(subject_address, jsonld)` pairs that would be passed to [`put_subject_data`]
+    ///
+    /// # Returns
+    ///
+    /// Returns the total number of scratchpads the pod would use (at least 1), or an `Error` if:
+    /// - The pod address is invalid or doesn't exist
+    /// - Any of the subject data is malformed
+    ///
+    /// Compare the result with [`get_pod_scratchpads`] to find how many new scratchpads would be
+    /// created.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let pod_address = "80e79010a13e7eee779f799d99a20b418436828269b18192d92940bc9ddbfe295a7e1823d7bff75c59cbacbdea101a0d";
+    /// let subjects = vec![(
+    ///     "c859818c623ce4fc0899c2ab43061b19caa0b0598eec35ef309dbe50c8af8d59".to_string(),
+    ///     r#"{"@context": "http://schema.org/", "@type": "Book", "name": "Example"}"#.to_string(),
+    /// )];
+    ///
+    /// let needed = pod_manager.estimate_subject_batch_scratchpads(pod_address, &subjects)?;
+    /// let current = pod_manager.get_pod_scratchpads(pod_address)?.unwrap_or_default().len();
+    /// println!("Import needs {} new scratchpads", needed.saturating_sub(current));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`put_subject_data`] - Apply a subject update
+    /// - [`get_pod_scratchpads`] - List the scratchpads a pod currently uses
+    pub fn estimate_subject_batch_scratchpads(
+        &self,
+        pod_address: &str,
+        subjects: &[(String, String)],
+    ) -> Result<usize, Error> {
+        let pod_address = self.graph.check_pod_exists(pod_address)?;
+        let pod_address = pod_address.trim();
+
+        let graph_data = self.graph.pod_trig_with_subjects(pod_address, subjects)?;
+
+        // Same conversion, sorting and chunking as process_pod_data
+        let graph_string = String::from_utf8(graph_data)?;
+        let sorted_data = self.sort_graph_data(&graph_string);
+        let chunks = self.split_data_into_chunks(&sorted_data, self.scratchpad_size_limit);
+
+        Ok(std::cmp::max(1, chunks.len()))
+    }
+
+    /// Retrieves all semantic data associated with a specific subject across all pods.
+    ///
+    /// This function queries the graph database to find all RDF triples where the specified
+    /// subject address appears as the subject. It returns the data in JSON format, aggregating
+    /// information from all pods that contain data about this subject.
+    ///
+    /// # Parameters
+    ///
+    /// * `subject_address` - The Autonomi address of the object to retrieve data for
+    ///
+    /// # Returns
+    ///
+    /// Returns a JSON string containing all metadata associated with the subject, or an `Error` if:
+    /// - The subject address is invalid
+    /// - The graph database query fails
+    /// - JSON serialization fails
+    ///
+    /// The returned JSON follows the SPARQL JSON Results format with bindings for each
+    /// predicate-object pair associated with the subject.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let subject_address = "c859818c623ce4fc0899c2ab43061b19caa0b0598eec35ef309dbe50c8af8d59";
+    ///
+    /// // Retrieve all metadata for the subject
+    /// let metadata_json = pod_manager.get_subject_data(subject_address).await?;
+    ///
+    /// // Parse the JSON to work with the data
+    /// let metadata: serde_json::Value = serde_json::from_str(&metadata_json)?;
+    ///
+    /// // Access the SPARQL results
+    /// if let Some(bindings) = metadata["results"]["bindings"].as_array() {
+    ///     for binding in bindings {
+    ///         if let (Some(predicate), Some(object)) = (
+    ///             binding["predicate"]["value"].as_str(),
+    ///             binding["object"]["value"].as_str()
+    ///         ) {
+    ///             println!("Property: {}, Value: {}", predicate, object);
+    ///         }
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`put_subject_data`] - Add or update data for a subject
+    /// - [`search`] - Search for subjects with specific criteria
+    pub async fn get_subject_data(&mut self, subject_address: &str) -> Result<String, Error> {
+        // Perform a SPARQL query with the Autonomi object address and return the metadata as JSON results
+        let json_data = self.graph.get_subject_data(subject_address)?;
+
+        Ok(json_data)
+    }
+
+    /// Retrieves all metadata for a subject as a JSON-LD document.
+    ///
+    /// [`get_subject_data`] returns SPARQL JSON bindings, one per property. This function returns
+    /// the same data as compacted JSON-LD instead, in the shape of the documents passed to
+    /// [`put_subject_data`], so web applications can read it back with any JSON-LD library.
+    /// schema.org properties are written with the `schema:` prefix. Nested objects, like an
+    /// author, are included. When several pods describe the subject, their values are merged.
+    ///
+    /// # Parameters
+    ///
+    /// * `subject_address` - The hexadecimal address of the subject to retrieve data for
+    ///
+    /// # Returns
+    ///
+    /// Returns the JSON-LD document as a string, or an `Error` if the query or the serialization
+    /// fails. A subject without data gives a document without properties.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # fn example(pod_manager: &PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let subject_address = "c859818c623ce4fc0899c2ab43061b19caa0b0598eec35ef309dbe50c8af8d59";
+    /// let jsonld = pod_manager.get_subject_data_jsonld(subject_address)?;
+    /// let document: serde_json::Value = serde_json::from_str(&jsonld)?;
+    /// println!("{}", serde_json::to_string_pretty(&document)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`get_subject_data`] - Get the subject data as SPARQL JSON results
+    /// - [`put_subject_data`] - Add or update data for a subject
+    pub fn get_subject_data_jsonld(&self, subject_address: &str) -> Result<String, Error> {
+        Ok(self.graph.get_subject_data_jsonld(subject_address)?)
+    }
+
+    /// Retrieves a subject from the user's own pods, flagging where the pods disagree.
+    ///
+    /// [`get_subject_data`] merges everything known about a subject, so when several of your
+    /// pods describe the same subject their values are silently unioned. This function only
+    /// looks at your own pods (depth 0) and returns the merged view with the source pods of
+    /// every value. A predicate is flagged as a conflict when the pods that state it give it
+    /// different values, which makes overlapping catalogs easy to reconcile. Pods that do not
+    /// state a predicate at all don't count as disagreeing.
+    ///
+    /// # Parameters
+    ///
+    /// * `subject_address` - The Autonomi address of the subject
+    ///
+    /// # Returns
+    ///
+    /// Returns a [`SubjectView`] with the subject's properties sorted by predicate, or an
+    /// `Error` if the subject address is invalid or the graph database query fails. A subject
+    /// that none of your pods describe gives an empty view.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # fn example(pod_manager: &PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let subject_address = "c859818c623ce4fc0899c2ab43061b19caa0b0598eec35ef309dbe50c8af8d59";
+    /// let view = pod_manager.get_subject_data_local_with_conflicts(subject_address)?;
+    /// for property in view.conflicts() {
+    ///     println!("{} disagrees:", property.predicate);
+    ///     for value in &property.values {
+    ///         println!("  {} from {:?}", value.value, value.pods);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`get_subject_data`] - Merged subject data from every pod
+    /// - [`put_subject_data`] - Fix a conflicting value in one pod
+    pub fn get_subject_data_local_with_conflicts(
+        &self,
+        subject_address: &str,
+    ) -> Result<SubjectView, Error> {
+        Ok(self
+            .graph
+            .get_subject_data_local_with_conflicts(subject_address)?)
+    }
+
+    /// Adds a tag to a subject within a pod.
+    ///
+    /// Tags are a lightweight way to classify subjects beyond their RDF type. Each tag is stored
+    /// as a string literal with the `colonylib/v1/tag` predicate in the pod's graph, and a subject
+    /// can have any number of tags. Adding a tag the subject already has changes nothing. The
+    /// pod's scratchpads are updated and the pod is queued for upload.
+    ///
+    /// # Parameters
+    ///
+    /// * `pod_address` - The address or name of the pod that stores the tag
+    /// * `subject_address` - The Autonomi address of the subject to tag
+    /// * `tag` - The tag to add
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success, or an `Error` if:
+    /// - The tag is empty
+    /// - The pod doesn't exist
+    /// - The graph database update fails
+    /// - Scratchpad operations fail
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let subject_address = "c859818c623ce4fc0899c2ab43061b19caa0b0598eec35ef309dbe50c8af8d59";
+    /// pod_manager.add_subject_tag("My Music", subject_address, "favorites").await?;
+    /// pod_manager.upload_all().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`remove_subject_tag`] - Remove a tag from a subject
+    /// - [`list_subjects_by_tag`] - Find the subjects with a tag
+    /// - [`list_all_tags`] - List every tag in use
+    pub async fn add_subject_tag(
+        &mut self,
+        pod_address: &str,
+        subject_address: &str,
+        tag: &str,
+    ) -> Result<(), Error> {
+        self.subject_tag_entry(pod_address, subject_address, tag, true)
+            .await
+    }
+
+    /// Removes a tag from a subject within a pod.
+    ///
+    /// Only the tag stored in the given pod is removed; the same tag on the subject in other pods
+    /// is kept. The pod's scratchpads are updated and the pod is queued for upload.
+    ///
+    /// # Parameters
+    ///
+    /// * `pod_address` - The address or name of the pod that stores the tag
+    /// * `subject_address` - The Autonomi address of the tagged subject
+    /// * `tag` - The tag to remove
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success, or an `Error` if:
+    /// - The tag is empty
+    /// - The pod doesn't exist
+    /// - The graph database update fails
+    /// - Scratchpad operations fail
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let subject_address = "c859818c623ce4fc0899c2ab43061b19caa0b0598eec35ef309dbe50c8af8d59";
+    /// pod_manager.remove_subject_tag("My Music", subject_address, "favorites").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`add_subject_tag`] - Add a tag to a subject
+    pub async fn remove_subject_tag(
+        &mut self,
+        pod_address: &str,
+        subject_address: &str,
+        tag: &str,
+    ) -> Result<(), Error> {
+        self.subject_tag_entry(pod_address, subject_address, tag, false)
+            .await
+    }
+
+    // Add or remove a subject tag and update the pod and configuration scratchpads
+    async fn subject_tag_entry(
+        &mut self,
+        pod_address: &str,
+        subject_address: &str,
+        tag: &str,
+        add: bool,
+    ) -> Result<(), Error> {
+        let tag = tag.trim();
+        if tag.is_empty() {
+            return Err(Error::Pod("Tag cannot be empty".to_string()));
+        }
+        let pod_address = self.graph.check_pod_exists(pod_address)?;
+        let pod_address = pod_address.trim();
+        let configuration_address = self.key_store.get_configuration_address()?;
+        let configuration_address = configuration_address.as_str();
+
+        let (graph, configuration) = self.graph.subject_tag_entry(
+            pod_address,
+            subject_address,
+            configuration_address,
+            tag,
+            add,
+        )?;
+
+        // Process the pod data with proper scratchpad management
+        self.process_pod_data(pod_address, graph).await?;
+        // Update the configuration graph with the updated key count
+        let num_keys = self.key_store.get_num_keys();
+        self.graph
+            .update_key_count(configuration_address, num_keys)?;
+        self.process_configuration_data(configuration_address, configuration)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Lists the subjects that carry a tag.
+    ///
+    /// The lookup uses the graph database index for the tag predicate and value, so it stays fast
+    /// regardless of how much other data is stored. Tags from all pods are considered, including
+    /// downloaded referenced pods, unless `local_only` is set.
+    ///
+    /// # Parameters
+    ///
+    /// * `tag` - The tag to look up, matched exactly
+    /// * `local_only` - Only consider tags stored in the user's own pods (depth 0)
+    ///
+    /// # Returns
+    ///
+    /// Returns the distinct subject addresses with the tag in sorted order, or an `Error` if the
+    /// graph database query fails.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # fn example(pod_manager: &PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// for subject in pod_manager.list_subjects_by_tag("favorites", true)? {
+    ///     println!("{}", subject);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`add_subject_tag`] - Add a tag to a subject
+    /// - [`list_all_tags`] - List every tag in use
+    pub fn list_subjects_by_tag(&self, tag: &str, local_only: bool) -> Result<Vec<String>, Error> {
+        Ok(self.graph.get_subjects_by_tag(tag.trim(), local_only)?)
+    }
+
+    /// Lists every tag in use along with the number of subjects carrying it.
+    ///
+    /// This is intended for tag-cloud style views. Subjects tagged the same way in several pods
+    /// are counted once.
+    ///
+    /// # Returns
+    ///
+    /// Returns `(tag, subject_count)` pairs sorted by tag, or an `Error` if the graph database
+    /// query fails.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # fn example(pod_manager: &PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// for (tag, count) in pod_manager.list_all_tags()? {
+    ///     println!("{} ({})", tag, count);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`list_subjects_by_tag`] - Find the subjects with a tag
+    pub fn list_all_tags(&self) -> Result<Vec<(String, usize)>, Error> {
+        Ok(self.graph.get_all_tags()?)
+    }
+
+    /// Retrieves the RDF types of a subject.
+    ///
+    /// This answers "what kind of thing is this subject?" without pulling all of its data, which
+    /// is cheaper than [`get_subject_data`] for type-driven UI routing (showing a map for a
+    /// `Place`, a player for an `AudioObject`, etc.). Types are collected from every pod that
+    /// describes the subject.
+    ///
+    /// # Parameters
+    ///
+    /// * `subject_address` - The Autonomi address of the object to look up
+    ///
+    /// # Returns
+    ///
+    /// Returns the distinct type IRIs of the subject in sorted order, which is empty if the subject
+    /// has no type, or an `Error` if the graph database query fails.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # fn example(pod_manager: &PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let subject_address = "c859818c623ce4fc0899c2ab43061b19caa0b0598eec35ef309dbe50c8af8d59";
+    ///
+    /// let types = pod_manager.get_subject_types(subject_address)?;
+    /// if types.iter().any(|t| t == "http://schema.org/AudioObject") {
+    ///     println!("Show the audio player");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`get_subject_data`] - Retrieve all data for a subject
+    /// - [`search`] - Search for subjects by type
+    pub fn get_subject_types(&self, subject_address: &str) -> Result<Vec<String>, Error> {
+        Ok(self.graph.get_subject_types(subject_address)?)
+    }
+
+    /// Finds the pods that hold data about a subject.
+    ///
+    /// A subject can be described in any number of pods. Before updating a subject with
+    /// [`put_subject_data`], this tells which pod (or pods) already carry its metadata. Every
+    /// loaded pod is considered, including pods referenced by the user's own pods; the
+    /// configuration pod is never returned.
+    ///
+    /// # Parameters
+    ///
+    /// * `subject_address` - The Autonomi address of the object to look up
+    ///
+    /// # Returns
+    ///
+    /// Returns the addresses of the pods describing the subject in sorted order, which is empty
+    /// if no pod mentions it, or an `Error` if the graph database query fails.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let subject_address = "c859818c623ce4fc0899c2ab43061b19caa0b0598eec35ef309dbe50c8af8d59";
+    ///
+    /// let pods = pod_manager.find_subject_pods(subject_address)?;
+    /// if let Some(pod_address) = pods.first() {
+    ///     let metadata = r#"{"@context": {"schema": "http://schema.org/"}, "schema:name": "Renamed"}"#;
+    ///     pod_manager.put_subject_data(pod_address, subject_address, metadata).await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`get_subject_data`] - Retrieve all data for a subject
+    /// - [`put_subject_data`] - Update the data of a subject in a pod
+    pub fn find_subject_pods(&self, subject_address: &str) -> Result<Vec<String>, Error> {
+        Ok(self.graph.get_subject_pods(subject_address)?)
+    }
+
+    /// Counts the subjects of each RDF type for a faceted browse panel.
+    ///
+    /// This is the aggregate companion to searching by type: instead of fetching every subject
+    /// to count categories client side, a single grouped query returns each type with the number
+    /// of distinct subjects that have it ("Documents (42), Images (17), People (8)"). The types
+    /// colonylib uses for its own pod bookkeeping are not included.
+    ///
+    /// # Parameters
+    ///
+    /// * `scope` - Which pods to count in: all pods, only the user's own pods, or a single pod
+    ///
+    /// # Returns
+    ///
+    /// Returns `(type IRI, subject count)` pairs ordered by count, most common first, or an `Error`
+    /// if the graph database query fails.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # fn example(pod_manager: &PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// use colonylib::graph::SearchScope;
+    ///
+    /// for (type_iri, count) in pod_manager.type_facets(SearchScope::Local)? {
+    ///     println!("{type_iri} ({count})");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`get_subject_types`] - Get the types of a single subject
+    /// - [`search`] - Search for the subjects of a type
+    pub fn type_facets(&self, scope: SearchScope) -> Result<Vec<(String, u64)>, Error> {
+        Ok(self.graph.type_facets(&scope)?)
+    }
+
+    /// Lists the distinct values used with a predicate, for value pickers and autocomplete.
+    ///
+    /// Where [`search`] with `by_predicate` returns the subjects that have a predicate, this returns
+    /// the values themselves, such as every `schema:author` ever used. Each value is listed once,
+    /// ordered by the number of subjects using it, most common first. IRI values are returned as
+    /// is and literal values by their text.
+    ///
+    /// # Parameters
+    ///
+    /// * `predicate_uri` - The full IRI of the predicate, e.g. `http://schema.org/author`
+    /// * `scope` - Which pods to look in: all pods, only the user's own pods, or a single pod
+    ///
+    /// # Returns
+    ///
+    /// Returns the distinct values ordered by frequency, or an `Error` if the predicate is not a
+    /// valid IRI or the graph database query fails.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # fn example(pod_manager: &PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// use colonylib::graph::SearchScope;
+    ///
+    /// let authors = pod_manager
+    ///     .distinct_objects_for_predicate("http://schema.org/author", SearchScope::All)?;
+    /// for author in authors.iter().take(10) {
+    ///     println!("{author}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`type_facets`] - Count the subjects of each type
+    /// - [`search`] - Search for the subjects with a predicate
+    pub fn distinct_objects_for_predicate(
+        &self,
+        predicate_uri: &str,
+        scope: SearchScope,
+    ) -> Result<Vec<String>, Error> {
+        Ok(self
+            .graph
+            .distinct_objects_for_predicate(predicate_uri, &scope)?)
+    }
+
+    /// Exports a subject and the subjects it references as a self-contained JSON-LD bundle.
+    ///
+    /// When sharing a single catalog entry, the entry alone is often not enough: its author,
+    /// album, or other linked objects are separate subjects. This follows object references
+    /// outward from the subject, up to `max_depth` hops, and collects every referenced subject
+    /// that has data in the local graph into one flattened JSON-LD `@graph`. Nested blank nodes
+    /// are always included with their parent, each subject is visited once so reference cycles
+    /// are safe, and colonylib's internal pod bookkeeping is left out.
+    ///
+    /// # Parameters
+    ///
+    /// * `subject_address` - The Autonomi address of the subject to export
+    /// * `max_depth` - How many reference hops to follow, `0` exports only the subject itself
+    ///
+    /// # Returns
+    ///
+    /// Returns a JSON-LD document of the form `{"@graph": [...]}` with the subject first, which
+    /// is empty if the subject has no data, or an `Error` if the address is not a valid IRI or
+    /// the graph database query fails.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # fn example(pod_manager: &PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let subject_address = "c859818c623ce4fc0899c2ab43061b19caa0b0598eec35ef309dbe50c8af8d59";
+    ///
+    /// // The song plus its artist and album
+    /// let bundle = pod_manager.export_subject_bundle(subject_address, 1)?;
+    /// std::fs::write("song.jsonld", serde_json::to_string_pretty(&bundle)?)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`get_subject_data`] - Retrieve the data of a single subject
+    /// - [`put_subject_data`] - Add a shared bundle to one of your pods
+    pub fn export_subject_bundle(
+        &self,
+        subject_address: &str,
+        max_depth: u8,
+    ) -> Result<Value, Error> {
+        Ok(self
+            .graph
+            .export_subject_bundle(subject_address, max_depth)?)
+    }
+
+    /// Retrieves semantic data for many subjects at once.
+    ///
+    /// This is the batch form of [`get_subject_data`]. Instead of running one query per subject,
+    /// all subjects are fetched with a single SPARQL query and the results are grouped per subject,
+    /// which makes list views that show metadata for many items much faster.
+    ///
+    /// # Parameters
+    ///
+    /// * `subject_addresses` - The Autonomi addresses of the objects to retrieve data for
+    ///
+    /// # Returns
+    ///
+    /// Returns a map from each requested subject address to its data in the same SPARQL JSON
+    /// Results format returned by [`get_subject_data`]. Subjects without any data are included with
+    /// an empty `bindings` array. Returns an `Error` if:
+    /// - A subject address is invalid
+    /// - The graph database query fails
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let subjects = vec![
+    ///     "c859818c623ce4fc0899c2ab43061b19caa0b0598eec35ef309dbe50c8af8d59".to_string(),
+    ///     "a1b2c3d4e5f6789012345678901234567890abcdef1234567890abcdef123456".to_string(),
+    /// ];
+    ///
+    /// let subjects_data = pod_manager.get_subjects_data(&subjects).await?;
+    /// for (subject, data) in &subjects_data {
+    ///     let count = data["results"]["bindings"].as_array().map(|b| b.len()).unwrap_or(0);
+    ///     println!("{}: {} properties", subject, count);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`get_subject_data`] - Retrieve data for a single subject
+    /// - [`search`] - Search for subjects with specific criteria
+    pub async fn get_subjects_data(
+        &mut self,
+        subject_addresses: &[String],
+    ) -> Result<HashMap<String, Value>, Error> {
+        let subjects_data = self.graph.get_subjects_data(subject_addresses)?;
+
+        Ok(subjects_data)
+    }
+
+    pub fn get_pod_scratchpads(&self, address: &str) -> Result<Option<Vec<String>>, Error> {
+        // Get all scratchpad addresses for this pod from the graph database
+        match self.graph.get_pod_scratchpads(address) {
+            Ok(scratchpads) => {
+                if scratchpads.is_empty() {
+                    // Fallback to the pointer target if no scratchpads found in graph
+                    let target = self.data_store.get_pointer_target(address)?;
+                    Ok(Some(vec![target]))
+                } else {
+                    Ok(Some(scratchpads))
+                }
+            }
+            Err(_) => {
+                // Fallback to the pointer target if graph query fails
+                let target = self.data_store.get_pointer_target(address)?;
+                Ok(Some(vec![target]))
+            }
+        }
+    }
+
+    async fn remove_pod_data(
+        &mut self,
+        pod_address: &str,
+        pod_scratchpads: Vec<String>,
+        abandon_keys: bool,
+    ) -> Result<(), Error> {
+        if abandon_keys {
+            // Keys that may be compromised go to the bad keys so they are never reused
+            self.key_store.abandon_key(pod_address)?;
+            for scratchpad in pod_scratchpads.clone() {
+                self.key_store.abandon_key(scratchpad.trim())?;
+            }
+        } else {
+            // Remove the pod address from the key store pointers list
+            self.key_store.remove_pointer_key(pod_address)?;
+            // Remove the pod scratchpads from the key store scratchpads list
+            for scratchpad in pod_scratchpads.clone() {
+                self.key_store.remove_scratchpad_key(scratchpad.trim())?;
+            }
+        }
+
+        // Remove each scratchpad file from the data store
+        for scratchpad in pod_scratchpads.clone() {
+            self.data_store.remove_scratchpad_file(scratchpad.trim())?;
+        }
+        // Remove the pod pointer file from the data store
+        self.data_store.remove_pointer_file(pod_address)?;
+
+        // Remember which scratchpads the pod held so verify_removal can check them later
+        self.data_store
+            .record_removed_pod(pod_address, &pod_scratchpads)?;
+
+        // Mark the removal of the pod pointer and scratchpads for the next upload_all operation
+        self.data_store
+            .append_removal_list(pod_address, "pointer")?;
+        for scratchpad in pod_scratchpads {
+            self.data_store
+                .append_removal_list(scratchpad.trim(), "scratchpad")?;
+        }
+
+        Ok(())
+    }
+
+    /// Processes pod data by managing scratchpad allocation and data distribution.
+    ///
+    /// This function handles the complex task of distributing pod graph data across multiple
+    /// scratchpads, ensuring that each scratchpad stays within the 4MB size limit. It also
+    /// manages the creation of additional scratchpads when needed and properly sorts the
+    /// data to ensure pod_index and pod_ref entries are prioritized.
+    ///
+    /// # Parameters
+    ///
+    /// * `pod_address` - The hexadecimal address of the pod
+    /// * `graph_data` - The TriG-formatted graph data as a byte vector
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success, or an `Error` if scratchpad operations fail.
+    async fn process_pod_data(
+        &mut self,
+        pod_address: &str,
+        graph_data: Vec<u8>,
+    ) -> Result<(), Error> {
+        let default_graph_count = self.graph.default_graph_triple_count()?;
+
+        // Convert graph data to string for processing
+        let graph_string = String::from_utf8(graph_data)?;
+
+        // Check current scratchpads for this pod
+        let current_scratchpads = self
+            .get_pod_scratchpads(pod_address)?
+            .unwrap_or_else(Vec::new);
+
+        // Keep the local copy of the pod before it is overwritten
+        if self.history_depth > 0 {
+            self.save_pod_version(pod_address, &current_scratchpads)?;
+        }
+
+        // Sort the graph data to prioritize pod_index and pod_ref entries
+        let sorted_data = self.sort_graph_data(&graph_string);
+
+        // Split the sorted data into chunks that fit in scratchpads
+        let chunks = self.split_data_into_chunks(&sorted_data, self.scratchpad_size_limit);
+
+        // One scratchpad is needed for each chunk. Chunks end on line boundaries and reserve room
+        // for the timestamp comment, so there can be more of them than the data size suggests
+        let required_scratchpads = std::cmp::max(1, chunks.len()); // At least 1 scratchpad
+
+        // Create additional scratchpads if needed
+        let mut all_scratchpads = current_scratchpads.clone();
+        while all_scratchpads.len() < required_scratchpads {
+            let new_scratchpad = self.add_scratchpad(pod_address).await?;
+            let new_address = new_scratchpad.to_hex();
+            all_scratchpads.push(new_address.clone());
+
+            // Add the new scratchpad to the graph with proper pod_index
+            let scratchpad_iri = format!("ant://{new_address}");
+            let pod_iri = format!("ant://{pod_address}");
+            let index = (all_scratchpads.len() - 1).to_string();
+
+            self.graph
+                .put_quad(&scratchpad_iri, graph::HAS_INDEX, &index, Some(&pod_iri))?;
+
+            // Add the modified date to the new scratchpad
+            let date = self.clock.now().to_rfc3339();
+            self.graph.put_quad(
+                &scratchpad_iri,
+                graph::HAS_MODIFIED_DATE,
+                &date,
+                Some(&pod_iri),
+            )?;
+
+            // Update the key count
+            let num_keys = self.key_store.get_num_keys();
+            self.graph.update_key_count(pod_address, num_keys)?;
+        }
+
+        // Update scratchpads with the chunked data. Scratchpads whose data is the same apart from
+        // the timestamp comment are left as they are, so they don't need to be uploaded again
+        let mut changed_scratchpads: HashSet<String> = HashSet::new();
+        for (i, chunk) in chunks.iter().enumerate() {
+            if i < all_scratchpads.len() {
+                let scratchpad_address = all_scratchpads[i].trim();
+                if i < current_scratchpads.len()
+                    && self.data_store.address_is_scratchpad(scratchpad_address)?
+                {
+                    let stored = self.data_store.get_scratchpad_data(scratchpad_address)?;
+                    if Self::strip_timestamp_comment(&stored).trim_end()
+                        == Self::strip_timestamp_comment(chunk).trim_end()
+                    {
+                        debug!("Scratchpad {} is unchanged", scratchpad_address);
+                        continue;
+                    }
+                }
+                self.data_store
+                    .update_scratchpad_data(scratchpad_address, chunk)?;
+                changed_scratchpads.insert(scratchpad_address.to_string());
+            }
+        }
+
+        // Clear any unused scratchpads
+        for scratchpad_address in all_scratchpads.iter().skip(chunks.len()) {
+            self.data_store
+                .remove_scratchpad_file(scratchpad_address.trim())?;
+            self.data_store
+                .append_removal_list(scratchpad_address.trim(), "scratchpad")?;
+            self.graph
+                .remove_scratchpad_entry(pod_address, scratchpad_address.trim())?;
+            self.key_store
+                .remove_scratchpad_key(scratchpad_address.trim())?;
+        }
+
+        // Processing pod data must never leave triples in the default graph
+        self.graph
+            .ensure_default_graph_unchanged(default_graph_count, "process_pod_data")?;
+
+        // Add the pod pointer address to the update list along with the scratchpads that changed,
+        // so the upload sends exactly these scratchpads even if the graph changes before then.
+        // Scratchpads changed by earlier edits that were not uploaded yet stay queued, and an
+        // entry queued without scratchpads means all of them
+        let pending_scratchpads = self
+            .data_store
+            .get_update_list()?
+            .pods
+            .get(pod_address)
+            .cloned();
+        let mut queued_scratchpads: Vec<String> = all_scratchpads
+            .iter()
+            .take(chunks.len())
+            .map(|scratchpad_address| scratchpad_address.trim().to_string())
+            .filter(|scratchpad_address| {
+                changed_scratchpads.contains(scratchpad_address)
+                    || pending_scratchpads.as_ref().is_some_and(|pending| {
+                        pending.is_empty() || pending.contains(scratchpad_address)
+                    })
+            })
+            .collect();
+        // An empty list would upload every scratchpad, so always queue at least the first one
+        if queued_scratchpads.is_empty()
+            && let Some(first_scratchpad) = all_scratchpads.first()
+        {
+            queued_scratchpads.push(first_scratchpad.trim().to_string());
+        }
+        debug!(
+            "Queueing {} of {} scratchpads for pod {}",
+            queued_scratchpads.len(),
+            chunks.len(),
+            pod_address
+        );
+        self.data_store
+            .append_update_list_with_scratchpads(pod_address, &queued_scratchpads)?;
+
+        Ok(())
+    }
+
+    // Save the data currently in the pod scratchpad files as the version for the current pointer
+    // count. Only the first save for a count is kept, so the version holds the pod as it was
+    // before any of the local edits since the last upload
+    fn save_pod_version(&self, pod_address: &str, scratchpads: &[String]) -> Result<(), Error> {
+        if !self.data_store.address_is_pointer(pod_address)? {
+            return Ok(());
+        }
+        let version = self.data_store.get_pointer_count(pod_address)?;
+        if self
+            .data_store
+            .list_pod_versions(pod_address)?
+            .contains(&version)
+        {
+            return Ok(());
+        }
+
+        let mut data = String::new();
+        for scratchpad in scratchpads {
+            if !self.data_store.address_is_scratchpad(scratchpad.trim())? {
+                continue;
+            }
+            let chunk = self.data_store.get_scratchpad_data(scratchpad.trim())?;
+            data.push_str(&chunk);
+            if !chunk.ends_with('\n') {
+                data.push('\n');
+            }
+        }
+        if data.trim().is_empty() {
+            return Ok(());
+        }
+
+        self.data_store
+            .save_pod_version(pod_address, version, &data)?;
+        self.data_store
+            .prune_pod_versions(pod_address, self.history_depth)?;
+        Ok(())
+    }
+
+    // Write the configuration pod data to its scratchpads, or only mark it as pending when
+    // configuration sync is deferred. The configuration graph is always up to date either way
+    async fn process_configuration_data(
+        &mut self,
+        configuration_address: &str,
+        configuration: Vec<u8>,
+    ) -> Result<(), Error> {
+        if self.defer_configuration {
+            debug!("Deferring configuration pod update");
+            self.data_store.set_configuration_pending(true)?;
+            return Ok(());
+        }
+        self.process_pod_data(configuration_address, configuration)
+            .await
+    }
+
+    /// Sorts graph data to prioritize pod_index and pod_ref entries.
+    ///
+    /// This function ensures that statements containing pod_index predicates appear first,
+    /// ordered by their index, followed by statements containing pod_ref objects, with all
+    /// other data following. This ordering is important for proper scratchpad linking and pod
+    /// reference handling, the pointer can only point to the first scratchpad.
+    ///
+    /// The data is parsed as TriG and written back one triple per line, so literals spanning
+    /// several lines and statements continued with `;` or `,` are kept intact. Triples are
+    /// grouped by subject and each group is ordered by its highest priority triple. If the
+    /// data can't be parsed it is returned unchanged.
+    ///
+    /// # Parameters
+    ///
+    /// * `data` - The TriG-formatted graph data as a string
     ///
-    /// # Supported Query Types
+    /// # Returns
     ///
-    /// ## Text Search
-    /// ```json
-    /// {
-    ///   "type": "text",
-    ///   "text": "search term",
-    ///   "limit": 50
-    /// }
-    /// ```
+    /// Returns the sorted data as a string with prioritized entries first.
+    pub fn sort_graph_data(&self, data: &str) -> String {
+        // Group the triples by subject, in the order the subjects first appear
+        let mut statements: Vec<((u8, u64), Vec<String>)> = Vec::new();
+        let mut subject_positions: HashMap<String, usize> = HashMap::new();
+
+        for quad in TriGParser::new().for_reader(data.as_bytes()) {
+            let quad = match quad {
+                Ok(quad) => quad,
+                Err(e) => {
+                    warn!("Unable to parse graph data for sorting, leaving it unsorted: {e}");
+                    return data.to_string();
+                }
+            };
+
+            let priority = self.get_statement_priority(&quad);
+            let position = *subject_positions
+                .entry(quad.subject.to_string())
+                .or_insert_with(|| {
+                    statements.push(((u8::MAX, u64::MAX), Vec::new()));
+                    statements.len() - 1
+                });
+            let (statement_priority, lines) = &mut statements[position];
+            *statement_priority = std::cmp::min(*statement_priority, priority);
+            lines.push(format!(
+                "{} {} {} .",
+                quad.subject, quad.predicate, quad.object
+            ));
+        }
+
+        // Stable sort, so subjects of equal priority keep their original order
+        statements.sort_by_key(|(priority, _)| *priority);
+
+        statements
+            .into_iter()
+            .flat_map(|(_, lines)| lines)
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Determines the sorting priority for a triple.
     ///
-    /// ## Type-based Search
-    /// ```json
-    /// {
-    ///   "type": "by_type",
-    ///   "type_uri": "http://example.org/MyType",
-    ///   "limit": 100
-    /// }
-    /// ```
+    /// # Parameters
     ///
-    /// ## Predicate-based Search
-    /// ```json
-    /// {
-    ///   "type": "by_predicate",
-    ///   "predicate_uri": "http://example.org/hasProperty",
-    ///   "limit": 25
+    /// * `quad` - A parsed triple of the graph data
+    ///
+    /// # Returns
+    ///
+    /// Returns a priority and an index, where lower values indicate higher priority.
+    fn get_statement_priority(&self, quad: &Quad) -> (u8, u64) {
+        if quad.predicate.as_str() == graph::HAS_INDEX {
+            // Pod scratchpads should always be first in the scratchpad (pointer can only point to the first scratchpad)
+            let index = match &quad.object {
+                Term::Literal(literal) => literal.value().parse::<u64>().unwrap_or(u64::MAX),
+                _ => u64::MAX,
+            };
+            (0, index)
+        } else if quad.predicate.as_str() == graph::POD_REF
+            || matches!(&quad.object, Term::NamedNode(node) if node.as_str() == graph::POD_REF)
+        {
+            // Pod references are next for future enhancement to thread the data fetches
+            (1, 0)
+        } else {
+            // Everything else in the pod
+            (2, 0)
+        }
+    }
+
+    /// Splits data into chunks that fit within the scratchpad size limit.
+    ///
+    /// This function intelligently splits the data while trying to preserve line boundaries
+    /// when possible. It ensures that no chunk exceeds the specified size limit. Each chunk
+    /// starts with a timestamp comment in the format '#<timestamp>\n' (37 bytes total).
+    ///
+    /// # Parameters
+    ///
+    /// * `data` - The data to split
+    /// * `chunk_size` - Maximum size for each chunk in bytes (includes the 37-byte timestamp comment)
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of string chunks, each within the size limit and starting with a timestamp comment.
+    pub fn split_data_into_chunks(&self, data: &str, chunk_size: usize) -> Vec<String> {
+        let mut chunks = Vec::new();
+        let mut current_chunk = String::new();
+
+        // Reserve bytes for the timestamp comment: '#' + RFC3339 timestamp + '\n'
+        // RFC3339 format can be up to 35 characters (e.g., "2024-01-15T10:30:45.123456789+00:00")
+        // Plus '#' (1 char) and '\n' (1 char) = 37 bytes total
+        const TIMESTAMP_COMMENT_SIZE: usize = 37;
+        let effective_chunk_size = chunk_size.saturating_sub(TIMESTAMP_COMMENT_SIZE);
+
+        // Handle the case where data doesn't end with newline
+        let data_with_newline = if data.ends_with('\n') {
+            data.to_string()
+        } else {
+            format!("{data}\n")
+        };
+
+        for line in data_with_newline.lines() {
+            let line_with_newline = format!("{line}\n");
+
+            // If adding this line would exceed the effective chunk size, start a new chunk
+            if !current_chunk.is_empty()
+                && current_chunk.len() + line_with_newline.len() > effective_chunk_size
+            {
+                chunks.push(current_chunk.clone());
+                current_chunk.clear();
+            }
+
+            // If a single line is larger than effective_chunk_size, we need to split it
+            if line_with_newline.len() > effective_chunk_size {
+                // Add any existing chunk first
+                if !current_chunk.is_empty() {
+                    chunks.push(current_chunk.clone());
+                    current_chunk.clear();
+                }
+
+                // Split the large line into smaller pieces, only the last one keeping the
+                // newline. Pieces end on character boundaries so multi-byte UTF-8 characters
+                // are never cut in half, and each piece holds at least one character.
+                let mut piece_start = 0;
+                for (index, character) in line_with_newline.char_indices() {
+                    let char_end = index + character.len_utf8();
+                    if index > piece_start && char_end - piece_start > effective_chunk_size {
+                        chunks.push(line_with_newline[piece_start..index].to_string());
+                        piece_start = index;
+                    }
+                }
+                chunks.push(line_with_newline[piece_start..].to_string());
+            } else {
+                current_chunk.push_str(&line_with_newline);
+            }
+        }
+
+        // Add the final chunk if it has content
+        if !current_chunk.is_empty() {
+            chunks.push(current_chunk);
+        }
+
+        // Ensure we always have at least one chunk (even if empty)
+        if chunks.is_empty() {
+            chunks.push(String::new());
+        }
+
+        // Add timestamp comment to the beginning of each chunk
+        let timestamp = self.clock.now().to_rfc3339();
+        let timestamp_comment = format!("#{timestamp}\n");
+
+        for chunk in chunks.iter_mut() {
+            *chunk = format!("{timestamp_comment}{chunk}");
+        }
+
+        chunks
+    }
+
+    // The scratchpad data without the timestamp comment that split_data_into_chunks puts on
+    // the first line
+    fn strip_timestamp_comment(data: &str) -> &str {
+        match data.strip_prefix('#') {
+            Some(rest) => rest.split_once('\n').map_or("", |(_, rest)| rest),
+            None => data,
+        }
+    }
+
+    /// Selects the newest scratchpad from a vector of scratchpads based on timestamp comments.
+    ///
+    /// This function reads the encrypted data from each scratchpad, looks for a timestamp comment
+    /// in the first line (format: #<RFC3339_timestamp>), and returns the scratchpad with the
+    /// latest timestamp. If only one scratchpad has a timestamp, it's assumed to be the newest.
+    /// If none have timestamps, the first scratchpad in the vector is returned and the selection
+    /// is flagged as ambiguous in the returned [`ForkInfo`].
+    ///
+    /// # Parameters
+    ///
+    /// * `scratchpads` - Vector of scratchpads to compare
+    ///
+    /// # Returns
+    ///
+    /// Returns the scratchpad with the latest timestamp, or the first one if no timestamps are found,
+    /// along with a [`ForkInfo`] describing every version that was considered. Returns `None` if
+    /// `scratchpads` is empty.
+    pub fn select_newest_scratchpad(
+        scratchpads: Vec<Scratchpad>,
+    ) -> Option<(Scratchpad, ForkInfo)> {
+        if scratchpads.is_empty() {
+            warn!("Scratchpad fork has no versions to select from");
+            return None;
+        }
+
+        let mut fork_info = ForkInfo {
+            address: scratchpads[0].address().to_hex(),
+            version_count: scratchpads.len(),
+            ..Default::default()
+        };
+
+        let mut newest_index = 0;
+        let mut newest_timestamp: Option<chrono::DateTime<chrono::Utc>> = None;
+
+        for (index, scratchpad) in scratchpads.iter().enumerate() {
+            fork_info.counters.push(scratchpad.counter());
+
+            // Extract the encrypted data and check if the first line is a timestamp comment
+            let data = scratchpad.encrypted_data();
+            let timestamp = String::from_utf8(data.to_vec())
+                .ok()
+                .and_then(|data_string| {
+                    let first_line = data_string.lines().next()?;
+                    let timestamp_str = first_line.strip_prefix('#')?;
+                    chrono::DateTime::parse_from_rfc3339(timestamp_str)
+                        .ok()
+                        .map(|timestamp| timestamp.with_timezone(&chrono::Utc))
+                });
+
+            fork_info
+                .timestamps
+                .push(timestamp.map(|timestamp| timestamp.to_rfc3339()));
+
+            // Check if this is the newest timestamp so far
+            if let Some(utc_timestamp) = timestamp
+                && (newest_timestamp.is_none() || Some(utc_timestamp) > newest_timestamp)
+            {
+                newest_timestamp = Some(utc_timestamp);
+                newest_index = index;
+            }
+        }
+
+        // If no timestamps were found, the first scratchpad is returned
+        fork_info.selected_index = newest_index;
+        fork_info.ambiguous = scratchpads.len() > 1 && newest_timestamp.is_none();
+
+        if scratchpads.len() > 1 {
+            if fork_info.ambiguous {
+                warn!(
+                    "Scratchpad {} forked into {} versions with no parseable timestamps, selected the first one",
+                    fork_info.address, fork_info.version_count
+                );
+            } else {
+                info!(
+                    "Scratchpad {} forked into {} versions, selected version {} with counter {}",
+                    fork_info.address,
+                    fork_info.version_count,
+                    newest_index,
+                    fork_info.counters[newest_index]
+                );
+            }
+        }
+
+        Some((scratchpads[newest_index].clone(), fork_info))
+    }
+
+    // A fork error is supposed to list the conflicting versions, an empty list is treated as a
+    // scratchpad that can't be read
+    fn empty_fork_error(address: &ScratchpadAddress) -> Error {
+        Error::Pod(format!(
+            "Scratchpad {} forked without any versions",
+            address.to_hex()
+        ))
+    }
+
+    // Keep a fork report so callers can see which scratchpads were healed during a refresh
+    fn record_fork(fork_reports: &std::sync::Mutex<Vec<ForkInfo>>, fork_info: ForkInfo) {
+        match fork_reports.lock() {
+            Ok(mut reports) => reports.push(fork_info),
+            Err(poisoned) => poisoned.into_inner().push(fork_info),
+        }
+    }
+
+    /// Returns and clears the fork reports collected while refreshing pods.
+    ///
+    /// When a scratchpad is downloaded and the network returns several conflicting versions, the
+    /// newest one is kept and a [`ForkInfo`] describing all versions is recorded. This function
+    /// drains those reports so applications can tell users that a fork was encountered and healed,
+    /// and whether the choice of version was ambiguous.
+    ///
+    /// # Returns
+    ///
+    /// Returns every [`ForkInfo`] recorded since the last call, oldest first.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// pod_manager.refresh_cache(false).await?;
+    /// for fork in pod_manager.take_fork_reports() {
+    ///     println!("Healed fork at {} ({} versions, ambiguous: {})",
+    ///              fork.address, fork.version_count, fork.ambiguous);
     /// }
+    /// # Ok(())
+    /// # }
     /// ```
     ///
-    /// ## Advanced Search
-    /// ```json
-    /// {
-    ///   "type": "advanced",
-    ///   "sparql": "SELECT ?s ?p ?o WHERE { ?s ?p ?o }"
-    /// }
-    /// ```
+    /// # Related Functions
+    ///
+    /// - [`refresh_cache`] - Refresh local pods from the network
+    /// - [`refresh_ref`] - Refresh referenced pods from the network
+    pub fn take_fork_reports(&mut self) -> Vec<ForkInfo> {
+        match self.fork_reports.get_mut() {
+            Ok(reports) => std::mem::take(reports),
+            Err(poisoned) => std::mem::take(poisoned.into_inner()),
+        }
+    }
+
+    ///////////////////////////////////////////
+    // Local data operations
+    ///////////////////////////////////////////
+
+    /// Creates a new pod with the specified name in the local data store.
+    ///
+    /// This function creates a complete pod structure including:
+    /// - A new pointer address for the pod
+    /// - A new scratchpad address for data storage
+    /// - Initial graph data with pod metadata
+    /// - Local files for both pointer and scratchpad
+    /// - Adds the addresses to the upload queue
+    ///
+    /// The pod will be ready for use immediately and will be uploaded to the Autonomi network
+    /// on the next call to `upload_all()`.
+    ///
+    /// # Parameters
     ///
-    /// ## Browse
-    /// ```json
-    /// {
-    ///   "type": "browse",
-    ///   "limit": 100
-    /// }
-    /// ```
-    /// Browse all subjects with their name, type, and description, ordered by pod depth.
+    /// * `pod_name` - A human-readable name for the pod (used in metadata)
     ///
     /// # Returns
     ///
-    /// Returns a JSON object containing:
-    /// - `sparql_results` - The raw SPARQL query results
-    /// - `result_count` - Number of results found
-    /// - `pods_found` - Array of pod addresses that contain matching data
-    /// - `search_timestamp` - ISO 8601 timestamp of when the search was performed
-    ///
-    /// # Errors
+    /// Returns `Ok((pointer_address, scratchpad_address))` containing:
+    /// - `pointer_address` - The hexadecimal address of the pod's pointer
+    /// - `scratchpad_address` - The hexadecimal address of the pod's primary scratchpad
     ///
-    /// Returns an error if:
-    /// - The query format is invalid
-    /// - Required parameters are missing for the specified search type
-    /// - The underlying graph database query fails
-    /// - JSON parsing of results fails
+    /// Returns an `Error` if:
+    /// - Key generation fails
+    /// - File creation fails
+    /// - Graph database update fails
+    /// - Local storage operations fail
     ///
     /// # Example
     ///
     /// ```ignore
-    /// use serde_json::{json, Value};
-    ///
     /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
-    /// // Simple text search
-    /// let results = pod_manager.search(json!("my search term")).await?;
+    /// // Create a new pod for storing document metadata
+    /// let (pod_address, scratchpad_address) = pod_manager.add_pod("My Documents").await?;
     ///
-    /// // Structured search for specific type
-    /// let type_search = json!({
-    ///     "type": "by_type",
-    ///     "type_uri": "http://schema.org/Person",
-    ///     "limit": 10
-    /// });
-    /// let results = pod_manager.search(type_search).await?;
+    /// println!("Created pod at address: {}", pod_address);
+    /// println!("Primary scratchpad at: {}", scratchpad_address);
     ///
-    /// // Browse all subjects ordered by pod depth
-    /// let browse_search = json!({
-    ///     "type": "browse",
-    ///     "limit": 50
-    /// });
-    /// let results = pod_manager.search(browse_search).await?;
+    /// // The pod is now ready to store data
+    /// let subject_data = r#"{
+    ///     "@context": "http://schema.org/",
+    ///     "@type": "Collection",
+    ///     "name": "My Documents",
+    ///     "description": "A collection of important documents"
+    /// }"#;
     ///
-    /// // Advanced SPARQL query
-    /// let advanced_search = json!({
-    ///     "type": "advanced",
-    ///     "sparql": "SELECT ?name WHERE { ?person <http://schema.org/name> ?name }"
-    /// });
-    /// let results = pod_manager.search(advanced_search).await?;
+    /// pod_manager.put_subject_data(&pod_address, &pod_address, subject_data).await?;
+    ///
+    /// // Upload the new pod to the network
+    /// pod_manager.upload_all().await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn search(&mut self, query: Value) -> Result<Value, Error> {
-        info!("Performing search with query: {}", query);
-
-        // Parse the query to determine search type and parameters
-        let search_results = if let Some(query_obj) = query.as_object() {
-            // Handle structured query
-            if let Some(search_type) = query_obj.get("type").and_then(|v| v.as_str()) {
-                match search_type {
-                    "text" => {
-                        // Text search across all literal values
-                        if let Some(text) = query_obj.get("text").and_then(|v| v.as_str()) {
-                            let limit = query_obj.get("limit").and_then(|v| v.as_u64());
-                            self.graph.search_content(text, limit)?
-                        } else {
-                            return Ok(Value::Object(serde_json::Map::from_iter([(
-                                "error".to_string(),
-                                Value::String(
-                                    "Missing 'text' parameter for text search".to_string(),
-                                ),
-                            )])));
-                        }
-                    }
-                    "by_type" => {
-                        // Search by RDF type
-                        if let Some(type_uri) = query_obj.get("type_uri").and_then(|v| v.as_str()) {
-                            let limit = query_obj.get("limit").and_then(|v| v.as_u64());
-                            self.graph.search_by_type(type_uri, limit)?
-                        } else {
-                            return Ok(Value::Object(serde_json::Map::from_iter([(
-                                "error".to_string(),
-                                Value::String(
-                                    "Missing 'type_uri' parameter for type search".to_string(),
-                                ),
-                            )])));
-                        }
-                    }
-                    "by_predicate" => {
-                        // Search by predicate
-                        if let Some(predicate_uri) =
-                            query_obj.get("predicate_uri").and_then(|v| v.as_str())
-                        {
-                            let limit = query_obj.get("limit").and_then(|v| v.as_u64());
-                            self.graph.search_by_predicate(predicate_uri, limit)?
-                        } else {
-                            return Ok(Value::Object(serde_json::Map::from_iter([(
-                                "error".to_string(),
-                                Value::String(
-                                    "Missing 'predicate_uri' parameter for predicate search"
-                                        .to_string(),
-                                ),
-                            )])));
-                        }
-                    }
-                    "advanced" => {
-                        // Advanced search with multiple criteria
-                        if let Some(sparql) = query_obj.get("sparql").and_then(|v| v.as_str()) {
-                            self.graph.advanced_search(sparql)?
-                        } else {
-                            return Ok(Value::Object(serde_json::Map::from_iter([(
-                                "error".to_string(),
-                                Value::String(
-                                    "Missing 'sparql' parameter for advanced search".to_string(),
-                                ),
-                            )])));
-                        }
-                    }
-                    "browse" => {
-                        // Browse all subjects ordered by pod depth
-                        let limit = query_obj.get("limit").and_then(|v| v.as_u64());
-                        self.graph.browse(limit)?
-                    }
-                    _ => {
-                        return Ok(Value::Object(serde_json::Map::from_iter([(
-                            "error".to_string(),
-                            Value::String(format!("Unknown search type: {search_type}")),
-                        )])));
-                    }
-                }
-            } else {
-                // No explicit type, treat as advanced search
-                return Ok(Value::Object(serde_json::Map::from_iter([(
-                    "error".to_string(),
-                    Value::String("No search type provided: none".to_string()),
-                )])));
-            }
-        } else if let Some(text) = query.as_str() {
-            // Simple text search if query is just a string
-            self.graph.search_content(text, Some(50))?
-        } else {
-            return Ok(Value::Object(serde_json::Map::from_iter([(
-                "error".to_string(),
-                Value::String("Invalid query format. Expected object or string.".to_string()),
-            )])));
-        };
-
-        // Parse the SPARQL JSON results and return them
-        let results: Value = serde_json::from_str(&search_results)?;
-
-        // Enhance the results with additional metadata
-        let enhanced_results = self.enhance_search_results(results)?;
-
-        info!("Search completed successfully");
-        Ok(enhanced_results)
-    }
-
-    // Helper method to enhance search results with additional metadata
-    fn enhance_search_results(&self, results: Value) -> Result<Value, Error> {
-        let mut enhanced = serde_json::Map::new();
+    ///
+    /// # Related Functions
+    ///
+    /// - [`add_pod_ref`] - Add a reference to another pod
+    /// - [`upload_all`] - Upload the new pod to the network
+    /// - [`put_subject_data`] - Add data to the pod
+    pub async fn add_pod(&mut self, pod_name: &str) -> Result<(String, String), Error> {
+        let pod_address = self.add_pointer().await?;
+        let pod_address = pod_address.to_hex();
+        let pod_address = pod_address.as_str();
+        let scratchpad_address = self.add_scratchpad(pod_address).await?;
+        let scratchpad_address = scratchpad_address.to_hex();
+        let scratchpad_address = scratchpad_address.as_str();
 
-        if let Some(results_obj) = results.as_object() {
-            // Copy the original results
-            enhanced.insert("sparql_results".to_string(), results.clone());
+        // Add the scratchpad address to the pointer files
+        self.data_store
+            .update_pointer_target(pod_address, scratchpad_address)?;
 
-            // Add metadata
-            enhanced.insert(
-                "search_timestamp".to_string(),
-                Value::String(chrono::Utc::now().to_rfc3339()),
-            );
+        let configuration_address = self.key_store.get_configuration_address()?;
+        let configuration_address = configuration_address.as_str();
+        let configuration_scratchpad_address =
+            self.key_store.get_configuration_scratchpad_address()?;
+        let configuration_scratchpad_address = configuration_scratchpad_address.as_str();
+        self.data_store
+            .update_pointer_target(configuration_address, configuration_scratchpad_address)?;
 
-            // Count results
-            if let Some(bindings) = results_obj
-                .get("results")
-                .and_then(|r| r.get("bindings"))
-                .and_then(|b| b.as_array())
-            {
-                enhanced.insert(
-                    "result_count".to_string(),
-                    Value::Number(serde_json::Number::from(bindings.len())),
-                );
+        // Get the number of keys to store in the graph
+        let num_keys = self.key_store.get_num_keys();
 
-                // Extract unique graphs (pods) from results
-                let mut unique_graphs = std::collections::HashSet::new();
-                for binding in bindings {
-                    if let Some(graph_value) = binding
-                        .get("graph")
-                        .and_then(|g| g.get("value"))
-                        .and_then(|v| v.as_str())
-                    {
-                        unique_graphs.insert(graph_value.to_string());
-                    }
-                }
+        // Add the pointer address to the graph
+        let (graph, configuration) = self.graph.add_pod_entry(
+            pod_name,
+            pod_address,
+            scratchpad_address,
+            configuration_address,
+            configuration_scratchpad_address,
+            num_keys,
+        )?;
 
-                let graphs_vec: Vec<Value> = unique_graphs.into_iter().map(Value::String).collect();
-                enhanced.insert("pods_found".to_string(), Value::Array(graphs_vec));
-            } else {
-                enhanced.insert(
-                    "result_count".to_string(),
-                    Value::Number(serde_json::Number::from(0)),
-                );
-                enhanced.insert("pods_found".to_string(), Value::Array(vec![]));
-            }
-        } else {
-            // If results is not an object, just wrap it
-            enhanced.insert("sparql_results".to_string(), results);
-            enhanced.insert(
-                "result_count".to_string(),
-                Value::Number(serde_json::Number::from(0)),
-            );
-            enhanced.insert("pods_found".to_string(), Value::Array(vec![]));
-        }
+        // Process the pod data with proper scratchpad management
+        self.process_pod_data(pod_address, graph).await?;
 
-        Ok(Value::Object(enhanced))
+        // Update the configuration graph with the updated key count
+        let num_keys = self.key_store.get_num_keys();
+        self.graph
+            .update_key_count(configuration_address, num_keys)?;
+
+        self.process_configuration_data(configuration_address, configuration)
+            .await?;
+
+        Ok((pod_address.to_string(), scratchpad_address.to_string()))
     }
 
-    /// Adds, modifies, or removes semantic data for a specific subject within a pod.
+    /// Removes a pod and all its associated data from the local store and network.
     ///
-    /// This function updates the RDF graph data associated with a subject (identified by its Autonomi address)
-    /// within a specific pod. The data is stored in the pod's graph entry in the database and automatically
-    /// synchronized to the associated scratchpad(s) for network storage. The operation is queued
-    /// for upload to the Autonomi network.
+    /// This function completely removes a pod from the Colony system, including:
+    /// - Removing the pod entry from the graph database
+    /// - Removing all associated scratchpad data
+    /// - Removing the pod from the configuration pod's reference list
+    /// - Cleaning up local files and key store entries
+    /// - Adding addresses to the removal queue for network cleanup
+    ///
+    /// The pod will be marked for removal from the Autonomi network on the next call to `upload_all()`.
+    /// This operation cannot be undone once the changes are uploaded to the network.
     ///
     /// # Parameters
     ///
-    /// * `pod_address` - The hexadecimal Autonomi address of the pod to update
-    /// * `subject_address` - The hexadecimal Autonomi address of the object whose metadata is being updated
-    /// * `subject_data` - JSON-LD structured RDF data describing the subject. Use empty string to remove data.
+    /// * `pod_address` - The hexadecimal address of the pod to remove
     ///
     /// # Returns
     ///
-    /// Returns `Ok(())` on successful update, or an `Error` if:
-    /// - The pod address is invalid or doesn't exist
-    /// - The subject data is malformed
-    /// - Graph database update fails
-    /// - Local storage update fails
+    /// Returns `Ok(())` if the pod was successfully removed from local storage and queued for network removal.
     ///
-    /// # Side Effects
+    /// Returns an `Error` if:
+    /// - The pod address does not exist in the local store
+    /// - The pod address is the configuration pod (cannot be removed)
+    /// - Graph database operations fail
+    /// - Local file operations fail
+    /// - Key store operations fail
     ///
-    /// - Updates the local graph database with the new subject data
-    /// - Writes updated graph data to associated scratchpad files
-    /// - Adds the pod and scratchpad addresses to the upload queue
-    /// - The changes will be uploaded to the network on the next `upload_all()` call
+    /// # Safety
+    ///
+    /// - **Cannot remove configuration pod**: The configuration pod is protected and cannot be removed
+    /// - **Irreversible operation**: Once uploaded to the network, the pod removal cannot be undone
+    /// - **Cascading removal**: All scratchpads associated with the pod are also removed
     ///
     /// # Example
     ///
     /// ```ignore
-    /// use serde_json::json;
-    ///
     /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
-    /// let pod_address = "80e79010a13e7eee779f799d99a20b418436828269b18192d92940bc9ddbfe295a7e1823d7bff75c59cbacbdea101a0d"; // Pod Autonomi address
-    /// let subject_address = "c859818c623ce4fc0899c2ab43061b19caa0b0598eec35ef309dbe50c8af8d59"; // Subject Autonomi address
+    /// // Create a pod to demonstrate removal
+    /// let (pod_address, _) = pod_manager.add_pod("Temporary Pod").await?;
     ///
-    /// // Add metadata for a document
-    /// let metadata = json!({
-    ///     "@context": "http://schema.org/",
-    ///     "@type": "TextDigitalDocument",
-    ///     "name": "Important Document",
-    ///     "author": "John Doe",
-    ///     "dateCreated": "2024-01-15",
-    ///     "description": "A document containing important information"
-    /// }).to_string();
+    /// // Add some data to the pod
+    /// let subject_data = r#"{
+    ///     "@context": {"schema": "http://schema.org/"},
+    ///     "@type": "schema:Document",
+    ///     "schema:name": "Temporary Document"
+    /// }"#;
+    /// pod_manager.put_subject_data(&pod_address, &pod_address, subject_data).await?;
     ///
-    /// pod_manager.put_subject_data(pod_address, subject_address, &metadata).await?;
+    /// // Remove the pod (this only removes it locally and queues for network removal)
+    /// pod_manager.remove_pod(&pod_address).await?;
     ///
-    /// // Remove metadata by providing empty data
-    /// pod_manager.put_subject_data(pod_address, subject_address, "").await?;
+    /// // The pod is now removed from local storage but still exists on the network
+    /// // Upload the removal to the network
+    /// pod_manager.upload_all().await?;
+    ///
+    /// // Now the pod is completely removed from both local storage and the network
     /// # Ok(())
     /// # }
     /// ```
     ///
     /// # Related Functions
     ///
-    /// - [`get_subject_data`] - Retrieve data for a specific subject
-    /// - [`upload_all`] - Upload pending changes to the network
-    /// - [`search`] - Search for subjects across pods
-    pub async fn put_subject_data(
-        &mut self,
-        pod_address: &str,
-        subject_address: &str,
-        subject_data: &str,
-    ) -> Result<(), Error> {
+    /// - [`add_pod`] - Create a new pod
+    /// - [`rename_pod`] - Rename an existing pod
+    /// - [`upload_all`] - Upload pending removals to the network
+    /// - [`list_my_pods`] - List all local pods
+    pub async fn remove_pod(&mut self, pod_address: &str) -> Result<(), Error> {
         let pod_address = self.graph.check_pod_exists(pod_address)?;
         let pod_address = pod_address.trim();
         let configuration_address = self.key_store.get_configuration_address()?;
         let configuration_address = configuration_address.as_str();
 
-        // Inject the JSON data into the graph using the pod address as the named graph
-        // And return the resulting graph data as a TriG formatted byte vector
-        let (graph, configuration) = self.graph.put_subject_data(
+        // Return an error if trying to remove the configuration pod
+        if pod_address == configuration_address {
+            return Err(Error::Pod("Cannot remove configuration pod".to_string()));
+        }
+
+        // Check current scratchpads for this pod
+        let pod_scratchpads = self
+            .get_pod_scratchpads(pod_address)?
+            .unwrap_or_else(Vec::new);
+
+        // Remove the pod from the graph
+        let configuration = self.graph.remove_pod_entry(
             pod_address,
-            subject_address,
+            pod_scratchpads.clone(),
             configuration_address,
-            subject_data,
         )?;
+        self.process_configuration_data(configuration_address, configuration)
+            .await?;
 
         // Process the pod data with proper scratchpad management
-        self.process_pod_data(pod_address, graph).await?;
-        // Update the configuration graph with the updated key count
-        let num_keys = self.key_store.get_num_keys();
-        self.graph
-            .update_key_count(configuration_address, num_keys)?;
-        self.process_pod_data(configuration_address, configuration)
+        self.remove_pod_data(pod_address, pod_scratchpads, false)
             .await?;
 
         Ok(())
     }
 
-    /// Retrieves all semantic data associated with a specific subject across all pods.
+    /// Moves a pod to freshly allocated keys, for recovering from a compromised key.
     ///
-    /// This function queries the graph database to find all RDF triples where the specified
-    /// subject address appears as the subject. It returns the data in JSON format, aggregating
-    /// information from all pods that contain data about this subject.
+    /// Pod keys are derived from the user's secret and can't be rotated in place, so this moves
+    /// the pod to a new pointer and scratchpad instead. The pod's graph data is copied under the
+    /// new pod address, and references to the pod in the user's own pods are rewritten to the
+    /// new address. The old pointer and scratchpads are marked abandoned in the configuration
+    /// pod so their keys are never handed out again, and are queued for removal from the
+    /// network. The new pod is queued for upload. Everything takes effect on the next
+    /// `upload_all()`.
+    ///
+    /// Pods owned by other users that reference the old address are not changed, and will see
+    /// the old pod as removed once the removal is uploaded.
     ///
     /// # Parameters
     ///
-    /// * `subject_address` - The Autonomi address of the object to retrieve data for
+    /// * `pod_address` - The hexadecimal address or name of the pod to move
     ///
     /// # Returns
     ///
-    /// Returns a JSON string containing all metadata associated with the subject, or an `Error` if:
-    /// - The subject address is invalid
-    /// - The graph database query fails
-    /// - JSON serialization fails
-    ///
-    /// The returned JSON follows the SPARQL JSON Results format with bindings for each
-    /// predicate-object pair associated with the subject.
+    /// Returns the new `(pod_address, scratchpad_address)` of the pod, or an `Error` if:
+    /// - The pod address does not exist in the local store
+    /// - The pod address is the configuration pod
+    /// - Key derivation fails
+    /// - Graph database or local file operations fail
     ///
     /// # Example
     ///
     /// ```ignore
     /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
-    /// let subject_address = "c859818c623ce4fc0899c2ab43061b19caa0b0598eec35ef309dbe50c8af8d59";
-    ///
-    /// // Retrieve all metadata for the subject
-    /// let metadata_json = pod_manager.get_subject_data(subject_address).await?;
-    ///
-    /// // Parse the JSON to work with the data
-    /// let metadata: serde_json::Value = serde_json::from_str(&metadata_json)?;
-    ///
-    /// // Access the SPARQL results
-    /// if let Some(bindings) = metadata["results"]["bindings"].as_array() {
-    ///     for binding in bindings {
-    ///         if let (Some(predicate), Some(object)) = (
-    ///             binding["predicate"]["value"].as_str(),
-    ///             binding["object"]["value"].as_str()
-    ///         ) {
-    ///             println!("Property: {}, Value: {}", predicate, object);
-    ///         }
-    ///     }
-    /// }
+    /// let (new_pod, new_scratchpad) = pod_manager.rotate_pod_keys("My Documents").await?;
+    /// println!("Pod moved to {new_pod}");
+    /// pod_manager.upload_all().await?;
     /// # Ok(())
     /// # }
     /// ```
     ///
     /// # Related Functions
     ///
-    /// - [`put_subject_data`] - Add or update data for a subject
-    /// - [`search`] - Search for subjects with specific criteria
-    pub async fn get_subject_data(&mut self, subject_address: &str) -> Result<String, Error> {
-        // Perform a SPARQL query with the Autonomi object address and return the metadata as JSON results
-        let json_data = self.graph.get_subject_data(subject_address)?;
-
-        Ok(json_data)
-    }
-
-    pub fn get_pod_scratchpads(&self, address: &str) -> Result<Option<Vec<String>>, Error> {
-        // Get all scratchpad addresses for this pod from the graph database
-        match self.graph.get_pod_scratchpads(address) {
-            Ok(scratchpads) => {
-                if scratchpads.is_empty() {
-                    // Fallback to the pointer target if no scratchpads found in graph
-                    let target = self.data_store.get_pointer_target(address)?;
-                    Ok(Some(vec![target]))
-                } else {
-                    Ok(Some(scratchpads))
-                }
-            }
-            Err(_) => {
-                // Fallback to the pointer target if graph query fails
-                let target = self.data_store.get_pointer_target(address)?;
-                Ok(Some(vec![target]))
-            }
-        }
-    }
+    /// - [`add_pod`] - Create a new pod
+    /// - [`remove_pod`] - Remove a pod, freeing its keys for reuse
+    /// - [`upload_all`] - Upload the new pod and the removal of the old one
+    pub async fn rotate_pod_keys(&mut self, pod_address: &str) -> Result<(String, String), Error> {
+        let pod_address = self.graph.check_pod_exists(pod_address)?;
+        let pod_address = pod_address.trim();
+        let configuration_address = self.key_store.get_configuration_address()?;
+        let configuration_address = configuration_address.as_str();
 
-    async fn remove_pod_data(
-        &mut self,
-        pod_address: &str,
-        pod_scratchpads: Vec<String>,
-    ) -> Result<(), Error> {
-        // Remove the pod address from the key store pointers list
-        self.key_store.remove_pointer_key(pod_address)?;
-        // Remove the pod scratchpads from the key store scratchpads list
-        for scratchpad in pod_scratchpads.clone() {
-            self.key_store.remove_scratchpad_key(scratchpad.trim())?;
+        if pod_address == configuration_address {
+            return Err(Error::Pod(
+                "Cannot rotate the configuration pod keys".to_string(),
+            ));
         }
 
-        // Remove each scratchpad file from the data store
-        for scratchpad in pod_scratchpads.clone() {
-            self.data_store.remove_scratchpad_file(scratchpad.trim())?;
-        }
-        // Remove the pod pointer file from the data store
-        self.data_store.remove_pointer_file(pod_address)?;
+        let pod_scratchpads = self
+            .get_pod_scratchpads(pod_address)?
+            .unwrap_or_else(Vec::new);
 
-        // Mark the removal of the pod pointer and scratchpads for the next upload_all operation
+        // Allocate the new pointer and scratchpad
+        let new_pod_address = self.add_pointer().await?.to_hex();
+        let new_scratchpad_address = self.add_scratchpad(&new_pod_address).await?.to_hex();
         self.data_store
-            .append_removal_list(pod_address, "pointer")?;
-        for scratchpad in pod_scratchpads {
-            self.data_store
-                .append_removal_list(scratchpad.trim(), "scratchpad")?;
+            .update_pointer_target(&new_pod_address, &new_scratchpad_address)?;
+
+        // Move the pod data to the new address in the graph
+        let num_keys = self.key_store.get_num_keys();
+        let (graph, configuration, referencing_pods) = self.graph.rotate_pod_entry(
+            pod_address,
+            &pod_scratchpads,
+            &new_pod_address,
+            &new_scratchpad_address,
+            configuration_address,
+            num_keys,
+        )?;
+        self.process_pod_data(&new_pod_address, graph).await?;
+
+        // Rewrite the pods that referenced the old address
+        for referencing_pod in &referencing_pods {
+            let referencing_graph = self.graph.get_pod_graph_data(referencing_pod)?;
+            self.process_pod_data(referencing_pod, referencing_graph)
+                .await?;
         }
 
-        Ok(())
+        // Abandon the old keys and queue the old pod for removal
+        self.remove_pod_data(pod_address, pod_scratchpads, true)
+            .await?;
+
+        let num_keys = self.key_store.get_num_keys();
+        self.graph
+            .update_key_count(configuration_address, num_keys)?;
+        self.process_configuration_data(configuration_address, configuration)
+            .await?;
+
+        info!(
+            "Rotated pod {} to new pod {} with scratchpad {}",
+            pod_address, new_pod_address, new_scratchpad_address
+        );
+        Ok((new_pod_address, new_scratchpad_address))
     }
 
-    /// Processes pod data by managing scratchpad allocation and data distribution.
+    /// Lists the versions of a pod saved in the local pod history.
     ///
-    /// This function handles the complex task of distributing pod graph data across multiple
-    /// scratchpads, ensuring that each scratchpad stays within the 4MB size limit. It also
-    /// manages the creation of additional scratchpads when needed and properly sorts the
-    /// data to ensure pod_index and pod_ref entries are prioritized.
+    /// Versions are only saved while pod history is enabled with [`set_history_depth`]. Each
+    /// version is the pod pointer count at the time it was saved and holds the pod as it was
+    /// before the next local edit.
+    ///
+    /// # Parameters
+    ///
+    /// * `pod_address` - The address or name of the pod
+    ///
+    /// # Returns
+    ///
+    /// Returns the saved versions, oldest first, or an `Error` if the pod doesn't exist or the
+    /// history cannot be read.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # fn example(pod_manager: &PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// for version in pod_manager.list_pod_history("my_pod")? {
+    ///     println!("version {version}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`set_history_depth`] - Enable pod history
+    /// - [`restore_pod_version`] - Roll a pod back to a saved version
+    pub fn list_pod_history(&self, pod_address: &str) -> Result<Vec<u64>, Error> {
+        let pod_address = self.graph.check_pod_exists(pod_address)?;
+        Ok(self.data_store.list_pod_versions(pod_address.trim())?)
+    }
+
+    /// Rolls a pod back to a version saved in the local pod history.
+    ///
+    /// The pod graph is replaced with the saved version and the pod is queued for the next
+    /// [`upload_all`], the same as any other edit. The pod keeps its current scratchpads, and
+    /// when pod history is still enabled the state being replaced is saved first, so a restore
+    /// can itself be undone.
     ///
     /// # Parameters
     ///
-    /// * `pod_address` - The hexadecimal address of the pod
-    /// * `graph_data` - The TriG-formatted graph data as a byte vector
+    /// * `pod_address` - The address or name of the pod
+    /// * `version` - A version returned by [`list_pod_history`]
     ///
     /// # Returns
     ///
-    /// Returns `Ok(())` on success, or an `Error` if scratchpad operations fail.
-    async fn process_pod_data(
+    /// Returns `Ok(())` on success, or an `Error` if:
+    /// - The pod doesn't exist or the version was not saved
+    /// - The saved version cannot be loaded into the graph
+    /// - Scratchpad or configuration updates fail
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// // Undo the last uploaded change
+    /// let history = pod_manager.list_pod_history("my_pod")?;
+    /// if let Some(version) = history.last() {
+    ///     pod_manager.restore_pod_version("my_pod", *version).await?;
+    ///     pod_manager.upload_all().await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`list_pod_history`] - List the saved versions of a pod
+    /// - [`set_history_depth`] - Enable pod history
+    /// - [`upload_all`] - Upload the restored pod
+    pub async fn restore_pod_version(
         &mut self,
         pod_address: &str,
-        graph_data: Vec<u8>,
+        version: u64,
     ) -> Result<(), Error> {
-        const SCRATCHPAD_SIZE_LIMIT: usize = 4 * 1024 * 1024; // 4MB in bytes
+        let pod_address = self.graph.check_pod_exists(pod_address)?;
+        let pod_address = pod_address.trim();
+        let configuration_address = self.key_store.get_configuration_address()?;
+        let configuration_address = configuration_address.as_str();
 
-        // Convert graph data to string for processing
-        let graph_string: String = graph_data.into_iter().map(|b| b as char).collect();
+        if !self
+            .data_store
+            .list_pod_versions(pod_address)?
+            .contains(&version)
+        {
+            return Err(Error::Pod(format!(
+                "Version {version} of pod {pod_address} not found in the pod history"
+            )));
+        }
+        let saved_data = self.data_store.get_pod_version(pod_address, version)?;
 
-        // Check current scratchpads for this pod
         let current_scratchpads = self
             .get_pod_scratchpads(pod_address)?
             .unwrap_or_else(Vec::new);
+        let (graph, configuration) = self.graph.restore_pod_graph(
+            pod_address,
+            &saved_data,
+            &current_scratchpads,
+            configuration_address,
+        )?;
 
-        // Calculate how many scratchpads we need for the data
-        let data_size = graph_string.len();
-        let required_scratchpads = data_size.div_ceil(SCRATCHPAD_SIZE_LIMIT);
-        let required_scratchpads = std::cmp::max(1, required_scratchpads); // At least 1 scratchpad
-
-        // Create additional scratchpads if needed
-        let mut all_scratchpads = current_scratchpads.clone();
-        while all_scratchpads.len() < required_scratchpads {
-            let new_scratchpad = self.add_scratchpad(pod_address).await?;
-            let new_address = new_scratchpad.to_hex();
-            all_scratchpads.push(new_address.clone());
-
-            // Add the new scratchpad to the graph with proper pod_index
-            let scratchpad_iri = format!("ant://{new_address}");
-            let pod_iri = format!("ant://{pod_address}");
-            let index = (all_scratchpads.len() - 1).to_string();
-
-            self.graph
-                .put_quad(&scratchpad_iri, graph::HAS_INDEX, &index, Some(&pod_iri))?;
-
-            // Add the modified date to the new scratchpad
-            let date = Utc::now().to_rfc3339();
-            self.graph.put_quad(
-                &scratchpad_iri,
-                graph::HAS_MODIFIED_DATE,
-                &date,
-                Some(&pod_iri),
-            )?;
-
-            // Update the key count
-            let num_keys = self.key_store.get_num_keys();
-            self.graph.update_key_count(pod_address, num_keys)?;
-        }
-
-        // Sort the graph data to prioritize pod_index and pod_ref entries
-        let sorted_data = self.sort_graph_data(&graph_string);
-
-        // Split the sorted data into chunks that fit in scratchpads
-        let chunks = self.split_data_into_chunks(&sorted_data, SCRATCHPAD_SIZE_LIMIT);
-
-        // Update scratchpads with the chunked data
-        for (i, chunk) in chunks.iter().enumerate() {
-            if i < all_scratchpads.len() {
-                let scratchpad_address = &all_scratchpads[i];
-                self.data_store
-                    .update_scratchpad_data(scratchpad_address.trim(), chunk)?;
-                self.data_store
-                    .add_scratchpad_to_pod(pod_address, scratchpad_address)?;
-            }
-        }
-
-        // Clear any unused scratchpads
-        for scratchpad_address in all_scratchpads.iter().skip(chunks.len()) {
-            self.data_store
-                .remove_scratchpad_file(scratchpad_address.trim())?;
-            self.data_store
-                .append_removal_list(scratchpad_address.trim(), "scratchpad")?;
-            self.graph
-                .remove_scratchpad_entry(pod_address, scratchpad_address.trim())?;
-            self.key_store
-                .remove_scratchpad_key(scratchpad_address.trim())?;
-        }
-
-        // Add the pod pointer address to the update list
-        self.data_store.append_update_list(pod_address)?;
+        self.process_pod_data(pod_address, graph).await?;
+        let num_keys = self.key_store.get_num_keys();
+        self.graph
+            .update_key_count(configuration_address, num_keys)?;
+        self.process_configuration_data(configuration_address, configuration)
+            .await?;
 
+        info!("Restored pod {} to version {}", pod_address, version);
         Ok(())
     }
 
-    /// Sorts graph data to prioritize pod_index and pod_ref entries.
-    ///
-    /// This function ensures that statements containing pod_index predicates appear first,
-    /// followed by statements containing pod_ref objects, with all other data following.
-    /// This ordering is important for proper scratchpad linking and pod reference handling.
+    /// Reports what [`remove_pod`] would delete, without changing anything.
     ///
-    /// The function properly handles multi-line TriG statements by grouping continuation
-    /// lines (those starting with whitespace) with their subject line.
+    /// Removing a pod is irreversible once uploaded, so a UI can show this plan in a
+    /// confirmation dialog first. It lists the pod's scratchpads, how many subjects it describes,
+    /// which of the user's other pods reference it (those references will break), and the
+    /// pointer and scratchpad addresses whose keys return to the free pool.
     ///
     /// # Parameters
     ///
-    /// * `data` - The TriG-formatted graph data as a string
+    /// * `pod_address` - The hexadecimal address of the pod to check
     ///
     /// # Returns
     ///
-    /// Returns the sorted data as a string with prioritized entries first.
-    pub fn sort_graph_data(&self, data: &str) -> String {
-        let lines: Vec<&str> = data.lines().collect();
-        let mut statements: Vec<Vec<&str>> = Vec::new();
-        let mut current_statement: Vec<&str> = Vec::new();
-
-        // Group lines into statements (subject + continuation lines)
-        // FIXME: this works for the current library, but should probably make this more robust at some point to check
-        // for proper TriG syntax, i.e. look for a ';' at the end of the previous line to know the following statment
-        // is a continuation of the previous statement
-        for line in lines {
-            if line.trim().is_empty() {
-                // Empty line - add to current statement if it exists, otherwise skip
-                if !current_statement.is_empty() {
-                    current_statement.push(line);
-                }
-            } else if line.starts_with(char::is_whitespace) {
-                // Continuation line (starts with whitespace) - add to current statement
-                if !current_statement.is_empty() {
-                    current_statement.push(line);
-                } else {
-                    // Orphaned continuation line - treat as new statement
-                    current_statement.push(line);
-                }
-            } else {
-                // New subject line - save previous statement and start new one
-                if !current_statement.is_empty() {
-                    statements.push(current_statement);
-                }
-                current_statement = vec![line];
-            }
-        }
-
-        // Don't forget the last statement
-        if !current_statement.is_empty() {
-            statements.push(current_statement);
-        }
+    /// Returns a [`RemovalPlan`], or an `Error` if:
+    /// - The pod address does not exist in the local store
+    /// - The pod address is the configuration pod (cannot be removed)
+    /// - Graph database queries fail
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let pod_address = "80e79010a10d4b5b3c6b3e5b4c0e3c4c1b6d5f3e3c9c6b8e5f4c3b2a1d0e9f8a";
+    /// let plan = pod_manager.remove_pod_preview(pod_address)?;
+    /// println!(
+    ///     "Remove {} subjects? {} of your pods reference this pod.",
+    ///     plan.subject_count,
+    ///     plan.referenced_by.len()
+    /// );
+    /// pod_manager.remove_pod(pod_address).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`remove_pod`] - Remove the pod
+    /// - [`list_pod_subjects`] - List the subjects in the pod
+    pub fn remove_pod_preview(&self, pod_address: &str) -> Result<RemovalPlan, Error> {
+        let pod_address = self.graph.check_pod_exists(pod_address)?;
+        let pod_address = pod_address.trim();
+        let configuration_address = self.key_store.get_configuration_address()?;
 
-        // Sort statements based on the priority of their first (subject) line
-        statements.sort_by(|a, b| {
-            let a_priority = if !a.is_empty() {
-                self.get_statement_priority(a)
-            } else {
-                2
-            };
-            let b_priority = if !b.is_empty() {
-                self.get_statement_priority(b)
-            } else {
-                2
-            };
-            a_priority.cmp(&b_priority)
-        });
-
-        // Reconstruct the sorted data
-        let mut result = Vec::new();
-        for statement in statements {
-            for line in statement {
-                result.push(line);
-            }
+        // Same check as remove_pod
+        if pod_address == configuration_address {
+            return Err(Error::Pod("Cannot remove configuration pod".to_string()));
         }
 
-        result.join("\n")
+        let scratchpads = self.get_pod_scratchpads(pod_address)?.unwrap_or_default();
+        let mut freed_keys = vec![pod_address.to_string()];
+        freed_keys.extend(scratchpads.iter().cloned());
+
+        Ok(RemovalPlan {
+            pod_address: pod_address.to_string(),
+            name: self.graph.get_pod_name(pod_address)?,
+            subject_count: self.graph.get_pod_subject_digests(pod_address)?.len(),
+            referenced_by: self.graph.get_pods_referencing(pod_address, true)?,
+            scratchpads,
+            freed_keys,
+        })
     }
 
-    /// Determines the sorting priority for a TriG statement.
+    /// Returns the local and network state of a pod pointer, for diagnosing sync problems.
+    ///
+    /// A pod is only downloaded by [`refresh_cache`] when the counter of its pointer on the
+    /// network is newer than the local counter (unless the refresh is forced), and only uploaded
+    /// by [`upload_all`] when it is in the update list. Comparing the values reported here explains most cases of a pod that
+    /// "won't update". Local values are read from the data store; remote values are only fetched
+    /// when `fetch_remote` is true. Failing to fetch the remote pointer is reported in
+    /// `remote_error` rather than returned as an error.
     ///
     /// # Parameters
     ///
-    /// * `statement` - A vector of lines representing a complete TriG statement
+    /// * `pod_address` - The hexadecimal address of the pod pointer
+    /// * `fetch_remote` - Whether to fetch the pointer from the network as well
     ///
     /// # Returns
     ///
-    /// Returns a priority value where lower numbers indicate higher priority.
-    fn get_statement_priority(&self, statement: &[&str]) -> u8 {
-        // Check all lines in the statement for priority indicators
-        for line in statement {
-            if line.contains(graph::HAS_INDEX) {
-                return 0; // Pod scratchpads should always be first in the scratchpad (pointer can only point to the first scratchpad)
-            } else if line.contains(graph::POD_REF) {
-                return 1; // Pod references are next for future enhancement to thread the data fetches
-            }
-        }
-        2 // Everything else in the pod
-    }
-
-    /// Splits data into chunks that fit within the scratchpad size limit.
-    ///
-    /// This function intelligently splits the data while trying to preserve line boundaries
-    /// when possible. It ensures that no chunk exceeds the specified size limit. Each chunk
-    /// starts with a timestamp comment in the format '#<timestamp>\n' (37 bytes total).
+    /// Returns a [`PointerInfo`], or an `Error` if:
+    /// - The pod address cannot be parsed
+    /// - The local pointer file or update list cannot be read
     ///
-    /// # Parameters
+    /// # Example
     ///
-    /// * `data` - The data to split
-    /// * `chunk_size` - Maximum size for each chunk in bytes (includes the 37-byte timestamp comment)
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let pod_address = "80e79010a10d4b5b3c6b3e5b4c0e3c4c1b6d5f3e3c9c6b8e5f4c3b2a1d0e9f8a";
+    /// let info = pod_manager.get_pointer_info(pod_address, true).await?;
+    /// println!(
+    ///     "local counter: {:?}, remote counter: {:?}",
+    ///     info.local_counter, info.remote_counter
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
     ///
-    /// # Returns
+    /// # Related Functions
     ///
-    /// Returns a vector of string chunks, each within the size limit and starting with a timestamp comment.
-    pub fn split_data_into_chunks(&self, data: &str, chunk_size: usize) -> Vec<String> {
-        let mut chunks = Vec::new();
-        let mut current_chunk = String::new();
-
-        // Reserve bytes for the timestamp comment: '#' + RFC3339 timestamp + '\n'
-        // RFC3339 format can be up to 35 characters (e.g., "2024-01-15T10:30:45.123456789+00:00")
-        // Plus '#' (1 char) and '\n' (1 char) = 37 bytes total
-        const TIMESTAMP_COMMENT_SIZE: usize = 37;
-        let effective_chunk_size = chunk_size.saturating_sub(TIMESTAMP_COMMENT_SIZE);
+    /// - [`refresh_cache`] - Download pods whose pointers changed
+    /// - [`upload_all`] - Upload pods with pending changes
+    /// - [`get_update_list`] - List the pods with pending changes
+    pub async fn get_pointer_info(
+        &self,
+        pod_address: &str,
+        fetch_remote: bool,
+    ) -> Result<PointerInfo, Error> {
+        let pod_address = pod_address.trim();
+        let pointer_address = PointerAddress::from_hex(pod_address)?;
 
-        // Handle the case where data doesn't end with newline
-        let data_with_newline = if data.ends_with('\n') {
-            data.to_string()
-        } else {
-            format!("{data}\n")
+        let mut info = PointerInfo {
+            pod_address: pod_address.to_string(),
+            pending_upload: self
+                .data_store
+                .get_update_list()?
+                .pods
+                .contains_key(pod_address),
+            ..Default::default()
         };
+        if self.data_store.address_is_pointer(pod_address)? {
+            info.local_target = Some(self.data_store.get_pointer_target(pod_address)?);
+            info.local_counter = Some(self.data_store.get_pointer_count(pod_address)?);
+        }
 
-        for line in data_with_newline.lines() {
-            let line_with_newline = format!("{line}\n");
-
-            // If adding this line would exceed the effective chunk size, start a new chunk
-            if !current_chunk.is_empty()
-                && current_chunk.len() + line_with_newline.len() > effective_chunk_size
-            {
-                chunks.push(current_chunk.clone());
-                current_chunk.clear();
-            }
-
-            // If a single line is larger than effective_chunk_size, we need to split it
-            if line_with_newline.len() > effective_chunk_size {
-                // Add any existing chunk first
-                if !current_chunk.is_empty() {
-                    chunks.push(current_chunk.clone());
-                    current_chunk.clear();
-                }
-
-                // Split the large line into smaller pieces (without adding extra newlines)
-                let line_str = line; // Use the line without the newline we added
-                let line_bytes = line_str.as_bytes();
-                for chunk_start in (0..line_bytes.len()).step_by(effective_chunk_size) {
-                    let chunk_end =
-                        std::cmp::min(chunk_start + effective_chunk_size, line_bytes.len());
-                    let chunk_bytes = &line_bytes[chunk_start..chunk_end];
-                    if let Ok(chunk_str) = std::str::from_utf8(chunk_bytes) {
-                        // Only add newline to the last chunk of this line
-                        if chunk_end == line_bytes.len() {
-                            chunks.push(format!("{chunk_str}\n"));
-                        } else {
-                            chunks.push(chunk_str.to_string());
-                        }
-                    }
+        if fetch_remote {
+            match self.client.pointer_get(&pointer_address).await {
+                Ok(pointer) => {
+                    info.remote_target = Some(pointer.target().to_hex());
+                    info.remote_counter = Some(pointer.counter() as u64);
                 }
-            } else {
-                current_chunk.push_str(&line_with_newline);
+                Err(e) => info.remote_error = Some(Self::broken_reference_reason(&e)),
             }
         }
 
-        // Add the final chunk if it has content
-        if !current_chunk.is_empty() {
-            chunks.push(current_chunk);
-        }
-
-        // Ensure we always have at least one chunk (even if empty)
-        if chunks.is_empty() {
-            chunks.push(String::new());
-        }
-
-        // Add timestamp comment to the beginning of each chunk
-        let timestamp = chrono::Utc::now().to_rfc3339();
-        let timestamp_comment = format!("#{timestamp}\n");
-
-        for chunk in chunks.iter_mut() {
-            *chunk = format!("{timestamp_comment}{chunk}");
-        }
-
-        chunks
+        Ok(info)
     }
 
-    /// Selects the newest scratchpad from a vector of scratchpads based on timestamp comments.
+    /// Checks on the network that a removed pod was actually removed.
     ///
-    /// This function reads the encrypted data from each scratchpad, looks for a timestamp comment
-    /// in the first line (format: #<RFC3339_timestamp>), and returns the scratchpad with the
-    /// latest timestamp. If only one scratchpad has a timestamp, it's assumed to be the newest.
-    /// If none have timestamps, the first scratchpad in the vector is returned.
+    /// Data on the Autonomi network cannot be deleted. [`upload_all`] removes a pod by pointing
+    /// its pointer at itself and overwriting its scratchpads with empty data, and older versions
+    /// may still be cached by peers. This function fetches the pointer and the scratchpads the
+    /// pod held when [`remove_pod`] was called, and reports which of them still hold data so a
+    /// user can be told honestly what the removal achieved.
+    ///
+    /// Scratchpads whose keys have since been reused by another pod are skipped and listed in
+    /// `reused_scratchpads`. Pods removed before the scratchpads were recorded only have their
+    /// pointer checked.
     ///
     /// # Parameters
     ///
-    /// * `scratchpads` - Vector of scratchpads to compare
+    /// * `pod_address` - The hexadecimal address of the removed pod
     ///
     /// # Returns
     ///
-    /// Returns the scratchpad with the latest timestamp, or the first one if no timestamps are found.
-    fn select_newest_scratchpad(scratchpads: Vec<Scratchpad>) -> Scratchpad {
-        if scratchpads.is_empty() {
-            panic!("Cannot select from empty scratchpads vector");
-        }
-
-        if scratchpads.len() == 1 {
-            return scratchpads[0].clone();
+    /// Returns a [`RemovalStatus`], or an `Error` if:
+    /// - The pod address is still in use by a local pod
+    /// - The addresses cannot be parsed
+    /// - Network errors other than a missing record occur
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let pod_address = "80e79010a10d4b5b3c6b3e5b4c0e3c4c1b6d5f3e3c9c6b8e5f4c3b2a1d0e9f8a";
+    /// pod_manager.remove_pod(pod_address).await?;
+    /// pod_manager.upload_all().await?;
+    ///
+    /// let status = pod_manager.verify_removal(pod_address).await?;
+    /// if !status.is_complete() {
+    ///     println!("Still holding data: {:?}", status.scratchpads_with_data);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`remove_pod`] - Remove a pod locally and queue its removal
+    /// - [`upload_all`] - Upload pending removals to the network
+    /// - [`get_pending_removals`] - List removals that have not been uploaded yet
+    pub async fn verify_removal(&self, pod_address: &str) -> Result<RemovalStatus, Error> {
+        let pod_address = pod_address.trim();
+        if self.key_store.address_is_pointer(pod_address) {
+            return Err(Error::Pod(format!(
+                "Pod {pod_address} is in use and has not been removed"
+            )));
         }
 
-        let mut newest_scratchpad = &scratchpads[0];
-        let mut newest_timestamp: Option<chrono::DateTime<chrono::Utc>> = None;
-
-        for scratchpad in &scratchpads {
-            // Extract the encrypted data and convert to string
-            let data = scratchpad.encrypted_data();
-            if let Ok(data_string) = String::from_utf8(data.to_vec()) {
-                // Check if the first line is a timestamp comment
-                if let Some(first_line) = data_string.lines().next()
-                    && first_line.starts_with('#')
-                    && first_line.len() > 1
-                {
-                    let timestamp_str = &first_line[1..]; // Remove the '#' prefix
+        let pending_removals = self.data_store.get_pending_removals()?;
+        let mut status = RemovalStatus {
+            pod_address: pod_address.to_string(),
+            pending_upload: pending_removals.pointers.iter().any(|p| p == pod_address),
+            ..Default::default()
+        };
 
-                    // Try to parse the timestamp
-                    if let Ok(timestamp) = chrono::DateTime::parse_from_rfc3339(timestamp_str) {
-                        let utc_timestamp = timestamp.with_timezone(&chrono::Utc);
+        // Check the pointer points to itself
+        let pointer_address = PointerAddress::from_hex(pod_address)?;
+        match self.client.pointer_get(&pointer_address).await {
+            Ok(pointer) => status.pointer_removed = Self::pointer_is_tombstone(&pointer),
+            Err(e) => match e {
+                PointerError::CannotUpdateNewPointer
+                | PointerError::GetError(GetError::RecordNotFound) => {
+                    status
+                        .unreachable
+                        .push((pod_address.to_string(), Self::broken_reference_reason(&e)));
+                }
+                _ => return Err(Error::Pointer(Box::new(e))),
+            },
+        }
 
-                        // Check if this is the newest timestamp so far
-                        if newest_timestamp.is_none() || utc_timestamp > newest_timestamp.unwrap() {
-                            newest_timestamp = Some(utc_timestamp);
-                            newest_scratchpad = scratchpad;
+        // Check the scratchpads the pod held are empty
+        let scratchpads = self
+            .data_store
+            .get_removed_pod_scratchpads(pod_address)?
+            .unwrap_or_default();
+        let used_scratchpads = self.key_store.get_scratchpads();
+        for scratchpad in scratchpads {
+            if used_scratchpads.contains_key(&scratchpad) {
+                status.reused_scratchpads.push(scratchpad);
+                continue;
+            }
+            let scratchpad_address = ScratchpadAddress::from_hex(&scratchpad)?;
+            let data = match self.client.scratchpad_get(&scratchpad_address).await {
+                Ok(scratchpad) => scratchpad.encrypted_data().clone(),
+                Err(ScratchpadError::Fork(scratchpads)) => {
+                    match Self::select_newest_scratchpad(scratchpads) {
+                        Some((newest, _)) => newest.encrypted_data().clone(),
+                        None => {
+                            status
+                                .unreachable
+                                .push((scratchpad, "forked without any versions".to_string()));
+                            continue;
                         }
                     }
                 }
+                Err(
+                    ScratchpadError::CannotUpdateNewScratchpad
+                    | ScratchpadError::GetError(GetError::RecordNotFound),
+                ) => {
+                    status
+                        .unreachable
+                        .push((scratchpad, "not found".to_string()));
+                    continue;
+                }
+                Err(e) => return Err(Error::Scratchpad(Box::new(e))),
+            };
+            if data.is_empty() {
+                status.empty_scratchpads.push(scratchpad);
+            } else {
+                status.scratchpads_with_data.push(scratchpad);
             }
-        }
-
-        // If we found at least one timestamp, return the newest one
-        // If no timestamps were found, return the first scratchpad
-        newest_scratchpad.clone()
-    }
-
-    ///////////////////////////////////////////
-    // Local data operations
-    ///////////////////////////////////////////
+        }
 
-    /// Creates a new pod with the specified name in the local data store.
-    ///
-    /// This function creates a complete pod structure including:
-    /// - A new pointer address for the pod
-    /// - A new scratchpad address for data storage
-    /// - Initial graph data with pod metadata
-    /// - Local files for both pointer and scratchpad
-    /// - Adds the addresses to the upload queue
+        Ok(status)
+    }
+
+    /// Renames an existing pod in the local store and queues the change for network upload.
     ///
-    /// The pod will be ready for use immediately and will be uploaded to the Autonomi network
-    /// on the next call to `upload_all()`.
+    /// This function updates the human-readable name of a pod in the graph database.
+    /// The pod's address remains unchanged, but its display name is updated throughout
+    /// the system. The change will be uploaded to the Autonomi network on the next call
+    /// to `upload_all()`.
     ///
     /// # Parameters
     ///
-    /// * `pod_name` - A human-readable name for the pod (used in metadata)
+    /// * `pod_address` - The hexadecimal address of the pod to rename
+    /// * `new_name` - The new human-readable name for the pod
     ///
     /// # Returns
     ///
-    /// Returns `Ok((pointer_address, scratchpad_address))` containing:
-    /// - `pointer_address` - The hexadecimal address of the pod's pointer
-    /// - `scratchpad_address` - The hexadecimal address of the pod's primary scratchpad
+    /// Returns `Ok(())` if the pod was successfully renamed in local storage and queued for network upload.
     ///
     /// Returns an `Error` if:
-    /// - Key generation fails
-    /// - File creation fails
-    /// - Graph database update fails
-    /// - Local storage operations fail
+    /// - The pod address does not exist in the local store
+    /// - Graph database operations fail
+    /// - Local file operations fail
+    /// - The new name is invalid or empty
     ///
     /// # Example
     ///
     /// ```ignore
     /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
-    /// // Create a new pod for storing document metadata
-    /// let (pod_address, scratchpad_address) = pod_manager.add_pod("My Documents").await?;
-    ///
-    /// println!("Created pod at address: {}", pod_address);
-    /// println!("Primary scratchpad at: {}", scratchpad_address);
+    /// // Create a pod with an initial name
+    /// let (pod_address, _) = pod_manager.add_pod("Initial Name").await?;
     ///
-    /// // The pod is now ready to store data
+    /// // Add some data to the pod
     /// let subject_data = r#"{
-    ///     "@context": "http://schema.org/",
-    ///     "@type": "Collection",
-    ///     "name": "My Documents",
-    ///     "description": "A collection of important documents"
+    ///     "@context": {"schema": "http://schema.org/"},
+    ///     "@type": "schema:Collection",
+    ///     "schema:name": "My Collection",
+    ///     "schema:description": "A collection of important items"
     /// }"#;
-    ///
     /// pod_manager.put_subject_data(&pod_address, &pod_address, subject_data).await?;
     ///
-    /// // Upload the new pod to the network
+    /// // Rename the pod to something more descriptive
+    /// pod_manager.rename_pod(&pod_address, "Important Documents Collection").await?;
+    ///
+    /// // The pod is now renamed locally, upload the change to the network
     /// pod_manager.upload_all().await?;
+    ///
+    /// // Verify the new name appears in the pod list
+    /// let pods = pod_manager.list_my_pods().await?;
+    /// let renamed_pod = pods.iter().find(|p| p.address == pod_address).unwrap();
+    /// assert_eq!(renamed_pod.name, "Important Documents Collection");
     /// # Ok(())
     /// # }
     /// ```
     ///
     /// # Related Functions
     ///
-    /// - [`add_pod_ref`] - Add a reference to another pod
-    /// - [`upload_all`] - Upload the new pod to the network
-    /// - [`put_subject_data`] - Add data to the pod
-    pub async fn add_pod(&mut self, pod_name: &str) -> Result<(String, String), Error> {
-        let pod_address = self.add_pointer().await?;
-        let pod_address = pod_address.to_hex();
-        let pod_address = pod_address.as_str();
-        let scratchpad_address = self.add_scratchpad(pod_address).await?;
-        let scratchpad_address = scratchpad_address.to_hex();
-        let scratchpad_address = scratchpad_address.as_str();
-
-        // Add the scratchpad address to the pointer files
-        self.data_store
-            .update_pointer_target(pod_address, scratchpad_address)?;
-
-        let configuration_address = self.key_store.get_configuration_address()?;
-        let configuration_address = configuration_address.as_str();
-        let configuration_scratchpad_address =
-            self.key_store.get_configuration_scratchpad_address()?;
-        let configuration_scratchpad_address = configuration_scratchpad_address.as_str();
-        self.data_store
-            .update_pointer_target(configuration_address, configuration_scratchpad_address)?;
-
-        // Get the number of keys to store in the graph
-        let num_keys = self.key_store.get_num_keys();
+    /// - [`add_pod`] - Create a new pod with a name
+    /// - [`remove_pod`] - Remove an existing pod
+    /// - [`list_my_pods`] - List all pods with their current names
+    /// - [`upload_all`] - Upload the name change to the network
+    pub async fn rename_pod(&mut self, pod_address: &str, new_name: &str) -> Result<(), Error> {
+        let pod_address = self.graph.check_pod_exists(pod_address)?;
+        let pod_address = pod_address.trim();
 
-        // Add the pointer address to the graph
-        let (graph, configuration) = self.graph.add_pod_entry(
-            pod_name,
-            pod_address,
-            scratchpad_address,
-            configuration_address,
-            configuration_scratchpad_address,
-            num_keys,
-        )?;
+        // Rename the pod in the graph
+        let graph = self.graph.rename_pod_entry(pod_address, new_name)?;
 
         // Process the pod data with proper scratchpad management
         self.process_pod_data(pod_address, graph).await?;
 
-        // Update the configuration graph with the updated key count
-        let num_keys = self.key_store.get_num_keys();
-        self.graph
-            .update_key_count(configuration_address, num_keys)?;
+        Ok(())
+    }
 
-        self.process_pod_data(configuration_address, configuration)
-            .await?;
+    /// Renames several pods at once and queues the changes for network upload.
+    ///
+    /// All names are checked before any pod is changed, so a batch with a bad entry fails without
+    /// renaming anything. The graph is updated for every pod first, then the scratchpads of each
+    /// renamed pod are reprocessed once.
+    ///
+    /// # Parameters
+    ///
+    /// * `renames` - Pairs of pod address (or current name) and new name
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success, or an `Error` if:
+    /// - A pod does not exist in the local store
+    /// - A new name is empty
+    /// - A pod appears more than once in the batch
+    /// - A new name is given to more than one pod or is already used by another pod
+    /// - Graph database or local file operations fail
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// pod_manager
+    ///     .rename_pods(&[
+    ///         ("Music".to_string(), "Music (old)".to_string()),
+    ///         ("New Music".to_string(), "Music".to_string()),
+    ///     ])
+    ///     .await?;
+    /// pod_manager.upload_all().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`rename_pod`] - Rename a single pod
+    /// - [`upload_all`] - Upload the renamed pods
+    pub async fn rename_pods(&mut self, renames: &[(String, String)]) -> Result<(), Error> {
+        // Validate the whole batch before renaming anything
+        let renames = self.graph.resolve_pod_renames(renames)?;
 
-        Ok((pod_address.to_string(), scratchpad_address.to_string()))
+        let mut renamed_pods = Vec::new();
+        for (pod_address, new_name) in renames {
+            let graph = self.graph.rename_pod_entry(&pod_address, &new_name)?;
+            renamed_pods.push((pod_address, graph));
+        }
+
+        for (pod_address, graph) in renamed_pods {
+            self.process_pod_data(&pod_address, graph).await?;
+        }
+
+        Ok(())
     }
 
-    /// Removes a pod and all its associated data from the local store and network.
-    ///
-    /// This function completely removes a pod from the Colony system, including:
-    /// - Removing the pod entry from the graph database
-    /// - Removing all associated scratchpad data
-    /// - Removing the pod from the configuration pod's reference list
-    /// - Cleaning up local files and key store entries
-    /// - Adding addresses to the removal queue for network cleanup
+    /// Sets the icon shown for a pod in directory listings.
     ///
-    /// The pod will be marked for removal from the Autonomi network on the next call to `upload_all()`.
-    /// This operation cannot be undone once the changes are uploaded to the network.
+    /// The icon is stored by reference: the pod gets an icon triple pointing at the Autonomi
+    /// address that holds the image, the same way subjects reference their data. Upload the image
+    /// first, then set its address here. Any previous icon is replaced. The change is queued for
+    /// upload on the next call to [`upload_all`].
     ///
     /// # Parameters
     ///
-    /// * `pod_address` - The hexadecimal address of the pod to remove
+    /// * `pod_address` - The hexadecimal address or name of the pod
+    /// * `icon_subject_address` - The Autonomi address of the image
     ///
     /// # Returns
     ///
-    /// Returns `Ok(())` if the pod was successfully removed from local storage and queued for network removal.
-    ///
-    /// Returns an `Error` if:
+    /// Returns `Ok(())` on success, or an `Error` if:
     /// - The pod address does not exist in the local store
-    /// - The pod address is the configuration pod (cannot be removed)
+    /// - The icon address is empty
     /// - Graph database operations fail
     /// - Local file operations fail
-    /// - Key store operations fail
-    ///
-    /// # Safety
-    ///
-    /// - **Cannot remove configuration pod**: The configuration pod is protected and cannot be removed
-    /// - **Irreversible operation**: Once uploaded to the network, the pod removal cannot be undone
-    /// - **Cascading removal**: All scratchpads associated with the pod are also removed
     ///
     /// # Example
     ///
     /// ```ignore
     /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
-    /// // Create a pod to demonstrate removal
-    /// let (pod_address, _) = pod_manager.add_pod("Temporary Pod").await?;
-    ///
-    /// // Add some data to the pod
-    /// let subject_data = r#"{
-    ///     "@context": {"schema": "http://schema.org/"},
-    ///     "@type": "schema:Document",
-    ///     "schema:name": "Temporary Document"
-    /// }"#;
-    /// pod_manager.put_subject_data(&pod_address, &pod_address, subject_data).await?;
-    ///
-    /// // Remove the pod (this only removes it locally and queues for network removal)
-    /// pod_manager.remove_pod(&pod_address).await?;
-    ///
-    /// // The pod is now removed from local storage but still exists on the network
-    /// // Upload the removal to the network
+    /// let icon_address = "c859818c623ce4fc0899c2ab43061b19caa0b0598eec35ef309dbe50c8af8d59";
+    /// pod_manager.set_pod_icon("My Music", icon_address).await?;
     /// pod_manager.upload_all().await?;
-    ///
-    /// // Now the pod is completely removed from both local storage and the network
     /// # Ok(())
     /// # }
     /// ```
     ///
     /// # Related Functions
     ///
-    /// - [`add_pod`] - Create a new pod
-    /// - [`rename_pod`] - Rename an existing pod
-    /// - [`upload_all`] - Upload pending removals to the network
-    /// - [`list_my_pods`] - List all local pods
-    pub async fn remove_pod(&mut self, pod_address: &str) -> Result<(), Error> {
+    /// - [`get_pod_icon`] - Get the icon of a pod
+    /// - [`list_pods_by_modified`] - List pods with their icons
+    pub async fn set_pod_icon(
+        &mut self,
+        pod_address: &str,
+        icon_subject_address: &str,
+    ) -> Result<(), Error> {
         let pod_address = self.graph.check_pod_exists(pod_address)?;
         let pod_address = pod_address.trim();
-        let configuration_address = self.key_store.get_configuration_address()?;
-        let configuration_address = configuration_address.as_str();
-
-        // Return an error if trying to remove the configuration pod
-        if pod_address == configuration_address {
-            return Err(Error::Pod("Cannot remove configuration pod".to_string()));
+        let icon_subject_address = icon_subject_address.trim();
+        if icon_subject_address.is_empty() {
+            return Err(Error::Pod("Icon address cannot be empty".to_string()));
         }
 
-        // Check current scratchpads for this pod
-        let pod_scratchpads = self
-            .get_pod_scratchpads(pod_address)?
-            .unwrap_or_else(Vec::new);
-
-        // Remove the pod from the graph
-        let configuration = self.graph.remove_pod_entry(
-            pod_address,
-            pod_scratchpads.clone(),
-            configuration_address,
-        )?;
-        self.process_pod_data(configuration_address, configuration)
-            .await?;
+        let graph = self
+            .graph
+            .pod_icon_entry(pod_address, icon_subject_address)?;
 
         // Process the pod data with proper scratchpad management
-        self.remove_pod_data(pod_address, pod_scratchpads).await?;
+        self.process_pod_data(pod_address, graph).await?;
 
         Ok(())
     }
 
-    /// Renames an existing pod in the local store and queues the change for network upload.
-    ///
-    /// This function updates the human-readable name of a pod in the graph database.
-    /// The pod's address remains unchanged, but its display name is updated throughout
-    /// the system. The change will be uploaded to the Autonomi network on the next call
-    /// to `upload_all()`.
+    /// Gets the icon of a pod.
     ///
     /// # Parameters
     ///
-    /// * `pod_address` - The hexadecimal address of the pod to rename
-    /// * `new_name` - The new human-readable name for the pod
+    /// * `pod_address` - The hexadecimal address of the pod
     ///
     /// # Returns
     ///
-    /// Returns `Ok(())` if the pod was successfully renamed in local storage and queued for network upload.
-    ///
-    /// Returns an `Error` if:
-    /// - The pod address does not exist in the local store
-    /// - Graph database operations fail
-    /// - Local file operations fail
-    /// - The new name is invalid or empty
+    /// Returns the Autonomi address of the pod's icon image, `None` if the pod has no icon, or an
+    /// `Error` if the graph database query fails.
     ///
     /// # Example
     ///
     /// ```ignore
-    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
-    /// // Create a pod with an initial name
-    /// let (pod_address, _) = pod_manager.add_pod("Initial Name").await?;
+    /// # fn example(pod_manager: &PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let pod_address = "80e79010a10d4b5b3c6b3e5b4c0e3c4c1b6d5f3e3c9c6b8e5f4c3b2a1d0e9f8a";
+    /// if let Some(icon_address) = pod_manager.get_pod_icon(pod_address)? {
+    ///     println!("Download the icon from {icon_address}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
     ///
-    /// // Add some data to the pod
-    /// let subject_data = r#"{
-    ///     "@context": {"schema": "http://schema.org/"},
-    ///     "@type": "schema:Collection",
-    ///     "schema:name": "My Collection",
-    ///     "schema:description": "A collection of important items"
-    /// }"#;
-    /// pod_manager.put_subject_data(&pod_address, &pod_address, subject_data).await?;
+    /// # Related Functions
     ///
-    /// // Rename the pod to something more descriptive
-    /// pod_manager.rename_pod(&pod_address, "Important Documents Collection").await?;
+    /// - [`set_pod_icon`] - Set the icon of a pod
+    pub fn get_pod_icon(&self, pod_address: &str) -> Result<Option<String>, Error> {
+        Ok(self.graph.get_pod_icon(pod_address.trim())?)
+    }
+
+    /// Sets the display name of the configuration pod, used as the account label.
+    ///
+    /// The configuration pod holds the bookkeeping data for all of the user's pods and is named
+    /// "User Configuration" when created. This function gives it a friendly name that views can show
+    /// as the account label. Only the name changes; the configuration pod stays protected and still
+    /// cannot be removed with [`remove_pod`]. The configuration scratchpads are rewritten and the
+    /// configuration pod is queued for upload, unless configuration sync is deferred with
+    /// [`defer_configuration_sync`].
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The new account name
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success, or an `Error` if:
+    /// - The configuration address cannot be retrieved
+    /// - The graph database update fails
+    /// - Scratchpad operations fail
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// pod_manager.set_account_name("Alice's Library").await?;
+    /// assert_eq!(pod_manager.get_account_name()?, Some("Alice's Library".to_string()));
     ///
-    /// // The pod is now renamed locally, upload the change to the network
+    /// // Upload the new name to the network
     /// pod_manager.upload_all().await?;
-    ///
-    /// // Verify the new name appears in the pod list
-    /// let pods = pod_manager.list_my_pods().await?;
-    /// let renamed_pod = pods.iter().find(|p| p.address == pod_address).unwrap();
-    /// assert_eq!(renamed_pod.name, "Important Documents Collection");
     /// # Ok(())
     /// # }
     /// ```
     ///
     /// # Related Functions
     ///
-    /// - [`add_pod`] - Create a new pod with a name
-    /// - [`remove_pod`] - Remove an existing pod
-    /// - [`list_my_pods`] - List all pods with their current names
+    /// - [`get_account_name`] - Get the current account name
+    /// - [`rename_pod`] - Rename a regular pod
     /// - [`upload_all`] - Upload the name change to the network
-    pub async fn rename_pod(&mut self, pod_address: &str, new_name: &str) -> Result<(), Error> {
-        let pod_address = self.graph.check_pod_exists(pod_address)?;
-        let pod_address = pod_address.trim();
+    pub async fn set_account_name(&mut self, name: &str) -> Result<(), Error> {
+        let configuration_address = self.key_store.get_configuration_address()?;
+        let configuration_address = configuration_address.as_str();
 
-        // Rename the pod in the graph
-        let graph = self.graph.rename_pod_entry(pod_address, new_name)?;
+        // Rename the configuration pod in the graph
+        let configuration = self.graph.rename_pod_entry(configuration_address, name)?;
 
-        // Process the pod data with proper scratchpad management
-        self.process_pod_data(pod_address, graph).await?;
+        // Update the configuration scratchpads
+        self.process_configuration_data(configuration_address, configuration)
+            .await?;
 
         Ok(())
     }
 
+    /// Gets the display name of the configuration pod, used as the account label.
+    ///
+    /// # Returns
+    ///
+    /// Returns the account name, or `None` if the configuration pod has no name, or an `Error` if
+    /// the configuration address cannot be retrieved or the graph database query fails.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # fn example(pod_manager: &PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// if let Some(name) = pod_manager.get_account_name()? {
+    ///     println!("Signed in as {}", name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`set_account_name`] - Set the account name
+    pub fn get_account_name(&self) -> Result<Option<String>, Error> {
+        let configuration_address = self.key_store.get_configuration_address()?;
+        Ok(self.graph.get_pod_name(&configuration_address)?)
+    }
+
     /// Adds a reference from one pod to another pod in the graph database.
     ///
     /// This function creates a semantic link between two pods, allowing for the creation
@@ -1475,7 +5689,7 @@ impl<'a> PodManager<'a> {
         let num_keys = self.key_store.get_num_keys();
         self.graph
             .update_key_count(configuration_address, num_keys)?;
-        self.process_pod_data(configuration_address, configuration)
+        self.process_configuration_data(configuration_address, configuration)
             .await?;
 
         Ok(())
@@ -1551,7 +5765,7 @@ impl<'a> PodManager<'a> {
         let num_keys = self.key_store.get_num_keys();
         self.graph
             .update_key_count(configuration_address, num_keys)?;
-        self.process_pod_data(configuration_address, configuration)
+        self.process_configuration_data(configuration_address, configuration)
             .await?;
 
         Ok(())
@@ -1586,45 +5800,499 @@ impl<'a> PodManager<'a> {
         Ok(pointer_address)
     }
 
-    /// Lists all pods owned by the user.
+    /// Lists all pods owned by the user.
+    ///
+    /// This function retrieves a comprehensive list of all pods that belong to the current user,
+    /// including both locally created pods and pods downloaded from the network. The results
+    /// include pod metadata such as names, addresses, creation information, and reference counts.
+    ///
+    /// # Returns
+    ///
+    /// Returns a JSON object containing SPARQL query results with the following structure:
+    /// - `results.bindings` - Array of pod objects, each containing:
+    ///   - `pod.value` - The pod's Autonomi address
+    ///   - `name.value` - The human-readable pod name
+    ///   - `created.value` - ISO 8601 timestamp of pod creation
+    ///   - Additional metadata fields as available
+    ///
+    /// Returns an `Error` if:
+    /// - The graph database query fails
+    /// - JSON parsing fails
+    /// - Local storage access fails
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// // Get all user pods
+    /// let pods_result = pod_manager.list_my_pods()?;
+    ///
+    /// // Parse the results
+    /// if let Some(bindings) = pods_result["results"]["bindings"].as_array() {
+    ///     println!("Found {} pods:", bindings.len());
+    ///
+    ///     for pod in bindings {
+    ///         let pod_address = pod["pod"]["value"].as_str().unwrap_or("unknown");
+    ///         let pod_name = pod["name"]["value"].as_str().unwrap_or("unnamed");
+    ///         let created = pod["created"]["value"].as_str().unwrap_or("unknown");
+    ///
+    ///         println!("Pod: {} ({})", pod_name, pod_address);
+    ///         println!("Created: {}", created);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`add_pod`] - Create new pods
+    /// - [`list_pod_subjects`] - List subjects within a specific pod
+    /// - [`search`] - Search across all pods
+    /// - [`refresh_cache`] - Update the list with network changes
+    pub fn list_my_pods(&self) -> Result<Value, Error> {
+        let configuration_address = self.key_store.get_configuration_address()?;
+        let search_results = self.graph.get_my_pods(configuration_address.as_str())?;
+
+        // Parse the SPARQL JSON results and return them
+        let results: Value = serde_json::from_str(&search_results)?;
+
+        Ok(results)
+    }
+
+    /// Checks that the current key store derives a known configuration pod address.
+    ///
+    /// When restoring on a new device, users can confirm their seed phrase is correct before
+    /// running a full [`refresh_cache`], by comparing the configuration pod address derived from
+    /// the key store to one they noted down earlier. This is a purely local check, nothing is
+    /// read from the network.
+    ///
+    /// # Parameters
+    ///
+    /// * `expected_config_address` - The configuration pod address the seed phrase should
+    ///   derive, with or without an `ant://` prefix
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if the addresses match and `false` otherwise, or an `Error` if the key store
+    /// cannot derive its configuration address.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let expected = "80e79010a13e7eee779f799d99a20b418436828269b18192d92940bc9ddbfe295a7e1823d7bff75c59cbacbdea101a0d";
+    /// if pod_manager.verify_mnemonic_matches(expected)? {
+    ///     pod_manager.refresh_cache(false).await?;
+    /// } else {
+    ///     println!("This seed phrase belongs to a different account");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`refresh_cache`] - Download the user's pods from the network
+    /// - [`export_directory`] - List the configuration pod address with your pods
+    pub fn verify_mnemonic_matches(&self, expected_config_address: &str) -> Result<bool, Error> {
+        Ok(self
+            .key_store
+            .verify_configuration_address(expected_config_address)?)
+    }
+
+    /// Builds a shareable directory of the pods you publish.
+    ///
+    /// The directory lists the address and name of each of your pods, so others can add pod
+    /// references to them, for example to publish a "follow my pods" page. It is built from
+    /// [`KeyStore::export_public_manifest`] and only holds public pointer addresses: private keys,
+    /// scratchpad addresses, wallet details and the seed phrase are never included. The
+    /// configuration pod is listed separately, because it only holds bookkeeping data. Pods
+    /// hidden with [`set_pod_hidden`] are left out, the same way as in [`list_my_pods`].
+    ///
+    /// # Returns
+    ///
+    /// Returns a JSON object with the following structure, or an `Error` if the hidden pod list
+    /// or a pod name cannot be read:
+    /// - `configuration` - The configuration pod address
+    /// - `pods` - Array of objects, each containing:
+    ///   - `address` - The pod's Autonomi address
+    ///   - `name` - The pod's name, or `null` if it has none
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # fn example(pod_manager: &PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let directory = pod_manager.export_directory()?;
+    /// std::fs::write("my_pods.json", serde_json::to_string_pretty(&directory)?)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`list_my_pods`] - List your pods with their full metadata
+    /// - [`set_pod_hidden`] - Hide a pod from listings
+    /// - [`add_pod_ref`] - Reference a pod from someone else's directory
+    pub fn export_directory(&self) -> Result<Value, Error> {
+        let manifest = self.key_store.export_public_manifest();
+        let hidden_pods: HashSet<String> = self.get_hidden_pods()?.into_iter().collect();
+
+        let mut pods = Vec::new();
+        for pod_address in &manifest.pod_addresses {
+            if hidden_pods.contains(pod_address) {
+                continue;
+            }
+            let name = self.graph.get_pod_name(pod_address)?;
+            pods.push(serde_json::json!({
+                "address": pod_address,
+                "name": name,
+            }));
+        }
+
+        Ok(serde_json::json!({
+            "configuration": manifest.configuration_address,
+            "pods": pods,
+        }))
+    }
+
+    /// Reports how much disk space the local store uses.
+    ///
+    /// Deep [`refresh_ref`] operations can pull in many referenced pods, so the local cache may
+    /// grow well beyond the user's own pods. This function walks the local directories and sums
+    /// the file sizes of the pointer files, scratchpad files, graph database and downloads
+    /// directory. Note that the downloads directory defaults to the user's regular downloads
+    /// folder, so it can include files that were not downloaded through Colony.
+    ///
+    /// # Returns
+    ///
+    /// Returns a [`DiskUsage`] with the bytes used per category and in total, or an `Error` if a
+    /// directory cannot be read.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # fn example(pod_manager: &PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let usage = pod_manager.local_storage_usage()?;
+    /// println!("Graph database: {} MB", usage.graph / 1_000_000);
+    /// println!("Total: {} MB", usage.total / 1_000_000);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`gc_orphan_files`] - Remove local files that no longer belong to any pod
+    /// - [`refresh_ref`] - Download referenced pods into the local cache
+    pub fn local_storage_usage(&self) -> Result<DiskUsage, Error> {
+        Ok(self.data_store.disk_usage()?)
+    }
+
+    /// Reports how many pod files the local store holds and how much space they use.
+    ///
+    /// This is meant for node operators keeping an eye on the disk footprint of their pod cache.
+    /// It counts the local pointer and scratchpad files and sums the size of the scratchpad
+    /// files and the downloads directory. Unlike [`get_update_list`], which only lists the
+    /// changes waiting to be uploaded, this covers everything stored locally.
+    ///
+    /// # Returns
+    ///
+    /// Returns a [`StorageStats`] with the file counts and byte totals, or an `Error` if a
+    /// directory cannot be read.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # fn example(pod_manager: &PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let stats = pod_manager.get_storage_stats()?;
+    /// println!(
+    ///     "{} pointers, {} scratchpads using {} bytes",
+    ///     stats.num_pointers, stats.num_scratchpads, stats.total_scratchpad_bytes
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`local_storage_usage`] - Bytes used by each part of the local store
+    /// - [`get_update_list`] - Changes waiting to be uploaded
+    pub fn get_storage_stats(&self) -> Result<StorageStats, Error> {
+        Ok(self.data_store.storage_stats()?)
+    }
+
+    /// Compacts the graph database on disk.
+    ///
+    /// After many add and remove cycles the graph database keeps the space of deleted data until
+    /// it is compacted. This flushes pending writes and compacts the store, which reclaims disk
+    /// space and can make queries faster. Compaction can take a while on a large graph and holds
+    /// the pod manager for its whole duration, so run it as an occasional maintenance task, for
+    /// example when [`local_storage_usage`] reports a large graph.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` once the store is compacted, or an `Error` if the storage backend fails.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let before = pod_manager.local_storage_usage()?.graph;
+    /// pod_manager.compact_store()?;
+    /// let after = pod_manager.local_storage_usage()?.graph;
+    /// println!("Reclaimed {} bytes", before.saturating_sub(after));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`local_storage_usage`] - Get the disk space used by the local cache
+    /// - [`rebuild_graph_from_local`] - Rebuild a damaged graph from local files
+    pub fn compact_store(&mut self) -> Result<(), Error> {
+        Ok(self.graph.compact()?)
+    }
+
+    /// Reports pods whose stored depth does not match the reference tree.
+    ///
+    /// Depths only ever decrease while refreshing, so after a reference is removed or a complex
+    /// sequence of refreshes, a pod can keep a depth that no longer matches any reference path.
+    /// This recomputes each pod's depth as its shortest reference path from the user's own pods,
+    /// following the references in the locally loaded pod graphs, and lists every pod where the
+    /// stored depth differs. Nothing is changed.
+    ///
+    /// # Returns
+    ///
+    /// Returns the [`DepthAnomaly`] list sorted by pod address, which is empty if all depths are
+    /// consistent, or an `Error` if graph database queries fail.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// for anomaly in pod_manager.audit_depths()? {
+    ///     println!(
+    ///         "{}: stored {:?}, computed {:?}",
+    ///         anomaly.pod_address, anomaly.stored_depth, anomaly.computed_depth
+    ///     );
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`recompute_depths`] - Fix the reported depths
+    /// - [`refresh_ref`] - Download referenced pods and update their depths
+    pub fn audit_depths(&self) -> Result<Vec<DepthAnomaly>, Error> {
+        let configuration_address = self.key_store.get_configuration_address()?;
+        Ok(self.graph.audit_depths(&configuration_address)?)
+    }
+
+    /// Resets the depth of every pod to its shortest reference path from the user's own pods.
+    ///
+    /// Fixes the anomalies reported by [`audit_depths`] in one pass. Pods that no local pod
+    /// leads to keep their stored depth. Depths are local bookkeeping, so nothing is queued for
+    /// upload.
+    ///
+    /// # Returns
+    ///
+    /// Returns the anomalies that were fixed, or an `Error` if graph database operations fail.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let fixed = pod_manager.recompute_depths()?;
+    /// println!("Fixed {} pod depths", fixed.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`audit_depths`] - Report depth anomalies without changing anything
+    pub fn recompute_depths(&mut self) -> Result<Vec<DepthAnomaly>, Error> {
+        let configuration_address = self.key_store.get_configuration_address()?;
+        Ok(self.graph.recompute_depths(&configuration_address)?)
+    }
+
+    /// Lists the name and address of every named pod.
+    ///
+    /// This is a lightweight alternative to [`list_my_pods`] for name autocomplete in a pod
+    /// picker. All names are fetched with a single query and returned as plain pairs instead of
+    /// SPARQL JSON. Both local and referenced pods are included, the configuration pod follows the
+    /// [`set_show_configuration_pod`] setting, and pods without a name are omitted.
+    ///
+    /// # Returns
+    ///
+    /// Returns a list of `(name, address)` pairs sorted by name, or an `Error` if the graph database
+    /// query fails.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # fn example(pod_manager: &PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let typed = "mus";
+    /// let suggestions: Vec<(String, String)> = pod_manager
+    ///     .list_pod_names()?
+    ///     .into_iter()
+    ///     .filter(|(name, _)| name.to_lowercase().starts_with(typed))
+    ///     .collect();
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`list_my_pods`] - List pods with all of their data
+    /// - [`rename_pod`] - Change a pod's name
+    pub fn list_pod_names(&self) -> Result<Vec<(String, String)>, Error> {
+        Ok(self.graph.get_pod_names()?)
+    }
+
+    /// Lists pods ordered by their last modified date, most recent first.
+    ///
+    /// This serves "recent activity" views without fetching every pod through [`list_my_pods`]
+    /// and sorting the SPARQL JSON client side. Both local and referenced pods are included,
+    /// use the `depth` field to tell them apart. Pods without a modified date sort last, and the
+    /// configuration pod follows the [`set_show_configuration_pod`] setting.
+    ///
+    /// # Parameters
+    ///
+    /// * `limit` - Optional maximum number of pods to return
+    ///
+    /// # Returns
+    ///
+    /// Returns a list of [`PodInfo`] with the address, name, dates and depth of each pod,
+    /// or an `Error` if the graph database query fails. The scratchpad and subject counts
+    /// are left at 0, see [`get_pod_info`].
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # fn example(pod_manager: &PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// for pod in pod_manager.list_pods_by_modified(Some(5))? {
+    ///     println!(
+    ///         "{} modified {}",
+    ///         pod.name.as_deref().unwrap_or(&pod.address),
+    ///         pod.modified.as_deref().unwrap_or("never")
+    ///     );
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`list_my_pods`] - List pods with all of their data
+    /// - [`list_pod_names`] - List pod names for autocomplete
+    pub fn list_pods_by_modified(&self, limit: Option<u64>) -> Result<Vec<PodInfo>, Error> {
+        Ok(self.graph.get_pods_by_modified(limit)?)
+    }
+
+    /// Retrieves the summary of a single pod.
     ///
-    /// This function retrieves a comprehensive list of all pods that belong to the current user,
-    /// including both locally created pods and pods downloaded from the network. The results
-    /// include pod metadata such as names, addresses, creation information, and reference counts.
+    /// Collects what a pod details view needs in one call instead of combining depth,
+    /// scratchpad and name lookups: the pod's name, creation and modified dates, depth, icon,
+    /// the number of scratchpads holding its data and the number of subjects it describes.
+    /// The pod itself and its scratchpads don't count as subjects.
+    ///
+    /// # Parameters
+    ///
+    /// * `pod_address` - The address or the name of the pod
     ///
     /// # Returns
     ///
-    /// Returns a JSON object containing SPARQL query results with the following structure:
-    /// - `results.bindings` - Array of pod objects, each containing:
-    ///   - `pod.value` - The pod's Autonomi address
-    ///   - `name.value` - The human-readable pod name
-    ///   - `created.value` - ISO 8601 timestamp of pod creation
-    ///   - Additional metadata fields as available
+    /// Returns the [`PodInfo`] of the pod, or an `Error` if the pod doesn't exist or the graph
+    /// database query fails.
     ///
-    /// Returns an `Error` if:
-    /// - The graph database query fails
-    /// - JSON parsing fails
-    /// - Local storage access fails
+    /// # Example
+    ///
+    /// ```ignore
+    /// # fn example(pod_manager: &PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let info = pod_manager.get_pod_info("My Music Collection")?;
+    /// println!(
+    ///     "{} holds {} subjects in {} scratchpads",
+    ///     info.name.as_deref().unwrap_or(&info.address),
+    ///     info.subject_count.unwrap_or(0),
+    ///     info.scratchpad_count.unwrap_or(0)
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`list_pods_by_modified`] - List the summaries of all pods
+    /// - [`get_pod_scratchpads`] - List the scratchpads of a pod
+    pub fn get_pod_info(&self, pod_address: &str) -> Result<PodInfo, Error> {
+        let pod_address = self.graph.check_pod_exists(pod_address)?;
+        Ok(self.graph.get_pod_info(pod_address.trim())?)
+    }
+
+    /// Creates a read-only handle to the graph database that can be shared across tasks.
+    ///
+    /// `PodManager` holds the graph mutably, so its own search methods can't run while another
+    /// operation such as [`refresh_ref`] is in progress. A [`GraphReader`] is `Clone + Send + Sync`
+    /// and can serve search queries from other tasks in the meantime. Each query sees a
+    /// consistent snapshot of the graph, so readers never observe a partially loaded pod.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `GraphReader` sharing the same underlying store.
     ///
     /// # Example
     ///
     /// ```ignore
     /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
-    /// // Get all user pods
-    /// let pods_result = pod_manager.list_my_pods()?;
+    /// let reader = pod_manager.graph_reader();
+    /// let search = tokio::task::spawn_blocking(move || reader.search_content("music", Some(10), None));
     ///
-    /// // Parse the results
-    /// if let Some(bindings) = pods_result["results"]["bindings"].as_array() {
-    ///     println!("Found {} pods:", bindings.len());
+    /// // Refresh in the meantime, the search is not blocked
+    /// pod_manager.refresh_ref(3).await?;
+    /// let results = search.await??;
+    /// # Ok(())
+    /// # }
+    /// ```
     ///
-    ///     for pod in bindings {
-    ///         let pod_address = pod["pod"]["value"].as_str().unwrap_or("unknown");
-    ///         let pod_name = pod["name"]["value"].as_str().unwrap_or("unnamed");
-    ///         let created = pod["created"]["value"].as_str().unwrap_or("unknown");
+    /// # Related Functions
     ///
-    ///         println!("Pod: {} ({})", pod_name, pod_address);
-    ///         println!("Created: {}", created);
-    ///     }
+    /// - [`search`] - Search through the pod manager
+    /// - [`refresh_ref`] - Update the graph from the network
+    pub fn graph_reader(&self) -> GraphReader {
+        self.graph.reader()
+    }
+
+    /// Lists the subjects of a pod that changed since a given version of the pod.
+    ///
+    /// The version of a pod is the counter of its pointer. Each time a newer version of a pod
+    /// is downloaded by [`refresh_cache`] or [`refresh_ref`], the data of every subject is
+    /// compared against the previously loaded version, and the subjects that were added,
+    /// removed, or modified are recorded with the new version. Sync clients can remember the
+    /// version they last rendered and update only the subjects returned here instead of
+    /// re-rendering the whole pod.
+    ///
+    /// # Parameters
+    ///
+    /// * `pod_address` - The hexadecimal address of the pod
+    /// * `since_version` - The last pointer counter the caller has seen, `0` for all changes
+    ///
+    /// # Returns
+    ///
+    /// Returns the sorted addresses of the subjects that changed in a newer version, or an
+    /// `Error` if the pod address is invalid or the local change record cannot be read.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let pod_address = "80e79010a10d4b5b3c6b3e5b4c0e3c4c1b6d5f3e3c9c6b8e5f4c3b2a1d0e9f8a";
+    /// let seen_version = 12;
+    ///
+    /// pod_manager.refresh_ref(1).await?;
+    /// for subject in pod_manager.subjects_changed_since(pod_address, seen_version)? {
+    ///     println!("Re-render {subject}");
     /// }
     /// # Ok(())
     /// # }
@@ -1632,18 +6300,71 @@ impl<'a> PodManager<'a> {
     ///
     /// # Related Functions
     ///
-    /// - [`add_pod`] - Create new pods
-    /// - [`list_pod_subjects`] - List subjects within a specific pod
-    /// - [`search`] - Search across all pods
-    /// - [`refresh_cache`] - Update the list with network changes
-    pub fn list_my_pods(&self) -> Result<Value, Error> {
-        let configuration_address = self.key_store.get_configuration_address()?;
-        let search_results = self.graph.get_my_pods(configuration_address.as_str())?;
-
-        // Parse the SPARQL JSON results and return them
-        let results: Value = serde_json::from_str(&search_results)?;
+    /// - [`refresh_ref`] - Download newer versions of referenced pods
+    /// - [`list_pod_subjects`] - List every subject in a pod
+    pub fn subjects_changed_since(
+        &mut self,
+        pod_address: &str,
+        since_version: u64,
+    ) -> Result<Vec<String>, Error> {
+        let pod_address = PointerAddress::from_hex(pod_address.trim())?.to_hex();
+        Ok(self
+            .data_store
+            .get_subjects_changed_since(&pod_address, since_version)?)
+    }
 
-        Ok(results)
+    /// Lists the subjects modified within a time window, for activity feeds and incremental
+    /// exports.
+    ///
+    /// Each time the data of a subject is set with [`put_subject_data`],
+    /// [`merge_subject_data`] or [`import_csv_into_pod`], the subject gets a modified date in the
+    /// pod. This returns the subjects whose modified date falls within the window, newest first.
+    /// Subjects in pods written by an older version of colonylib have no modified date and are
+    /// never returned.
+    ///
+    /// # Parameters
+    ///
+    /// * `start` - Start of the window, inclusive
+    /// * `end` - End of the window, exclusive
+    /// * `scope` - Which pods to look in: all pods, only the user's own pods, or a single pod
+    ///
+    /// # Returns
+    ///
+    /// Returns the addresses of the subjects modified within the window, most recently modified
+    /// first, or an `Error` if the graph database query fails.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # fn example(pod_manager: &PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// use colonylib::graph::SearchScope;
+    ///
+    /// let end = chrono::Utc::now();
+    /// let start = end - chrono::Duration::days(7);
+    /// for subject in pod_manager.subjects_modified_between(start, end, SearchScope::Local)? {
+    ///     println!("Changed this week: {subject}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`subjects_changed_since`] - Subjects changed since a pod version
+    /// - [`list_pods_by_modified`] - Pods ordered by their modified date
+    pub fn subjects_modified_between(
+        &self,
+        start: chrono::DateTime<chrono::Utc>,
+        end: chrono::DateTime<chrono::Utc>,
+        scope: SearchScope,
+    ) -> Result<Vec<String>, Error> {
+        let scope = match scope {
+            SearchScope::Pod(pod) => {
+                SearchScope::Pod(self.graph.check_pod_exists(&pod)?.trim().to_string())
+            }
+            scope => scope,
+        };
+        Ok(self.graph.subjects_modified_between(&start, &end, &scope)?)
     }
 
     /// Lists all subjects (resources) contained within a specific pod.
@@ -1713,6 +6434,352 @@ impl<'a> PodManager<'a> {
         Ok(subjects)
     }
 
+    /// Finds subjects that are missing a given predicate, producing a data quality report.
+    ///
+    /// This function locates incomplete catalog entries, for example subjects that lack a
+    /// `http://schema.org/name`. Internal pod bookkeeping subjects (pods, scratchpads and pod
+    /// references) are never reported. The search can span every pod in the graph database
+    /// or be restricted to a single pod.
+    ///
+    /// # Parameters
+    ///
+    /// * `predicate_uri` - The full URI of the predicate every subject is expected to have
+    /// * `within_pod` - Optional pod address or pod name to restrict the report to
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of subject addresses (as hex strings) missing the predicate, or an `Error` if:
+    /// - The predicate URI is not a valid IRI
+    /// - The given pod does not exist locally
+    /// - The graph database query fails
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// // Find every subject across all pods without a name
+    /// let unnamed = pod_manager.find_subjects_missing("http://schema.org/name", None)?;
+    /// println!("{} subjects have no name", unnamed.len());
+    ///
+    /// // Restrict the report to a single pod
+    /// let unnamed_in_pod = pod_manager.find_subjects_missing("http://schema.org/name", Some("My Documents"))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`list_pod_subjects`] - List all subjects within a specific pod
+    /// - [`put_subject_data`] - Fill in the missing metadata for a subject
+    pub fn find_subjects_missing(
+        &self,
+        predicate_uri: &str,
+        within_pod: Option<&str>,
+    ) -> Result<Vec<String>, Error> {
+        let pod_address = match within_pod {
+            Some(pod) => Some(self.graph.check_pod_exists(pod)?.trim().to_string()),
+            None => None,
+        };
+        let subjects = self
+            .graph
+            .find_subjects_missing(predicate_uri, pod_address.as_deref())?;
+        Ok(subjects)
+    }
+
+    /// Finds stub subjects that have no user metadata.
+    ///
+    /// Failed or partial edits can leave subjects in a pod with nothing but an RDF type or other
+    /// colonylib bookkeeping triples (index, depth, key count and dates). This reports those
+    /// subjects so they can be filled in with [`put_subject_data`] or removed. Tags count as user
+    /// metadata. Internal pod bookkeeping subjects (pods, scratchpads and pod references) are never
+    /// reported. The search can span every pod in the graph database or be restricted to a single
+    /// pod, in which case only the metadata within that pod is considered.
+    ///
+    /// # Parameters
+    ///
+    /// * `within_pod` - Optional pod address or pod name to restrict the report to
+    ///
+    /// # Returns
+    ///
+    /// Returns a sorted vector of subject addresses (as hex strings) without user metadata, or an
+    /// `Error` if:
+    /// - The given pod does not exist locally
+    /// - The graph database query fails
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// for subject in pod_manager.find_empty_subjects(Some("My Documents"))? {
+    ///     println!("Stub entry: {}", subject);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`find_subjects_missing`] - Find subjects missing a specific predicate
+    /// - [`put_subject_data`] - Fill in the metadata for a subject
+    pub fn find_empty_subjects(&self, within_pod: Option<&str>) -> Result<Vec<String>, Error> {
+        let pod_address = match within_pod {
+            Some(pod) => Some(self.graph.check_pod_exists(pod)?.trim().to_string()),
+            None => None,
+        };
+        Ok(self.graph.find_empty_subjects(pod_address.as_deref())?)
+    }
+
+    /// Compares the scratchpads of a pod recorded in the graph, on disk and in the key store.
+    ///
+    /// Interrupted operations can leave the scratchpads listed in a pod's graph out of step with
+    /// the scratchpad files on disk and the keys in the key store. This reports where each
+    /// scratchpad is recorded, to pinpoint which part is out of sync. The scratchpads on disk are
+    /// found by following the pod pointer file to its first scratchpad and reading the scratchpad
+    /// list stored there. Key store checks only apply to the user's own pods, since the keys of
+    /// referenced pods belong to someone else.
+    ///
+    /// # Parameters
+    ///
+    /// * `pod_address` - The hexadecimal address or name of the pod to check
+    ///
+    /// # Returns
+    ///
+    /// Returns a [`ScratchpadDiff`] listing the scratchpads by where they are recorded, or an
+    /// `Error` if:
+    /// - The pod does not exist locally
+    /// - The graph database query fails
+    /// - The local files cannot be read
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let diff = pod_manager.diff_pod_scratchpads("My Documents")?;
+    /// if !diff.in_graph_only.is_empty() {
+    ///     println!("Missing local files: {:?}", diff.in_graph_only);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`get_pod_scratchpads`] - Get the scratchpads of a pod
+    /// - [`gc_orphan_files`] - Remove local files that don't belong to any pod
+    pub fn diff_pod_scratchpads(&self, pod_address: &str) -> Result<ScratchpadDiff, Error> {
+        let pod_address = self.graph.check_pod_exists(pod_address)?;
+        let pod_address = pod_address.trim();
+
+        let in_graph: HashSet<String> = self
+            .graph
+            .get_pod_scratchpads(pod_address)?
+            .into_iter()
+            .collect();
+
+        // Follow the pointer file to the first scratchpad, which lists the rest of them
+        let mut on_disk: HashSet<String> = HashSet::new();
+        if let Ok(target) = self.data_store.get_pointer_target(pod_address) {
+            let target = target.trim();
+            if !target.is_empty() && self.data_store.address_is_scratchpad(target)? {
+                on_disk.insert(target.to_string());
+                let data = self.data_store.get_scratchpad_data(target)?;
+                for scratchpad in self.graph.get_pod_scratchpads_from_string(&data)? {
+                    if self.data_store.address_is_scratchpad(&scratchpad)? {
+                        on_disk.insert(scratchpad);
+                    }
+                }
+            }
+        }
+
+        let is_own_pod = self.key_store.get_pointers().contains_key(pod_address);
+        let keys: HashSet<String> = self.key_store.get_scratchpads().into_keys().collect();
+
+        fn sorted<'b>(addresses: impl Iterator<Item = &'b String>) -> Vec<String> {
+            let mut addresses: Vec<String> = addresses.cloned().collect();
+            addresses.sort();
+            addresses
+        }
+
+        let mut diff = ScratchpadDiff {
+            in_graph_only: sorted(in_graph.difference(&on_disk)),
+            on_disk_only: sorted(on_disk.difference(&in_graph)),
+            ..Default::default()
+        };
+        if is_own_pod {
+            let recorded: HashSet<String> = in_graph.union(&on_disk).cloned().collect();
+            diff.missing_key = sorted(recorded.iter().filter(|a| !keys.contains(*a)));
+            diff.in_all = sorted(
+                in_graph
+                    .intersection(&on_disk)
+                    .filter(|a| keys.contains(*a)),
+            );
+
+            // Keys that aren't the scratchpad of any pod or a local file
+            let known_addresses = self.get_known_addresses(false)?;
+            diff.in_keystore_only = sorted(
+                keys.iter()
+                    .filter(|a| !recorded.contains(*a) && !known_addresses.contains(*a)),
+            );
+        } else {
+            diff.in_all = sorted(in_graph.intersection(&on_disk));
+        }
+
+        Ok(diff)
+    }
+
+    /// Finds and optionally deletes local pointer and scratchpad files not referenced by any pod.
+    ///
+    /// After many add/remove cycles, failed operations or crashes, the local data store can
+    /// accumulate pointer and scratchpad files that no longer belong to any pod. This function
+    /// builds the set of known addresses from the graph database, the key store, and the pending
+    /// update and removal lists, and reports every file in the pointers and scratchpads
+    /// directories that is not part of that set. The configuration pod files are always known.
+    ///
+    /// With `scratchpads_only` set the cleanup is stricter: pointer files are left alone, and a
+    /// key in the key store doesn't protect a scratchpad file. Use it when the key store holds
+    /// keys for scratchpads that were never attached to a pod.
+    ///
+    /// # Parameters
+    ///
+    /// * `dry_run` - If `true`, only report the orphaned files without deleting them
+    /// * `scratchpads_only` - Only collect scratchpad files no pod in the graph database refers to
+    ///
+    /// # Returns
+    ///
+    /// Returns the paths of the orphaned files (deleted unless `dry_run` is set), or an `Error` if:
+    /// - The graph database query fails
+    /// - The local data directories cannot be read
+    /// - A file cannot be deleted
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// // See what would be removed first
+    /// let orphans = pod_manager.gc_orphan_files(true, false)?;
+    /// println!("{} orphaned files found", orphans.len());
+    ///
+    /// // Then reclaim the disk space
+    /// pod_manager.gc_orphan_files(false, false)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`remove_pod`] - Remove a pod and its files
+    /// - [`refresh_cache`] - Rebuild the local cache from the network
+    pub fn gc_orphan_files(
+        &mut self,
+        dry_run: bool,
+        scratchpads_only: bool,
+    ) -> Result<Vec<PathBuf>, Error> {
+        let known_addresses = self.get_known_addresses(!scratchpads_only)?;
+        let mut orphans = self.data_store.find_orphan_files(&known_addresses)?;
+        let pointers_dir = self.data_store.get_pointers_dir();
+        if scratchpads_only {
+            orphans.retain(|path| path.parent() != Some(pointers_dir.as_path()));
+        }
+
+        if !dry_run {
+            for path in &orphans {
+                let Some(address) = path.file_name().and_then(|name| name.to_str()) else {
+                    continue;
+                };
+                // Go through the data store so a scratchpad's checksum is removed with it
+                if path.parent() == Some(pointers_dir.as_path()) {
+                    self.data_store.remove_pointer_file(address)?;
+                } else {
+                    self.data_store.remove_scratchpad_file(address)?;
+                }
+                info!("Removed orphaned file: {:?}", path);
+            }
+        }
+
+        Ok(orphans)
+    }
+
+    /// Finds local scratchpad files that were corrupted on disk.
+    ///
+    /// Every time a scratchpad file is written its checksum is recorded next to it. This function
+    /// checks each local scratchpad file against its checksum and reports the ones that no longer
+    /// match, so they can be fetched from the network again with [`refresh_cache`]. Files written
+    /// before checksums were recorded are not reported.
+    ///
+    /// # Returns
+    ///
+    /// Returns the addresses of the scratchpads that failed verification, or an `Error` if the
+    /// local data directories cannot be read.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let corrupted = pod_manager.verify_local_cache()?;
+    /// if !corrupted.is_empty() {
+    ///     println!("{} corrupted scratchpads, refreshing", corrupted.len());
+    ///     pod_manager.refresh_cache(false).await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`refresh_cache`] - Download the user's pods from the network again
+    /// - [`gc_orphan_files`] - Delete files that don't belong to any pod
+    pub fn verify_local_cache(&self) -> Result<Vec<String>, Error> {
+        let mut corrupted = Vec::new();
+        for address in self.data_store.list_scratchpad_files()? {
+            if !self.data_store.verify_scratchpad(&address)? {
+                corrupted.push(address);
+            }
+        }
+        info!(
+            "Verified local cache, {} corrupted scratchpads",
+            corrupted.len()
+        );
+        Ok(corrupted)
+    }
+
+    // Collect every pointer and scratchpad address that belongs to a known pod, optionally
+    // counting every key in the key store as known. The configuration scratchpad, free
+    // scratchpads and pending removals are always known.
+    fn get_known_addresses(&self, include_keys: bool) -> Result<HashSet<String>, Error> {
+        let configuration_address = self.key_store.get_configuration_address()?;
+        let mut known_addresses: HashSet<String> = HashSet::new();
+        known_addresses.insert(configuration_address.clone());
+        known_addresses.insert(self.key_store.get_configuration_scratchpad_address()?);
+        known_addresses.extend(self.graph.get_free_scratchpads(&configuration_address)?);
+        known_addresses.extend(self.data_store.get_pending_removals()?.scratchpads);
+        if include_keys {
+            known_addresses.extend(self.key_store.get_pointers().into_keys());
+            known_addresses.extend(self.key_store.get_scratchpads().into_keys());
+        }
+        known_addresses.extend(self.graph.get_pointers(&configuration_address)?);
+        known_addresses.extend(self.graph.get_scratchpads(&configuration_address)?);
+
+        // Local and referenced pods along with their scratchpads
+        for pod_address in self.graph.get_all_pods()? {
+            known_addresses.extend(self.graph.get_pod_scratchpads(&pod_address)?);
+            if let Ok(target) = self.data_store.get_pointer_target(&pod_address)
+                && !target.trim().is_empty()
+            {
+                known_addresses.insert(target.trim().to_string());
+            }
+            known_addresses.insert(pod_address);
+        }
+
+        // Pods and scratchpads waiting to be uploaded
+        let update_list = self.data_store.get_update_list()?;
+        for (pod_address, scratchpads) in update_list.pods {
+            known_addresses.insert(pod_address);
+            known_addresses.extend(scratchpads);
+        }
+
+        Ok(known_addresses)
+    }
+
     ///////////////////////////////////////////
     // Autonomi network operations
     ///////////////////////////////////////////
@@ -1777,11 +6844,12 @@ impl<'a> PodManager<'a> {
     ///
     /// # Process
     ///
-    /// 1. Reads the update list containing addresses that need uploading
-    /// 2. For each address, determines if it's a pointer or scratchpad
-    /// 3. Checks if the address exists on the network (create vs update)
-    /// 4. Performs the appropriate network operation
-    /// 5. Clears the update list upon successful completion
+    /// 1. Writes a deferred configuration pod (see [`defer_configuration_sync`])
+    /// 2. Reads the update list containing addresses that need uploading
+    /// 3. For each address, determines if it's a pointer or scratchpad
+    /// 4. Checks if the address exists on the network (create vs update)
+    /// 5. Performs the appropriate network operation
+    /// 6. Clears the update list upon successful completion
     ///
     /// # Returns
     ///
@@ -1797,7 +6865,8 @@ impl<'a> PodManager<'a> {
     /// - Creating new pointers and scratchpads (used to construct pods)
     /// - Adding data to an existing pod that causes a new scratchpad to be required (each scratchpad's max size is 4MB)
     ///
-    /// Costs are automatically paid using the configured wallet. Updates to existing pod components are free.
+    /// Costs are automatically paid using the configured wallet, or the payment option set with
+    /// `set_payment_override()`. Updates to existing pod components are free.
     ///
     /// # Example
     ///
@@ -1828,16 +6897,421 @@ impl<'a> PodManager<'a> {
     /// - [`add_pod`] - Creates pods that need uploading
     /// - [`put_subject_data`] - Modifies pods that need uploading
     /// - [`refresh_cache`] - Downloads updates from the network
+    /// - [`upload_all_report`] - Upload and report what was created, updated and paid for
     pub async fn upload_all(&mut self) -> Result<(), Error> {
+        self.upload_all_report().await?;
+        Ok(())
+    }
+
+    /// Uploads all pending changes like [`upload_all`] and reports what was done.
+    ///
+    /// Every pointer and scratchpad operation that completes is counted as a create, an update
+    /// or a removal, and the cost paid for each one is added to the total. This lets callers
+    /// show how many network objects were paid for after an upload.
+    ///
+    /// # Returns
+    ///
+    /// Returns an [`UploadReport`] on successful upload of all items, or an `Error` in the same
+    /// cases as [`upload_all`].
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let report = pod_manager.upload_all_report().await?;
+    /// println!("Created {} pointers and {} scratchpads for {} atto tokens",
+    ///          report.created_pointers, report.created_scratchpads, report.total_cost);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`upload_all`] - Upload all pending changes without a report
+    /// - [`upload_pod_report`] - Upload a single pod and report what was done
+    /// - [`estimate_upload_cost`] - Count the new network objects before uploading
+    pub async fn upload_all_report(&mut self) -> Result<UploadReport, Error> {
+        // Write out any deferred configuration changes so they are part of the upload
+        self.sync_configuration().await?;
+
+        // Phase 1: Preprocess all operations and collect data
+        let (removal_operations, upload_operations, pod_uploads) =
+            self.prepare_upload_all_operations()?;
+
+        // Phase 2: Execute all operations with maximum concurrency - removals and uploads simultaneously
+        info!(
+            "Executing {} removal operations and {} upload operations concurrently",
+            removal_operations.len(),
+            upload_operations.len()
+        );
+
+        let report = self
+            .execute_all_operations_concurrent(removal_operations, upload_operations, &pod_uploads)
+            .await?;
+
+        // Clear out the update list
+        self.data_store.clear_update_list()?;
+        Ok(report)
+    }
+
+    /// Queries the EVM network for the funds in the wallet used to pay for uploads.
+    ///
+    /// Creating pointers and scratchpads costs tokens, and every payment also needs gas. Checking
+    /// both balances before calling [`upload_all`] lets an application warn the user about
+    /// insufficient funds instead of failing partway through an upload.
+    ///
+    /// # Returns
+    ///
+    /// Returns a [`WalletBalance`] with the token and gas balances as decimal strings, or an
+    /// `Error` if the EVM network cannot be reached.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let balance = pod_manager.get_wallet_balance().await?;
+    /// if balance.token_balance == "0" || balance.gas_balance == "0" {
+    ///     println!("Add funds to your wallet before uploading");
+    /// } else {
+    ///     pod_manager.upload_all().await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`estimate_upload_cost`] - Find out what an upload would pay for
+    /// - [`upload_all`] - Upload the pending changes
+    pub async fn get_wallet_balance(&self) -> Result<WalletBalance, Error> {
+        let token_balance = self
+            .wallet
+            .balance_of_tokens()
+            .await
+            .map_err(|e| Error::Pod(format!("Failed to get wallet token balance: {e}")))?;
+        let gas_balance = self
+            .wallet
+            .balance_of_gas_tokens()
+            .await
+            .map_err(|e| Error::Pod(format!("Failed to get wallet gas balance: {e}")))?;
+
+        let balance = WalletBalance {
+            token_balance: token_balance.to_string(),
+            gas_balance: gas_balance.to_string(),
+        };
+        info!(
+            "Wallet {} balance: {} tokens, {} gas",
+            self.wallet.address(),
+            balance.token_balance,
+            balance.gas_balance
+        );
+        Ok(balance)
+    }
+
+    /// Estimates what [`upload_all`] would pay for, without uploading anything.
+    ///
+    /// Only creating pointers and scratchpads costs anything on the network, updates to existing
+    /// ones are free. This function reads the update list and checks the network for each
+    /// pointer and scratchpad that would be uploaded, the same way [`upload_all`] decides
+    /// between creating and updating, but never writes to the network. Addresses derived
+    /// locally that were never uploaded are counted as new without a network check.
+    ///
+    /// # Returns
+    ///
+    /// Returns a JSON object with the following fields, or an `Error` if the update list or the
+    /// local pod files cannot be read:
+    /// - `new_pointers` - Pointers that would be created and paid for
+    /// - `new_scratchpads` - Scratchpads that would be created and paid for
+    /// - `updated_pointers` - Existing pointers that would be updated for free
+    /// - `updated_scratchpads` - Existing scratchpads that would be updated for free
+    /// - `removals` - Pointers and scratchpads queued for removal, which are free updates
+    /// - `bytes` - Total size of the scratchpad data that would be written
+    /// - `configuration_pending` - True if a deferred configuration pod update is not in the
+    ///   update list yet. [`upload_all`] syncs it first, so it is uploaded as well
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let estimate = pod_manager.estimate_upload_cost().await?;
+    /// let new_items = estimate["new_pointers"].as_u64().unwrap_or(0)
+    ///     + estimate["new_scratchpads"].as_u64().unwrap_or(0);
+    /// if new_items == 0 {
+    ///     // Only free updates, upload right away
+    ///     pod_manager.upload_all().await?;
+    /// } else {
+    ///     println!("Upload will create {new_items} new network objects");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`upload_all`] - Upload the pending changes
+    /// - [`get_update_list`] - List the pending changes
+    pub async fn estimate_upload_cost(&self) -> Result<Value, Error> {
+        let plan = self.plan_upload_operations().await?;
+
+        let mut new_pointers = 0;
+        let mut new_scratchpads = 0;
+        let mut updated_pointers = 0;
+        let mut updated_scratchpads = 0;
+        let mut removals = 0;
+        let mut bytes = 0;
+        for (kind, action, _address, size) in &plan {
+            match (kind, action) {
+                (_, OperationAction::Remove) => removals += 1,
+                (OperationKind::Pointer, OperationAction::Create) => new_pointers += 1,
+                (OperationKind::Pointer, OperationAction::Update) => updated_pointers += 1,
+                (OperationKind::Scratchpad, OperationAction::Create) => new_scratchpads += 1,
+                (OperationKind::Scratchpad, OperationAction::Update) => updated_scratchpads += 1,
+            }
+            bytes += size;
+        }
+
+        info!(
+            "Upload estimate: {} new pointers, {} new scratchpads, {} bytes",
+            new_pointers, new_scratchpads, bytes
+        );
+        Ok(serde_json::json!({
+            "new_pointers": new_pointers,
+            "new_scratchpads": new_scratchpads,
+            "updated_pointers": updated_pointers,
+            "updated_scratchpads": updated_scratchpads,
+            "removals": removals,
+            "bytes": bytes,
+            "configuration_pending": self.data_store.configuration_pending(),
+        }))
+    }
+
+    /// Lists every pointer and scratchpad operation [`upload_all`] would perform, without
+    /// performing any of them.
+    ///
+    /// This runs the same preprocessing as [`upload_all`], collecting the removals and uploads
+    /// for everything in the update list, and checks the network to label each upload as a
+    /// create or an update. Nothing is written to the network and the update list is left
+    /// as it is, so the same changes can be uploaded afterwards. A deferred configuration pod
+    /// update is not synced, it is reported with the `configuration_pending` field instead.
+    ///
+    /// # Returns
+    ///
+    /// Returns a JSON object, or an `Error` if the update list or the local pod files cannot be
+    /// read. The object has the following fields:
+    /// - `operations` - Array of planned operations, removals first, each with:
+    ///   - `address` - Address of the pointer or scratchpad
+    ///   - `kind` - `"pointer"` or `"scratchpad"`
+    ///   - `action` - `"create"`, `"update"` or `"remove"`
+    ///   - `size` - Size in bytes of the scratchpad data that would be written, 0 for pointers
+    ///     and removals
+    /// - `configuration_pending` - True if a deferred configuration pod update would be synced
+    ///   and uploaded by [`upload_all`] as well
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let plan = pod_manager.upload_all_dry_run().await?;
+    /// for operation in plan["operations"].as_array().unwrap() {
+    ///     println!("{} {} {} ({} bytes)",
+    ///              operation["action"], operation["kind"], operation["address"], operation["size"]);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`upload_all`] - Perform the planned operations
+    /// - [`estimate_upload_cost`] - Count the new network objects that would be paid for
+    pub async fn upload_all_dry_run(&mut self) -> Result<Value, Error> {
+        let plan = self.plan_upload_operations().await?;
+        info!("Dry run planned {} upload operations", plan.len());
+
+        let operations: Vec<Value> = plan
+            .into_iter()
+            .map(|(kind, action, address, size)| {
+                let kind = match kind {
+                    OperationKind::Pointer => "pointer",
+                    OperationKind::Scratchpad => "scratchpad",
+                };
+                let action = match action {
+                    OperationAction::Create => "create",
+                    OperationAction::Update => "update",
+                    OperationAction::Remove => "remove",
+                };
+                serde_json::json!({
+                    "address": address,
+                    "kind": kind,
+                    "action": action,
+                    "size": size,
+                })
+            })
+            .collect();
+
+        Ok(serde_json::json!({
+            "operations": operations,
+            "configuration_pending": self.data_store.configuration_pending(),
+        }))
+    }
+
+    /// Uploads all pending changes and streams the outcome of each network operation.
+    ///
+    /// This performs the same work as [`upload_all`], but instead of waiting for every operation
+    /// to finish it returns a stream that yields one item per pointer or scratchpad operation as
+    /// soon as it completes. Each [`OperationOutcome`] carries the address, the kind of object,
+    /// whether it was created, updated or removed, and the cost paid. Failed operations are
+    /// yielded as errors while the remaining operations keep running, so callers can handle
+    /// partial failures.
+    ///
+    /// The update list is cleared once the stream is exhausted, but only if every operation
+    /// succeeded. If any operation failed, the update list is kept so the upload can be retried.
+    ///
+    /// Unlike [`upload_all`], a deferred configuration pod is not synced automatically. Call
+    /// [`sync_configuration`] first to include it in the upload.
+    ///
+    /// # Returns
+    ///
+    /// Returns a stream of `Result<OperationOutcome, Error>`. Errors preparing the upload (for
+    /// example a missing local pointer file) are yielded as the only item of the stream.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// use futures::StreamExt;
+    ///
+    /// let mut uploads = pod_manager.upload_all_stream();
+    /// while let Some(result) = uploads.next().await {
+    ///     match result {
+    ///         Ok(outcome) => println!("{:?} {:?} {} cost {:?}",
+    ///                                 outcome.action, outcome.kind, outcome.address, outcome.cost),
+    ///         Err(e) => eprintln!("Operation failed: {}", e),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`upload_all`] - Upload all pending changes and wait for completion
+    /// - [`upload_pod`] - Upload a single pod
+    pub fn upload_all_stream(
+        &mut self,
+    ) -> impl futures::Stream<Item = Result<OperationOutcome, Error>> + '_ {
+        if self.defer_configuration && self.data_store.configuration_pending() {
+            warn!("Configuration pod has deferred changes that are not part of this upload");
+        }
+
+        let this: &Self = self;
+        let (operations, pointer_updates, pod_uploads, pending_error) =
+            match this.prepare_upload_all_operations() {
+                Ok((removal_operations, upload_operations, pod_uploads)) => {
+                    info!(
+                        "Streaming {} removal operations and {} upload operations",
+                        removal_operations.len(),
+                        upload_operations.len()
+                    );
+                    let (all_futures, pointer_updates) =
+                        this.build_operation_futures(removal_operations, upload_operations);
+                    (
+                        futures::stream::iter(all_futures).buffer_unordered(this.max_concurrency),
+                        pointer_updates,
+                        pod_uploads,
+                        None,
+                    )
+                }
+                Err(e) => (
+                    futures::stream::iter(Vec::new()).buffer_unordered(this.max_concurrency),
+                    HashMap::new(),
+                    HashMap::new(),
+                    Some(e),
+                ),
+            };
+        let data_store: &DataStore = &*this.data_store;
+        let operation_handle = this.operation_handle.clone();
+
+        let state = UploadStreamState {
+            operations,
+            pointer_updates,
+            pod_uploads,
+            completed: HashSet::new(),
+            pending_error,
+            failed: false,
+        };
+        futures::stream::unfold(Some(state), move |state| {
+            let operation_handle = operation_handle.clone();
+            async move {
+                let mut state = state?;
+                if let Some(e) = state.pending_error.take() {
+                    return Some((Err(e), None));
+                }
+
+                match state.operations.next().await {
+                    Some(Ok(outcome)) => {
+                        // Record the new pointer counter as soon as the pointer is uploaded
+                        if outcome.kind == OperationKind::Pointer
+                            && outcome.action != OperationAction::Remove
+                            && let Some(counter) = state.pointer_updates.get(&outcome.address)
+                            && let Err(e) =
+                                data_store.update_pointer_count(&outcome.address, *counter)
+                        {
+                            warn!(
+                                "Failed to update pointer count for {}: {}",
+                                outcome.address, e
+                            );
+                        }
+                        state.completed.insert(outcome.address.clone());
+                        Some((Ok(outcome), Some(state)))
+                    }
+                    Some(Err(e)) => {
+                        state.failed = true;
+                        Some((Err(e), Some(state)))
+                    }
+                    None => {
+                        operation_handle.reset();
+                        // Clear the update list once everything made it to the network,
+                        // otherwise keep the entries that still need uploading
+                        let result = if state.failed {
+                            let finished =
+                                Self::finished_update_entries(&state.pod_uploads, &state.completed);
+                            data_store.remove_from_update_list(&finished)
+                        } else {
+                            data_store.clear_update_list()
+                        };
+                        if let Err(e) = result {
+                            return Some((Err(e.into()), None));
+                        }
+                        None
+                    }
+                }
+            }
+        })
+    }
+
+    // Collect the removal and upload operations for everything in the update list,
+    // along with the addresses each pod entry of the update list uploads
+    #[allow(clippy::type_complexity)]
+    fn prepare_upload_all_operations(
+        &self,
+    ) -> Result<
+        (
+            Vec<(String, String, String)>,
+            Vec<(String, String, String)>,
+            HashMap<String, Vec<String>>,
+        ),
+        Error,
+    > {
         let update_list = self.data_store.get_update_list()?;
         info!(
             "Starting upload_all with {} pods to upload",
             update_list.pods.len()
         );
 
-        // Phase 1: Preprocess all operations and collect data
         let mut removal_operations = Vec::new();
         let mut upload_operations = Vec::new();
+        let mut pod_uploads: HashMap<String, Vec<String>> = HashMap::new();
 
         // Preprocess removals
         info!(
@@ -1864,7 +7338,7 @@ impl<'a> PodManager<'a> {
 
         // Preprocess uploads
         info!("Preprocessing {} pod uploads", update_list.pods.len());
-        for pod_address in update_list.pods.keys() {
+        for (pod_address, queued_scratchpads) in &update_list.pods {
             let address = self.graph.check_pod_exists(pod_address)?;
             let address = address.trim();
 
@@ -1878,10 +7352,16 @@ impl<'a> PodManager<'a> {
                 address.to_string(),
                 target.to_string(),
             ));
+            let mut uploaded_addresses = vec![address.to_string()];
 
-            // Get all scratchpads for this pod
-            let data = self.data_store.get_scratchpad_data(target)?;
-            let scratchpads = self.graph.get_pod_scratchpads_from_string(data.trim())?;
+            // Use the scratchpads recorded when the pod was queued. Entries queued without
+            // scratchpads fall back to the scratchpads listed in the pointer target data
+            let scratchpads = if queued_scratchpads.is_empty() {
+                let data = self.data_store.get_scratchpad_data(target)?;
+                self.graph.get_pod_scratchpads_from_string(data.trim())?
+            } else {
+                queued_scratchpads.clone()
+            };
 
             // Add scratchpad upload operations
             for scratchpad_address in scratchpads {
@@ -1892,22 +7372,98 @@ impl<'a> PodManager<'a> {
                     scratchpad_address.to_string(),
                     scratchpad_data.trim().to_string(),
                 ));
+                uploaded_addresses.push(scratchpad_address.to_string());
             }
+            pod_uploads.insert(pod_address.clone(), uploaded_addresses);
         }
 
-        // Phase 2: Execute all operations with maximum concurrency - removals and uploads simultaneously
-        info!(
-            "Executing {} removal operations and {} upload operations concurrently",
-            removal_operations.len(),
-            upload_operations.len()
-        );
+        Ok((removal_operations, upload_operations, pod_uploads))
+    }
 
-        self.execute_all_operations_concurrent(removal_operations, upload_operations)
-            .await?;
+    // Label each operation upload_all would perform as a create, update or removal, along with
+    // the size of the scratchpad data it writes. Only reads from the network
+    async fn plan_upload_operations(
+        &self,
+    ) -> Result<Vec<(OperationKind, OperationAction, String, usize)>, Error> {
+        let (attempts, base_delay) = (self.retry_attempts, self.retry_base_delay);
+        let (removal_operations, upload_operations, _pod_uploads) =
+            self.prepare_upload_all_operations()?;
+        let known_new: HashSet<String> = self
+            .data_store
+            .get_update_list()?
+            .known_new
+            .into_iter()
+            .collect();
 
-        // Clear out the update list
-        self.data_store.clear_update_list()?;
-        Ok(())
+        let mut plan = Vec::new();
+        for (op_type, address, _data) in removal_operations {
+            let kind = if op_type.as_str() == "pointer" {
+                OperationKind::Pointer
+            } else {
+                OperationKind::Scratchpad
+            };
+            plan.push((kind, OperationAction::Remove, address, 0));
+        }
+
+        let checks = upload_operations
+            .into_iter()
+            .map(|(op_type, address, data)| {
+                let known_new = known_new.contains(&address);
+                async move {
+                    let (kind, exists, size) = if op_type.as_str() == "pointer" {
+                        let exists = Self::pointer_exists(
+                            &self.client,
+                            &address,
+                            known_new,
+                            attempts,
+                            base_delay,
+                        )
+                        .await?;
+                        (OperationKind::Pointer, exists, 0)
+                    } else {
+                        let exists = Self::scratchpad_exists(
+                            &self.client,
+                            &address,
+                            known_new,
+                            attempts,
+                            base_delay,
+                        )
+                        .await?;
+                        (OperationKind::Scratchpad, exists, data.len())
+                    };
+                    let action = if exists {
+                        OperationAction::Update
+                    } else {
+                        OperationAction::Create
+                    };
+                    Ok::<_, Error>((kind, action, address, size))
+                }
+            });
+        for result in join_limited(checks, self.max_concurrency).await {
+            plan.push(result?);
+        }
+
+        Ok(plan)
+    }
+
+    // Work out which update list entries made it to the network: completed removals,
+    // and pods whose pointer and every scratchpad completed
+    fn finished_update_entries(
+        pod_uploads: &HashMap<String, Vec<String>>,
+        completed: &HashSet<String>,
+    ) -> HashSet<String> {
+        let mut finished: HashSet<String> = completed.clone();
+        for (pod_address, uploaded_addresses) in pod_uploads {
+            if uploaded_addresses
+                .iter()
+                .all(|address| completed.contains(address))
+            {
+                finished.insert(pod_address.clone());
+            } else {
+                finished.remove(pod_address);
+            }
+        }
+        finished
     }
 
     /// Uploads a specific pod to the Autonomi network.
@@ -1946,7 +7502,8 @@ impl<'a> PodManager<'a> {
     /// - Creating new scratchpads (if additional storage is needed)
     /// - Updates to existing pointers and scratchpads are free
     ///
-    /// Costs are automatically paid using the configured wallet.
+    /// Costs are automatically paid using the configured wallet, or the payment option set with
+    /// `set_payment_override()`.
     ///
     /// # Example
     ///
@@ -1979,7 +7536,40 @@ impl<'a> PodManager<'a> {
     /// - [`add_pod`] - Create pods that need uploading
     /// - [`put_subject_data`] - Modify pods that need uploading
     /// - [`refresh_cache`] - Download updates from the network
+    /// - [`upload_pod_report`] - Upload and report what was created, updated and paid for
     pub async fn upload_pod(&mut self, address: &str) -> Result<(), Error> {
+        self.upload_pod_report(address).await?;
+        Ok(())
+    }
+
+    /// Uploads a specific pod like [`upload_pod`] and reports what was done.
+    ///
+    /// # Parameters
+    ///
+    /// * `address` - The hexadecimal Autonomi address of the pod to upload
+    ///
+    /// # Returns
+    ///
+    /// Returns an [`UploadReport`] counting the pointer and scratchpad that were created or
+    /// updated along with the total cost, or an `Error` in the same cases as [`upload_pod`].
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let pod_address = "80e79010a13e7eee779f799d99a20b418557e86d34ea5ed6ee4e5e5f0a8d2d9c";
+    /// let report = pod_manager.upload_pod_report(pod_address).await?;
+    /// println!("Upload cost {} atto tokens", report.total_cost);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`upload_pod`] - Upload a single pod without a report
+    /// - [`upload_all_report`] - Upload all pending pods and report what was done
+    pub async fn upload_pod_report(&mut self, address: &str) -> Result<UploadReport, Error> {
+        let mut report = UploadReport::default();
         let mut create_mode = false;
         let address = self.graph.check_pod_exists(address)?;
         let address = address.trim();
@@ -1988,7 +7578,7 @@ impl<'a> PodManager<'a> {
         let target = self.data_store.get_pointer_target(address)?;
         let target = target.trim();
         match self.update_pointer(address, target).await {
-            Ok(_) => {}
+            Ok(cost) => report.record(OperationKind::Pointer, OperationAction::Update, Some(&cost)),
             Err(e) => {
                 match e {
                     Error::Pointer(ref boxed_err)
@@ -2023,7 +7613,8 @@ impl<'a> PodManager<'a> {
 
         // If the pointer didn't exist, call create_pointer()
         if create_mode {
-            self.create_pointer(address, target).await?;
+            let cost = self.create_pointer(address, target).await?;
+            report.record(OperationKind::Pointer, OperationAction::Create, Some(&cost));
         }
 
         create_mode = false;
@@ -2039,7 +7630,11 @@ impl<'a> PodManager<'a> {
             let data = data.trim();
 
             match self.update_scratchpad(address, data).await {
-                Ok(_) => {}
+                Ok(cost) => report.record(
+                    OperationKind::Scratchpad,
+                    OperationAction::Update,
+                    Some(&cost),
+                ),
                 Err(e) => {
                     match e {
                         Error::Scratchpad(ref boxed_err)
@@ -2077,12 +7672,17 @@ impl<'a> PodManager<'a> {
 
             // If the pointer didn't exist, call create_pointer()
             if create_mode {
-                self.create_scratchpad(address, data).await?;
+                let cost = self.create_scratchpad(address, data).await?;
+                report.record(
+                    OperationKind::Scratchpad,
+                    OperationAction::Create,
+                    Some(&cost),
+                );
             }
         }
 
         debug!("Pod {} uploaded successfully", address);
-        Ok(())
+        Ok(report)
     }
 
     async fn create_pointer(&mut self, address: &str, target: &str) -> Result<String, Error> {
@@ -2098,7 +7698,7 @@ impl<'a> PodManager<'a> {
         );
 
         // Put the pointer on the network
-        let payment_option = PaymentOption::from(self.wallet);
+        let payment_option = self.payment_option();
         let (pointer_cost, _pointer_address) = match self
             .client
             .pointer_put(pointer, payment_option)
@@ -2155,7 +7755,7 @@ impl<'a> PodManager<'a> {
         info!("Scratchpad total size: {}", scratchpad.payload_size());
 
         // Put the scratchpad on the network
-        let payment_option = PaymentOption::from(self.wallet);
+        let payment_option = self.payment_option();
         let (scratchpad_cost, _scratchpad_address) = self
             .client
             .scratchpad_put(scratchpad, payment_option.clone())
@@ -2165,7 +7765,7 @@ impl<'a> PodManager<'a> {
         Ok(scratchpad_cost.to_string())
     }
 
-    async fn update_pointer(&mut self, address: &str, target: &str) -> Result<(), Error> {
+    async fn update_pointer(&mut self, address: &str, target: &str) -> Result<String, Error> {
         let key_string = self.key_store.get_pointer_key(address.to_string())?;
         let key: SecretKey = SecretKey::from_hex(key_string.trim())?;
 
@@ -2179,9 +7779,10 @@ impl<'a> PodManager<'a> {
         // Create new pointer with Unix timestamp as counter and put it on the network
         let timestamp_counter = chrono::Utc::now().timestamp() as u64;
         let new_pointer = Pointer::new(&key, timestamp_counter, target);
-        let payment_option = PaymentOption::from(self.wallet);
+        let payment_option = self.payment_option();
+        let mut pointer_cost = "0".to_string();
         match self.client.pointer_put(new_pointer, payment_option).await {
-            Ok(_) => {}
+            Ok((cost, _)) => pointer_cost = cost.to_string(),
             Err(e) => {
                 match &e {
                     PointerError::PutError(autonomi::client::PutError::Network {
@@ -2206,10 +7807,10 @@ impl<'a> PodManager<'a> {
         // Update the local pointer file counter with the new timestamp
         self.data_store
             .update_pointer_count(address, timestamp_counter)?;
-        Ok(())
+        Ok(pointer_cost)
     }
 
-    async fn update_scratchpad(&mut self, address: &str, data: &str) -> Result<(), Error> {
+    async fn update_scratchpad(&mut self, address: &str, data: &str) -> Result<String, Error> {
         let key_string = self.key_store.get_scratchpad_key(address.to_string())?;
         let key: SecretKey = SecretKey::from_hex(key_string.trim())?;
 
@@ -2218,7 +7819,11 @@ impl<'a> PodManager<'a> {
         let _scratchpad = match self.client.scratchpad_get(&scratchpad_address).await {
             Ok(scratchpad) => scratchpad,
             Err(e) => match e {
-                ScratchpadError::Fork(scratchpads) => Self::select_newest_scratchpad(scratchpads),
+                ScratchpadError::Fork(scratchpads) => {
+                    Self::select_newest_scratchpad(scratchpads)
+                        .ok_or_else(|| Self::empty_fork_error(&scratchpad_address))?
+                        .0
+                }
                 _ => return Err(Error::Scratchpad(Box::new(e))),
             },
         };
@@ -2239,7 +7844,7 @@ impl<'a> PodManager<'a> {
         );
 
         // Put the new scratchpad on the network
-        let payment_option = PaymentOption::from(self.wallet);
+        let payment_option = self.payment_option();
         let (scratchpad_cost, _scratchpad_address) = self
             .client
             .scratchpad_put(scratchpad, payment_option.clone())
@@ -2247,7 +7852,7 @@ impl<'a> PodManager<'a> {
         info!("Scratchpad update cost: {scratchpad_cost:?}");
         debug!("Scratchpad updated");
 
-        Ok(())
+        Ok(scratchpad_cost.to_string())
     }
 
     /// Execute all operations with maximum concurrency - all client operations run simultaneously
@@ -2255,27 +7860,103 @@ impl<'a> PodManager<'a> {
         &mut self,
         removal_operations: Vec<(String, String, String)>,
         upload_operations: Vec<(String, String, String)>,
-    ) -> Result<(), Error> {
+        pod_uploads: &HashMap<String, Vec<String>>,
+    ) -> Result<UploadReport, Error> {
+        let (all_futures, pointer_updates) =
+            self.build_operation_futures(removal_operations, upload_operations);
+
+        // Execute ALL operations concurrently, letting every operation finish or be cancelled
+        info!(
+            "Executing {} total client operations concurrently",
+            all_futures.len()
+        );
+        let results = join_limited(all_futures, self.max_concurrency).await;
+        self.operation_handle.reset();
+
+        let mut completed: HashSet<String> = HashSet::new();
+        let mut first_error: Option<Error> = None;
+        let mut report = UploadReport::default();
+        for result in results {
+            match result {
+                Ok(outcome) => {
+                    report.record(outcome.kind, outcome.action, outcome.cost.as_deref());
+                    // Update data store with pointer counts of the pointers that were uploaded
+                    if outcome.kind == OperationKind::Pointer
+                        && outcome.action != OperationAction::Remove
+                        && let Some(timestamp_counter) = pointer_updates.get(&outcome.address)
+                        && let Err(e) = self
+                            .data_store
+                            .update_pointer_count(&outcome.address, *timestamp_counter)
+                    {
+                        warn!(
+                            "Failed to update pointer count for {}: {}",
+                            outcome.address, e
+                        );
+                    }
+                    completed.insert(outcome.address);
+                }
+                Err(e) => {
+                    error!("Operation failed: {}", e);
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+
+        // Keep the update list in sync with what actually reached the network
+        if let Some(e) = first_error {
+            let finished = Self::finished_update_entries(pod_uploads, &completed);
+            self.data_store.remove_from_update_list(&finished)?;
+            return Err(e);
+        }
+
+        Ok(report)
+    }
+
+    /// Build one future per removal and upload operation, along with the pointer counters to store once they succeed
+    #[allow(clippy::type_complexity)]
+    fn build_operation_futures(
+        &self,
+        removal_operations: Vec<(String, String, String)>,
+        upload_operations: Vec<(String, String, String)>,
+    ) -> (
+        Vec<BoxFuture<'_, Result<OperationOutcome, Error>>>,
+        HashMap<String, u64>,
+    ) {
+        let (attempts, base_delay) = (self.retry_attempts, self.retry_base_delay);
         // Phase 1: Collect all keys and prepare data structures upfront
         let mut removal_data = Vec::new();
         let mut upload_pointer_data = Vec::new();
         let mut upload_scratchpad_data = Vec::new();
 
         // Create payment option upfront
-        let payment_option = PaymentOption::from(self.wallet);
+        let payment_option = self.payment_option();
+
+        // Addresses known not to exist on the network skip the existence check
+        let known_new: HashSet<String> = self
+            .data_store
+            .get_update_list()
+            .map(|update_list| update_list.known_new.into_iter().collect())
+            .unwrap_or_default();
 
         // Collect removal operation data
         for (op_type, address, data) in removal_operations {
             match op_type.as_str() {
+                // Keys abandoned by rotate_pod_keys are kept with the bad keys
                 "pointer" => {
-                    if let Ok(key_string) = self.key_store.get_free_pointer_key(address.clone())
+                    if let Ok(key_string) = self
+                        .key_store
+                        .get_free_pointer_key(address.clone())
+                        .or_else(|_| self.key_store.get_bad_key(address.clone()))
                         && let Ok(key) = SecretKey::from_hex(key_string.trim())
                     {
                         removal_data.push(("pointer".to_string(), address, data, key));
                     }
                 }
                 "scratchpad" => {
-                    if let Ok(key_string) = self.key_store.get_scratchpad_key(address.clone())
+                    if let Ok(key_string) = self
+                        .key_store
+                        .get_scratchpad_key(address.clone())
+                        .or_else(|_| self.key_store.get_bad_key(address.clone()))
                         && let Ok(key) = SecretKey::from_hex(key_string.trim())
                     {
                         removal_data.push(("scratchpad".to_string(), address, data, key));
@@ -2307,9 +7988,8 @@ impl<'a> PodManager<'a> {
             }
         }
 
-        // Phase 2: Execute ALL client operations concurrently
-        use futures::future::BoxFuture;
-        let mut all_futures: Vec<BoxFuture<'_, Result<(), Error>>> = Vec::new();
+        // Phase 2: Build ALL client operations so they can run concurrently
+        let mut all_futures: Vec<BoxFuture<'_, Result<OperationOutcome, Error>>> = Vec::new();
 
         // Add removal futures - all concurrent
         for (op_type, address, data, key) in removal_data {
@@ -2320,16 +8000,23 @@ impl<'a> PodManager<'a> {
 
             if op_type == "pointer" {
                 let future = Box::pin(async move {
+                    let mut cost = None;
                     let pointer_address = PointerAddress::from_hex(&addr_clone)?;
-                    match client.pointer_get(&pointer_address).await {
+                    match retry_with_backoff(attempts, base_delay, || {
+                        client.pointer_get(&pointer_address)
+                    })
+                    .await
+                    {
                         Ok(_) => {
                             let target_address = ScratchpadAddress::from_hex(&data_clone)?;
                             let target = PointerTarget::ScratchpadAddress(target_address);
                             // Create new pointer with Unix timestamp as counter and put it on the network
                             let timestamp_counter = chrono::Utc::now().timestamp() as u64;
                             let new_pointer = Pointer::new(&key, timestamp_counter, target);
+                            // Puts are paid before they are verified, so they are never retried
                             match client.pointer_put(new_pointer, payment_opt).await {
-                                Ok(_) => {
+                                Ok((pointer_cost, _)) => {
+                                    cost = Some(pointer_cost.to_string());
                                     info!("Successfully removed pointer: {}", addr_clone);
                                 }
                                 Err(e) => match &e {
@@ -2358,75 +8045,68 @@ impl<'a> PodManager<'a> {
                             );
                         }
                     }
-                    Ok::<(), Error>(())
+                    Ok::<OperationOutcome, Error>(OperationOutcome {
+                        address: addr_clone,
+                        kind: OperationKind::Pointer,
+                        action: OperationAction::Remove,
+                        cost,
+                    })
                 });
-                all_futures.push(future);
+                all_futures.push(self.operation_handle.track(address, future));
             } else if op_type == "scratchpad" {
                 let future = Box::pin(async move {
+                    let mut cost = None;
                     let scratchpad_address = ScratchpadAddress::from_hex(&addr_clone)?;
-                    match client.scratchpad_get(&scratchpad_address).await {
-                        Ok(_scratchpad) => {
-                            // Create updated scratchpad with empty data (removal) using Unix timestamp
-                            let bytes = Bytes::from("".as_bytes().to_vec());
-                            let timestamp_counter = chrono::Utc::now().timestamp() as u64;
-                            let updated_scratchpad = Scratchpad::new_with_signature(
-                                key.clone().public_key(),
+                    let exists = match retry_with_backoff(attempts, base_delay, || {
+                        client.scratchpad_get(&scratchpad_address)
+                    })
+                    .await
+                    {
+                        Ok(_scratchpad) => true,
+                        Err(ScratchpadError::Fork(_)) => true,
+                        Err(_) => {
+                            info!(
+                                "Scratchpad {} not found on network, already removed",
+                                addr_clone
+                            );
+                            false
+                        }
+                    };
+                    if exists {
+                        // Create updated scratchpad with empty data (removal) using Unix timestamp
+                        let bytes = Bytes::from("".as_bytes().to_vec());
+                        let timestamp_counter = chrono::Utc::now().timestamp() as u64;
+                        let updated_scratchpad = Scratchpad::new_with_signature(
+                            key.clone().public_key(),
+                            0,
+                            bytes.clone(),
+                            timestamp_counter,
+                            key.sign(Scratchpad::bytes_for_signature(
+                                scratchpad_address,
                                 0,
-                                bytes.clone(),
+                                &bytes,
                                 timestamp_counter,
-                                key.sign(Scratchpad::bytes_for_signature(
-                                    scratchpad_address,
-                                    0,
-                                    &bytes,
-                                    timestamp_counter,
-                                )),
-                            );
-                            client
-                                .scratchpad_put(updated_scratchpad, payment_opt)
-                                .await?;
-                            info!("Successfully removed scratchpad: {}", addr_clone);
-                        }
-                        Err(e) => {
-                            match e {
-                                ScratchpadError::Fork(scratchpads) => {
-                                    let _scratchpad = Self::select_newest_scratchpad(scratchpads);
-                                    // Create updated scratchpad with empty data (removal) using Unix timestamp
-                                    let bytes = Bytes::from("".as_bytes().to_vec());
-                                    let timestamp_counter = chrono::Utc::now().timestamp() as u64;
-                                    let updated_scratchpad = Scratchpad::new_with_signature(
-                                        key.clone().public_key(),
-                                        0,
-                                        bytes.clone(),
-                                        timestamp_counter,
-                                        key.sign(Scratchpad::bytes_for_signature(
-                                            scratchpad_address,
-                                            0,
-                                            &bytes,
-                                            timestamp_counter,
-                                        )),
-                                    );
-                                    client
-                                        .scratchpad_put(updated_scratchpad, payment_opt)
-                                        .await?;
-                                    info!("Successfully removed scratchpad: {}", addr_clone);
-                                }
-                                _ => {
-                                    info!(
-                                        "Scratchpad {} not found on network, already removed",
-                                        addr_clone
-                                    );
-                                }
-                            }
-                        }
+                            )),
+                        );
+                        let (scratchpad_cost, _) = client
+                            .scratchpad_put(updated_scratchpad, payment_opt)
+                            .await?;
+                        cost = Some(scratchpad_cost.to_string());
+                        info!("Successfully removed scratchpad: {}", addr_clone);
                     }
-                    Ok::<(), Error>(())
+                    Ok::<OperationOutcome, Error>(OperationOutcome {
+                        address: addr_clone,
+                        kind: OperationKind::Scratchpad,
+                        action: OperationAction::Remove,
+                        cost,
+                    })
                 });
-                all_futures.push(future);
+                all_futures.push(self.operation_handle.track(address, future));
             }
         }
 
         // Collect pointer update information for later data store updates
-        let mut pointer_updates = Vec::new();
+        let mut pointer_updates = HashMap::new();
 
         // Add upload pointer existence checks and operations - all concurrent
         for (address, target, key) in upload_pointer_data {
@@ -2439,72 +8119,63 @@ impl<'a> PodManager<'a> {
             let timestamp_counter = chrono::Utc::now().timestamp() as u64;
 
             // Store the address and timestamp for later data store update
-            pointer_updates.push((addr_clone.clone(), timestamp_counter));
+            pointer_updates.insert(addr_clone.clone(), timestamp_counter);
+            let known_new = known_new.contains(&address);
 
             let future = Box::pin(async move {
-                let pointer_address = PointerAddress::from_hex(&addr_clone)?;
-                let exists = client.pointer_get(&pointer_address).await.is_ok();
-
-                if exists {
-                    // Update existing pointer using the pre-generated timestamp as counter
-                    let target_address = ScratchpadAddress::from_hex(&target_clone)?;
-                    let target_obj = PointerTarget::ScratchpadAddress(target_address);
-                    let new_pointer = Pointer::new(&key, timestamp_counter, target_obj);
-                    match client.pointer_put(new_pointer, payment_opt.clone()).await {
-                        Ok(_) => {}
-                        Err(e) => match &e {
-                            PointerError::PutError(autonomi::client::PutError::Network {
-                                network_error,
-                                ..
-                            }) => {
-                                let error_msg = format!("{network_error}");
-                                if error_msg.contains("Put verification failed: Peers have conflicting entries for this record") {
-                                        info!("Pointer failed put verification due to peers having conflicting entries, ignoring: {addr_clone}");
-                                    } else {
-                                        error!("PointerError occurred: {:?}", e);
-                                        return Err(Error::Pointer(Box::new(e)));
-                                    }
-                            }
-                            _ => {
-                                error!("PointerError occurred: {:?}", e);
-                                return Err(Error::Pointer(Box::new(e)));
-                            }
-                        },
-                    }
-                    debug!("Successfully updated pointer: {}", addr_clone);
-                } else {
-                    // Create new pointer using the pre-generated timestamp as counter
-                    let target_address = ScratchpadAddress::from_hex(&target_clone)?;
-                    let pointer = Pointer::new(
-                        &key,
-                        timestamp_counter,
-                        PointerTarget::ScratchpadAddress(target_address),
-                    );
-                    match client.pointer_put(pointer, payment_opt).await {
-                        Ok(_) => {
+                let mut cost = None;
+                let exists =
+                    Self::pointer_exists(client, &addr_clone, known_new, attempts, base_delay)
+                        .await?;
+
+                // Create or update the pointer using the pre-generated timestamp as counter
+                let target_address = ScratchpadAddress::from_hex(&target_clone)?;
+                let pointer = Pointer::new(
+                    &key,
+                    timestamp_counter,
+                    PointerTarget::ScratchpadAddress(target_address),
+                );
+                // Puts are paid before they are verified, so they are never retried
+                match client.pointer_put(pointer, payment_opt).await {
+                    Ok((pointer_cost, _)) => {
+                        cost = Some(pointer_cost.to_string());
+                        if exists {
+                            debug!("Successfully updated pointer: {}", addr_clone);
+                        } else {
                             debug!("Successfully created pointer: {}", addr_clone);
                         }
-                        Err(e) => match &e {
-                            PointerError::PutError(autonomi::client::PutError::Network {
-                                network_error,
-                                ..
-                            }) => {
-                                let error_msg = format!("{network_error}");
-                                if error_msg.contains("Put verification failed: Peers have conflicting entries for this record") {
-                                        info!("Pointer failed put verification due to peers having conflicting entries, ignoring: {addr_clone}");
-                                    } else {
-                                        return Err(Error::Pointer(Box::new(e)));
-                                    }
-                            }
-                            _ => {
-                                return Err(Error::Pointer(Box::new(e)));
-                            }
-                        },
                     }
+                    Err(e) => match &e {
+                        PointerError::PutError(autonomi::client::PutError::Network {
+                            network_error,
+                            ..
+                        }) => {
+                            let error_msg = format!("{network_error}");
+                            if error_msg.contains("Put verification failed: Peers have conflicting entries for this record") {
+                                    info!("Pointer failed put verification due to peers having conflicting entries, ignoring: {addr_clone}");
+                                } else {
+                                    error!("PointerError occurred: {:?}", e);
+                                    return Err(Error::Pointer(Box::new(e)));
+                                }
+                        }
+                        _ => {
+                            error!("PointerError occurred: {:?}", e);
+                            return Err(Error::Pointer(Box::new(e)));
+                        }
+                    },
                 }
-                Ok::<(), Error>(())
+                Ok::<OperationOutcome, Error>(OperationOutcome {
+                    address: addr_clone,
+                    kind: OperationKind::Pointer,
+                    action: if exists {
+                        OperationAction::Update
+                    } else {
+                        OperationAction::Create
+                    },
+                    cost,
+                })
             });
-            all_futures.push(future);
+            all_futures.push(self.operation_handle.track(address, future));
         }
 
         // Add upload scratchpad existence checks and operations - all concurrent
@@ -2513,100 +8184,97 @@ impl<'a> PodManager<'a> {
             let addr_clone = address.clone();
             let data_clone = data.clone();
             let payment_opt = payment_option.clone();
+            let known_new = known_new.contains(&address);
 
             let future = Box::pin(async move {
                 let scratchpad_address = ScratchpadAddress::from_hex(&addr_clone)?;
                 let bytes = Bytes::from(data_clone.as_bytes().to_vec());
 
-                match client.scratchpad_get(&scratchpad_address).await {
-                    Ok(_existing_scratchpad) => {
-                        // Update existing scratchpad using Unix timestamp
-                        let timestamp_counter = chrono::Utc::now().timestamp() as u64;
-                        let updated_scratchpad = Scratchpad::new_with_signature(
-                            key.clone().public_key(),
-                            0,
-                            bytes.clone(),
-                            timestamp_counter,
-                            key.sign(Scratchpad::bytes_for_signature(
-                                scratchpad_address,
-                                0,
-                                &bytes,
-                                timestamp_counter,
-                            )),
-                        );
-                        client
-                            .scratchpad_put(updated_scratchpad, payment_opt.clone())
-                            .await?;
-                        debug!("Successfully updated scratchpad: {}", addr_clone);
-                    }
-                    Err(e) => {
-                        match e {
-                            ScratchpadError::Fork(scratchpads) => {
-                                let _existing_scratchpad =
-                                    Self::select_newest_scratchpad(scratchpads);
-                                // Update existing scratchpad using Unix timestamp
-                                let timestamp_counter = chrono::Utc::now().timestamp() as u64;
-                                let updated_scratchpad = Scratchpad::new_with_signature(
-                                    key.clone().public_key(),
-                                    0,
-                                    bytes.clone(),
-                                    timestamp_counter,
-                                    key.sign(Scratchpad::bytes_for_signature(
-                                        scratchpad_address,
-                                        0,
-                                        &bytes,
-                                        timestamp_counter,
-                                    )),
-                                );
-                                client
-                                    .scratchpad_put(updated_scratchpad, payment_opt.clone())
-                                    .await?;
-                                debug!("Successfully updated scratchpad: {}", addr_clone);
-                            }
-                            _ => {
-                                // Create new scratchpad using Unix timestamp
-                                let timestamp_counter = chrono::Utc::now().timestamp() as u64;
-                                let new_scratchpad = Scratchpad::new_with_signature(
-                                    key.clone().public_key(),
-                                    0,
-                                    bytes.clone(),
-                                    timestamp_counter,
-                                    key.sign(Scratchpad::bytes_for_signature(
-                                        scratchpad_address,
-                                        0,
-                                        &bytes,
-                                        timestamp_counter,
-                                    )),
-                                );
-                                client.scratchpad_put(new_scratchpad, payment_opt).await?;
-                                debug!("Successfully created scratchpad: {}", addr_clone);
-                            }
-                        }
-                    }
+                let exists =
+                    Self::scratchpad_exists(client, &addr_clone, known_new, attempts, base_delay)
+                        .await?;
+
+                // Create or update the scratchpad using Unix timestamp
+                let timestamp_counter = chrono::Utc::now().timestamp() as u64;
+                let scratchpad = Scratchpad::new_with_signature(
+                    key.clone().public_key(),
+                    0,
+                    bytes.clone(),
+                    timestamp_counter,
+                    key.sign(Scratchpad::bytes_for_signature(
+                        scratchpad_address,
+                        0,
+                        &bytes,
+                        timestamp_counter,
+                    )),
+                );
+                let (scratchpad_cost, _) = client.scratchpad_put(scratchpad, payment_opt).await?;
+                if exists {
+                    debug!("Successfully updated scratchpad: {}", addr_clone);
+                } else {
+                    debug!("Successfully created scratchpad: {}", addr_clone);
                 }
-                Ok::<(), Error>(())
+                Ok::<OperationOutcome, Error>(OperationOutcome {
+                    address: addr_clone,
+                    kind: OperationKind::Scratchpad,
+                    action: if exists {
+                        OperationAction::Update
+                    } else {
+                        OperationAction::Create
+                    },
+                    cost: Some(scratchpad_cost.to_string()),
+                })
             });
-            all_futures.push(future);
+            all_futures.push(self.operation_handle.track(address, future));
         }
 
-        // Execute ALL operations concurrently
-        info!(
-            "Executing {} total client operations concurrently",
-            all_futures.len()
-        );
-        try_join_all(all_futures).await?;
+        (all_futures, pointer_updates)
+    }
 
-        // Update data store with pointer counts after all operations complete
-        for (address, timestamp_counter) in pointer_updates {
-            if let Err(e) = self
-                .data_store
-                .update_pointer_count(&address, timestamp_counter)
-            {
-                warn!("Failed to update pointer count for {}: {}", address, e);
-            }
+    // Check if an upload will update an existing pointer rather than create a new one.
+    // Addresses known not to exist on the network yet skip the network check
+    async fn pointer_exists(
+        client: &Client,
+        address: &str,
+        known_new: bool,
+        attempts: u32,
+        base_delay: Duration,
+    ) -> Result<bool, Error> {
+        if known_new {
+            return Ok(false);
         }
+        let pointer_address = PointerAddress::from_hex(address)?;
+        Ok(retry_with_backoff(attempts, base_delay, || {
+            client.pointer_get(&pointer_address)
+        })
+        .await
+        .is_ok())
+    }
 
-        Ok(())
+    // Check if an upload will update an existing scratchpad rather than create a new one.
+    // A forked scratchpad exists, whichever version wins
+    async fn scratchpad_exists(
+        client: &Client,
+        address: &str,
+        known_new: bool,
+        attempts: u32,
+        base_delay: Duration,
+    ) -> Result<bool, Error> {
+        if known_new {
+            return Ok(false);
+        }
+        let scratchpad_address = ScratchpadAddress::from_hex(address)?;
+        Ok(
+            match retry_with_backoff(attempts, base_delay, || {
+                client.scratchpad_get(&scratchpad_address)
+            })
+            .await
+            {
+                Ok(_existing_scratchpad) => true,
+                Err(ScratchpadError::Fork(_)) => true,
+                Err(_) => false,
+            },
+        )
     }
 
     /// Refreshes the local cache by discovering and downloading user created pods from the Autonomi network.
@@ -2620,14 +8288,24 @@ impl<'a> PodManager<'a> {
     /// The function automatically discovers pods that may have been created on other devices
     /// using the same key derivation, ensuring synchronization across multiple clients.
     ///
+    /// A pod is skipped when the counter of its pointer on the network is not newer than the
+    /// local one, see [`set_refresh_mode`]. The network can be flaky, and a scratchpad fetch that
+    /// failed after its pointer counter was stored hides the change until the pod is modified
+    /// again. Pass `force` to download every pod regardless of the counters, the refresh mode and
+    /// the refresh TTL.
+    ///
     /// # Process
     ///
     /// 1. **Key Discovery**: Checks the next few derived keys for network activity
     /// 2. **Pod Discovery**: Downloads any new pods found at discovered addresses
-    /// 3. **Update Check**: Compares local and remote versions of known pods
+    /// 3. **Update Check**: Compares local and remote versions of known pods, unless forced
     /// 4. **Data Sync**: Downloads updated pod data and updates the graph database
     /// 5. **Depth Setting**: Marks all discovered pods with depth 0 (local pods)
     ///
+    /// # Parameters
+    ///
+    /// * `force` - Download every pod, even when the local pointer counter is up to date
+    ///
     /// # Returns
     ///
     /// Returns `Ok(())` on successful refresh, or an `Error` if:
@@ -2641,9 +8319,12 @@ impl<'a> PodManager<'a> {
     /// ```ignore
     /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
     /// // Refresh the cache to discover any new or updated local pods
-    /// pod_manager.refresh_cache().await?;
+    /// pod_manager.refresh_cache(false).await?;
     ///
     /// // The cache is now up to date with the network
+    ///
+    /// // Download every pod again, e.g. when the user asks for a full refresh
+    /// pod_manager.refresh_cache(true).await?;
     /// # Ok(())
     /// # }
     /// ```
@@ -2659,93 +8340,219 @@ impl<'a> PodManager<'a> {
     /// - [`refresh_ref`] - Refresh cache including pod references
     /// - [`upload_all`] - Upload local changes before refreshing
     /// - [`search`] - Search across refreshed pod data
-    pub async fn refresh_cache(&mut self) -> Result<(), Error> {
-        // Get the configuration address
+    /// - [`set_refresh_ttl`] - Skip pods that were refreshed recently
+    /// - [`set_refresh_mode`] - Choose which pods are downloaded
+    pub async fn refresh_cache(&mut self, force: bool) -> Result<(), Error> {
+        self.refresh_cache_internal(self.refresh_mode, force).await
+    }
+
+    /// Probes the network for pods created with this key store beyond the known key count.
+    ///
+    /// [`refresh_cache`] only derives keys up to the key count stored in the configuration pod.
+    /// If the configuration pod was lost, or another device created pods that are not yet
+    /// recorded in it, those pods are never found. This function derives the next `scan_ahead`
+    /// keys after the known ones and fetches the pointer and scratchpad at each address. Pods
+    /// found this way are downloaded as local pods, and every key up to the last one found on
+    /// the network is recorded in the configuration pod (in use, or free if nothing was stored
+    /// there) so later refreshes keep deriving them. The configuration change is queued for
+    /// upload on the next call to [`upload_all`].
+    ///
+    /// # Parameters
+    ///
+    /// * `scan_ahead` - How many keys past the known key count to probe
+    ///
+    /// # Returns
+    ///
+    /// Returns the addresses of the recovered pods, which is empty if nothing was found, or an
+    /// `Error` if:
+    /// - Network errors other than a missing record occur
+    /// - Key derivation fails
+    /// - Graph database or local file operations fail
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let recovered = pod_manager.scan_for_owned_pods(50).await?;
+    /// println!("Recovered {} pods", recovered.len());
+    /// pod_manager.upload_all().await?; // save the recovered keys in the configuration pod
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`refresh_cache`] - Refresh the pods already known
+    /// - [`list_my_pods`] - List the local pods, including recovered ones
+    pub async fn scan_for_owned_pods(&mut self, scan_ahead: u64) -> Result<Vec<String>, Error> {
+        let (attempts, base_delay) = (self.retry_attempts, self.retry_base_delay);
         let configuration_address = self.key_store.get_configuration_address()?;
         let configuration_address = configuration_address.as_str();
-        debug!(
-            "Refreshing configuration address: {}",
-            configuration_address
+        // Start after every index handed out so far, free keys included
+        let first_index = self.key_store.get_num_derived_keys();
+        info!(
+            "Scanning keys {} to {} for owned pods",
+            first_index,
+            first_index + scan_ahead
         );
 
-        // Download the configuration pod pointer
-        let pointer_address = PointerAddress::from_hex(configuration_address)?;
-        let pointer = match self.client.pointer_get(&pointer_address).await {
-            Ok(pointer) => pointer,
-            Err(e) => {
-                match e {
-                    PointerError::CannotUpdateNewPointer => {
-                        warn!("Configuration pointer not found on network, skipping");
-                        return Ok(()); // Skip to the next pointer
-                    }
-                    // Catch Pointer(Network(GetRecordError(RecordNotFound))) error when there is nothing on the network
-                    PointerError::GetError(GetError::RecordNotFound) => {
-                        warn!("Configuration pointer not found on network, skipping");
-                        return Ok(()); // Skip to the next pointer
-                    }
-                    _ => {
-                        error!("Error occurred: {:?}", e); // Log the error
-                        return Err(Error::Pointer(Box::new(e))); // Propagate the error to the higher-level function
+        let keys = scan_owned_keys(
+            &self.client,
+            self.key_store,
+            first_index,
+            scan_ahead,
+            attempts,
+            base_delay,
+            self.max_concurrency,
+        )
+        .await?;
+        if keys.is_empty() {
+            info!("No owned pods found beyond the known key count");
+            return Ok(Vec::new());
+        }
+
+        let mut pods = Vec::new();
+        let mut recovered = Vec::new();
+        let mut scratchpads = Vec::new();
+        let mut free_pointers = Vec::new();
+        let mut free_scratchpads = Vec::new();
+        let mut scratchpad_operations = Vec::new();
+        for (_, address, kind, pointer) in keys {
+            match (kind, pointer) {
+                (KeyKind::Pointer, Some(pointer)) => {
+                    match Self::pointer_scratchpad_target(&pointer) {
+                        Ok(target) => {
+                            info!("Recovered pod: {}", address);
+                            self.data_store.create_pointer_file(&address)?;
+                            self.data_store
+                                .update_pointer_target(&address, target.to_hex().as_str())?;
+                            self.data_store
+                                .update_pointer_count(&address, pointer.counter())?;
+                            scratchpad_operations.push((
+                                address.clone(),
+                                target,
+                                false,
+                                pointer.counter() as u64,
+                            ));
+                            recovered.push(address.clone());
+                        }
+                        Err(reason) => {
+                            warn!("Owned pod {} not downloaded: {}", address, reason);
+                        }
                     }
+                    pods.push(address);
                 }
+                (KeyKind::FreePointer, _) => free_pointers.push(address),
+                (KeyKind::Scratchpad, _) => scratchpads.push(address),
+                _ => free_scratchpads.push(address),
             }
-        };
+        }
 
-        debug!("Retrieved pointer. Update count: {}", pointer.counter());
-        // FIXME: should look into a 'force' or 'override' argument to force a cache update if something is busted on the network
-        // For now while the network is flakey, just always refresh because pointers fail, scratchpads fail, everything fails
-        // Check if the pointer counter is newer than the local cache. If the pointer is older, we are done.
-        // The MAX condition is the special case where the pointer file is not found and we always want to refresh
-        // let local_pointer_count = self
-        //     .data_store
-        //     .get_pointer_count(configuration_address)
-        //     .unwrap_or(u64::MAX);
-        // if pointer.counter() as u64 <= local_pointer_count && local_pointer_count != u64::MAX {
-        //     info!("Local pods are up to date, skipping refresh");
-        //     return Ok(());
-        // }
-
-        // Check the configuration pod target
-        let target = pointer.target();
-        let target = match target {
-            PointerTarget::ScratchpadAddress(scratchpad_address) => scratchpad_address,
-            _ => {
-                error!("Configuration pointer target is not a scratchpad address");
-                return Ok(());
-            }
-        };
-        debug!("Retrieved scratchpad address: {}", target.to_hex());
+        if !scratchpad_operations.is_empty() {
+            self.execute_scratchpad_operations_concurrent(scratchpad_operations)
+                .await?;
+        }
 
-        // Download the configuration pod data using concurrent approach
-        let scratchpad_operations = vec![(
-            configuration_address.to_string(),
-            *target,
-            true,
-            pointer.counter() as u64,
-        )];
-        self.execute_scratchpad_operations_concurrent(scratchpad_operations)
+        // Record the keys in the configuration pod so later refreshes derive them
+        let configuration = self.graph.recovered_keys_entry(
+            configuration_address,
+            &pods,
+            &scratchpads,
+            &free_pointers,
+            &free_scratchpads,
+            self.key_store.get_num_keys(),
+        )?;
+        self.process_configuration_data(configuration_address, configuration)
             .await?;
-        debug!("Retrieved scratchpad data");
 
-        // Update the configuration local pointer count value after the scratchpads are fetched
-        self.data_store
-            .update_pointer_count(configuration_address, pointer.counter())?;
+        info!("Recovered {} owned pods", recovered.len());
+        Ok(recovered)
+    }
 
-        // When the configuration pointer fails to fetch, the file gets corrupted, so we need to update the target as well
-        self.data_store
-            .update_pointer_target(configuration_address, target.to_hex().as_str())?;
+    // Refresh the configuration pod and the user's pods. `force` downloads every pod, ignoring the
+    // refresh mode, pointer counters and the refresh TTL
+    async fn refresh_cache_internal(
+        &mut self,
+        mode: RefreshMode,
+        force: bool,
+    ) -> Result<(), Error> {
+        // Get the configuration address
+        let configuration_address = self.key_store.get_configuration_address()?;
+        let configuration_address = configuration_address.as_str();
+        debug!(
+            "Refreshing configuration address: {}",
+            configuration_address
+        );
+        self.report_progress(RefreshPhase::Configuration, 0, 1, 0);
+
+        // Download the configuration pod pointer and check if it is newer than the local cache
+        let Some((pointer, download_configuration)) = check_configuration_pointer(
+            &self.client,
+            self.data_store,
+            configuration_address,
+            mode,
+            force,
+        )
+        .await?
+        else {
+            return Ok(());
+        };
+
+        // Local changes to the configuration pod that were not uploaded take precedence, unless the
+        // conflict policy says otherwise
+        let pending_changes = self.pods_with_local_changes()?;
+        if download_configuration
+            && self.refresh_conflict_policy.should_download(
+                configuration_address,
+                pending_changes.contains(configuration_address),
+            )?
+        {
+            // Check the configuration pod target
+            let target = match Self::pointer_scratchpad_target(&pointer) {
+                Ok(scratchpad_address) => scratchpad_address,
+                Err(reason) => {
+                    error!("Configuration pointer is invalid: {}", reason);
+                    return Ok(());
+                }
+            };
+            debug!("Retrieved scratchpad address: {}", target.to_hex());
+
+            // Download the configuration pod data using concurrent approach
+            let scratchpad_operations = vec![(
+                configuration_address.to_string(),
+                target,
+                true,
+                pointer.counter() as u64,
+            )];
+            self.execute_scratchpad_operations_concurrent(scratchpad_operations)
+                .await?;
+            debug!("Retrieved scratchpad data");
+
+            // Update the configuration local pointer count value after the scratchpads are fetched
+            self.data_store
+                .update_pointer_count(configuration_address, pointer.counter())?;
+
+            // When the configuration pointer fails to fetch, the file gets corrupted, so we need to update the target as well
+            self.data_store
+                .update_pointer_target(configuration_address, target.to_hex().as_str())?;
+        }
+        self.report_progress(RefreshPhase::Configuration, 1, 1, 0);
 
         // Get the list of used and free pointers and scratchpads from the graph
         let mut free_pointers = self.graph.get_free_pointers(configuration_address)?;
         let mut free_scratchpads = self.graph.get_free_scratchpads(configuration_address)?;
         let pointers = self.graph.get_pointers(configuration_address)?;
         let scratchpads = self.graph.get_scratchpads(configuration_address)?;
+        // Abandoned keys are never used again, but still take up a derivation index
+        let abandoned = self.graph.get_abandoned(configuration_address)?;
         // FIXME: should just remove this get_key_count() function because it tends to get corrupted. Use the
         // actual lists to determine the key count
         //let key_count = self.graph.get_key_count(configuration_address)?;
-        let key_count =
-            (free_pointers.len() + free_scratchpads.len() + pointers.len() + scratchpads.len())
-                as u64;
+        let key_count = (free_pointers.len()
+            + free_scratchpads.len()
+            + pointers.len()
+            + scratchpads.len()
+            + abandoned.len()) as u64;
 
         // Check if the update_list pods section contains any of the free pointers or scratchpads
         // If so, remove them from the free pointers and scratchpads lists
@@ -2789,17 +8596,19 @@ impl<'a> PodManager<'a> {
             // Check if the address matches any of the values in the pointers, scratchpads, free_pointers, or free_scratchpads vectors
             // If a match is found, map it to the proper key store hashmap
             // If a match is not found, add it to the bad_keys hashmap
-            if pointers.contains(&address) {
-                self.key_store.add_pointer_key()?;
+            let kind = if pointers.contains(&address) {
+                KeyKind::Pointer
             } else if scratchpads.contains(&address) {
-                self.key_store.add_scratchpad_key()?;
+                KeyKind::Scratchpad
             } else if free_pointers.contains(&address) {
-                self.key_store.add_free_pointer_key()?;
+                KeyKind::FreePointer
             } else if free_scratchpads.contains(&address) {
-                self.key_store.add_free_scratchpad_key()?;
+                KeyKind::FreeScratchpad
             } else {
-                self.key_store.add_bad_key()?;
-            }
+                KeyKind::Bad
+            };
+            // Record the key at this index, so a pointer key never picks up an earlier free key
+            self.key_store.add_key_at_index(i, kind)?;
         }
 
         // Once the key store is updated, proceed with the normal refresh
@@ -2828,96 +8637,77 @@ impl<'a> PodManager<'a> {
         );
 
         // Execute all operations with maximum concurrency
-        self.execute_refresh_cache_concurrent(pointer_addresses)
+        self.execute_refresh_cache_concurrent(pointer_addresses, mode, force)
             .await?;
         Ok(())
     }
 
+    // Pods whose local copy has changes that were not uploaded yet: pods in the update list or
+    // queued for removal, and the configuration pod while its update is deferred
+    fn pods_with_local_changes(&self) -> Result<HashSet<String>, Error> {
+        let update_list = self.data_store.get_update_list()?;
+        let mut pods: HashSet<String> = update_list.pods.into_keys().collect();
+        pods.extend(update_list.remove.pointers);
+        if self.data_store.configuration_pending() {
+            pods.insert(self.key_store.get_configuration_address()?);
+        }
+        Ok(pods)
+    }
+
     /// Execute refresh cache operations with maximum concurrency - all client operations run simultaneously
     async fn execute_refresh_cache_concurrent(
         &mut self,
         pointer_addresses: Vec<(String, PointerAddress)>,
+        mode: RefreshMode,
+        force: bool,
     ) -> Result<(), Error> {
-        // Phase 1: Fetch all pointers concurrently
-        info!(
-            "Phase 1: Fetching {} pointers concurrently",
-            pointer_addresses.len()
-        );
-
-        let pointer_futures: Vec<_> = pointer_addresses
-            .iter()
-            .map(|(address, pointer_address)| {
-                let client = &self.client;
-                let address = address.clone();
-                async move {
-                    info!("Checking pointer: {}", address);
-                    let result = client.pointer_get(pointer_address).await;
-                    (address, result)
+        // Skip pods that were refreshed within the TTL
+        let mut pointer_addresses = pointer_addresses;
+        if !force && mode.uses_ttl() && !self.refresh_ttl.is_zero() {
+            let now = self.clock.now();
+            let mut fresh_pointers = Vec::new();
+            for (address, _) in &pointer_addresses {
+                if self.data_store.address_is_pointer(address)?
+                    && self
+                        .data_store
+                        .refreshed_within(address, self.refresh_ttl, now)?
+                {
+                    info!("Pod refreshed within TTL, skipping: {}", address);
+                    fresh_pointers.push(address.clone());
                 }
-            })
-            .collect();
-
-        let pointer_results = join_all(pointer_futures).await;
-
-        // Phase 2: Collect all scratchpad operations that need to be performed
-        let mut scratchpad_operations: Vec<(String, ScratchpadAddress, bool, u64)> = Vec::new(); // (address, scratchpad_addr, pointer_exists, counter)
-
-        for (address, pointer_result) in pointer_results {
-            let pointer = match pointer_result {
-                Ok(pointer) => pointer,
-                Err(e) => match e {
-                    PointerError::CannotUpdateNewPointer => {
-                        warn!("Pointer not found on network, skipping: {}", address);
-                        continue;
-                    }
-                    PointerError::GetError(GetError::RecordNotFound) => {
-                        warn!("Pointer not found on network, skipping: {}", address);
-                        continue;
-                    }
-                    _ => {
-                        error!("Error occurred: {:?}", e);
-                        return Err(Error::Pointer(Box::new(e)));
-                    }
-                },
-            };
-
-            info!("Pointer found: {:?}", pointer);
-
-            // Check if the pointer file exists in the local data store
-            let pointer_exists = self.data_store.address_is_pointer(&address)?;
-            if !pointer_exists {
-                info!("Pointer file does not exist, creating it");
-                self.data_store.create_pointer_file(&address)?;
-                self.data_store
-                    .update_pointer_target(&address, pointer.target().to_hex().as_str())?;
-                self.data_store
-                    .update_pointer_count(&address, pointer.counter())?;
-            }
-
-            // Check if the pointer is newer than the local cache
-            let local_pointer_count = self.data_store.get_pointer_count(&address)?;
-            if (pointer.counter() as u64 > local_pointer_count) || !pointer_exists {
-                info!("Pointer is newer, queuing scratchpad update");
-                let target = match pointer.target() {
-                    PointerTarget::ScratchpadAddress(scratchpad_address) => scratchpad_address,
-                    _ => {
-                        error!("Pointer target is not a scratchpad address, skipping");
-                        continue;
-                    }
-                };
-
-                scratchpad_operations.push((
-                    address,
-                    *target,
-                    pointer_exists,
-                    pointer.counter() as u64,
-                ));
-            } else {
-                info!("Pointer is up to date");
             }
+            pointer_addresses.retain(|(address, _)| !fresh_pointers.contains(address));
         }
 
+        let config = PodManagerConfig {
+            refresh_mode: mode,
+            ..self.get_config()
+        };
+        let pending_changes = self.pods_with_local_changes()?;
+        let progress_callback = &self.progress_callback;
+        let (scratchpad_operations, refreshed_pods) = plan_pod_downloads(
+            &self.client,
+            self.data_store,
+            pointer_addresses,
+            &pending_changes,
+            &config,
+            force,
+            |fetched, total| {
+                if let Some(callback) = progress_callback {
+                    callback(RefreshProgress {
+                        phase: RefreshPhase::Pointers,
+                        pods_processed: fetched,
+                        pods_total: total,
+                        current_depth: 0,
+                    });
+                }
+            },
+        )
+        .await?;
+
         // Phase 3: Execute all scratchpad operations with maximum concurrency
+        let pods_total = scratchpad_operations.len();
+        self.report_progress(RefreshPhase::Scratchpads, 0, pods_total, 0);
         if !scratchpad_operations.is_empty() {
             info!(
                 "Phase 3: Processing {} scratchpad operations with maximum concurrency",
@@ -2926,57 +8716,44 @@ impl<'a> PodManager<'a> {
             self.execute_scratchpad_operations_concurrent(scratchpad_operations)
                 .await?;
         }
+        self.report_progress(RefreshPhase::Scratchpads, pods_total, pods_total, 0);
+
+        // Record when each pod was last refreshed so the TTL can skip it next time
+        let now = self.clock.now();
+        for address in refreshed_pods {
+            self.data_store.update_last_refreshed(&address, now)?;
+        }
 
         Ok(())
     }
 
     /// Execute scratchpad operations with maximum concurrency - all client operations run simultaneously
-    async fn execute_scratchpad_operations_concurrent(
-        &mut self,
-        scratchpad_operations: Vec<(String, ScratchpadAddress, bool, u64)>,
-    ) -> Result<(), Error> {
-        // Phase 1: Download main scratchpads concurrently to discover additional scratchpads
-        info!(
-            "Phase 1: Downloading {} main scratchpads concurrently",
-            scratchpad_operations.len()
-        );
-
-        let main_scratchpad_futures: Vec<_> = scratchpad_operations
-            .iter()
-            .map(
-                |(pod_address, scratchpad_address, _pointer_exists, _counter)| {
-                    let client = &self.client;
-                    let address = *scratchpad_address;
-                    let pod_addr = pod_address.clone();
-                    async move {
-                        match client.scratchpad_get(&address).await {
-                            Ok(scratchpad) => {
-                                let data = scratchpad.encrypted_data();
-                                let data_string = String::from_utf8(data.to_vec())?;
-                                Ok((pod_addr, address.to_hex(), data_string))
-                            }
-                            Err(e) => match e {
-                                ScratchpadError::Fork(scratchpads) => {
-                                    let scratchpad = Self::select_newest_scratchpad(scratchpads);
-                                    let data = scratchpad.encrypted_data();
-                                    let data_string = String::from_utf8(data.to_vec())?;
-                                    Ok((pod_addr, address.to_hex(), data_string))
-                                }
-                                _ => {
-                                    info!(
-                                        "Main scratchpad not found on network: {}",
-                                        address.to_hex()
-                                    );
-                                    Err(Error::Scratchpad(Box::new(e)))
-                                }
-                            },
-                        }
-                    }
-                },
-            )
+    async fn execute_scratchpad_operations_concurrent(
+        &mut self,
+        scratchpad_operations: Vec<(String, ScratchpadAddress, bool, u64)>,
+    ) -> Result<(), Error> {
+        let (attempts, base_delay) = (self.retry_attempts, self.retry_base_delay);
+        // The pointer counter of each pod is the version of the data being loaded
+        let pod_versions: HashMap<String, u64> = scratchpad_operations
+            .iter()
+            .map(|(pod_address, _, _, counter)| (pod_address.clone(), *counter))
             .collect();
 
-        let main_results = join_all(main_scratchpad_futures).await;
+        // Phase 1: Download main scratchpads concurrently to discover additional scratchpads
+        info!(
+            "Phase 1: Downloading {} main scratchpads concurrently",
+            scratchpad_operations.len()
+        );
+
+        let main_results = fetch_main_scratchpads(
+            &self.client,
+            &scratchpad_operations,
+            attempts,
+            base_delay,
+            self.max_concurrency,
+            &self.fork_reports,
+        )
+        .await;
 
         // Phase 2: Collect all additional scratchpad addresses from main scratchpads
         let mut all_scratchpad_operations: Vec<(String, ScratchpadAddress, usize)> = Vec::new(); // (pod_address, scratchpad_addr, order_index)
@@ -3030,6 +8807,7 @@ impl<'a> PodManager<'a> {
                 .iter()
                 .map(|(pod_address, scratchpad_address, order_index)| {
                     let client = &self.client;
+                    let fork_reports = &self.fork_reports;
                     let address = *scratchpad_address;
                     let pod_addr = pod_address.clone();
                     let index = *order_index;
@@ -3037,7 +8815,11 @@ impl<'a> PodManager<'a> {
                         // Create scratchpad file if it doesn't exist
                         let scratchpad_hex = address.to_hex();
 
-                        match client.scratchpad_get(&address).await {
+                        match retry_with_backoff(attempts, base_delay, || {
+                            client.scratchpad_get(&address)
+                        })
+                        .await
+                        {
                             Ok(scratchpad) => {
                                 let data = scratchpad.encrypted_data();
                                 let data_string = String::from_utf8(data.to_vec())?;
@@ -3045,7 +8827,10 @@ impl<'a> PodManager<'a> {
                             }
                             Err(e) => match e {
                                 ScratchpadError::Fork(scratchpads) => {
-                                    let scratchpad = Self::select_newest_scratchpad(scratchpads);
+                                    let (scratchpad, fork_info) =
+                                        Self::select_newest_scratchpad(scratchpads)
+                                            .ok_or_else(|| Self::empty_fork_error(&address))?;
+                                    Self::record_fork(fork_reports, fork_info);
                                     let data = scratchpad.encrypted_data();
                                     let data_string = String::from_utf8(data.to_vec())?;
                                     Ok((pod_addr, scratchpad_hex, data_string, index))
@@ -3063,7 +8848,7 @@ impl<'a> PodManager<'a> {
                 })
                 .collect();
 
-            let additional_results = join_all(additional_futures).await;
+            let additional_results = join_limited(additional_futures, self.max_concurrency).await;
 
             // Phase 4: Group additional scratchpad data by pod and sort by order
             let mut pod_additional_data: std::collections::HashMap<
@@ -3112,7 +8897,11 @@ impl<'a> PodManager<'a> {
                 // Load the pod data into the graph database
                 info!("Loading pod into graph database: {}", pod_address);
                 if !combined_data.trim().is_empty()
-                    && let Err(e) = self.load_pod_into_graph(&pod_address, combined_data.trim())
+                    && let Err(e) = self.load_pod_into_graph(
+                        &pod_address,
+                        combined_data.trim(),
+                        pod_versions.get(&pod_address).copied(),
+                    )
                 {
                     warn!(
                         "Failed to load pod data into graph for {}: {}",
@@ -3136,7 +8925,11 @@ impl<'a> PodManager<'a> {
                 // Load the pod data into the graph database
                 info!("Loading pod into graph database: {}", pod_address);
                 if !main_data.trim().is_empty()
-                    && let Err(e) = self.load_pod_into_graph(&pod_address, main_data.trim())
+                    && let Err(e) = self.load_pod_into_graph(
+                        &pod_address,
+                        main_data.trim(),
+                        pod_versions.get(&pod_address).copied(),
+                    )
                 {
                     warn!(
                         "Failed to load pod data into graph for {}: {}",
@@ -3165,6 +8958,7 @@ impl<'a> PodManager<'a> {
         scratchpad_operations: Vec<(String, ScratchpadAddress, bool, u64)>,
         depth: u64,
     ) -> Result<(), Error> {
+        let (attempts, base_delay) = (self.retry_attempts, self.retry_base_delay);
         // Phase 1: Download main scratchpads concurrently to discover additional scratchpads
         info!(
             "Phase 1: Downloading {} main scratchpads concurrently",
@@ -3176,11 +8970,16 @@ impl<'a> PodManager<'a> {
             .map(
                 |(pod_address, scratchpad_address, _pointer_exists, counter)| {
                     let client = &self.client;
+                    let fork_reports = &self.fork_reports;
                     let address = *scratchpad_address;
                     let pod_addr = pod_address.clone();
                     let pod_counter = *counter;
                     async move {
-                        match client.scratchpad_get(&address).await {
+                        match retry_with_backoff(attempts, base_delay, || {
+                            client.scratchpad_get(&address)
+                        })
+                        .await
+                        {
                             Ok(scratchpad) => {
                                 let data = scratchpad.encrypted_data();
                                 let data_string = String::from_utf8(data.to_vec())?;
@@ -3188,7 +8987,10 @@ impl<'a> PodManager<'a> {
                             }
                             Err(e) => match e {
                                 ScratchpadError::Fork(scratchpads) => {
-                                    let scratchpad = Self::select_newest_scratchpad(scratchpads);
+                                    let (scratchpad, fork_info) =
+                                        Self::select_newest_scratchpad(scratchpads)
+                                            .ok_or_else(|| Self::empty_fork_error(&address))?;
+                                    Self::record_fork(fork_reports, fork_info);
                                     let data = scratchpad.encrypted_data();
                                     let data_string = String::from_utf8(data.to_vec())?;
                                     Ok((pod_addr, address.to_hex(), data_string, pod_counter))
@@ -3207,7 +9009,7 @@ impl<'a> PodManager<'a> {
             )
             .collect();
 
-        let main_results = join_all(main_scratchpad_futures).await;
+        let main_results = join_limited(main_scratchpad_futures, self.max_concurrency).await;
 
         // Phase 2: Collect all additional scratchpad addresses from main scratchpads
         let mut all_scratchpad_operations: Vec<(String, ScratchpadAddress, usize, u64)> =
@@ -3270,12 +9072,17 @@ impl<'a> PodManager<'a> {
                 .iter()
                 .map(|(pod_address, scratchpad_address, order_index, counter)| {
                     let client = &self.client;
+                    let fork_reports = &self.fork_reports;
                     let address = *scratchpad_address;
                     let pod_addr = pod_address.clone();
                     let index = *order_index;
                     let pod_counter = *counter;
                     async move {
-                        match client.scratchpad_get(&address).await {
+                        match retry_with_backoff(attempts, base_delay, || {
+                            client.scratchpad_get(&address)
+                        })
+                        .await
+                        {
                             Ok(scratchpad) => {
                                 let data = scratchpad.encrypted_data();
                                 let data_string = String::from_utf8(data.to_vec())?;
@@ -3283,7 +9090,10 @@ impl<'a> PodManager<'a> {
                             }
                             Err(e) => match e {
                                 ScratchpadError::Fork(scratchpads) => {
-                                    let scratchpad = Self::select_newest_scratchpad(scratchpads);
+                                    let (scratchpad, fork_info) =
+                                        Self::select_newest_scratchpad(scratchpads)
+                                            .ok_or_else(|| Self::empty_fork_error(&address))?;
+                                    Self::record_fork(fork_reports, fork_info);
                                     let data = scratchpad.encrypted_data();
                                     let data_string = String::from_utf8(data.to_vec())?;
                                     Ok((
@@ -3307,7 +9117,7 @@ impl<'a> PodManager<'a> {
                 })
                 .collect();
 
-            let additional_results = join_all(additional_futures).await;
+            let additional_results = join_limited(additional_futures, self.max_concurrency).await;
 
             // Phase 4: Group additional scratchpad data by pod and sort by order
             let mut pod_additional_data: std::collections::HashMap<
@@ -3359,7 +9169,8 @@ impl<'a> PodManager<'a> {
                     pod_address
                 );
                 if !combined_data.trim().is_empty()
-                    && let Err(e) = self.load_pod_into_graph(&pod_address, combined_data.trim())
+                    && let Err(e) =
+                        self.load_pod_into_graph(&pod_address, combined_data.trim(), Some(counter))
                 {
                     warn!(
                         "Failed to load pod data into graph for {}: {}",
@@ -3394,7 +9205,8 @@ impl<'a> PodManager<'a> {
                     pod_address
                 );
                 if !main_data.trim().is_empty()
-                    && let Err(e) = self.load_pod_into_graph(&pod_address, main_data.trim())
+                    && let Err(e) =
+                        self.load_pod_into_graph(&pod_address, main_data.trim(), Some(counter))
                 {
                     warn!(
                         "Failed to load pod data into graph for {}: {}",
@@ -3427,7 +9239,7 @@ impl<'a> PodManager<'a> {
 
     /// Refreshes the pod cache including referenced pods up to a specified depth.
     ///
-    /// This function extends `refresh_cache()` by also discovering and downloading pods
+    /// This function extends `refresh_cache(true)` by also discovering and downloading pods
     /// that are referenced by local pods, creating a network of interconnected pods.
     /// It processes pod references iteratively up to the specified depth to avoid
     /// taking excessive time.
@@ -3441,7 +9253,7 @@ impl<'a> PodManager<'a> {
     ///
     /// # Process
     ///
-    /// 1. **Initial Refresh**: Calls `refresh_cache()` to update local pods
+    /// 1. **Initial Refresh**: Calls `refresh_cache(true)` to update local pods
     /// 2. **Iterative Processing**: For each depth level:
     ///    - Gets all pods at the current depth
     ///    - Extracts pod references from their graph data
@@ -3451,11 +9263,12 @@ impl<'a> PodManager<'a> {
     ///
     /// # Returns
     ///
-    /// Returns `Ok(())` on successful refresh, or an `Error` if:
+    /// Returns a [`RefreshReport`] listing the referenced pods that were downloaded and the ones
+    /// that could not be (not found, not pointing to a scratchpad, or a fetch error), or an `Error` if:
     /// - Network communication fails
-    /// - Referenced pods cannot be downloaded
     /// - Graph database operations fail
     /// - Local storage operations fail
+    /// - The refresh was cancelled, see [`set_cancellation_token`]
     ///
     /// # Network Costs
     ///
@@ -3469,7 +9282,10 @@ impl<'a> PodManager<'a> {
     /// ```ignore
     /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
     /// // Refresh with depth 1 to include directly referenced pods
-    /// pod_manager.refresh_ref(1).await?;
+    /// let report = pod_manager.refresh_ref(1).await?;
+    /// for (pod_address, reason) in &report.broken_references {
+    ///     println!("Broken reference {}: {}", pod_address, reason);
+    /// }
     ///
     /// // Search across all local and referenced pods
     /// let results = pod_manager.search(serde_json::json!({
@@ -3491,19 +9307,33 @@ impl<'a> PodManager<'a> {
     /// - [`refresh_cache`] - Refresh only local pods
     /// - [`add_pod_ref`] - Create pod references
     /// - [`search`] - Search across all cached pods
-    pub async fn refresh_ref(&mut self, depth: u64) -> Result<(), Error> {
-        self.refresh_cache().await?;
+    /// - [`list_broken_references`] - Re-check all known references against the network
+    pub async fn refresh_ref(&mut self, depth: u64) -> Result<RefreshReport, Error> {
+        // Local pods are always downloaded until the counter checks have proven reliable
+        self.refresh_cache(true).await?;
+        let mut report = RefreshReport::default();
 
         // Process pods iteratively up to the specified depth to avoid async recursion
         let mut all_processed_pods = std::collections::HashSet::new();
         let mut current_depth: u64 = 0;
 
         loop {
+            self.operation_handle.check_cancelled("Refresh")?;
             info!("Processing pod references at depth {}", current_depth);
 
             // Get all pods at the current depth
             let pod_addresses = self.get_pods_at_depth(current_depth)?;
             let mut newly_downloaded_pods: Vec<String> = Vec::new();
+            let unprocessed = pod_addresses
+                .iter()
+                .filter(|pod_address| !all_processed_pods.contains(*pod_address))
+                .count();
+            self.report_progress(
+                RefreshPhase::References,
+                all_processed_pods.len(),
+                all_processed_pods.len() + unprocessed,
+                current_depth,
+            );
 
             // Collect all pod references that need to be downloaded
             let mut pod_refs_to_download: Vec<String> = Vec::new();
@@ -3537,9 +9367,13 @@ impl<'a> PodManager<'a> {
                     pod_refs_to_download.len()
                 );
 
-                let successful_downloads = self
+                let (successful_downloads, broken_references) = self
                     .download_referenced_pods_batch(&pod_refs_to_download, current_depth + 1)
                     .await?;
+                report
+                    .downloaded
+                    .extend(successful_downloads.iter().cloned());
+                report.broken_references.extend(broken_references);
                 newly_downloaded_pods.extend(successful_downloads);
             }
 
@@ -3551,6 +9385,8 @@ impl<'a> PodManager<'a> {
             // Process newly downloaded pods for their references in the same iteration
             let mut pods_to_process = newly_downloaded_pods;
             while !pods_to_process.is_empty() {
+                // The previous batch is fully stored, so this is a safe point to stop
+                self.operation_handle.check_cancelled("Refresh")?;
                 let mut pod_refs_to_download: Vec<String> = Vec::new();
 
                 // Collect all references from newly downloaded pods
@@ -3584,8 +9420,14 @@ impl<'a> PodManager<'a> {
                         "Batch downloading {} referenced pods from newly downloaded pods",
                         pod_refs_to_download.len()
                     );
-                    self.download_referenced_pods_batch(&pod_refs_to_download, current_depth + 1)
-                        .await?
+                    let (successful_downloads, broken_references) = self
+                        .download_referenced_pods_batch(&pod_refs_to_download, current_depth + 1)
+                        .await?;
+                    report
+                        .downloaded
+                        .extend(successful_downloads.iter().cloned());
+                    report.broken_references.extend(broken_references);
+                    successful_downloads
                 } else {
                     Vec::new()
                 };
@@ -3597,6 +9439,12 @@ impl<'a> PodManager<'a> {
 
                 pods_to_process = next_batch;
             }
+            self.report_progress(
+                RefreshPhase::References,
+                all_processed_pods.len(),
+                all_processed_pods.len(),
+                current_depth,
+            );
 
             // Check if we should continue to the next depth
             if depth > 0 && current_depth >= depth {
@@ -3614,6 +9462,382 @@ impl<'a> PodManager<'a> {
             current_depth += 1;
         }
 
+        if !report.broken_references.is_empty() {
+            warn!(
+                "{} referenced pods could not be downloaded",
+                report.broken_references.len()
+            );
+        }
+        Ok(report)
+    }
+
+    /// Re-checks every known pod reference against the network and lists the broken ones.
+    ///
+    /// Pods in a directory can reference pods that were never uploaded, were removed, or no longer
+    /// point to a scratchpad. This function collects all pod references from the local graph
+    /// database, fetches their pointers concurrently, and reports the ones that cannot be resolved.
+    /// Nothing is downloaded or changed locally.
+    ///
+    /// # Returns
+    ///
+    /// Returns a list of `(pod_address, reason)` pairs sorted by address, where the reason is one of
+    /// `not found`, `not a scratchpad target`, `invalid address: ...` or `fetch error: ...`, or an
+    /// `Error` if the graph database query fails.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// for (pod_address, reason) in pod_manager.list_broken_references().await? {
+    ///     println!("Dead link to {}: {}", pod_address, reason);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`refresh_ref`] - Download referenced pods and report failures
+    /// - [`add_pod_ref`] - Create pod references
+    pub async fn list_broken_references(&self) -> Result<Vec<(String, String)>, Error> {
+        let (attempts, base_delay) = (self.retry_attempts, self.retry_base_delay);
+        // Collect every pod reference known locally
+        let mut references: HashSet<String> = HashSet::new();
+        for pod_address in self.graph.get_all_pods()? {
+            references.extend(self.graph.get_pod_references(&pod_address)?);
+        }
+        info!(
+            "Checking {} pod references on the network",
+            references.len()
+        );
+
+        let reference_futures: Vec<_> = references
+            .into_iter()
+            .map(|pod_address| {
+                let client = &self.client;
+                async move {
+                    let reason = match PointerAddress::from_hex(&pod_address) {
+                        Ok(pointer_address) => {
+                            match retry_with_backoff(attempts, base_delay, || {
+                                client.pointer_get(&pointer_address)
+                            })
+                            .await
+                            {
+                                Ok(pointer) => Self::pointer_scratchpad_target(&pointer)
+                                    .err()
+                                    .map(|e| e.to_string()),
+                                Err(e) => Some(Self::broken_reference_reason(&e)),
+                            }
+                        }
+                        Err(e) => Some(format!("invalid address: {e}")),
+                    };
+                    reason.map(|reason| (pod_address, reason))
+                }
+            })
+            .collect();
+
+        let mut broken_references: Vec<(String, String)> =
+            join_limited(reference_futures, self.max_concurrency)
+                .await
+                .into_iter()
+                .flatten()
+                .collect();
+        broken_references.sort();
+        Ok(broken_references)
+    }
+
+    /// Downloads several pods concurrently given their addresses.
+    ///
+    /// Useful for importing a shared list of pod addresses, such as a community directory export,
+    /// in one operation instead of calling [`add_pod_ref`] and [`refresh_ref`] for each of them.
+    /// All pointers are fetched at once, then the scratchpads of the pods that are new or newer
+    /// than the local copy are downloaded concurrently. The pods are loaded into the graph at the
+    /// given depth but no reference to them is added, and the pods they reference are not
+    /// followed. Duplicate addresses are only fetched once.
+    ///
+    /// # Parameters
+    ///
+    /// * `addresses` - The hexadecimal addresses of the pods to download
+    /// * `depth` - The depth to record for the downloaded pods, usually 1 for pods referenced
+    ///   directly by the user
+    ///
+    /// # Returns
+    ///
+    /// Returns a [`FetchReport`] listing the pods that were fetched and the ones that failed with
+    /// the reason (invalid address, not found, not pointing to a scratchpad, or a fetch error), or
+    /// an `Error` if:
+    /// - Graph database operations fail
+    /// - Local storage operations fail
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let directory: Vec<String> = std::fs::read_to_string("directory.txt")?
+    ///     .lines()
+    ///     .map(|line| line.trim().to_string())
+    ///     .collect();
+    ///
+    /// let report = pod_manager.fetch_pods(&directory, 1).await?;
+    /// println!("Fetched {} pods", report.fetched.len());
+    /// for (pod_address, reason) in &report.failed {
+    ///     println!("Could not fetch {}: {}", pod_address, reason);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`add_pod_ref`] - Create pod references
+    /// - [`refresh_ref`] - Download the pods referenced by the user's pods
+    pub async fn fetch_pods(
+        &mut self,
+        addresses: &[String],
+        depth: u64,
+    ) -> Result<FetchReport, Error> {
+        let mut pod_addresses: Vec<String> = Vec::new();
+        for address in addresses {
+            let address = address.trim().to_string();
+            if !address.is_empty() && !pod_addresses.contains(&address) {
+                pod_addresses.push(address);
+            }
+        }
+        info!(
+            "Fetching {} pods at depth {} with maximum concurrency",
+            pod_addresses.len(),
+            depth
+        );
+
+        let (fetched, failed) = self
+            .execute_download_pods_concurrent(&pod_addresses, depth)
+            .await?;
+
+        info!("Fetched {} pods, {} failed", fetched.len(), failed.len());
+        Ok(FetchReport { fetched, failed })
+    }
+
+    /// Exports the pod reference network as a compact RDF graph in Turtle format.
+    ///
+    /// The exported graph contains one node per known pod (local and referenced) with its name and
+    /// depth, plus a `colonylib/v1/references` edge from each pod to every pod it references. Subject
+    /// metadata is left out, which keeps the document small enough to feed directly into graph
+    /// visualization libraries for rendering the federation topology.
+    ///
+    /// # Returns
+    ///
+    /// Returns the reference graph serialized as Turtle, or an `Error` if the graph database query
+    /// or the serialization fails.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # fn example(pod_manager: &PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let turtle = pod_manager.export_reference_graph()?;
+    /// std::fs::write("pod_network.ttl", turtle)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`add_pod_ref`] - Create pod references
+    /// - [`list_broken_references`] - Find references that no longer resolve
+    pub fn export_reference_graph(&self) -> Result<String, Error> {
+        Ok(self.graph.export_reference_graph()?)
+    }
+
+    /// Determines which way the references between two pods point.
+    ///
+    /// Relationship UIs can use this to render bidirectional links in a federation view or to
+    /// suggest adding a reciprocal reference. Only references already in the local graph database
+    /// are considered, so referenced pods should be refreshed first with [`refresh_ref`].
+    ///
+    /// # Parameters
+    ///
+    /// * `pod_a` - The address of the first pod
+    /// * `pod_b` - The address of the second pod
+    ///
+    /// # Returns
+    ///
+    /// Returns a [`Relationship`]: `None` if neither pod references the other, `AtoB` if only
+    /// `pod_a` references `pod_b`, `BtoA` if only `pod_b` references `pod_a`, or `Mutual` if both
+    /// reference each other. Returns an `Error` if the graph database query fails.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # fn example(pod_manager: &PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// use colonylib::graph::Relationship;
+    ///
+    /// let my_pod = "80e79010a10d4b5b3c6b3e5b4c0e3c4c1b6d5f3e3c9c6b8e5f4c3b2a1d0e9f8a";
+    /// let their_pod = "a1b2c3d4e5f6789012345678901234567890abcdef1234567890abcdef123456";
+    /// if pod_manager.reference_relationship(my_pod, their_pod)? == Relationship::BtoA {
+    ///     println!("They reference you, add a reference back?");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`are_mutual_references`] - Check only for mutual references
+    /// - [`add_pod_ref`] - Create pod references
+    pub fn reference_relationship(&self, pod_a: &str, pod_b: &str) -> Result<Relationship, Error> {
+        Ok(self.graph.reference_relationship(pod_a, pod_b)?)
+    }
+
+    /// Checks whether two pods reference each other.
+    ///
+    /// This is a shorthand for [`reference_relationship`] returning [`Relationship::Mutual`].
+    ///
+    /// # Parameters
+    ///
+    /// * `pod_a` - The address of the first pod
+    /// * `pod_b` - The address of the second pod
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if `pod_a` references `pod_b` and `pod_b` references `pod_a`, or an `Error`
+    /// if the graph database query fails.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # fn example(pod_manager: &PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let my_pod = "80e79010a10d4b5b3c6b3e5b4c0e3c4c1b6d5f3e3c9c6b8e5f4c3b2a1d0e9f8a";
+    /// let their_pod = "a1b2c3d4e5f6789012345678901234567890abcdef1234567890abcdef123456";
+    /// if pod_manager.are_mutual_references(my_pod, their_pod)? {
+    ///     println!("Linked both ways");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`reference_relationship`] - Get the direction of the references
+    pub fn are_mutual_references(&self, pod_a: &str, pod_b: &str) -> Result<bool, Error> {
+        Ok(self.reference_relationship(pod_a, pod_b)? == Relationship::Mutual)
+    }
+
+    // Get the scratchpad a pod pointer targets. Pod pointers always target the first scratchpad
+    // of the pod. If the pointer targets anything else, the error describes the actual target
+    // type so it can be reported, for example as a broken reference by refresh_ref, instead of
+    // the pod silently not loading
+    pub(crate) fn pointer_scratchpad_target(pointer: &Pointer) -> Result<ScratchpadAddress, Error> {
+        let target_type = match pointer.target() {
+            PointerTarget::ScratchpadAddress(scratchpad_address) => return Ok(*scratchpad_address),
+            PointerTarget::ChunkAddress(_) => "chunk",
+            PointerTarget::GraphEntryAddress(_) => "graph entry",
+            PointerTarget::PointerAddress(_) => "pointer",
+        };
+        Err(Error::Pod(format!(
+            "pointer targets a {target_type} ({}), not a scratchpad",
+            pointer.target().to_hex()
+        )))
+    }
+
+    // Check if a pointer points to itself, which is how upload_all removes a pod. Data on the
+    // network cannot be deleted, so a removed pod pointer is overwritten to target its own
+    // address instead of the pod's first scratchpad
+    pub(crate) fn pointer_is_tombstone(pointer: &Pointer) -> bool {
+        pointer.target().to_hex() == pointer.address().to_hex()
+    }
+
+    // Describe why a referenced pod pointer could not be fetched
+    fn broken_reference_reason(error: &PointerError) -> String {
+        match error {
+            PointerError::CannotUpdateNewPointer
+            | PointerError::GetError(GetError::RecordNotFound) => "not found".to_string(),
+            _ => format!("fetch error: {error}"),
+        }
+    }
+
+    /// Loads pod data from a local TriG file as if it had been downloaded from the network.
+    ///
+    /// This supports offline development and testing: catalogs can be built and queried without
+    /// network access or payment. The file is validated first and nothing is loaded if any part of
+    /// it fails to parse. The data then replaces the pod's graph, and a local pointer file and a
+    /// scratchpad file for the pod's first scratchpad are created so later operations treat the
+    /// pod as cached. The pod gets depth 0 if its pointer key is in the key store (one of the
+    /// user's own pods), otherwise it is treated as a referenced pod with depth 1. The pod is not
+    /// queued for upload.
+    ///
+    /// # Parameters
+    ///
+    /// * `pod_address` - The hexadecimal address of the pod the data belongs to
+    /// * `trig_path` - Path to a file holding the pod data in TriG format
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success, or an `Error` if:
+    /// - The pod address is not a valid pointer address
+    /// - The file cannot be read
+    /// - The TriG data fails to parse
+    /// - The data doesn't list any scratchpads for the pod
+    /// - Local file or graph database operations fail
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// use std::path::Path;
+    ///
+    /// let pod_address = "80e79010a10d4b5b3c6b3e5b4c0e3c4c1b6d5f3e3c9c6b8e5f4c3b2a1d0e9f8a";
+    /// pod_manager.load_pod_from_file(pod_address, Path::new("fixtures/music_pod.trig"))?;
+    /// let results = pod_manager.search(serde_json::json!("beethoven")).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`refresh_cache`] - Download local pods from the network
+    /// - [`refresh_ref`] - Download referenced pods from the network
+    pub fn load_pod_from_file(&mut self, pod_address: &str, trig_path: &Path) -> Result<(), Error> {
+        let pod_address = PointerAddress::from_hex(pod_address.trim())?.to_hex();
+        let pod_data = std::fs::read_to_string(trig_path)?;
+
+        // Validate everything up front so a bad file never leaves partial data behind
+        self.graph.validate_trig(&pod_data)?;
+        let scratchpads = self.graph.get_pod_scratchpads_from_string(&pod_data)?;
+        let Some(target) = scratchpads.first() else {
+            return Err(Error::Pod(format!(
+                "No scratchpads listed for pod {pod_address} in {}",
+                trig_path.display()
+            )));
+        };
+        let target = ScratchpadAddress::from_hex(target)?.to_hex();
+
+        // Create the local files as if the pod had been downloaded
+        if !self.data_store.address_is_pointer(&pod_address)? {
+            self.data_store.create_pointer_file(&pod_address)?;
+        }
+        self.data_store
+            .update_pointer_target(&pod_address, &target)?;
+        if !self.data_store.address_is_scratchpad(&target)? {
+            self.data_store.create_scratchpad_file(&target)?;
+        }
+        self.data_store
+            .update_scratchpad_data(&target, pod_data.trim())?;
+
+        // Load the pod data into the graph database
+        self.graph
+            .load_pod_into_graph(&pod_address, pod_data.trim())?;
+        let depth = if self.key_store.get_pointer_key(pod_address.clone()).is_ok() {
+            0
+        } else {
+            1
+        };
+        self.update_pod_depth(&pod_address, depth)?;
+
+        info!(
+            "Loaded pod {} from {} at depth {}",
+            pod_address,
+            trig_path.display(),
+            depth
+        );
         Ok(())
     }
 
@@ -3636,12 +9860,14 @@ impl<'a> PodManager<'a> {
         Ok(self.graph.get_pod_references(pod_address)?)
     }
 
-    // Download multiple referenced pods with maximum concurrent network operations
+    // Download multiple referenced pods with maximum concurrent network operations.
+    // Returns the pods that were found and the (address, reason) of the ones that were not
+    #[allow(clippy::type_complexity)]
     async fn download_referenced_pods_batch(
         &mut self,
         pod_addresses: &[String],
         depth: u64,
-    ) -> Result<Vec<String>, Error> {
+    ) -> Result<(Vec<String>, Vec<(String, String)>), Error> {
         info!(
             "Batch downloading {} referenced pods at depth {} with maximum concurrency",
             pod_addresses.len(),
@@ -3654,11 +9880,13 @@ impl<'a> PodManager<'a> {
     }
 
     /// Execute download operations with maximum concurrency - all client operations run simultaneously
+    #[allow(clippy::type_complexity)]
     async fn execute_download_pods_concurrent(
         &mut self,
         pod_addresses: &[String],
         depth: u64,
-    ) -> Result<Vec<String>, Error> {
+    ) -> Result<(Vec<String>, Vec<(String, String)>), Error> {
+        let (attempts, base_delay) = (self.retry_attempts, self.retry_base_delay);
         // Phase 1: Fetch all pointers concurrently
         info!(
             "Phase 1: Fetching {} pointers concurrently",
@@ -3671,37 +9899,57 @@ impl<'a> PodManager<'a> {
                 let client = &self.client;
                 let address = pod_address.clone();
                 async move {
-                    let pointer_address = PointerAddress::from_hex(&address)?;
-                    let result = client.pointer_get(&pointer_address).await;
-                    Ok::<(String, Result<Pointer, PointerError>), Error>((address, result))
+                    let pointer_address = match PointerAddress::from_hex(&address) {
+                        Ok(pointer_address) => pointer_address,
+                        Err(e) => return (address, Err(format!("invalid address: {e}"))),
+                    };
+                    let result = retry_with_backoff(attempts, base_delay, || {
+                        client.pointer_get(&pointer_address)
+                    })
+                    .await;
+                    (address, Ok(result))
                 }
             })
             .collect();
 
-        let pointer_results = try_join_all(pointer_futures).await?;
+        let pointer_results = join_limited(pointer_futures, self.max_concurrency).await;
 
         // Phase 2: Process results and collect operations to perform
-        let mut pods_to_download: Vec<(String, Pointer)> = Vec::new();
+        let mut pods_to_download: Vec<(String, ScratchpadAddress, u64)> = Vec::new();
         let mut successful_downloads: Vec<String> = Vec::new();
+        let mut broken_references: Vec<(String, String)> = Vec::new();
 
         for (pod_address, pointer_result) in pointer_results {
             let pointer = match pointer_result {
-                Ok(pointer) => pointer,
-                Err(e) => {
+                Ok(Ok(pointer)) => pointer,
+                Ok(Err(e)) => {
                     match e {
-                        PointerError::CannotUpdateNewPointer => {
-                            warn!("Referenced pod not found on network: {}", pod_address);
-                            continue; // Skip this pod if it doesn't exist
-                        }
-                        PointerError::GetError(GetError::RecordNotFound) => {
+                        PointerError::CannotUpdateNewPointer
+                        | PointerError::GetError(GetError::RecordNotFound) => {
                             warn!("Referenced pod not found on network: {}", pod_address);
-                            continue; // Skip this pod if it doesn't exist
                         }
                         _ => {
                             error!("Error occurred for pod {}: {:?}", pod_address, e);
-                            continue; // Skip this pod on error
                         }
                     }
+                    // Skip this pod, but keep track of why
+                    broken_references.push((pod_address, Self::broken_reference_reason(&e)));
+                    continue;
+                }
+                Err(reason) => {
+                    error!("Referenced pod {} skipped: {}", pod_address, reason);
+                    broken_references.push((pod_address, reason));
+                    continue;
+                }
+            };
+
+            // A pointer that doesn't target a scratchpad can't be loaded as a pod
+            let target = match Self::pointer_scratchpad_target(&pointer) {
+                Ok(target) => target,
+                Err(reason) => {
+                    warn!("Referenced pod {} skipped: {}", pod_address, reason);
+                    broken_references.push((pod_address, reason.to_string()));
+                    continue;
                 }
             };
 
@@ -3732,45 +9980,184 @@ impl<'a> PodManager<'a> {
                 true
             };
 
-            if should_download {
-                pods_to_download.push((pod_address.clone(), pointer));
+            if should_download {
+                pods_to_download.push((pod_address.clone(), target, pointer.counter()));
+            }
+
+            // Always update depth and mark as processed
+            self.update_pod_depth(&pod_address, depth)?;
+            successful_downloads.push(pod_address);
+        }
+
+        // Phase 3: Download scratchpad data for pods that need it with maximum concurrency
+        if !pods_to_download.is_empty() {
+            info!(
+                "Phase 3: Processing {} pod downloads with maximum concurrency",
+                pods_to_download.len()
+            );
+
+            // Convert to scratchpad operations format
+            let mut scratchpad_operations = Vec::new();
+            for (pod_address, target, counter) in pods_to_download {
+                scratchpad_operations.push((pod_address, target, true, counter));
+            }
+
+            // Execute scratchpad downloads with maximum concurrency
+            self.execute_download_scratchpad_operations_concurrent(scratchpad_operations, depth)
+                .await?;
+        }
+
+        Ok((successful_downloads, broken_references))
+    }
+
+    /// Purges the graph database and rebuilds it from the local pointer and scratchpad files.
+    ///
+    /// This is an offline disaster-recovery path for when the graph database is in a bad state
+    /// but the local files are intact. Every pod with a local pointer file is reloaded: the data
+    /// of the pointer's target scratchpad is combined with the data of any additional scratchpads
+    /// it lists, in `HAS_INDEX` order, and loaded into the pod's graph. The configuration pod is
+    /// loaded first so the pod depths can be written into it afterwards. Depths are kept from
+    /// the old graph where they can still be read, otherwise a pod gets depth 0 if its pointer
+    /// key is in the key store and depth 1 if not. No network calls are made.
+    ///
+    /// # Returns
+    ///
+    /// Returns a [`RebuildReport`] listing the pods that were rebuilt and the pods that failed
+    /// with the reason, or an `Error` if:
+    /// - The configuration address cannot be derived
+    /// - The pointer directory cannot be read
+    /// - The graph database cannot be cleared
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let report = pod_manager.rebuild_graph_from_local()?;
+    /// println!("Rebuilt {} pods", report.rebuilt.len());
+    /// for (pod, reason) in &report.failed {
+    ///     println!("Could not rebuild {pod}: {reason}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`refresh_cache`] - Rebuild local pods from the network instead
+    /// - [`load_pod_from_file`] - Load a single pod from a TriG file
+    pub fn rebuild_graph_from_local(&mut self) -> Result<RebuildReport, Error> {
+        let configuration_address = self.key_store.get_configuration_address()?;
+        let mut pods = self.data_store.list_pointer_addresses()?;
+
+        // Load the configuration pod first, loading a pod clears its graph
+        if let Some(position) = pods.iter().position(|pod| *pod == configuration_address) {
+            let configuration = pods.remove(position);
+            pods.insert(0, configuration);
+        }
+
+        // Keep the depths that can still be read from the old graph
+        let mut depths = HashMap::new();
+        for pod in &pods {
+            if let Ok(depth) = self.graph.get_pod_depth(pod)
+                && depth != u64::MAX
+            {
+                depths.insert(pod.clone(), depth);
+            }
+        }
+
+        self.graph.clear_all()?;
+
+        let mut report = RebuildReport::default();
+        for pod in &pods {
+            let result = match self.read_local_pod_data(pod) {
+                Ok(pod_data) => self
+                    .graph
+                    .load_pod_into_graph(pod, pod_data.trim())
+                    .map_err(Error::from),
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(()) => report.rebuilt.push(pod.clone()),
+                Err(e) => {
+                    warn!("Failed to rebuild pod {} from local files: {}", pod, e);
+                    report.failed.push((pod.clone(), e.to_string()));
+                }
             }
+        }
 
-            // Always update depth and mark as processed
-            self.update_pod_depth(&pod_address, depth)?;
-            successful_downloads.push(pod_address);
+        for pod in &report.rebuilt {
+            let depth = match depths.get(pod) {
+                Some(depth) => *depth,
+                None if self.key_store.get_pointer_key(pod.clone()).is_ok() => 0,
+                None => 1,
+            };
+            if let Err(e) = self.update_pod_depth(pod, depth) {
+                warn!("Failed to update pod depth for {}: {}", pod, e);
+            }
         }
 
-        // Phase 3: Download scratchpad data for pods that need it with maximum concurrency
-        if !pods_to_download.is_empty() {
-            info!(
-                "Phase 3: Processing {} pod downloads with maximum concurrency",
-                pods_to_download.len()
-            );
+        info!(
+            "Rebuilt graph from local files: {} pods rebuilt, {} failed",
+            report.rebuilt.len(),
+            report.failed.len()
+        );
+        Ok(report)
+    }
 
-            // Convert to scratchpad operations format
-            let mut scratchpad_operations = Vec::new();
-            for (pod_address, pointer) in pods_to_download {
-                let target = match pointer.target() {
-                    PointerTarget::ScratchpadAddress(scratchpad_address) => scratchpad_address,
-                    _ => {
-                        error!(
-                            "Pointer target is not a scratchpad address for pod: {}",
-                            pod_address
-                        );
-                        continue;
-                    }
-                };
+    // Read a pod's data from its local scratchpad files, combined in HAS_INDEX order
+    fn read_local_pod_data(&self, pod_address: &str) -> Result<String, Error> {
+        let target = self.data_store.get_pointer_target(pod_address)?;
+        if target.is_empty() {
+            return Err(Error::Pod(format!(
+                "Pointer file for pod {pod_address} has no target"
+            )));
+        }
+        if !self.data_store.address_is_scratchpad(&target)? {
+            return Err(Error::Pod(format!(
+                "Scratchpad file {target} for pod {pod_address} is missing"
+            )));
+        }
 
-                scratchpad_operations.push((pod_address, *target, true, pointer.counter()));
+        // The first scratchpad lists all of the pod's scratchpads, the first one is the target
+        let mut pod_data = self.data_store.get_scratchpad_data(&target)?;
+        let scratchpads = self.graph.get_pod_scratchpads_from_string(&pod_data)?;
+        for scratchpad in scratchpads.iter().skip(1) {
+            if !self.data_store.address_is_scratchpad(scratchpad)? {
+                return Err(Error::Pod(format!(
+                    "Scratchpad file {scratchpad} for pod {pod_address} is missing"
+                )));
             }
-
-            // Execute scratchpad downloads with maximum concurrency
-            self.execute_download_scratchpad_operations_concurrent(scratchpad_operations, depth)
-                .await?;
+            pod_data.push_str(&self.data_store.get_scratchpad_data(scratchpad)?);
         }
+        Ok(pod_data)
+    }
 
-        Ok(successful_downloads)
+    // Record the subjects whose data differs from the digests taken before a pod was reloaded
+    fn record_subject_changes(
+        &self,
+        pod_address: &str,
+        version: u64,
+        previous_digests: &HashMap<String, String>,
+    ) -> Result<(), Error> {
+        let current_digests = self.graph.get_pod_subject_digests(pod_address)?;
+        let mut changed: Vec<String> = previous_digests
+            .keys()
+            .chain(current_digests.keys())
+            .filter(|subject| previous_digests.get(*subject) != current_digests.get(*subject))
+            .cloned()
+            .collect::<HashSet<String>>()
+            .into_iter()
+            .collect();
+        changed.sort();
+        debug!(
+            "{} subjects changed in pod {} version {}",
+            changed.len(),
+            pod_address,
+            version
+        );
+        self.data_store
+            .record_subject_changes(pod_address, version, &changed)?;
+        Ok(())
     }
 
     // Update the depth attribute of a pod in the graph database
@@ -3785,10 +10172,21 @@ impl<'a> PodManager<'a> {
         Ok(())
     }
 
-    // Load pod data into the graph database
-    fn load_pod_into_graph(&mut self, pod_address: &str, pod_data: &str) -> Result<(), Error> {
+    // Load pod data into the graph database. When the pod version (pointer counter) is known,
+    // the subjects that changed compared to the previously loaded data are recorded for
+    // subjects_changed_since
+    fn load_pod_into_graph(
+        &mut self,
+        pod_address: &str,
+        pod_data: &str,
+        version: Option<u64>,
+    ) -> Result<(), Error> {
         // The pod data should be in TriG format
         // Load it into the graph database using the Graph's method
+        let previous_digests = match version {
+            Some(_) => self.graph.get_pod_subject_digests(pod_address).ok(),
+            None => None,
+        };
 
         match self.graph.load_pod_into_graph(pod_address, pod_data) {
             Ok(_) => {
@@ -3796,6 +10194,15 @@ impl<'a> PodManager<'a> {
                     "Successfully loaded pod {} data into graph database",
                     pod_address
                 );
+                if let (Some(version), Some(previous_digests)) = (version, previous_digests)
+                    && let Err(e) =
+                        self.record_subject_changes(pod_address, version, &previous_digests)
+                {
+                    warn!(
+                        "Failed to record changed subjects for pod {}: {}",
+                        pod_address, e
+                    );
+                }
             }
             Err(e) => {
                 warn!(
@@ -3844,12 +10251,15 @@ impl<'a> PodManager<'a> {
     /// ```json
     /// {
     ///     "update": ["pod_address_1", "pod_address_2", ...],
-    ///     "remove": ["pod_address_3", "pod_address_4", ...]
+    ///     "remove": ["pod_address_3", "pod_address_4", ...],
+    ///     "remove_scratchpads": ["scratchpad_address_1", ...]
     /// }
     /// ```
     ///
     /// The "update" array contains addresses of pods that need to be updated or created on the network.
     /// The "remove" array contains addresses of pods that need to be removed from the network.
+    /// The "remove_scratchpads" array contains scratchpad addresses that will be emptied on the
+    /// network, either because their pod was removed or because the pod shrank.
     ///
     /// # Errors
     ///
@@ -3886,9 +10296,210 @@ impl<'a> PodManager<'a> {
             remove_array.push(Value::String(pod_address));
         }
         update_list_json.insert("remove".to_string(), Value::Array(remove_array));
+        let mut remove_scratchpads_array = Vec::new();
+        for scratchpad_address in update_list.remove.scratchpads {
+            remove_scratchpads_array.push(Value::String(scratchpad_address));
+        }
+        update_list_json.insert(
+            "remove_scratchpads".to_string(),
+            Value::Array(remove_scratchpads_array),
+        );
         Ok(Value::Object(update_list_json))
     }
 
+    /// Returns the pointer and scratchpad addresses queued for removal from the network.
+    ///
+    /// Removed pods queue their pointer (which will be pointed at itself) and all of their
+    /// scratchpads (which will be emptied). Pods that shrink queue only the scratchpads they no
+    /// longer use. Unlike [`get_update_list`], the two kinds of address are kept apart so a UI
+    /// can show exactly what the next upload will delete.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `RemovalSection` with the queued pointer and scratchpad addresses, or an
+    /// `Error` if the update list cannot be read from local storage.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let removals = pod_manager.get_pending_removals()?;
+    /// println!("Pointers to remove: {:?}", removals.pointers);
+    /// println!("Scratchpads to empty: {:?}", removals.scratchpads);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`get_update_list`] - Returns the combined update list
+    /// - [`remove_pod`] - Queues a pod for removal
+    /// - [`remove_pod_preview`] - Reports what removing a pod would delete
+    /// - [`upload_all`] - Uploads all pending changes to the network
+    pub fn get_pending_removals(&self) -> Result<RemovalSection, Error> {
+        Ok(self.data_store.get_pending_removals()?)
+    }
+
+    /// Discards every pending local change and restores the pods to their last uploaded state.
+    ///
+    /// This is a "reset to last saved" for the whole store, without downloading the pods again.
+    /// Each pod with pending changes is restored from the local store: pods that were created
+    /// locally and never uploaded are deleted, and pods with a version saved by the pod history
+    /// (see [`set_history_depth`]) are restored to the version saved before the first local edit.
+    /// The update and removal lists are cleared last, once every pod is restored. **All changes
+    /// that were not uploaded are lost.**
+    ///
+    /// Pods removed since the last upload, and edited pods without a saved version, can only be
+    /// brought back from the network. When `restore_from_network` is set they are downloaded
+    /// again as with a forced [`refresh_cache`]. If that fails, for example while offline, the
+    /// failure is logged and those pods keep their local state.
+    ///
+    /// # Parameters
+    ///
+    /// * `restore_from_network` - Download the pods that can't be restored locally
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of pods with pending changes that were reverted, not counting the
+    /// configuration pod, or an `Error` if local file or graph database operations fail. Pods that
+    /// were not reverted, because of an error or because they could not be restored, keep their
+    /// pending changes.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # async fn example(pod_manager: &mut PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let reverted = pod_manager.discard_all_pending(true).await?;
+    /// println!("Reverted {} pods to their last uploaded state", reverted);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`get_update_list`] - List the changes that would be discarded
+    /// - [`restore_pod_version`] - Restore a single pod from its history
+    /// - [`refresh_cache`] - Download all pods without discarding pending changes when forced
+    /// - [`upload_all`] - Keep the changes by uploading them instead
+    pub async fn discard_all_pending(
+        &mut self,
+        restore_from_network: bool,
+    ) -> Result<usize, Error> {
+        let pending = self.data_store.get_update_list()?;
+        let configuration_address = self.key_store.get_configuration_address()?;
+        let mut pending_pods: HashSet<String> = pending.pods.keys().cloned().collect();
+        pending_pods.extend(pending.remove.pointers.iter().cloned());
+        warn!(
+            "Discarding pending changes to {} pods, changes that were not uploaded are lost",
+            pending_pods.len()
+        );
+        let mut reverted = 0;
+
+        // Restore the queued pods from the local store. Removed pods are gone from the local
+        // store, so they can only come back from the network
+        let mut unrestored = pending.remove.pointers.clone();
+        let mut queued_pods: Vec<&String> = pending
+            .pods
+            .keys()
+            .filter(|pod_address| **pod_address != configuration_address)
+            .collect();
+        queued_pods.sort();
+        for pod_address in queued_pods {
+            let queued_scratchpads = &pending.pods[pod_address];
+            // Uploading a pod stores its pointer counter, so a pod without one was never uploaded
+            let pointer_count = if self.data_store.address_is_pointer(pod_address)? {
+                self.data_store.get_pointer_count(pod_address)?
+            } else {
+                0
+            };
+            let mut scratchpads = self.graph.get_pod_scratchpads(pod_address)?;
+            if pointer_count == 0 {
+                info!("Deleting pod that was never uploaded: {}", pod_address);
+                for scratchpad in queued_scratchpads {
+                    if !scratchpads.contains(scratchpad) {
+                        scratchpads.push(scratchpad.clone());
+                    }
+                }
+                let configuration = self.graph.remove_pod_entry(
+                    pod_address,
+                    scratchpads.clone(),
+                    &configuration_address,
+                )?;
+                self.process_configuration_data(&configuration_address, configuration)
+                    .await?;
+                if self.key_store.address_is_pointer(pod_address) {
+                    self.key_store.remove_pointer_key(pod_address)?;
+                }
+                self.data_store.remove_pointer_file(pod_address)?;
+                for scratchpad in &scratchpads {
+                    // Queued scratchpads the pod no longer uses have already been freed
+                    let _ = self.key_store.remove_scratchpad_key(scratchpad.trim());
+                    self.data_store.remove_scratchpad_file(scratchpad.trim())?;
+                }
+            } else if self
+                .data_store
+                .list_pod_versions(pod_address)?
+                .contains(&pointer_count)
+            {
+                // The version saved for the current pointer counter is the pod as it was before
+                // the first local edit since the last upload
+                info!("Restoring pod {} to version {}", pod_address, pointer_count);
+                self.restore_pod_version(pod_address, pointer_count).await?;
+            } else {
+                warn!(
+                    "No saved version of pod {}, it can only be restored from the network",
+                    pod_address
+                );
+                unrestored.push(pod_address.clone());
+                continue;
+            }
+
+            // The pod is back to its last uploaded state, so it is no longer pending
+            let mut done: HashSet<String> = scratchpads.into_iter().collect();
+            done.extend(queued_scratchpads.iter().cloned());
+            done.insert(pod_address.clone());
+            self.data_store.remove_from_update_list(&done)?;
+            reverted += 1;
+        }
+
+        // Download the pods that could not be restored locally, overwriting their pending edits.
+        // The local state is kept when the network is not available
+        if restore_from_network && !unrestored.is_empty() {
+            let conflict_policy = std::mem::replace(
+                &mut self.refresh_conflict_policy,
+                RefreshConflictPolicy::PreferRemote,
+            );
+            let result = self.refresh_cache_internal(self.refresh_mode, true).await;
+            self.refresh_conflict_policy = conflict_policy;
+            match result {
+                Ok(()) => {
+                    reverted += unrestored.len();
+                    unrestored.clear();
+                }
+                Err(e) => warn!(
+                    "Failed to restore {} pods from the network, keeping their local state: {}",
+                    unrestored.len(),
+                    e
+                ),
+            }
+        }
+
+        // Only forget the remaining pending changes once every pod is back to its uploaded state,
+        // the pods that could not be restored keep theirs
+        if unrestored.is_empty() {
+            self.data_store.clear_update_list()?;
+            self.data_store.set_configuration_pending(false)?;
+        } else {
+            warn!(
+                "{} pods could not be restored and keep their pending changes",
+                unrestored.len()
+            );
+        }
+
+        info!("Reverted {} pods", reverted);
+        Ok(reverted)
+    }
+
     /// Adds a new wallet key to the key store with the specified name.
     ///
     /// This function stores a wallet private key in the key store using a human-readable name
@@ -4096,6 +10707,71 @@ impl<'a> PodManager<'a> {
         Ok((name, address))
     }
 
+    /// Returns the address of the active wallet for display or funding.
+    ///
+    /// This is a receive-only view of the active wallet: only the EIP-55 checksummed Ethereum
+    /// address is returned, never the private key. Unlike [`get_active_wallet`] the caller
+    /// doesn't have to unpack a `(name, address)` tuple.
+    ///
+    /// # Returns
+    ///
+    /// Returns the checksummed address (`0x` followed by 40 hex characters), or an `Error` if no
+    /// active wallet has been set or the active wallet file cannot be read.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # fn example(pod_manager: &PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let address = pod_manager.active_wallet_address()?;
+    /// println!("Send ETH and ANT tokens to {address}");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`get_active_wallet`] - Get the name and address of the active wallet
+    /// - [`wallet_address`] - Get the address of any named wallet
+    pub fn active_wallet_address(&self) -> Result<String, Error> {
+        let (name, address) = self.data_store.get_active_wallet()?;
+        // Prefer deriving from the key store in case the stored address is stale
+        match self.key_store.get_wallet_checksum_address(&name) {
+            Ok(address) => Ok(address),
+            Err(_) => Ok(KeyStore::checksum_address(&address)),
+        }
+    }
+
+    /// Returns the address of a named wallet for display or funding.
+    ///
+    /// Only the EIP-55 checksummed Ethereum address derived from the wallet key is returned, the
+    /// private key never leaves the key store.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name the wallet key was added under
+    ///
+    /// # Returns
+    ///
+    /// Returns the checksummed address, or an `Error` if no wallet key exists with that name.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # fn example(pod_manager: &PodManager<'_>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let address = pod_manager.wallet_address("main")?;
+    /// println!("Wallet 'main' receives at {address}");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Related Functions
+    ///
+    /// - [`active_wallet_address`] - Get the address of the active wallet
+    /// - [`get_wallet_addresses`] - Get the addresses of all wallets
+    pub fn wallet_address(&self, name: &str) -> Result<String, Error> {
+        Ok(self.key_store.get_wallet_checksum_address(name)?)
+    }
+
     /// Retrieves all wallet keys from the key store.
     ///
     /// # Returns
@@ -4201,3 +10877,460 @@ impl<'a> PodManager<'a> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Stand-in for the network client that fails a set number of times before answering
+    struct MockClient {
+        failures: u32,
+        permanent: bool,
+        calls: std::sync::atomic::AtomicU32,
+    }
+
+    #[derive(Debug)]
+    struct MockError {
+        transient: bool,
+    }
+
+    impl std::fmt::Display for MockError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "mock error, transient: {}", self.transient)
+        }
+    }
+
+    impl TransientError for MockError {
+        fn is_transient(&self) -> bool {
+            self.transient
+        }
+    }
+
+    impl MockClient {
+        async fn pointer_get(&self) -> Result<&'static str, MockError> {
+            let call = self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            if call < self.failures {
+                Err(MockError {
+                    transient: !self.permanent,
+                })
+            } else {
+                Ok("pointer")
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retry_with_backoff() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        // Transient failures are retried until the operation succeeds
+        let client = MockClient {
+            failures: 2,
+            permanent: false,
+            calls: AtomicU32::new(0),
+        };
+        let result = retry_with_backoff(3, Duration::from_millis(1), || client.pointer_get()).await;
+        assert_eq!(result.unwrap(), "pointer");
+        assert_eq!(client.calls.load(Ordering::SeqCst), 3);
+
+        // Retries stop after the last attempt
+        let client = MockClient {
+            failures: 2,
+            permanent: false,
+            calls: AtomicU32::new(0),
+        };
+        let result = retry_with_backoff(2, Duration::from_millis(1), || client.pointer_get()).await;
+        assert!(result.unwrap_err().transient);
+        assert_eq!(client.calls.load(Ordering::SeqCst), 2);
+
+        // Permanent failures like a missing record are returned right away
+        let client = MockClient {
+            failures: 2,
+            permanent: true,
+            calls: AtomicU32::new(0),
+        };
+        let result = retry_with_backoff(3, Duration::from_millis(1), || client.pointer_get()).await;
+        assert!(!result.unwrap_err().transient);
+        assert_eq!(client.calls.load(Ordering::SeqCst), 1);
+    }
+
+    // Stand-in for the network serving pods with fixed pointer counters, counting the scratchpads
+    // that are downloaded
+    #[derive(Default)]
+    struct StubNetwork {
+        pointers: HashMap<String, Pointer>,
+        scratchpads: HashMap<String, Scratchpad>,
+        scratchpad_gets: AtomicUsize,
+    }
+
+    impl StubNetwork {
+        // Publish a pod whose pointer on the network has the given counter
+        fn add_pod(&mut self, counter: u64) -> PointerAddress {
+            let key = SecretKey::random();
+            let scratchpad_address = ScratchpadAddress::new(key.public_key());
+            let data = Bytes::from(format!("pod version {counter}"));
+            let scratchpad = Scratchpad::new_with_signature(
+                key.public_key(),
+                0,
+                data.clone(),
+                counter,
+                key.sign(Scratchpad::bytes_for_signature(
+                    scratchpad_address,
+                    0,
+                    &data,
+                    counter,
+                )),
+            );
+            let pointer = Pointer::new(
+                &key,
+                counter,
+                PointerTarget::ScratchpadAddress(scratchpad_address),
+            );
+            let pointer_address = pointer.address();
+            self.scratchpads
+                .insert(scratchpad_address.to_hex(), scratchpad);
+            self.pointers.insert(pointer_address.to_hex(), pointer);
+            pointer_address
+        }
+
+        // Publish a pointer signed with the given key, targeting the given scratchpad
+        fn add_pointer(&mut self, key: &SecretKey, target: ScratchpadAddress) -> PointerAddress {
+            let pointer = Pointer::new(key, 0, PointerTarget::ScratchpadAddress(target));
+            let pointer_address = pointer.address();
+            self.pointers.insert(pointer_address.to_hex(), pointer);
+            pointer_address
+        }
+    }
+
+    impl RefreshNetwork for StubNetwork {
+        fn pointer_get<'a>(
+            &'a self,
+            address: &'a PointerAddress,
+        ) -> BoxFuture<'a, Result<Pointer, PointerError>> {
+            let pointer = self.pointers.get(&address.to_hex()).cloned();
+            Box::pin(async move { pointer.ok_or(PointerError::GetError(GetError::RecordNotFound)) })
+        }
+
+        fn scratchpad_get<'a>(
+            &'a self,
+            address: &'a ScratchpadAddress,
+        ) -> BoxFuture<'a, Result<Scratchpad, ScratchpadError>> {
+            self.scratchpad_gets.fetch_add(1, Ordering::SeqCst);
+            let scratchpad = self.scratchpads.get(&address.to_hex()).cloned();
+            Box::pin(async move {
+                scratchpad.ok_or(ScratchpadError::GetError(GetError::RecordNotFound))
+            })
+        }
+    }
+
+    // Refresh the configuration pod and the pods it lists from the stub network, making the same
+    // download decisions as refresh_cache_internal. Each pod is given with its local pointer
+    // counter, None for pods not cached yet. Returns the data downloaded and the data store
+    async fn refresh_pods(
+        network: &StubNetwork,
+        mode: RefreshMode,
+        force: bool,
+        configuration: (PointerAddress, Option<u64>),
+        pods: &[(PointerAddress, Option<u64>)],
+    ) -> (Vec<String>, DataStore, tempfile::TempDir) {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let data_store = DataStore::from_paths(
+            temp_dir.path().join("data"),
+            temp_dir.path().join("pods"),
+            temp_dir.path().join("downloads"),
+        )
+        .unwrap();
+        for (address, local_counter) in std::iter::once(&configuration).chain(pods) {
+            if let Some(local_counter) = local_counter {
+                data_store.create_pointer_file(&address.to_hex()).unwrap();
+                data_store
+                    .update_pointer_count(&address.to_hex(), *local_counter)
+                    .unwrap();
+            }
+        }
+
+        let configuration_address = configuration.0.to_hex();
+        let (pointer, download_configuration) =
+            check_configuration_pointer(network, &data_store, &configuration_address, mode, force)
+                .await
+                .unwrap()
+                .unwrap();
+        let mut scratchpad_operations = Vec::new();
+        if download_configuration {
+            let target = PodManager::pointer_scratchpad_target(&pointer).unwrap();
+            scratchpad_operations.push((configuration_address, target, true, pointer.counter()));
+        }
+
+        let config = PodManagerConfig {
+            refresh_mode: mode,
+            max_concurrency: 4,
+            retry_attempts: 1,
+            retry_base_delay: Duration::ZERO,
+            ..PodManagerConfig::default()
+        };
+        let pointer_addresses = pods
+            .iter()
+            .map(|(address, _)| (address.to_hex(), *address))
+            .collect();
+        let (pod_operations, _) = plan_pod_downloads(
+            network,
+            &data_store,
+            pointer_addresses,
+            &HashSet::new(),
+            &config,
+            force,
+            |_, _| {},
+        )
+        .await
+        .unwrap();
+        scratchpad_operations.extend(pod_operations);
+
+        let fork_reports = std::sync::Mutex::new(Vec::new());
+        let downloaded = fetch_main_scratchpads(
+            network,
+            &scratchpad_operations,
+            1,
+            Duration::ZERO,
+            4,
+            &fork_reports,
+        )
+        .await
+        .into_iter()
+        .map(|result| result.unwrap().2)
+        .collect();
+        (downloaded, data_store, temp_dir)
+    }
+
+    #[tokio::test]
+    async fn test_refresh_mode_counters() {
+        let mut network = StubNetwork::default();
+        let configuration = network.add_pod(7);
+        let unchanged = network.add_pod(5);
+        let updated = network.add_pod(6);
+        let uncached = network.add_pod(3);
+        let pods = [(unchanged, Some(5)), (updated, Some(5)), (uncached, None)];
+
+        // The configuration pod is current, but the pods that changed on the network are still
+        // downloaded
+        let (downloaded, data_store, _temp_dir) = refresh_pods(
+            &network,
+            RefreshMode::CounterAware,
+            false,
+            (configuration, Some(7)),
+            &pods,
+        )
+        .await;
+        assert_eq!(downloaded, vec!["pod version 6", "pod version 3"]);
+        assert_eq!(network.scratchpad_gets.load(Ordering::SeqCst), 2);
+        assert_eq!(data_store.get_pointer_count(&uncached.to_hex()).unwrap(), 3);
+
+        // Always downloads the configuration pod too, but still skips the unchanged pod
+        network.scratchpad_gets.store(0, Ordering::SeqCst);
+        let (downloaded, _, _temp_dir) = refresh_pods(
+            &network,
+            RefreshMode::Always,
+            false,
+            (configuration, Some(7)),
+            &pods,
+        )
+        .await;
+        assert_eq!(
+            downloaded,
+            vec!["pod version 7", "pod version 6", "pod version 3"]
+        );
+        assert_eq!(network.scratchpad_gets.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_refresh_cache_force() {
+        let mut network = StubNetwork::default();
+        let configuration = network.add_pod(7);
+        let first = network.add_pod(5);
+        let second = network.add_pod(9);
+        let pods = [(first, Some(5)), (second, Some(9))];
+
+        // Nothing changed on the network, so no scratchpad is downloaded
+        let (downloaded, _, _temp_dir) = refresh_pods(
+            &network,
+            RefreshMode::CounterAware,
+            false,
+            (configuration, Some(7)),
+            &pods,
+        )
+        .await;
+        assert!(downloaded.is_empty());
+        assert_eq!(network.scratchpad_gets.load(Ordering::SeqCst), 0);
+
+        // Forcing the refresh downloads every pod despite the up to date counters
+        let (downloaded, _, _temp_dir) = refresh_pods(
+            &network,
+            RefreshMode::CounterAware,
+            true,
+            (configuration, Some(7)),
+            &pods,
+        )
+        .await;
+        assert_eq!(
+            downloaded,
+            vec!["pod version 7", "pod version 5", "pod version 9"]
+        );
+        assert_eq!(network.scratchpad_gets.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_operation_handle_cancellation_token() {
+        let handle = OperationHandle::default();
+        let earlier = handle.clone();
+        let token = Arc::new(AtomicBool::new(false));
+        handle.set_token(token.clone());
+        assert!(!earlier.is_cancelled());
+
+        // Handles cloned before the token was set see it too, and the token is not cleared
+        token.store(true, Ordering::SeqCst);
+        assert!(earlier.is_cancelled());
+        assert!(earlier.check_cancelled("Refresh").is_err());
+        assert!(token.load(Ordering::SeqCst));
+        assert!(handle.check_cancelled("Refresh").is_err());
+
+        token.store(false, Ordering::SeqCst);
+        assert!(handle.check_cancelled("Refresh").is_ok());
+
+        // A cancel() request is cleared by the operation it stopped
+        earlier.cancel();
+        assert!(handle.check_cancelled("Refresh").is_err());
+        assert!(handle.check_cancelled("Refresh").is_ok());
+    }
+
+    #[test]
+    fn test_transient_errors() {
+        // Missing records are permanent, other get failures may go away on a retry
+        assert!(!PointerError::GetError(GetError::RecordNotFound).is_transient());
+        assert!(!ScratchpadError::GetError(GetError::RecordNotFound).is_transient());
+    }
+
+    #[test]
+    fn test_pointer_scratchpad_target() {
+        let key = SecretKey::random();
+
+        // A pod pointer targeting its first scratchpad
+        let scratchpad_address = ScratchpadAddress::new(SecretKey::random().public_key());
+        let pointer = Pointer::new(
+            &key,
+            0,
+            PointerTarget::ScratchpadAddress(scratchpad_address),
+        );
+        assert_eq!(
+            PodManager::pointer_scratchpad_target(&pointer).unwrap(),
+            scratchpad_address
+        );
+
+        // A pointer targeting another pointer is reported with its actual target type
+        let pointer_address = PointerAddress::new(SecretKey::random().public_key());
+        let pointer = Pointer::new(&key, 0, PointerTarget::PointerAddress(pointer_address));
+        let reason = PodManager::pointer_scratchpad_target(&pointer)
+            .unwrap_err()
+            .to_string();
+        assert!(reason.contains("targets a pointer"));
+        assert!(reason.contains(&pointer_address.to_hex()));
+    }
+
+    #[test]
+    fn test_pointer_is_tombstone() {
+        let key = SecretKey::random();
+
+        // A live pod pointer targets its first scratchpad
+        let scratchpad_address = ScratchpadAddress::new(SecretKey::random().public_key());
+        let pointer = Pointer::new(
+            &key,
+            0,
+            PointerTarget::ScratchpadAddress(scratchpad_address),
+        );
+        assert!(!PodManager::pointer_is_tombstone(&pointer));
+
+        // upload_all removes a pod by pointing the pointer at its own address
+        let own_address = ScratchpadAddress::from_hex(&pointer.address().to_hex()).unwrap();
+        let pointer = Pointer::new(&key, 1, PointerTarget::ScratchpadAddress(own_address));
+        assert!(PodManager::pointer_is_tombstone(&pointer));
+    }
+
+    #[tokio::test]
+    async fn test_scan_owned_keys_after_tombstone() {
+        let mut key_store = KeyStore::from_mnemonic(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+        )
+        .unwrap();
+        key_store.add_pointer_key().unwrap();
+        key_store.add_free_scratchpad_key().unwrap();
+        let first_index = key_store.get_num_derived_keys();
+
+        // Another device removed one pod and created another with the next two keys
+        let mut other_device = key_store.clone();
+        let (_, tombstone_secret) = other_device
+            .add_key_at_index(first_index, KeyKind::Pointer)
+            .unwrap();
+        let (_, live_secret) = other_device
+            .add_key_at_index(first_index + 1, KeyKind::Pointer)
+            .unwrap();
+        let tombstone_key = SecretKey::from_hex(&tombstone_secret).unwrap();
+        let live_key = SecretKey::from_hex(&live_secret).unwrap();
+
+        let mut network = StubNetwork::default();
+        let own_address = PointerAddress::new(tombstone_key.public_key()).to_hex();
+        let tombstone_address = network
+            .add_pointer(
+                &tombstone_key,
+                ScratchpadAddress::from_hex(&own_address).unwrap(),
+            )
+            .to_hex();
+        let live_address = network
+            .add_pointer(
+                &live_key,
+                ScratchpadAddress::new(SecretKey::random().public_key()),
+            )
+            .to_hex();
+
+        let keys = scan_owned_keys(
+            &network,
+            &mut key_store,
+            first_index,
+            4,
+            1,
+            Duration::ZERO,
+            4,
+        )
+        .await
+        .unwrap();
+        let kinds: Vec<(u64, String, KeyKind)> = keys
+            .into_iter()
+            .map(|(index, address, kind, _)| (index, address, kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (first_index, tombstone_address.clone(), KeyKind::FreePointer),
+                (first_index + 1, live_address.clone(), KeyKind::Pointer),
+            ]
+        );
+
+        // Both keys are recorded at the index they were derived at
+        assert_eq!(
+            key_store.index_of_address(&tombstone_address),
+            Some(first_index)
+        );
+        assert_eq!(
+            key_store.index_of_address(&live_address),
+            Some(first_index + 1)
+        );
+        assert_eq!(
+            key_store.get_pointers().get(&live_address),
+            Some(&live_secret)
+        );
+
+        // The next new pod gets the first key after the scanned ones
+        let (next_address, _) = key_store.add_next_pointer_key().unwrap();
+        assert_eq!(
+            key_store.index_of_address(&next_address),
+            Some(first_index + 2)
+        );
+    }
+}
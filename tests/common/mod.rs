@@ -1,6 +1,12 @@
-use colonylib::{DataStore, Graph, KeyStore};
+use autonomi::{Client, Wallet};
+use colonylib::{DataStore, Graph, KeyStore, PodManager};
 use tempfile::TempDir;
 
+/// Funded key of the local testnet
+#[allow(dead_code)]
+pub const LOCAL_PRIVATE_KEY: &str =
+    "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
 /// Helper function to create test components for integration tests
 #[allow(dead_code)]
 pub fn create_test_components() -> (DataStore, KeyStore, Graph, TempDir) {
@@ -21,6 +27,32 @@ pub fn create_test_components() -> (DataStore, KeyStore, Graph, TempDir) {
     (data_store, key_store, graph, temp_dir)
 }
 
+/// Helper function to connect to the local testnet, returning the client and a funded wallet
+#[allow(dead_code)]
+pub async fn create_test_client() -> (Client, Wallet) {
+    let client = Client::init_local()
+        .await
+        .expect("Failed to create test client");
+    let evm_network = client.evm_network().clone();
+    let wallet = Wallet::new_from_private_key(evm_network, LOCAL_PRIVATE_KEY)
+        .expect("Failed to create test wallet");
+    (client, wallet)
+}
+
+/// Helper function to create a PodManager on the local testnet from test components
+#[allow(dead_code)]
+pub async fn create_test_pod_manager<'a>(
+    client: Client,
+    wallet: &'a Wallet,
+    data_store: &'a mut DataStore,
+    key_store: &'a mut KeyStore,
+    graph: &'a mut Graph,
+) -> PodManager<'a> {
+    PodManager::new(client, wallet, data_store, key_store, graph)
+        .await
+        .expect("Failed to create PodManager")
+}
+
 /// Helper function to create a test DataStore
 #[allow(dead_code)]
 pub fn create_test_datastore() -> (DataStore, TempDir) {
@@ -1,5 +1,7 @@
 mod common;
 use common::create_test_datastore;
+use std::collections::HashSet;
+use std::time::Duration;
 
 #[test]
 fn test_datastore_creation() {
@@ -135,3 +137,432 @@ fn test_duplicate_update_list_entries() {
     assert_eq!(update_list.pods.len(), 1);
     assert!(update_list.pods.contains_key(address));
 }
+
+#[test]
+fn test_find_orphan_files() {
+    let (datastore, _temp_dir) = create_test_datastore();
+
+    // Files that belong to a pod
+    datastore.create_pointer_file("known_pointer").unwrap();
+    datastore
+        .create_scratchpad_file("known_scratchpad")
+        .unwrap();
+
+    // Files left behind with no pod referencing them
+    datastore.create_pointer_file("orphan_pointer").unwrap();
+    datastore
+        .create_scratchpad_file("orphan_scratchpad")
+        .unwrap();
+
+    let known_addresses: HashSet<String> = ["known_pointer", "known_scratchpad"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+
+    let orphans = datastore.find_orphan_files(&known_addresses).unwrap();
+    assert_eq!(orphans.len(), 2);
+    assert!(orphans.contains(&datastore.get_pointers_dir().join("orphan_pointer")));
+    assert!(orphans.contains(&datastore.get_scratchpads_dir().join("orphan_scratchpad")));
+
+    // Nothing is orphaned once every address is known
+    let mut all_addresses = known_addresses.clone();
+    all_addresses.insert("orphan_pointer".to_string());
+    all_addresses.insert("orphan_scratchpad".to_string());
+    assert!(
+        datastore
+            .find_orphan_files(&all_addresses)
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_refresh_ttl() {
+    use colonylib::clock::{Clock, FakeClock};
+
+    let (datastore, _temp_dir) = create_test_datastore();
+    let clock = FakeClock::new(
+        chrono::DateTime::parse_from_rfc3339("2025-06-01T08:00:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc),
+    );
+
+    let address = "ttl_pod_address";
+    let ttl = Duration::from_secs(300);
+
+    // A pod that was never refreshed is always due
+    assert_eq!(datastore.get_last_refreshed(address).unwrap(), None);
+    assert!(
+        !datastore
+            .refreshed_within(address, ttl, clock.now())
+            .unwrap()
+    );
+
+    // Right after a refresh the pod is skipped
+    datastore
+        .update_last_refreshed(address, clock.now())
+        .unwrap();
+    assert_eq!(
+        datastore.get_last_refreshed(address).unwrap(),
+        Some(clock.now().timestamp_millis())
+    );
+    assert!(
+        datastore
+            .refreshed_within(address, ttl, clock.now())
+            .unwrap()
+    );
+
+    // A zero TTL never skips
+    assert!(
+        !datastore
+            .refreshed_within(address, Duration::ZERO, clock.now())
+            .unwrap()
+    );
+
+    // The pod is skipped until the TTL has passed
+    clock.advance(chrono::Duration::seconds(299));
+    assert!(
+        datastore
+            .refreshed_within(address, ttl, clock.now())
+            .unwrap()
+    );
+    clock.advance(chrono::Duration::seconds(1));
+    assert!(
+        !datastore
+            .refreshed_within(address, ttl, clock.now())
+            .unwrap()
+    );
+}
+
+#[test]
+fn test_configuration_pending() {
+    let (datastore, _temp_dir) = create_test_datastore();
+
+    assert!(!datastore.configuration_pending());
+
+    datastore.set_configuration_pending(true).unwrap();
+    assert!(datastore.configuration_pending());
+    assert!(datastore.get_configuration_pending_path().exists());
+
+    // Setting it again is harmless
+    datastore.set_configuration_pending(true).unwrap();
+    assert!(datastore.configuration_pending());
+
+    datastore.set_configuration_pending(false).unwrap();
+    assert!(!datastore.configuration_pending());
+    datastore.set_configuration_pending(false).unwrap();
+    assert!(!datastore.configuration_pending());
+}
+
+#[test]
+fn test_local_encryption_round_trip() {
+    let (mut datastore, _temp_dir) = create_test_datastore();
+
+    let scratchpad_address = "encrypted_scratchpad";
+    let pointer_address = "encrypted_pointer";
+    let data = "<ant://subject> <http://schema.org/name> \"Secret\" .";
+
+    datastore.set_local_encryption(Some("local password"));
+    assert!(datastore.local_encryption_enabled());
+
+    // Scratchpad data round trips and is not stored as plaintext
+    datastore
+        .create_scratchpad_file(scratchpad_address)
+        .unwrap();
+    datastore
+        .update_scratchpad_data(scratchpad_address, data)
+        .unwrap();
+    assert_eq!(
+        datastore.get_scratchpad_data(scratchpad_address).unwrap(),
+        data
+    );
+    let on_disk = std::fs::read(datastore.get_scratchpads_dir().join(scratchpad_address)).unwrap();
+    assert!(!String::from_utf8_lossy(&on_disk).contains("Secret"));
+
+    // Pointer target and count round trip
+    datastore.create_pointer_file(pointer_address).unwrap();
+    datastore
+        .update_pointer_target(pointer_address, scratchpad_address)
+        .unwrap();
+    datastore.update_pointer_count(pointer_address, 42).unwrap();
+    assert_eq!(
+        datastore.get_pointer_target(pointer_address).unwrap(),
+        scratchpad_address
+    );
+    assert_eq!(datastore.get_pointer_count(pointer_address).unwrap(), 42);
+    let on_disk = std::fs::read(datastore.get_pointers_dir().join(pointer_address)).unwrap();
+    assert!(!String::from_utf8_lossy(&on_disk).contains(scratchpad_address));
+
+    // The wrong password cannot read the files
+    datastore.set_local_encryption(Some("wrong password"));
+    assert!(datastore.get_scratchpad_data(scratchpad_address).is_err());
+}
+
+#[test]
+fn test_local_encryption_reads_plaintext_files() {
+    let (mut datastore, _temp_dir) = create_test_datastore();
+
+    let address = "plaintext_scratchpad";
+    let data = "plaintext data written before encryption";
+
+    // Written without encryption
+    datastore.create_scratchpad_file(address).unwrap();
+    datastore.update_scratchpad_data(address, data).unwrap();
+
+    // Still readable once encryption is enabled, and encrypted on the next write
+    datastore.set_local_encryption(Some("local password"));
+    assert_eq!(datastore.get_scratchpad_data(address).unwrap(), data);
+    datastore.update_scratchpad_data(address, data).unwrap();
+    let on_disk = std::fs::read(datastore.get_scratchpads_dir().join(address)).unwrap();
+    assert_ne!(on_disk, data.as_bytes());
+    assert_eq!(datastore.get_scratchpad_data(address).unwrap(), data);
+
+    // Disabling encryption stores new writes as plaintext again
+    datastore.set_local_encryption(None);
+    datastore.update_scratchpad_data(address, data).unwrap();
+    let on_disk = std::fs::read(datastore.get_scratchpads_dir().join(address)).unwrap();
+    assert_eq!(on_disk, data.as_bytes());
+}
+
+#[test]
+fn test_disk_usage() {
+    let (datastore, _temp_dir) = create_test_datastore();
+
+    let empty = datastore.disk_usage().unwrap();
+    assert_eq!(empty.pointers, 0);
+    assert_eq!(empty.scratchpads, 0);
+    assert_eq!(empty.graph, 0);
+
+    datastore.create_pointer_file("usage_pointer").unwrap();
+    datastore
+        .update_pointer_target("usage_pointer", "usage_scratchpad")
+        .unwrap();
+    datastore
+        .create_scratchpad_file("usage_scratchpad")
+        .unwrap();
+    datastore
+        .update_scratchpad_data("usage_scratchpad", &"x".repeat(1000))
+        .unwrap();
+
+    // The graph database is a directory, nested files are counted too
+    let graph_dir = datastore.get_graph_path();
+    std::fs::create_dir_all(graph_dir.join("nested")).unwrap();
+    std::fs::write(graph_dir.join("nested").join("000001.sst"), vec![0u8; 500]).unwrap();
+    std::fs::write(graph_dir.join("CURRENT"), vec![0u8; 16]).unwrap();
+
+    std::fs::write(
+        datastore.get_downloads_path().join("file.bin"),
+        vec![0u8; 250],
+    )
+    .unwrap();
+
+    let usage = datastore.disk_usage().unwrap();
+    assert!(usage.pointers > 0);
+    assert_eq!(usage.scratchpads, 1000);
+    assert_eq!(usage.graph, 516);
+    assert_eq!(usage.downloads, empty.downloads + 250);
+    assert_eq!(
+        usage.total,
+        usage.pointers + usage.scratchpads + usage.graph + usage.downloads
+    );
+}
+
+#[test]
+fn test_storage_stats() {
+    let (datastore, _temp_dir) = create_test_datastore();
+
+    let empty = datastore.storage_stats().unwrap();
+    assert_eq!(empty.num_pointers, 0);
+    assert_eq!(empty.num_scratchpads, 0);
+    assert_eq!(empty.total_scratchpad_bytes, 0);
+
+    datastore.create_pointer_file("stats_pointer").unwrap();
+    datastore
+        .update_pointer_target("stats_pointer", "stats_scratchpad_1")
+        .unwrap();
+    for (address, size) in [("stats_scratchpad_1", 1000), ("stats_scratchpad_2", 234)] {
+        datastore.create_scratchpad_file(address).unwrap();
+        datastore
+            .update_scratchpad_data(address, &"x".repeat(size))
+            .unwrap();
+    }
+    std::fs::write(
+        datastore.get_downloads_path().join("stats_file.bin"),
+        vec![0u8; 300],
+    )
+    .unwrap();
+
+    let stats = datastore.storage_stats().unwrap();
+    assert_eq!(stats.num_pointers, 1);
+    assert_eq!(stats.num_scratchpads, 2);
+    assert_eq!(stats.total_scratchpad_bytes, 1234);
+    assert_eq!(stats.downloads_bytes, empty.downloads_bytes + 300);
+}
+
+#[test]
+fn test_list_pointer_addresses() {
+    let (datastore, _temp_dir) = create_test_datastore();
+    assert!(datastore.list_pointer_addresses().unwrap().is_empty());
+
+    datastore.create_pointer_file("pointer_b").unwrap();
+    datastore.create_pointer_file("pointer_a").unwrap();
+    datastore.create_scratchpad_file("scratchpad_c").unwrap();
+
+    // Only pointer files are listed, in sorted order
+    assert_eq!(
+        datastore.list_pointer_addresses().unwrap(),
+        vec!["pointer_a".to_string(), "pointer_b".to_string()]
+    );
+}
+
+#[test]
+fn test_list_scratchpad_files() {
+    let (datastore, _temp_dir) = create_test_datastore();
+    assert!(datastore.list_scratchpad_files().unwrap().is_empty());
+
+    datastore.create_scratchpad_file("scratchpad_b").unwrap();
+    datastore.create_scratchpad_file("scratchpad_a").unwrap();
+    datastore.create_pointer_file("pointer_c").unwrap();
+
+    // Only scratchpad files are listed, in sorted order
+    assert_eq!(
+        datastore.list_scratchpad_files().unwrap(),
+        vec!["scratchpad_a".to_string(), "scratchpad_b".to_string()]
+    );
+}
+
+#[test]
+fn test_verify_scratchpad() {
+    let (datastore, _temp_dir) = create_test_datastore();
+    let address = "checked_scratchpad";
+
+    // Without a recorded checksum there is nothing to compare against
+    datastore.create_scratchpad_file(address).unwrap();
+    assert!(datastore.verify_scratchpad(address).unwrap());
+
+    datastore
+        .update_scratchpad_data(address, "some pod data")
+        .unwrap();
+    assert!(datastore.verify_scratchpad(address).unwrap());
+
+    // Corrupt the file behind the data store's back
+    std::fs::write(
+        datastore.get_scratchpads_dir().join(address),
+        "some pod dat@",
+    )
+    .unwrap();
+    assert!(!datastore.verify_scratchpad(address).unwrap());
+
+    // Writing it again records a fresh checksum
+    datastore
+        .update_scratchpad_data(address, "some pod data")
+        .unwrap();
+    assert!(datastore.verify_scratchpad(address).unwrap());
+
+    // Removing the scratchpad removes its checksum too
+    datastore.remove_scratchpad_file(address).unwrap();
+    assert!(datastore.verify_scratchpad(address).unwrap());
+    assert!(
+        std::fs::read_dir(datastore.get_checksums_dir())
+            .unwrap()
+            .next()
+            .is_none()
+    );
+}
+
+#[test]
+fn test_subject_changes() {
+    let (datastore, _temp_dir) = create_test_datastore();
+    assert!(
+        datastore
+            .get_subjects_changed_since("pod", 0)
+            .unwrap()
+            .is_empty()
+    );
+
+    datastore
+        .record_subject_changes("pod", 3, &["song_b".to_string(), "song_a".to_string()])
+        .unwrap();
+    datastore
+        .record_subject_changes("pod", 5, &["song_a".to_string(), "song_c".to_string()])
+        .unwrap();
+    datastore
+        .record_subject_changes("other_pod", 9, &["song_d".to_string()])
+        .unwrap();
+
+    assert_eq!(
+        datastore.get_subjects_changed_since("pod", 0).unwrap(),
+        vec!["song_a", "song_b", "song_c"]
+    );
+    assert_eq!(
+        datastore.get_subjects_changed_since("pod", 3).unwrap(),
+        vec!["song_a", "song_c"]
+    );
+    assert!(
+        datastore
+            .get_subjects_changed_since("pod", 5)
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_record_removed_pod() {
+    let (datastore, _temp_dir) = create_test_datastore();
+    assert_eq!(datastore.get_removed_pod_scratchpads("pod").unwrap(), None);
+
+    let scratchpads = vec!["scratchpad_a".to_string(), "scratchpad_b".to_string()];
+    datastore.record_removed_pod("pod", &scratchpads).unwrap();
+    datastore.record_removed_pod("other_pod", &[]).unwrap();
+
+    assert_eq!(
+        datastore.get_removed_pod_scratchpads("pod").unwrap(),
+        Some(scratchpads)
+    );
+    assert_eq!(
+        datastore.get_removed_pod_scratchpads("other_pod").unwrap(),
+        Some(Vec::new())
+    );
+}
+
+#[test]
+fn test_hidden_pods() {
+    let (datastore, _temp_dir) = create_test_datastore();
+    assert!(datastore.get_hidden_pods().unwrap().is_empty());
+
+    datastore.set_pod_hidden("pod_b", true).unwrap();
+    datastore.set_pod_hidden("pod_a", true).unwrap();
+    datastore.set_pod_hidden("pod_a", true).unwrap();
+    assert_eq!(
+        datastore.get_hidden_pods().unwrap(),
+        vec!["pod_a".to_string(), "pod_b".to_string()]
+    );
+
+    datastore.set_pod_hidden("pod_b", false).unwrap();
+    datastore.set_pod_hidden("never_hidden", false).unwrap();
+    assert_eq!(
+        datastore.get_hidden_pods().unwrap(),
+        vec!["pod_a".to_string()]
+    );
+}
+
+#[test]
+fn test_pod_versions() {
+    let (datastore, _temp_dir) = create_test_datastore();
+    assert!(datastore.list_pod_versions("pod").unwrap().is_empty());
+
+    datastore.save_pod_version("pod", 10, "version 10").unwrap();
+    datastore.save_pod_version("pod", 2, "version 2").unwrap();
+    datastore.save_pod_version("pod", 7, "version 7").unwrap();
+    datastore.save_pod_version("other_pod", 1, "other").unwrap();
+
+    assert_eq!(datastore.list_pod_versions("pod").unwrap(), vec![2, 7, 10]);
+    assert_eq!(datastore.get_pod_version("pod", 7).unwrap(), "version 7");
+    assert!(datastore.get_pod_version("pod", 3).is_err());
+
+    // Only the newest versions survive pruning
+    assert_eq!(datastore.prune_pod_versions("pod", 2).unwrap(), vec![2]);
+    assert_eq!(datastore.list_pod_versions("pod").unwrap(), vec![7, 10]);
+    assert!(datastore.prune_pod_versions("pod", 5).unwrap().is_empty());
+    assert_eq!(datastore.list_pod_versions("other_pod").unwrap(), vec![1]);
+}
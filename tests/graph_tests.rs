@@ -238,7 +238,7 @@ fn test_search_content() {
         .unwrap();
 
     // Test text search
-    let results = graph.search_content("test", Some(10)).unwrap();
+    let results = graph.search_content("test", Some(10), None).unwrap();
     let parsed_results: serde_json::Value = serde_json::from_str(&results).unwrap();
 
     // Should find results containing "test"
@@ -247,16 +247,30 @@ fn test_search_content() {
     assert!(!bindings.is_empty());
 
     // Test case-insensitive search
-    let results = graph.search_content("TEST", Some(10)).unwrap();
+    let results = graph.search_content("TEST", Some(10), None).unwrap();
     let parsed_results: serde_json::Value = serde_json::from_str(&results).unwrap();
     let bindings = parsed_results["results"]["bindings"].as_array().unwrap();
     assert!(!bindings.is_empty());
 
     // Test search with no results
-    let results = graph.search_content("nonexistent", Some(10)).unwrap();
+    let results = graph.search_content("nonexistent", Some(10), None).unwrap();
     let parsed_results: serde_json::Value = serde_json::from_str(&results).unwrap();
     let bindings = parsed_results["results"]["bindings"].as_array().unwrap();
     assert_eq!(bindings.len(), 0);
+
+    // Backslashes in the search text are matched literally instead of breaking the query
+    graph
+        .put_quad(
+            "ant://subject4",
+            "ant://colonylib/vocabulary/0.1/predicate#name",
+            "C:\\Music\\Beatles",
+            Some(&pod_iri),
+        )
+        .unwrap();
+    let results = graph.search_content("c:\\music", Some(10), None).unwrap();
+    let parsed_results: serde_json::Value = serde_json::from_str(&results).unwrap();
+    let bindings = parsed_results["results"]["bindings"].as_array().unwrap();
+    assert!(!bindings.is_empty());
 }
 
 #[test]
@@ -287,7 +301,7 @@ fn test_search_by_type() {
 
     // Test type search
     let results = graph
-        .search_by_type("http://schema.org/MediaObject", Some(10))
+        .search_by_type("http://schema.org/MediaObject", Some(10), None)
         .unwrap();
     let parsed_results: serde_json::Value = serde_json::from_str(&results).unwrap();
 
@@ -327,7 +341,11 @@ fn test_search_by_predicate() {
 
     // Test predicate search
     let results = graph
-        .search_by_predicate("ant://colonylib/vocabulary/0.1/predicate#name", Some(10))
+        .search_by_predicate(
+            "ant://colonylib/vocabulary/0.1/predicate#name",
+            Some(10),
+            None,
+        )
         .unwrap();
     let parsed_results: serde_json::Value = serde_json::from_str(&results).unwrap();
 
@@ -588,7 +606,7 @@ fn test_enhanced_search_content() {
         .unwrap();
 
     // Search for content
-    let search_results = graph.search_content("important", Some(10)).unwrap();
+    let search_results = graph.search_content("important", Some(10), None).unwrap();
     assert!(
         !search_results.is_empty(),
         "Search results should not be empty"
@@ -709,7 +727,7 @@ fn test_enhanced_word_based_search() {
 
     // Search for "beatles abbey road" - should return results ordered by match count, then by depth
     let search_results = graph
-        .search_content("beatles abbey road", Some(10))
+        .search_content("beatles abbey road", Some(10), None)
         .unwrap();
     assert!(
         !search_results.is_empty(),
@@ -753,7 +771,7 @@ fn test_enhanced_word_based_search() {
     );
 
     // Test single word search
-    let single_word_results = graph.search_content("beatles", Some(10)).unwrap();
+    let single_word_results = graph.search_content("beatles", Some(10), None).unwrap();
     let single_json: serde_json::Value = serde_json::from_str(&single_word_results).unwrap();
     let single_bindings = single_json["results"]["bindings"].as_array().unwrap();
     assert!(
@@ -762,14 +780,14 @@ fn test_enhanced_word_based_search() {
     );
 
     // Test empty search
-    let empty_results = graph.search_content("", Some(10)).unwrap();
+    let empty_results = graph.search_content("", Some(10), None).unwrap();
     assert_eq!(
         empty_results, "[]",
         "Empty search should return empty array"
     );
 
     // Test search with no matches
-    let no_match_results = graph.search_content("nonexistent", Some(10)).unwrap();
+    let no_match_results = graph.search_content("nonexistent", Some(10), None).unwrap();
     let no_match_json: serde_json::Value = serde_json::from_str(&no_match_results).unwrap();
     let no_match_bindings = no_match_json["results"]["bindings"].as_array().unwrap();
     assert_eq!(
@@ -990,3 +1008,3260 @@ fn test_browse_without_limit() {
         "Results should have proper structure"
     );
 }
+
+#[test]
+fn test_find_subjects_missing() {
+    let (mut graph, _temp_dir) = create_test_graph();
+
+    let pod_address = "test_pod_missing";
+    let other_pod_address = "test_pod_missing_other";
+    let pod_iri = format!("ant://{pod_address}");
+    let other_pod_iri = format!("ant://{other_pod_address}");
+
+    // Pod bookkeeping data should never be reported
+    graph
+        .add_pod_entry(
+            "Missing Pod",
+            pod_address,
+            "test_missing_scratchpad",
+            "test_missing_config",
+            "test_missing_config_scratchpad",
+            0,
+        )
+        .unwrap();
+
+    // One named subject and one unnamed subject in the first pod
+    graph
+        .put_quad(
+            "ant://named_subject",
+            "http://schema.org/name",
+            "Named Subject",
+            Some(&pod_iri),
+        )
+        .unwrap();
+    graph
+        .put_quad(
+            "ant://unnamed_subject",
+            "http://schema.org/description",
+            "No name here",
+            Some(&pod_iri),
+        )
+        .unwrap();
+
+    // An unnamed subject in another pod
+    graph
+        .put_quad(
+            "ant://other_unnamed_subject",
+            "http://schema.org/description",
+            "No name here either",
+            Some(&other_pod_iri),
+        )
+        .unwrap();
+
+    let missing = graph
+        .find_subjects_missing("http://schema.org/name", None)
+        .unwrap();
+    assert_eq!(
+        missing,
+        vec![
+            "other_unnamed_subject".to_string(),
+            "unnamed_subject".to_string()
+        ]
+    );
+
+    let missing_in_pod = graph
+        .find_subjects_missing("http://schema.org/name", Some(pod_address))
+        .unwrap();
+    assert_eq!(missing_in_pod, vec!["unnamed_subject".to_string()]);
+
+    // Invalid predicate IRIs are rejected
+    assert!(graph.find_subjects_missing("not an iri", None).is_err());
+}
+
+#[test]
+fn test_get_subjects_data() {
+    let (mut graph, _temp_dir) = create_test_graph();
+
+    let pod_iri = "ant://test_pod_subjects_data";
+    graph
+        .put_quad(
+            "ant://subject_one",
+            "http://schema.org/name",
+            "Subject One",
+            Some(pod_iri),
+        )
+        .unwrap();
+    graph
+        .put_quad(
+            "ant://subject_one",
+            "http://schema.org/description",
+            "The first subject",
+            Some(pod_iri),
+        )
+        .unwrap();
+    graph
+        .put_quad(
+            "ant://subject_two",
+            "http://schema.org/name",
+            "Subject Two",
+            Some(pod_iri),
+        )
+        .unwrap();
+
+    let subjects = vec![
+        "subject_one".to_string(),
+        "subject_two".to_string(),
+        "subject_without_data".to_string(),
+    ];
+    let subjects_data = graph.get_subjects_data(&subjects).unwrap();
+    assert_eq!(subjects_data.len(), 3);
+
+    let bindings_len = |subject: &str| {
+        subjects_data[subject]["results"]["bindings"]
+            .as_array()
+            .unwrap()
+            .len()
+    };
+    assert_eq!(bindings_len("subject_one"), 2);
+    assert_eq!(bindings_len("subject_two"), 1);
+    assert_eq!(bindings_len("subject_without_data"), 0);
+
+    // Bindings match the single subject query layout
+    let binding = &subjects_data["subject_two"]["results"]["bindings"][0];
+    assert_eq!(binding["predicate"]["value"], "http://schema.org/name");
+    assert_eq!(binding["object"]["value"], "Subject Two");
+    assert_eq!(binding["graph"]["value"], pod_iri);
+    assert!(binding.get("subject").is_none());
+
+    // No subjects means no query and an empty map
+    assert!(graph.get_subjects_data(&[]).unwrap().is_empty());
+}
+
+#[test]
+fn test_pod_operations_never_populate_default_graph() {
+    let (mut graph, _temp_dir) = create_test_graph();
+
+    let pod_address = "test_pod_default_graph";
+    let configuration_address = "test_default_graph_config";
+    assert_eq!(graph.default_graph_triple_count().unwrap(), 0);
+
+    graph
+        .add_pod_entry(
+            "Default Graph Pod",
+            pod_address,
+            "test_default_graph_scratchpad",
+            configuration_address,
+            "test_default_graph_config_scratchpad",
+            0,
+        )
+        .unwrap();
+
+    let data = r#"{
+        "@context": {"schema": "http://schema.org/"},
+        "@id": "ant://default_graph_subject",
+        "schema:name": "Default Graph Subject"
+    }"#;
+    graph
+        .put_subject_data(
+            pod_address,
+            "default_graph_subject",
+            configuration_address,
+            data,
+        )
+        .unwrap();
+
+    // Default graph triples in a pod file are loaded into the pod's named graph
+    let trig_data = r#"<ant://loaded_subject> <http://schema.org/name> "Loaded" ."#;
+    graph.load_pod_into_graph(pod_address, trig_data).unwrap();
+
+    assert_eq!(graph.default_graph_triple_count().unwrap(), 0);
+
+    // Writing without a graph name is detected as stray data
+    graph
+        .put_quad(
+            "ant://stray_subject",
+            "http://schema.org/name",
+            "Stray",
+            None,
+        )
+        .unwrap();
+    assert_eq!(graph.default_graph_triple_count().unwrap(), 1);
+    assert!(graph.ensure_default_graph_unchanged(0, "test").is_err());
+    assert!(graph.ensure_default_graph_unchanged(1, "test").is_ok());
+}
+
+#[test]
+fn test_merge_subject_data() {
+    let (mut graph, _temp_dir) = create_test_graph();
+
+    let pod_address = "test_pod_merge";
+    let configuration_address = "test_merge_config";
+    graph
+        .add_pod_entry(
+            "Merge Pod",
+            pod_address,
+            "test_merge_scratchpad",
+            configuration_address,
+            "test_merge_config_scratchpad",
+            0,
+        )
+        .unwrap();
+
+    let data = r#"{
+        "@context": {"schema": "http://schema.org/"},
+        "@id": "ant://merge_subject",
+        "schema:name": "Original Name",
+        "schema:description": "Original description"
+    }"#;
+    graph
+        .put_subject_data(pod_address, "merge_subject", configuration_address, data)
+        .unwrap();
+
+    // Merge a single predicate
+    let update = r#"{
+        "@context": {"schema": "http://schema.org/"},
+        "@id": "ant://merge_subject",
+        "schema:description": "Updated description"
+    }"#;
+    graph
+        .merge_subject_data(pod_address, "merge_subject", configuration_address, update)
+        .unwrap();
+
+    let results: serde_json::Value =
+        serde_json::from_str(&graph.get_subject_data("merge_subject").unwrap()).unwrap();
+    let bindings = results["results"]["bindings"].as_array().unwrap();
+    let values_for = |predicate: &str| -> Vec<String> {
+        bindings
+            .iter()
+            .filter(|b| b["predicate"]["value"] == predicate)
+            .map(|b| b["object"]["value"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    // The merged predicate is replaced and the other one survives
+    assert_eq!(
+        values_for("http://schema.org/description"),
+        vec!["Updated description".to_string()]
+    );
+    assert_eq!(
+        values_for("http://schema.org/name"),
+        vec!["Original Name".to_string()]
+    );
+}
+
+#[test]
+fn test_validate_jsonld() {
+    let (graph, _temp_dir) = create_test_graph();
+
+    let data = r#"{
+        "@context": {"schema": "http://schema.org/"},
+        "@id": "ant://validate_subject",
+        "@type": ["schema:MusicRecording", "schema:CreativeWork"],
+        "schema:name": "Validated Song",
+        "schema:description": "Not stored anywhere"
+    }"#;
+    let result = graph.validate_jsonld(data).unwrap();
+    assert!(result.valid);
+    assert!(result.warnings.is_empty());
+    assert_eq!(result.triple_count, 4);
+    assert_eq!(
+        result.types,
+        vec![
+            "http://schema.org/CreativeWork".to_string(),
+            "http://schema.org/MusicRecording".to_string()
+        ]
+    );
+
+    // Nothing was written to the persistent store
+    let stored: serde_json::Value =
+        serde_json::from_str(&graph.get_subject_data("validate_subject").unwrap()).unwrap();
+    assert!(stored["results"]["bindings"].as_array().unwrap().is_empty());
+}
+
+#[test]
+fn test_validate_malformed_jsonld() {
+    let (graph, _temp_dir) = create_test_graph();
+
+    // Broken JSON
+    let result = graph
+        .validate_jsonld(r#"{"@id": "ant://broken", "schema:name": "#)
+        .unwrap();
+    assert!(!result.valid);
+    assert!(!result.warnings.is_empty());
+
+    // Invalid @context
+    let result = graph
+        .validate_jsonld(r#"{"@context": 42, "@id": "ant://broken", "name": "Broken"}"#)
+        .unwrap();
+    assert!(!result.valid);
+    assert!(!result.warnings.is_empty());
+}
+
+#[test]
+fn test_export_reference_graph() {
+    use colonylib::graph::{HAS_DEPTH, HAS_NAME, REFERENCES};
+    use oxigraph::io::RdfFormat;
+    use oxigraph::model::{GraphNameRef, LiteralRef, NamedNode, QuadRef};
+    use oxigraph::store::Store;
+
+    let (mut graph, _temp_dir) = create_test_graph();
+
+    let pod_address = "test_pod_export";
+    let ref_pod = "referenced_pod_export";
+    let configuration_address = "test_config_export";
+
+    graph
+        .add_pod_entry(
+            "Exported Pod",
+            pod_address,
+            "scratchpad_export",
+            configuration_address,
+            "test_config_scratchpad_export",
+            0,
+        )
+        .unwrap();
+    graph
+        .pod_ref_entry(pod_address, ref_pod, configuration_address, true, false)
+        .unwrap();
+    graph
+        .put_subject_data(
+            pod_address,
+            "subject_export",
+            configuration_address,
+            r#"{"@context": {"schema": "http://schema.org/"}, "@id": "ant://subject_export", "schema:description": "Left out of the export"}"#,
+        )
+        .unwrap();
+
+    let turtle = graph.export_reference_graph().unwrap();
+
+    // Load the export back in and check its contents
+    let store = Store::new().unwrap();
+    store
+        .load_from_reader(RdfFormat::Turtle, turtle.as_bytes())
+        .unwrap();
+
+    let pod = NamedNode::new(format!("ant://{pod_address}")).unwrap();
+    let reference = NamedNode::new(format!("ant://{ref_pod}")).unwrap();
+    let references = NamedNode::new(REFERENCES).unwrap();
+    let name = NamedNode::new(HAS_NAME).unwrap();
+    let depth = NamedNode::new(HAS_DEPTH).unwrap();
+
+    assert!(
+        store
+            .contains(QuadRef::new(
+                &pod,
+                &references,
+                &reference,
+                GraphNameRef::DefaultGraph,
+            ))
+            .unwrap()
+    );
+    assert!(
+        store
+            .contains(QuadRef::new(
+                &pod,
+                &name,
+                LiteralRef::new_simple_literal("Exported Pod"),
+                GraphNameRef::DefaultGraph,
+            ))
+            .unwrap()
+    );
+    assert!(
+        store
+            .contains(QuadRef::new(
+                &reference,
+                &depth,
+                LiteralRef::new_simple_literal("1"),
+                GraphNameRef::DefaultGraph,
+            ))
+            .unwrap()
+    );
+    assert!(!turtle.contains("Left out of the export"));
+}
+
+#[test]
+fn test_get_pod_graph_data() {
+    let (mut graph, _temp_dir) = create_test_graph();
+
+    let pod_address = "test_pod_dump";
+    let configuration_address = "test_config_dump";
+
+    let (_pod_data, configuration) = graph
+        .add_pod_entry(
+            "Dumped Pod",
+            pod_address,
+            "scratchpad_dump",
+            configuration_address,
+            "test_config_scratchpad_dump",
+            0,
+        )
+        .unwrap();
+
+    // The configuration pod can be rebuilt from the graph at any time
+    let rebuilt = graph.get_pod_graph_data(configuration_address).unwrap();
+    assert_eq!(rebuilt, configuration);
+
+    // Later changes to the configuration graph show up in the rebuilt data
+    graph.update_key_count(configuration_address, 42).unwrap();
+    let rebuilt =
+        String::from_utf8(graph.get_pod_graph_data(configuration_address).unwrap()).unwrap();
+    assert!(rebuilt.contains("\"42\""));
+
+    let pod_data = String::from_utf8(graph.get_pod_graph_data(pod_address).unwrap()).unwrap();
+    assert!(pod_data.contains("Dumped Pod"));
+    assert!(graph.get_pod_graph_data("missing_pod").unwrap().is_empty());
+}
+
+#[test]
+fn test_get_subject_types() {
+    let (mut graph, _temp_dir) = create_test_graph();
+
+    let configuration_address = "test_config_types";
+    for (pod_address, scratchpad_address) in [
+        ("test_pod_types_1", "scratchpad_types_1"),
+        ("test_pod_types_2", "scratchpad_types_2"),
+    ] {
+        graph
+            .add_pod_entry(
+                "Types Pod",
+                pod_address,
+                scratchpad_address,
+                configuration_address,
+                "test_config_scratchpad_types",
+                0,
+            )
+            .unwrap();
+    }
+
+    // The same subject typed in two pods, with one type in common
+    let data = r#"{
+        "@context": {"schema": "http://schema.org/"},
+        "@id": "ant://typed_subject",
+        "@type": ["schema:MusicRecording", "schema:AudioObject"],
+        "schema:name": "Typed Song"
+    }"#;
+    graph
+        .put_subject_data(
+            "test_pod_types_1",
+            "typed_subject",
+            configuration_address,
+            data,
+        )
+        .unwrap();
+    let data = r#"{
+        "@context": {"schema": "http://schema.org/"},
+        "@id": "ant://typed_subject",
+        "@type": "schema:AudioObject"
+    }"#;
+    graph
+        .put_subject_data(
+            "test_pod_types_2",
+            "typed_subject",
+            configuration_address,
+            data,
+        )
+        .unwrap();
+
+    let types = graph.get_subject_types("typed_subject").unwrap();
+    assert_eq!(
+        types,
+        vec![
+            "http://schema.org/AudioObject".to_string(),
+            "http://schema.org/MusicRecording".to_string()
+        ]
+    );
+
+    // Subjects without a type or without any data have no types
+    assert!(
+        graph
+            .get_subject_types("untyped_subject")
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_get_subject_pods() {
+    let (mut graph, _temp_dir) = create_test_graph();
+
+    let configuration_address = "test_config_subject_pods";
+    for (pod_address, scratchpad_address) in [
+        ("test_pod_subject_pods_1", "scratchpad_subject_pods_1"),
+        ("test_pod_subject_pods_2", "scratchpad_subject_pods_2"),
+        ("test_pod_subject_pods_3", "scratchpad_subject_pods_3"),
+    ] {
+        graph
+            .add_pod_entry(
+                "Subject Pods",
+                pod_address,
+                scratchpad_address,
+                configuration_address,
+                "test_config_scratchpad_subject_pods",
+                0,
+            )
+            .unwrap();
+    }
+
+    // The subject is described in the first two pods only
+    let data = r#"{
+        "@context": {"schema": "http://schema.org/"},
+        "@id": "ant://shared_subject",
+        "@type": "schema:MediaObject",
+        "schema:name": "Shared"
+    }"#;
+    for pod_address in ["test_pod_subject_pods_2", "test_pod_subject_pods_1"] {
+        graph
+            .put_subject_data(pod_address, "shared_subject", configuration_address, data)
+            .unwrap();
+    }
+
+    let pods = graph.get_subject_pods("shared_subject").unwrap();
+    assert_eq!(
+        pods,
+        vec!["test_pod_subject_pods_1", "test_pod_subject_pods_2"]
+    );
+
+    // A pod is also listed in the configuration graph, which is left out
+    let pods = graph.get_subject_pods("test_pod_subject_pods_3").unwrap();
+    assert_eq!(pods, vec!["test_pod_subject_pods_3"]);
+
+    assert!(
+        graph
+            .get_subject_pods("unknown_subject")
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_reference_relationship() {
+    use colonylib::graph::Relationship;
+
+    let (mut graph, _temp_dir) = create_test_graph();
+
+    let configuration_address = "test_config_relationship";
+    for (pod_address, scratchpad_address) in [
+        ("pod_a", "scratchpad_a"),
+        ("pod_b", "scratchpad_b"),
+        ("pod_c", "scratchpad_c"),
+    ] {
+        graph
+            .add_pod_entry(
+                pod_address,
+                pod_address,
+                scratchpad_address,
+                configuration_address,
+                "test_config_scratchpad_relationship",
+                0,
+            )
+            .unwrap();
+    }
+
+    assert_eq!(
+        graph.reference_relationship("pod_a", "pod_b").unwrap(),
+        Relationship::None
+    );
+
+    // One way
+    graph
+        .pod_ref_entry("pod_a", "pod_b", configuration_address, true, true)
+        .unwrap();
+    assert_eq!(
+        graph.reference_relationship("pod_a", "pod_b").unwrap(),
+        Relationship::AtoB
+    );
+    assert_eq!(
+        graph.reference_relationship("pod_b", "pod_a").unwrap(),
+        Relationship::BtoA
+    );
+
+    // Both ways
+    graph
+        .pod_ref_entry("pod_b", "pod_a", configuration_address, true, true)
+        .unwrap();
+    assert_eq!(
+        graph.reference_relationship("pod_a", "pod_b").unwrap(),
+        Relationship::Mutual
+    );
+    assert_eq!(
+        graph.reference_relationship("pod_b", "pod_a").unwrap(),
+        Relationship::Mutual
+    );
+
+    // Unrelated pods
+    assert_eq!(
+        graph.reference_relationship("pod_a", "pod_c").unwrap(),
+        Relationship::None
+    );
+}
+
+#[test]
+fn test_rename_configuration_pod() {
+    let (mut graph, _temp_dir) = create_test_graph();
+
+    let pod_address = "test_pod_account";
+    let configuration_address = "test_config_account";
+
+    graph
+        .add_pod_entry(
+            "Account Pod",
+            pod_address,
+            "scratchpad_account",
+            configuration_address,
+            "test_config_scratchpad_account",
+            0,
+        )
+        .unwrap();
+    assert_eq!(
+        graph.get_pod_name(configuration_address).unwrap(),
+        Some("User Configuration".to_string())
+    );
+
+    let configuration = graph
+        .rename_pod_entry(configuration_address, "Alice's Library")
+        .unwrap();
+    let configuration = String::from_utf8(configuration).unwrap();
+
+    // Only the name changed, the configuration data is intact
+    assert_eq!(
+        graph.get_pod_name(configuration_address).unwrap(),
+        Some("Alice's Library".to_string())
+    );
+    assert!(configuration.contains("Alice's Library"));
+    assert!(!configuration.contains("User Configuration"));
+    assert!(configuration.contains("ant://test_pod_account"));
+    assert_eq!(graph.get_pod_depth(pod_address).unwrap(), 0);
+    assert_eq!(
+        graph.get_pod_name(pod_address).unwrap(),
+        Some("Account Pod".to_string())
+    );
+}
+
+#[test]
+fn test_hidden_configuration_pod() {
+    let (mut graph, _temp_dir) = create_test_graph();
+
+    let pod_address = "test_pod_listed";
+    let configuration_address = "test_config_hidden";
+    let configuration_iri = format!("ant://{configuration_address}");
+
+    graph
+        .add_pod_entry(
+            "Listed Pod",
+            pod_address,
+            "scratchpad_listed",
+            configuration_address,
+            "test_config_scratchpad_hidden",
+            0,
+        )
+        .unwrap();
+
+    let bindings = |json: String| -> Vec<serde_json::Value> {
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        parsed["results"]["bindings"].as_array().unwrap().clone()
+    };
+    let mentions_configuration = |bindings: &[serde_json::Value]| {
+        bindings.iter().any(|binding| {
+            binding["subject"]["value"] == configuration_iri
+                || binding["graph"]["value"] == configuration_iri
+        })
+    };
+
+    // Shown by default
+    let my_pods = bindings(graph.get_my_pods(configuration_address).unwrap());
+    assert!(mentions_configuration(&my_pods));
+    let browse = bindings(graph.browse(None).unwrap());
+    assert!(mentions_configuration(&browse));
+
+    // Hidden from listings and searches
+    graph.set_hidden_configuration(Some(configuration_address));
+
+    let my_pods = bindings(graph.get_my_pods(configuration_address).unwrap());
+    assert!(!my_pods.is_empty());
+    assert!(
+        my_pods
+            .iter()
+            .all(|binding| binding["subject"]["value"] != configuration_iri)
+    );
+    assert!(
+        my_pods
+            .iter()
+            .any(|binding| binding["subject"]["value"] == "ant://test_pod_listed")
+    );
+
+    let browse = bindings(graph.browse(None).unwrap());
+    assert!(!browse.is_empty());
+    assert!(!mentions_configuration(&browse));
+
+    let search = bindings(
+        graph
+            .search_content("User Configuration", None, None)
+            .unwrap(),
+    );
+    assert!(search.is_empty());
+
+    let by_type = bindings(
+        graph
+            .search_by_type("ant://colonylib/v1/pod", None, None)
+            .unwrap(),
+    );
+    assert!(!mentions_configuration(&by_type));
+
+    // And shown again
+    graph.set_hidden_configuration(None);
+    let search = bindings(
+        graph
+            .search_content("User Configuration", None, None)
+            .unwrap(),
+    );
+    assert!(mentions_configuration(&search));
+}
+
+#[test]
+fn test_graph_dates_with_fake_clock() {
+    use colonylib::clock::FakeClock;
+    use std::sync::Arc;
+
+    let (mut graph, _temp_dir) = create_test_graph();
+    let clock = FakeClock::new(
+        chrono::DateTime::parse_from_rfc3339("2025-06-01T08:00:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc),
+    );
+    graph.set_clock(Arc::new(clock.clone()));
+
+    let pod_address = "test_pod_clock";
+    let configuration_address = "test_config_clock";
+    let (pod_data, _configuration) = graph
+        .add_pod_entry(
+            "Clock Pod",
+            pod_address,
+            "scratchpad_clock",
+            configuration_address,
+            "test_config_scratchpad_clock",
+            0,
+        )
+        .unwrap();
+    let pod_data = String::from_utf8(pod_data).unwrap();
+    assert!(pod_data.contains("2025-06-01T08:00:00+00:00"));
+
+    // Modified dates follow the clock
+    clock.advance(chrono::Duration::hours(1));
+    let (pod_data, configuration) = graph
+        .put_subject_data(
+            pod_address,
+            "clock_subject",
+            configuration_address,
+            r#"{"@context": {"schema": "http://schema.org/"}, "@id": "ant://clock_subject", "schema:name": "Timed"}"#,
+        )
+        .unwrap();
+    let pod_data = String::from_utf8(pod_data).unwrap();
+    let configuration = String::from_utf8(configuration).unwrap();
+    assert!(pod_data.contains("2025-06-01T08:00:00+00:00")); // creation date
+    assert!(pod_data.contains("2025-06-01T09:00:00+00:00")); // modified date
+    assert!(configuration.contains("2025-06-01T09:00:00+00:00"));
+}
+
+#[test]
+fn test_get_pod_names() {
+    let (mut graph, _temp_dir) = create_test_graph();
+
+    let configuration_address = "test_config_names";
+    for (name, pod_address, scratchpad_address) in [
+        ("Music", "pod_music", "scratchpad_music"),
+        ("Books", "pod_books", "scratchpad_books"),
+    ] {
+        graph
+            .add_pod_entry(
+                name,
+                pod_address,
+                scratchpad_address,
+                configuration_address,
+                "test_config_scratchpad_names",
+                0,
+            )
+            .unwrap();
+    }
+
+    // Referenced pods without a name are omitted
+    graph
+        .pod_ref_entry(
+            "pod_music",
+            "pod_unnamed",
+            configuration_address,
+            true,
+            false,
+        )
+        .unwrap();
+
+    graph.set_hidden_configuration(Some(configuration_address));
+    assert_eq!(
+        graph.get_pod_names().unwrap(),
+        vec![
+            ("Books".to_string(), "pod_books".to_string()),
+            ("Music".to_string(), "pod_music".to_string()),
+        ]
+    );
+
+    graph.set_hidden_configuration(None);
+    let names = graph.get_pod_names().unwrap();
+    assert_eq!(names.len(), 3);
+    assert!(names.contains(&(
+        "User Configuration".to_string(),
+        configuration_address.to_string()
+    )));
+}
+
+#[test]
+fn test_validate_trig() {
+    let (mut graph, _temp_dir) = create_test_graph();
+
+    let pod_data = r#"
+<ant://scratchpad_file> <ant://colonylib/v1/index> "0" .
+<ant://file_subject> <http://schema.org/name> "From a file" .
+"#;
+    assert_eq!(graph.validate_trig(pod_data).unwrap(), 2);
+    assert_eq!(
+        graph.get_pod_scratchpads_from_string(pod_data).unwrap(),
+        vec!["scratchpad_file".to_string()]
+    );
+
+    // A broken statement anywhere fails the whole document
+    let broken = format!(
+        "{pod_data}<ant://file_subject> <http://schema.org/description> \"Unterminated .\n"
+    );
+    assert!(graph.validate_trig(&broken).is_err());
+
+    // Named graphs are not allowed in pod data
+    let named = "<ant://other_graph> { <ant://a> <ant://b> <ant://c> . }";
+    assert!(graph.validate_trig(named).is_err());
+
+    // Valid data loads into the pod's graph
+    graph.load_pod_into_graph("file_pod", pod_data).unwrap();
+    let subject_data: serde_json::Value =
+        serde_json::from_str(&graph.get_subject_data("file_subject").unwrap()).unwrap();
+    assert_eq!(
+        subject_data["results"]["bindings"][0]["graph"]["value"],
+        "ant://file_pod"
+    );
+}
+
+#[test]
+fn test_subject_tags() {
+    let (mut graph, _temp_dir) = create_test_graph();
+
+    let configuration_address = "test_config_tags";
+    let local_pod = "test_pod_tags";
+    graph
+        .add_pod_entry(
+            "Tagged Pod",
+            local_pod,
+            "scratchpad_tags",
+            configuration_address,
+            "test_config_scratchpad_tags",
+            0,
+        )
+        .unwrap();
+
+    // A referenced pod (depth 1) that also uses tags
+    let remote_pod = "remote_pod_tags";
+    graph
+        .pod_ref_entry(local_pod, remote_pod, configuration_address, true, false)
+        .unwrap();
+    graph
+        .load_pod_into_graph(
+            remote_pod,
+            r#"<ant://remote_subject> <ant://colonylib/v1/tag> "favorites" ."#,
+        )
+        .unwrap();
+
+    let (pod_data, _configuration) = graph
+        .subject_tag_entry(
+            local_pod,
+            "song_1",
+            configuration_address,
+            "favorites",
+            true,
+        )
+        .unwrap();
+    assert!(String::from_utf8(pod_data).unwrap().contains("favorites"));
+    graph
+        .subject_tag_entry(local_pod, "song_1", configuration_address, "jazz", true)
+        .unwrap();
+    graph
+        .subject_tag_entry(
+            local_pod,
+            "song_2",
+            configuration_address,
+            "favorites",
+            true,
+        )
+        .unwrap();
+    // Tags that look like URIs stay literals
+    graph
+        .subject_tag_entry(
+            local_pod,
+            "song_2",
+            configuration_address,
+            "http://example.com/tag",
+            true,
+        )
+        .unwrap();
+
+    assert_eq!(
+        graph.get_subjects_by_tag("favorites", false).unwrap(),
+        vec![
+            "remote_subject".to_string(),
+            "song_1".to_string(),
+            "song_2".to_string()
+        ]
+    );
+    assert_eq!(
+        graph.get_subjects_by_tag("favorites", true).unwrap(),
+        vec!["song_1".to_string(), "song_2".to_string()]
+    );
+    assert_eq!(
+        graph
+            .get_subjects_by_tag("http://example.com/tag", false)
+            .unwrap(),
+        vec!["song_2".to_string()]
+    );
+    assert!(graph.get_subjects_by_tag("rock", false).unwrap().is_empty());
+
+    assert_eq!(
+        graph.get_all_tags().unwrap(),
+        vec![
+            ("favorites".to_string(), 3),
+            ("http://example.com/tag".to_string(), 1),
+            ("jazz".to_string(), 1),
+        ]
+    );
+
+    // Removing a tag only affects that subject
+    graph
+        .subject_tag_entry(
+            local_pod,
+            "song_1",
+            configuration_address,
+            "favorites",
+            false,
+        )
+        .unwrap();
+    assert_eq!(
+        graph.get_subjects_by_tag("favorites", true).unwrap(),
+        vec!["song_2".to_string()]
+    );
+    assert_eq!(
+        graph.get_subjects_by_tag("jazz", true).unwrap(),
+        vec!["song_1".to_string()]
+    );
+}
+
+#[test]
+fn test_graph_store_format_version() {
+    use colonylib::Graph;
+    use colonylib::graph::Error;
+
+    let temp_dir = tempfile::TempDir::new().expect("Failed to create temp directory");
+    let db_path = temp_dir.path().join("test_graph.db");
+    let version_path = temp_dir.path().join("test_graph.db.version");
+
+    // A new store records the current format version
+    assert_eq!(Graph::read_store_format_version(&db_path).unwrap(), None);
+    let graph = Graph::open(&db_path).unwrap();
+    drop(graph);
+    assert_eq!(
+        Graph::read_store_format_version(&db_path).unwrap(),
+        Some(Graph::store_format_version())
+    );
+
+    // Reopening a store with the current version works
+    let graph = Graph::open(&db_path).unwrap();
+    drop(graph);
+
+    // A store written by a newer version is rejected
+    std::fs::write(&version_path, "999").unwrap();
+    assert!(matches!(Graph::open(&db_path), Err(Error::StoreVersion(_))));
+
+    // An older version is rejected as well
+    std::fs::write(&version_path, "0").unwrap();
+    assert!(matches!(Graph::open(&db_path), Err(Error::StoreVersion(_))));
+
+    // So is an unreadable marker
+    std::fs::write(&version_path, "not a version").unwrap();
+    assert!(matches!(Graph::open(&db_path), Err(Error::StoreVersion(_))));
+}
+
+#[test]
+fn test_concurrent_search_with_reader() {
+    let (graph, _temp_dir) = create_test_graph();
+    let pod_iri = "ant://test_pod";
+
+    graph
+        .put_quad(
+            "ant://subject1",
+            "ant://colonylib/vocabulary/0.1/predicate#name",
+            "Test Document",
+            Some(pod_iri),
+        )
+        .unwrap();
+    graph
+        .put_quad(
+            "ant://subject2",
+            "ant://colonylib/vocabulary/0.1/predicate#description",
+            "This is a test description with searchable content",
+            Some(pod_iri),
+        )
+        .unwrap();
+
+    // Run searches from several threads while the graph is written to
+    let handles: Vec<_> = (0..8)
+        .map(|_| {
+            let reader = graph.reader();
+            std::thread::spawn(move || {
+                for _ in 0..20 {
+                    let results = reader.search_content("test", Some(100), None).unwrap();
+                    let parsed_results: serde_json::Value = serde_json::from_str(&results).unwrap();
+                    let bindings = parsed_results["results"]["bindings"].as_array().unwrap();
+                    assert!(!bindings.is_empty());
+                }
+            })
+        })
+        .collect();
+
+    for i in 0..20 {
+        graph
+            .put_quad(
+                &format!("ant://extra{i}"),
+                "ant://colonylib/vocabulary/0.1/predicate#name",
+                &format!("Test Extra {i}"),
+                Some("ant://other_pod"),
+            )
+            .unwrap();
+    }
+
+    for handle in handles {
+        handle.join().expect("search thread panicked");
+    }
+
+    // Readers see all writes made through the graph
+    let results = graph
+        .reader()
+        .search_content("extra", Some(100), None)
+        .unwrap();
+    let parsed_results: serde_json::Value = serde_json::from_str(&results).unwrap();
+    let bindings = parsed_results["results"]["bindings"].as_array().unwrap();
+    assert!(!bindings.is_empty());
+}
+
+#[test]
+fn test_clear_all() {
+    let (mut graph, _temp_dir) = create_test_graph();
+    graph
+        .put_quad(
+            "ant://subject1",
+            "ant://colonylib/vocabulary/0.1/predicate#name",
+            "Test Document",
+            Some("ant://pod1"),
+        )
+        .unwrap();
+    graph
+        .put_quad(
+            "ant://subject2",
+            "ant://colonylib/vocabulary/0.1/predicate#name",
+            "Other Document",
+            Some("ant://pod2"),
+        )
+        .unwrap();
+
+    graph.clear_all().unwrap();
+
+    let results = graph.search_content("document", None, None).unwrap();
+    let parsed_results: serde_json::Value = serde_json::from_str(&results).unwrap();
+    let bindings = parsed_results["results"]["bindings"].as_array().unwrap();
+    assert!(bindings.is_empty());
+}
+
+#[test]
+fn test_type_facets() {
+    use colonylib::graph::SearchScope;
+
+    let (mut graph, _temp_dir) = create_test_graph();
+
+    let configuration_address = "test_config_facets";
+    for (pod_address, scratchpad_address, depth) in [
+        ("test_pod_facets_local", "scratchpad_facets_local", 0),
+        ("test_pod_facets_ref", "scratchpad_facets_ref", 1),
+    ] {
+        graph
+            .add_pod_entry(
+                "Facets Pod",
+                pod_address,
+                scratchpad_address,
+                configuration_address,
+                "test_config_scratchpad_facets",
+                0,
+            )
+            .unwrap();
+        graph
+            .force_set_pod_depth(pod_address, configuration_address, depth)
+            .unwrap();
+    }
+
+    let subjects = [
+        ("test_pod_facets_local", "doc_1", "schema:DigitalDocument"),
+        ("test_pod_facets_local", "doc_2", "schema:DigitalDocument"),
+        ("test_pod_facets_local", "image_1", "schema:ImageObject"),
+        ("test_pod_facets_ref", "doc_1", "schema:DigitalDocument"),
+        ("test_pod_facets_ref", "doc_3", "schema:DigitalDocument"),
+        ("test_pod_facets_ref", "person_1", "schema:Person"),
+    ];
+    for (pod_address, subject, subject_type) in subjects {
+        let data = format!(
+            r#"{{
+                "@context": {{"schema": "http://schema.org/"}},
+                "@id": "ant://{subject}",
+                "@type": "{subject_type}",
+                "schema:name": "{subject}"
+            }}"#
+        );
+        graph
+            .put_subject_data(pod_address, subject, configuration_address, &data)
+            .unwrap();
+    }
+
+    // Subjects described in more than one pod are only counted once
+    assert_eq!(
+        graph.type_facets(&SearchScope::All).unwrap(),
+        vec![
+            ("http://schema.org/DigitalDocument".to_string(), 3),
+            ("http://schema.org/ImageObject".to_string(), 1),
+            ("http://schema.org/Person".to_string(), 1),
+        ]
+    );
+    assert_eq!(
+        graph.type_facets(&SearchScope::Local).unwrap(),
+        vec![
+            ("http://schema.org/DigitalDocument".to_string(), 2),
+            ("http://schema.org/ImageObject".to_string(), 1),
+        ]
+    );
+    assert_eq!(
+        graph
+            .type_facets(&SearchScope::Pod("test_pod_facets_ref".to_string()))
+            .unwrap(),
+        vec![
+            ("http://schema.org/DigitalDocument".to_string(), 2),
+            ("http://schema.org/Person".to_string(), 1),
+        ]
+    );
+    assert!(
+        graph
+            .type_facets(&SearchScope::Pod("missing_pod".to_string()))
+            .unwrap()
+            .is_empty()
+    );
+
+    // A pod address that is not a valid IRI is rejected instead of breaking the query
+    assert!(
+        graph
+            .type_facets(&SearchScope::Pod("bad pod> }".to_string()))
+            .is_err()
+    );
+}
+
+#[test]
+fn test_get_pod_subject_digests() {
+    let (mut graph, _temp_dir) = create_test_graph();
+    let pod_address = "digest_pod";
+
+    let version_1 = r#"
+        <ant://digest_pod> <http://schema.org/name> "Digest Pod" .
+        <ant://scratchpad_1> <ant://colonylib/v1/index> "0" .
+        <ant://song_1> <http://schema.org/name> "Song One" ;
+            <http://schema.org/byArtist> [ <http://schema.org/name> "Artist" ] .
+        <ant://song_2> <http://schema.org/name> "Song Two" .
+    "#;
+    graph.load_pod_into_graph(pod_address, version_1).unwrap();
+    let digests_1 = graph.get_pod_subject_digests(pod_address).unwrap();
+
+    // The pod itself and its scratchpads are not subjects
+    let mut subjects: Vec<&String> = digests_1.keys().collect();
+    subjects.sort();
+    assert_eq!(subjects, vec!["song_1", "song_2"]);
+
+    // Reloading the same data gives the same digests, even with new blank node labels
+    graph.load_pod_into_graph(pod_address, version_1).unwrap();
+    assert_eq!(
+        graph.get_pod_subject_digests(pod_address).unwrap(),
+        digests_1
+    );
+
+    // A change in a nested blank node changes the digest of its subject only
+    let version_2 = r#"
+        <ant://digest_pod> <http://schema.org/name> "Digest Pod" .
+        <ant://scratchpad_1> <ant://colonylib/v1/index> "0" .
+        <ant://song_1> <http://schema.org/name> "Song One" ;
+            <http://schema.org/byArtist> [ <http://schema.org/name> "Other Artist" ] .
+        <ant://song_2> <http://schema.org/name> "Song Two" .
+    "#;
+    graph.load_pod_into_graph(pod_address, version_2).unwrap();
+    let digests_2 = graph.get_pod_subject_digests(pod_address).unwrap();
+    assert_ne!(digests_2["song_1"], digests_1["song_1"]);
+    assert_eq!(digests_2["song_2"], digests_1["song_2"]);
+}
+
+#[test]
+fn test_get_pods_by_modified() {
+    use colonylib::clock::FakeClock;
+    use std::sync::Arc;
+
+    let (mut graph, _temp_dir) = create_test_graph();
+    let clock = FakeClock::new(
+        chrono::DateTime::parse_from_rfc3339("2025-06-01T08:00:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc),
+    );
+    graph.set_clock(Arc::new(clock.clone()));
+
+    let configuration_address = "test_config_modified";
+    for (name, pod_address, scratchpad_address) in [
+        ("Oldest", "pod_oldest", "scratchpad_oldest"),
+        ("Newest", "pod_newest", "scratchpad_newest"),
+        ("Middle", "pod_middle", "scratchpad_middle"),
+    ] {
+        graph
+            .add_pod_entry(
+                name,
+                pod_address,
+                scratchpad_address,
+                configuration_address,
+                "test_config_scratchpad_modified",
+                0,
+            )
+            .unwrap();
+        clock.advance(chrono::Duration::hours(1));
+    }
+
+    // Touch the middle pod again so it becomes the most recent
+    clock.advance(chrono::Duration::hours(1));
+    graph
+        .subject_tag_entry(
+            "pod_middle",
+            "song_1",
+            configuration_address,
+            "favorites",
+            true,
+        )
+        .unwrap();
+
+    // A referenced pod that was never loaded has no name or modified date
+    graph
+        .force_set_pod_depth("pod_unloaded", configuration_address, 1)
+        .unwrap();
+
+    let pods = graph.get_pods_by_modified(None).unwrap();
+    let addresses: Vec<&str> = pods.iter().map(|pod| pod.address.as_str()).collect();
+    assert_eq!(
+        addresses,
+        vec!["pod_middle", "pod_newest", "pod_oldest", "pod_unloaded"]
+    );
+    assert_eq!(pods[0].name.as_deref(), Some("Middle"));
+    assert_eq!(pods[0].depth, Some(0));
+    assert!(
+        pods[0]
+            .modified
+            .as_deref()
+            .unwrap()
+            .starts_with("2025-06-01T12:00:00")
+    );
+    assert_eq!(pods[3].name, None);
+    assert_eq!(pods[3].modified, None);
+    assert_eq!(pods[3].depth, Some(1));
+    // Listings don't count scratchpads and subjects
+    assert_eq!(pods[0].scratchpad_count, None);
+    assert_eq!(pods[0].subject_count, None);
+
+    let pods = graph.get_pods_by_modified(Some(2)).unwrap();
+    assert_eq!(pods.len(), 2);
+    assert_eq!(pods[1].address, "pod_newest");
+}
+
+#[test]
+fn test_get_pod_info() {
+    use colonylib::clock::FakeClock;
+    use std::sync::Arc;
+
+    let (mut graph, _temp_dir) = create_test_graph();
+    let clock = FakeClock::new(
+        chrono::DateTime::parse_from_rfc3339("2025-06-01T08:00:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc),
+    );
+    graph.set_clock(Arc::new(clock.clone()));
+
+    let configuration_address = "test_config_info";
+    graph
+        .add_pod_entry(
+            "Info Pod",
+            "pod_info",
+            "scratchpad_info",
+            configuration_address,
+            "test_config_scratchpad_info",
+            0,
+        )
+        .unwrap();
+
+    clock.advance(chrono::Duration::hours(1));
+    for subject_address in ["info_subject_1", "info_subject_2"] {
+        let data = format!(
+            r#"{{
+                "@context": {{"schema": "http://schema.org/"}},
+                "@id": "ant://{subject_address}",
+                "@type": "schema:MediaObject",
+                "schema:name": "{subject_address}"
+            }}"#
+        );
+        graph
+            .put_subject_data("pod_info", subject_address, configuration_address, &data)
+            .unwrap();
+    }
+
+    // The pod can be looked up by name
+    let pod_address = graph.check_pod_exists("Info Pod").unwrap();
+    let info = graph.get_pod_info(&pod_address).unwrap();
+    assert_eq!(info.address, "pod_info");
+    assert_eq!(info.name.as_deref(), Some("Info Pod"));
+    assert_eq!(info.depth, Some(0));
+    assert!(
+        info.created
+            .as_deref()
+            .unwrap()
+            .starts_with("2025-06-01T08:00:00")
+    );
+    assert!(
+        info.modified
+            .as_deref()
+            .unwrap()
+            .starts_with("2025-06-01T09:00:00")
+    );
+    assert_eq!(info.icon, None);
+    assert_eq!(info.scratchpad_count, Some(1));
+    assert_eq!(info.subject_count, Some(2));
+
+    assert!(graph.get_pod_info("pod_missing").is_err());
+}
+
+#[test]
+fn test_export_subject_bundle() {
+    let (mut graph, _temp_dir) = create_test_graph();
+    let pod_address = "bundle_pod";
+
+    let pod_data = r#"
+        <ant://bundle_pod> <http://schema.org/name> "Bundle Pod" .
+        <ant://song> a <http://schema.org/MusicRecording> ;
+            <http://schema.org/name> "Song" ;
+            <ant://colonylib/v1/tag> "favorites" ;
+            <http://schema.org/byArtist> <ant://artist> ;
+            <http://schema.org/offers> [ <http://schema.org/price> "1.5"^^<http://www.w3.org/2001/XMLSchema#decimal> ] .
+        <ant://artist> a <http://schema.org/Person> ;
+            <http://schema.org/name> "Artist"@en ;
+            <http://schema.org/memberOf> <ant://band> ;
+            <http://schema.org/knows> <ant://song> .
+        <ant://band> <http://schema.org/name> "Band" .
+        <ant://unrelated> <http://schema.org/name> "Unrelated" .
+    "#;
+    graph.load_pod_into_graph(pod_address, pod_data).unwrap();
+
+    let ids = |bundle: &serde_json::Value| -> Vec<String> {
+        bundle["@graph"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|node| node["@id"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    // Depth 0 is the subject and its blank nodes only
+    let bundle = graph.export_subject_bundle("song", 0).unwrap();
+    let nodes = ids(&bundle);
+    assert_eq!(nodes.len(), 2);
+    assert_eq!(nodes[0], "ant://song");
+    assert!(nodes[1].starts_with("_:"));
+
+    let song = &bundle["@graph"][0];
+    assert_eq!(
+        song["@type"],
+        serde_json::json!(["http://schema.org/MusicRecording"])
+    );
+    assert_eq!(
+        song["http://schema.org/byArtist"],
+        serde_json::json!([{ "@id": "ant://artist" }])
+    );
+    assert!(song.get("ant://colonylib/v1/tag").is_none());
+    let offer = &bundle["@graph"][1];
+    assert_eq!(
+        offer["http://schema.org/price"],
+        serde_json::json!([{ "@value": "1.5", "@type": "http://www.w3.org/2001/XMLSchema#decimal" }])
+    );
+
+    // Following references reaches the artist and then the band, the cycle back to the song
+    // doesn't repeat it
+    let nodes = ids(&graph.export_subject_bundle("song", 1).unwrap());
+    assert_eq!(nodes.len(), 3);
+    assert!(nodes.contains(&"ant://artist".to_string()));
+
+    let bundle = graph.export_subject_bundle("song", 5).unwrap();
+    let nodes = ids(&bundle);
+    assert_eq!(nodes.len(), 4);
+    assert!(nodes.contains(&"ant://band".to_string()));
+    assert!(!nodes.contains(&"ant://unrelated".to_string()));
+    let artist = bundle["@graph"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|node| node["@id"] == "ant://artist")
+        .unwrap();
+    assert_eq!(
+        artist["http://schema.org/name"],
+        serde_json::json!([{ "@value": "Artist", "@language": "en" }])
+    );
+
+    // A subject without data gives an empty bundle
+    assert_eq!(
+        graph.export_subject_bundle("missing", 3).unwrap(),
+        serde_json::json!({ "@graph": [] })
+    );
+}
+
+#[test]
+fn test_get_pods_referencing() {
+    let (mut graph, _temp_dir) = create_test_graph();
+
+    let configuration_address = "test_config_referencing";
+    for (pod_address, scratchpad_address) in [
+        ("pod_target", "scratchpad_target"),
+        ("pod_local_b", "scratchpad_local_b"),
+        ("pod_local_a", "scratchpad_local_a"),
+    ] {
+        graph
+            .add_pod_entry(
+                "Referencing Pod",
+                pod_address,
+                scratchpad_address,
+                configuration_address,
+                "test_config_scratchpad_referencing",
+                0,
+            )
+            .unwrap();
+    }
+    graph
+        .force_set_pod_depth("pod_remote", configuration_address, 1)
+        .unwrap();
+
+    for pod_address in ["pod_local_b", "pod_local_a", "pod_remote"] {
+        graph
+            .pod_ref_entry(
+                pod_address,
+                "pod_target",
+                configuration_address,
+                true,
+                false,
+            )
+            .unwrap();
+    }
+
+    assert_eq!(
+        graph.get_pods_referencing("pod_target", false).unwrap(),
+        vec!["pod_local_a", "pod_local_b", "pod_remote"]
+    );
+    assert_eq!(
+        graph.get_pods_referencing("pod_target", true).unwrap(),
+        vec!["pod_local_a", "pod_local_b"]
+    );
+    assert!(
+        graph
+            .get_pods_referencing("pod_local_a", false)
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_pod_icon() {
+    let (mut graph, _temp_dir) = create_test_graph();
+
+    let configuration_address = "test_config_icon";
+    graph
+        .add_pod_entry(
+            "Icon Pod",
+            "pod_icon",
+            "scratchpad_icon",
+            configuration_address,
+            "test_config_scratchpad_icon",
+            0,
+        )
+        .unwrap();
+    assert_eq!(graph.get_pod_icon("pod_icon").unwrap(), None);
+
+    let pod_data = graph.pod_icon_entry("pod_icon", "icon_1").unwrap();
+    let pod_data = String::from_utf8(pod_data).unwrap();
+    assert!(pod_data.contains("ant://icon_1"));
+    assert_eq!(
+        graph.get_pod_icon("pod_icon").unwrap(),
+        Some("icon_1".to_string())
+    );
+
+    // Setting a new icon replaces the old one
+    graph.pod_icon_entry("pod_icon", "icon_2").unwrap();
+    assert_eq!(
+        graph.get_pod_icon("pod_icon").unwrap(),
+        Some("icon_2".to_string())
+    );
+
+    // The icon is included in pod listings
+    let pods = graph.get_pods_by_modified(None).unwrap();
+    assert_eq!(pods.len(), 1);
+    assert_eq!(pods[0].icon.as_deref(), Some("icon_2"));
+}
+
+#[test]
+fn test_compact() {
+    let (mut graph, _temp_dir) = create_test_graph();
+    for i in 0..50 {
+        graph
+            .put_quad(
+                &format!("ant://subject{i}"),
+                "http://schema.org/name",
+                &format!("Document {i}"),
+                Some("ant://compact_pod"),
+            )
+            .unwrap();
+    }
+    graph.clear_pod_graph("compact_pod").unwrap();
+    graph
+        .put_quad(
+            "ant://kept",
+            "http://schema.org/name",
+            "Kept Document",
+            Some("ant://compact_pod"),
+        )
+        .unwrap();
+
+    graph.compact().unwrap();
+
+    // Data is intact after compaction
+    assert_eq!(
+        graph.get_pod_subjects("compact_pod").unwrap(),
+        vec!["kept".to_string()]
+    );
+}
+
+#[test]
+fn test_search_exact() {
+    let (graph, _temp_dir) = create_test_graph();
+    let pod_iri = "ant://exact_pod";
+
+    for (subject, predicate, object) in [
+        ("ant://book1", "http://schema.org/name", "The Hobbit"),
+        (
+            "ant://book2",
+            "http://schema.org/name",
+            "The Hobbit: Special Edition",
+        ),
+        (
+            "ant://book3",
+            "http://schema.org/alternateName",
+            "The Hobbit",
+        ),
+        ("ant://book4", "http://schema.org/name", "Quote \"Book\""),
+    ] {
+        graph
+            .put_quad(subject, predicate, object, Some(pod_iri))
+            .unwrap();
+    }
+
+    let subjects = |results: String| -> Vec<String> {
+        let parsed_results: serde_json::Value = serde_json::from_str(&results).unwrap();
+        let mut subjects: Vec<String> = parsed_results["results"]["bindings"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|binding| binding["subject"]["value"].as_str().unwrap().to_string())
+            .collect();
+        subjects.sort();
+        subjects.dedup();
+        subjects
+    };
+
+    // Substring search also finds the special edition
+    assert_eq!(
+        subjects(graph.search_content("\"The Hobbit\"", None, None).unwrap()),
+        vec!["ant://book1", "ant://book2", "ant://book3"]
+    );
+
+    // Exact search only finds equal values
+    assert_eq!(
+        subjects(
+            graph
+                .search_exact("The Hobbit", None, None, None, false)
+                .unwrap()
+        ),
+        vec!["ant://book1", "ant://book3"]
+    );
+    assert_eq!(
+        subjects(
+            graph
+                .search_exact(
+                    "The Hobbit",
+                    Some("http://schema.org/name"),
+                    None,
+                    None,
+                    false
+                )
+                .unwrap()
+        ),
+        vec!["ant://book1"]
+    );
+    assert_eq!(
+        subjects(
+            graph
+                .search_exact("Quote \"Book\"", None, None, None, false)
+                .unwrap()
+        ),
+        vec!["ant://book4"]
+    );
+    assert!(
+        subjects(
+            graph
+                .search_exact("the hobbit", None, None, None, false)
+                .unwrap()
+        )
+        .is_empty()
+    );
+}
+
+#[test]
+fn test_resolve_pod_renames() {
+    let (mut graph, _temp_dir) = create_test_graph();
+
+    let configuration_address = "test_config_renames";
+    for (name, pod_address, scratchpad_address) in [
+        ("Music", "pod_music", "scratchpad_music"),
+        ("New Music", "pod_new_music", "scratchpad_new_music"),
+        ("Books", "pod_books", "scratchpad_books"),
+    ] {
+        graph
+            .add_pod_entry(
+                name,
+                pod_address,
+                scratchpad_address,
+                configuration_address,
+                "test_config_scratchpad_renames",
+                0,
+            )
+            .unwrap();
+    }
+
+    let renames = |pairs: &[(&str, &str)]| -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(pod, name)| (pod.to_string(), name.to_string()))
+            .collect()
+    };
+
+    // Bad batches are rejected as a whole
+    assert!(matches!(
+        graph.resolve_pod_renames(&renames(&[("Music", " ")])),
+        Err(colonylib::graph::Error::InvalidInput(_))
+    ));
+    assert!(matches!(
+        graph.resolve_pod_renames(&renames(&[("Music", "A"), ("pod_music", "B")])),
+        Err(colonylib::graph::Error::InvalidInput(_))
+    ));
+    assert!(matches!(
+        graph.resolve_pod_renames(&renames(&[("Music", "A"), ("Books", "A")])),
+        Err(colonylib::graph::Error::InvalidInput(_))
+    ));
+    assert!(matches!(
+        graph.resolve_pod_renames(&renames(&[("Music", "Books")])),
+        Err(colonylib::graph::Error::InvalidInput(_))
+    ));
+    assert!(
+        graph
+            .resolve_pod_renames(&renames(&[("Music", "A"), ("pod_missing", "B")]))
+            .is_err()
+    );
+
+    // Names freed by the batch can be reused within it
+    let resolved = graph
+        .resolve_pod_renames(&renames(&[
+            ("Music", "Music (old)"),
+            ("New Music", "Music"),
+        ]))
+        .unwrap();
+    assert_eq!(
+        resolved,
+        renames(&[("pod_music", "Music (old)"), ("pod_new_music", "Music")])
+    );
+
+    for (pod_address, new_name) in resolved {
+        graph.rename_pod_entry(&pod_address, &new_name).unwrap();
+    }
+    graph.set_hidden_configuration(Some(configuration_address));
+    let names = graph.get_pod_names().unwrap();
+    assert_eq!(
+        names,
+        vec![
+            ("Books".to_string(), "pod_books".to_string()),
+            ("Music".to_string(), "pod_new_music".to_string()),
+            ("Music (old)".to_string(), "pod_music".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_recovered_keys_entry() {
+    let (mut graph, _temp_dir) = create_test_graph();
+
+    let configuration_address = "test_config_recovered";
+    graph
+        .add_pod_entry(
+            "Known",
+            "pod_known",
+            "scratchpad_known",
+            configuration_address,
+            "test_config_scratchpad_recovered",
+            3,
+        )
+        .unwrap();
+
+    let strings =
+        |values: &[&str]| -> Vec<String> { values.iter().map(|v| v.to_string()).collect() };
+    let configuration = graph
+        .recovered_keys_entry(
+            configuration_address,
+            &strings(&["pod_found"]),
+            &strings(&["scratchpad_found"]),
+            &strings(&["pod_removed"]),
+            &strings(&["key_unused"]),
+            7,
+        )
+        .unwrap();
+    let configuration = String::from_utf8(configuration).unwrap();
+    assert!(configuration.contains("pod_found"));
+
+    let pointers = graph.get_pointers(configuration_address).unwrap();
+    assert!(pointers.contains(&"pod_found".to_string()));
+    assert!(pointers.contains(&"pod_known".to_string()));
+    assert!(
+        graph
+            .get_scratchpads(configuration_address)
+            .unwrap()
+            .contains(&"scratchpad_found".to_string())
+    );
+    assert_eq!(
+        graph.get_free_pointers(configuration_address).unwrap(),
+        vec!["pod_removed".to_string()]
+    );
+    assert_eq!(
+        graph.get_free_scratchpads(configuration_address).unwrap(),
+        vec!["key_unused".to_string()]
+    );
+    assert_eq!(graph.get_pod_depth("pod_found").unwrap(), 0);
+    assert_eq!(graph.get_key_count(configuration_address).unwrap(), 7);
+}
+
+#[test]
+fn test_audit_and_recompute_depths() {
+    use colonylib::graph::DepthAnomaly;
+
+    let (mut graph, _temp_dir) = create_test_graph();
+
+    let configuration_address = "test_config_depths";
+    graph
+        .add_pod_entry(
+            "Local",
+            "pod_a",
+            "scratchpad_a",
+            configuration_address,
+            "test_config_scratchpad_depths",
+            0,
+        )
+        .unwrap();
+
+    // pod_a -> pod_b -> pod_c, where pod_c was stored at depth 1 by its reference entry
+    graph
+        .pod_ref_entry("pod_a", "pod_b", configuration_address, true, false)
+        .unwrap();
+    graph
+        .pod_ref_entry("pod_b", "pod_c", configuration_address, true, false)
+        .unwrap();
+    // pod_d is not referenced by anything loaded
+    graph
+        .force_set_pod_depth("pod_d", configuration_address, 3)
+        .unwrap();
+
+    assert_eq!(
+        graph.audit_depths(configuration_address).unwrap(),
+        vec![
+            DepthAnomaly {
+                pod_address: "pod_c".to_string(),
+                stored_depth: Some(1),
+                computed_depth: Some(2),
+            },
+            DepthAnomaly {
+                pod_address: "pod_d".to_string(),
+                stored_depth: Some(3),
+                computed_depth: None,
+            },
+        ]
+    );
+
+    // Only reachable pods are fixed
+    let fixed = graph.recompute_depths(configuration_address).unwrap();
+    assert_eq!(fixed.len(), 1);
+    assert_eq!(fixed[0].pod_address, "pod_c");
+    assert_eq!(graph.get_pod_depth("pod_c").unwrap(), 2);
+    assert_eq!(graph.get_pod_depth("pod_d").unwrap(), 3);
+
+    let remaining = graph.audit_depths(configuration_address).unwrap();
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].pod_address, "pod_d");
+}
+
+#[test]
+fn test_pod_trig_with_subjects() {
+    let (mut graph, _temp_dir) = create_test_graph();
+
+    let pod_address = "test_pod_estimate";
+    let configuration_address = "test_estimate_config";
+    graph
+        .add_pod_entry(
+            "Estimate Pod",
+            pod_address,
+            "test_estimate_scratchpad",
+            configuration_address,
+            "test_estimate_config_scratchpad",
+            0,
+        )
+        .unwrap();
+
+    let data = r#"{
+        "@context": {"schema": "http://schema.org/"},
+        "@id": "ant://existing_subject",
+        "schema:name": "Existing Name"
+    }"#;
+    graph
+        .put_subject_data(pod_address, "existing_subject", configuration_address, data)
+        .unwrap();
+    let before = graph.get_pod_graph_data(pod_address).unwrap();
+
+    let subjects = vec![
+        (
+            "existing_subject".to_string(),
+            r#"{
+                "@context": {"schema": "http://schema.org/"},
+                "@id": "ant://existing_subject",
+                "schema:name": "Replaced Name"
+            }"#
+            .to_string(),
+        ),
+        (
+            "new_subject".to_string(),
+            r#"{
+                "@context": {"schema": "http://schema.org/"},
+                "@id": "ant://new_subject",
+                "schema:name": "New Name"
+            }"#
+            .to_string(),
+        ),
+    ];
+    let trig = graph
+        .pod_trig_with_subjects(pod_address, &subjects)
+        .unwrap();
+    let trig = String::from_utf8(trig).unwrap();
+
+    // The preview contains the batch applied on top of the existing pod data
+    assert!(trig.contains("Replaced Name"));
+    assert!(trig.contains("New Name"));
+    assert!(!trig.contains("Existing Name"));
+    assert!(trig.contains("test_estimate_scratchpad"));
+
+    // The persistent store is unchanged
+    assert_eq!(graph.get_pod_graph_data(pod_address).unwrap(), before);
+    let stored: serde_json::Value =
+        serde_json::from_str(&graph.get_subject_data("new_subject").unwrap()).unwrap();
+    assert!(stored["results"]["bindings"].as_array().unwrap().is_empty());
+}
+
+#[test]
+fn test_hidden_pods() {
+    let (mut graph, _temp_dir) = create_test_graph();
+
+    let configuration_address = "test_hidden_pods_config";
+    let visible_pod = "test_visible_pod";
+    let hidden_pod = "test_hidden_pod";
+    for (name, pod, scratchpad, config_scratchpad) in [
+        (
+            "Visible Pod",
+            visible_pod,
+            "visible_scratchpad",
+            "config_scratchpad_1",
+        ),
+        (
+            "Noisy Pod",
+            hidden_pod,
+            "hidden_scratchpad",
+            "config_scratchpad_2",
+        ),
+    ] {
+        graph
+            .add_pod_entry(
+                name,
+                pod,
+                scratchpad,
+                configuration_address,
+                config_scratchpad,
+                0,
+            )
+            .unwrap();
+    }
+    graph.set_hidden_configuration(Some(configuration_address));
+
+    for (pod, subject, name) in [
+        (visible_pod, "visible_subject", "Visible Song"),
+        (hidden_pod, "hidden_subject", "Noisy Song"),
+    ] {
+        let data = format!(
+            r#"{{
+                "@context": {{"schema": "http://schema.org/"}},
+                "@id": "ant://{subject}",
+                "schema:name": "{name}"
+            }}"#
+        );
+        graph
+            .put_subject_data(pod, subject, configuration_address, &data)
+            .unwrap();
+    }
+
+    let bindings = |json: String| -> Vec<serde_json::Value> {
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        parsed["results"]["bindings"].as_array().unwrap().clone()
+    };
+    let has_subject = |bindings: &[serde_json::Value], subject: &str| {
+        bindings
+            .iter()
+            .any(|binding| binding["subject"]["value"] == format!("ant://{subject}"))
+    };
+
+    let browse = bindings(graph.browse(None).unwrap());
+    assert!(has_subject(&browse, "visible_subject"));
+    assert!(has_subject(&browse, "hidden_subject"));
+
+    // The hidden pod's subjects no longer show up in browse, search or pod listings
+    graph.set_hidden_pods(&[hidden_pod.to_string()]);
+
+    let browse = bindings(graph.browse(None).unwrap());
+    assert!(has_subject(&browse, "visible_subject"));
+    assert!(!has_subject(&browse, "hidden_subject"));
+
+    let search = bindings(graph.search_content("song", None, None).unwrap());
+    assert!(has_subject(&search, "visible_subject"));
+    assert!(!has_subject(&search, "hidden_subject"));
+
+    let pods = graph.get_pods_by_modified(None).unwrap();
+    assert!(pods.iter().any(|pod| pod.address == visible_pod));
+    assert!(pods.iter().all(|pod| pod.address != hidden_pod));
+
+    // The pod data itself is untouched
+    let stored: serde_json::Value =
+        serde_json::from_str(&graph.get_subject_data("hidden_subject").unwrap()).unwrap();
+    assert!(!stored["results"]["bindings"].as_array().unwrap().is_empty());
+
+    // And shown again
+    graph.set_hidden_pods(&[]);
+    let browse = bindings(graph.browse(None).unwrap());
+    assert!(has_subject(&browse, "hidden_subject"));
+}
+
+#[test]
+fn test_distinct_objects_for_predicate() {
+    use colonylib::graph::SearchScope;
+
+    let (mut graph, _temp_dir) = create_test_graph();
+
+    let configuration_address = "test_config_objects";
+    for (pod_address, scratchpad_address, depth) in [
+        ("test_pod_objects_local", "scratchpad_objects_local", 0),
+        ("test_pod_objects_ref", "scratchpad_objects_ref", 1),
+    ] {
+        graph
+            .add_pod_entry(
+                "Objects Pod",
+                pod_address,
+                scratchpad_address,
+                configuration_address,
+                "test_config_scratchpad_objects",
+                0,
+            )
+            .unwrap();
+        graph
+            .force_set_pod_depth(pod_address, configuration_address, depth)
+            .unwrap();
+    }
+
+    let subjects = [
+        ("test_pod_objects_local", "book_1", "Alice"),
+        ("test_pod_objects_local", "book_2", "Bob"),
+        ("test_pod_objects_local", "book_3", "Alice"),
+        ("test_pod_objects_ref", "book_4", "Carol"),
+        ("test_pod_objects_ref", "book_5", "Carol"),
+        ("test_pod_objects_ref", "book_6", "Carol"),
+    ];
+    for (pod_address, subject, author) in subjects {
+        let data = format!(
+            r#"{{
+                "@context": {{"schema": "http://schema.org/"}},
+                "@id": "ant://{subject}",
+                "schema:author": "{author}"
+            }}"#
+        );
+        graph
+            .put_subject_data(pod_address, subject, configuration_address, &data)
+            .unwrap();
+    }
+
+    let author = "http://schema.org/author";
+    assert_eq!(
+        graph
+            .distinct_objects_for_predicate(author, &SearchScope::All)
+            .unwrap(),
+        vec!["Carol".to_string(), "Alice".to_string(), "Bob".to_string()]
+    );
+    assert_eq!(
+        graph
+            .distinct_objects_for_predicate(author, &SearchScope::Local)
+            .unwrap(),
+        vec!["Alice".to_string(), "Bob".to_string()]
+    );
+    assert_eq!(
+        graph
+            .distinct_objects_for_predicate(
+                author,
+                &SearchScope::Pod("test_pod_objects_ref".to_string())
+            )
+            .unwrap(),
+        vec!["Carol".to_string()]
+    );
+    assert!(
+        graph
+            .distinct_objects_for_predicate("http://schema.org/publisher", &SearchScope::All)
+            .unwrap()
+            .is_empty()
+    );
+    assert!(
+        graph
+            .distinct_objects_for_predicate("not an iri", &SearchScope::All)
+            .is_err()
+    );
+}
+
+#[test]
+fn test_duplicate_scratchpad_indices() {
+    let (mut graph, _temp_dir) = create_test_graph();
+
+    // Two scratchpads claim index 1, the one with the newest modified date wins
+    let pod_data = r#"
+<ant://scratchpad_main> <ant://colonylib/v1/index> "0" .
+<ant://scratchpad_old> <ant://colonylib/v1/index> "1" .
+<ant://scratchpad_old> <ant://colonylib/v1/modified> "2024-01-01T00:00:00+00:00" .
+<ant://scratchpad_new> <ant://colonylib/v1/index> "1" .
+<ant://scratchpad_new> <ant://colonylib/v1/modified> "2024-06-01T00:00:00+00:00" .
+<ant://scratchpad_last> <ant://colonylib/v1/index> "2" .
+"#;
+    let expected = vec![
+        "scratchpad_main".to_string(),
+        "scratchpad_new".to_string(),
+        "scratchpad_last".to_string(),
+    ];
+    assert_eq!(
+        graph.get_pod_scratchpads_from_string(pod_data).unwrap(),
+        expected
+    );
+
+    // The same choice is made once the data is loaded into the graph
+    graph
+        .load_pod_into_graph("duplicate_index_pod", pod_data)
+        .unwrap();
+    assert_eq!(
+        graph.get_pod_scratchpads("duplicate_index_pod").unwrap(),
+        expected
+    );
+
+    // Without dates to decide, the lowest address wins regardless of the order in the data
+    let pod_data = r#"
+<ant://scratchpad_main> <ant://colonylib/v1/index> "0" .
+<ant://scratchpad_b> <ant://colonylib/v1/index> "1" .
+<ant://scratchpad_a> <ant://colonylib/v1/index> "1" .
+"#;
+    assert_eq!(
+        graph.get_pod_scratchpads_from_string(pod_data).unwrap(),
+        vec!["scratchpad_main".to_string(), "scratchpad_a".to_string()]
+    );
+}
+
+#[test]
+fn test_find_empty_subjects() {
+    let (mut graph, _temp_dir) = create_test_graph();
+
+    let pod_address = "test_pod_empty";
+    let other_pod_address = "test_pod_empty_other";
+    let pod_iri = format!("ant://{pod_address}");
+    let other_pod_iri = format!("ant://{other_pod_address}");
+
+    // Pod bookkeeping data should never be reported
+    graph
+        .add_pod_entry(
+            "Empty Pod",
+            pod_address,
+            "test_empty_scratchpad",
+            "test_empty_config",
+            "test_empty_config_scratchpad",
+            0,
+        )
+        .unwrap();
+
+    let type_predicate = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+    let quads = [
+        // A described subject
+        (
+            "ant://described_subject",
+            type_predicate,
+            "http://schema.org/Book",
+            &pod_iri,
+        ),
+        (
+            "ant://described_subject",
+            "http://schema.org/name",
+            "A Book",
+            &pod_iri,
+        ),
+        // A stub with only a type and a modified date
+        (
+            "ant://stub_subject",
+            type_predicate,
+            "http://schema.org/Book",
+            &pod_iri,
+        ),
+        (
+            "ant://stub_subject",
+            "ant://colonylib/v1/modified",
+            "2024-01-01T00:00:00+00:00",
+            &pod_iri,
+        ),
+        // Tags are user metadata
+        (
+            "ant://tagged_subject",
+            type_predicate,
+            "http://schema.org/Book",
+            &pod_iri,
+        ),
+        (
+            "ant://tagged_subject",
+            "ant://colonylib/v1/tag",
+            "favorite",
+            &pod_iri,
+        ),
+        // A stub in another pod
+        (
+            "ant://other_stub_subject",
+            type_predicate,
+            "http://schema.org/Book",
+            &other_pod_iri,
+        ),
+        // Only a type in this pod, but described in the other pod
+        (
+            "ant://split_subject",
+            type_predicate,
+            "http://schema.org/Book",
+            &pod_iri,
+        ),
+        (
+            "ant://split_subject",
+            "http://schema.org/name",
+            "Split",
+            &other_pod_iri,
+        ),
+    ];
+    for (subject, predicate, object, pod) in quads {
+        graph
+            .put_quad(subject, predicate, object, Some(pod))
+            .unwrap();
+    }
+
+    assert_eq!(
+        graph.find_empty_subjects(None).unwrap(),
+        vec!["other_stub_subject".to_string(), "stub_subject".to_string()]
+    );
+
+    // Within a single pod only that pod's metadata counts
+    assert_eq!(
+        graph.find_empty_subjects(Some(pod_address)).unwrap(),
+        vec!["split_subject".to_string(), "stub_subject".to_string()]
+    );
+}
+
+#[test]
+fn test_rotate_pod_entry() {
+    let (mut graph, _temp_dir) = create_test_graph();
+    let configuration_address = "test_config_rotate";
+
+    graph
+        .add_pod_entry(
+            "Rotated Pod",
+            "pod_a",
+            "scratchpad_a",
+            configuration_address,
+            "test_config_scratchpad_rotate",
+            0,
+        )
+        .unwrap();
+    graph
+        .add_pod_entry(
+            "Referencing Pod",
+            "pod_b",
+            "scratchpad_b",
+            configuration_address,
+            "test_config_scratchpad_rotate",
+            0,
+        )
+        .unwrap();
+    graph
+        .put_quad(
+            "ant://item_subject",
+            "http://schema.org/name",
+            "Item",
+            Some("ant://pod_a"),
+        )
+        .unwrap();
+    graph
+        .pod_ref_entry("pod_b", "pod_a", configuration_address, true, true)
+        .unwrap();
+
+    let (pod_data, _configuration, referencing_pods) = graph
+        .rotate_pod_entry(
+            "pod_a",
+            &["scratchpad_a".to_string()],
+            "pod_new",
+            "scratchpad_new",
+            configuration_address,
+            8,
+        )
+        .unwrap();
+    assert_eq!(referencing_pods, vec!["pod_b".to_string()]);
+
+    // The data and name move to the new address
+    let pod_data = String::from_utf8(pod_data).unwrap();
+    assert!(pod_data.contains("Item"));
+    assert!(!pod_data.contains("ant://pod_a"));
+    assert!(!pod_data.contains("scratchpad_a"));
+    assert_eq!(graph.check_pod_exists("Rotated Pod").unwrap(), "pod_new");
+    assert_eq!(
+        graph.get_pod_scratchpads("pod_new").unwrap(),
+        vec!["scratchpad_new".to_string()]
+    );
+    assert!(graph.get_pod_graph_data("pod_a").unwrap().is_empty());
+
+    // The referencing pod points at the new address
+    assert!(
+        graph
+            .get_pods_referencing("pod_a", true)
+            .unwrap()
+            .is_empty()
+    );
+    assert_eq!(
+        graph.get_pods_referencing("pod_new", true).unwrap(),
+        vec!["pod_b".to_string()]
+    );
+
+    // The old keys are abandoned in the configuration
+    let mut abandoned = graph.get_abandoned(configuration_address).unwrap();
+    abandoned.sort();
+    assert_eq!(
+        abandoned,
+        vec!["pod_a".to_string(), "scratchpad_a".to_string()]
+    );
+}
+
+#[test]
+fn test_subjects_modified_between() {
+    use colonylib::clock::FakeClock;
+    use colonylib::graph::SearchScope;
+    use std::sync::Arc;
+
+    let (mut graph, _temp_dir) = create_test_graph();
+    let at = |date: &str| {
+        chrono::DateTime::parse_from_rfc3339(date)
+            .unwrap()
+            .with_timezone(&chrono::Utc)
+    };
+    let clock = FakeClock::new(at("2025-06-01T08:00:00Z"));
+    graph.set_clock(Arc::new(clock.clone()));
+
+    let configuration_address = "test_config_window";
+    for (name, pod_address, scratchpad_address) in [
+        ("Window Pod", "pod_window", "scratchpad_window"),
+        (
+            "Other Window Pod",
+            "pod_window_other",
+            "scratchpad_window_other",
+        ),
+    ] {
+        graph
+            .add_pod_entry(
+                name,
+                pod_address,
+                scratchpad_address,
+                configuration_address,
+                "test_config_scratchpad_window",
+                0,
+            )
+            .unwrap();
+    }
+    let data = |subject: &str| {
+        format!(r#"{{"@id": "ant://{subject}", "http://schema.org/name": "{subject}"}}"#)
+    };
+
+    graph
+        .put_subject_data(
+            "pod_window",
+            "old_subject",
+            configuration_address,
+            &data("old_subject"),
+        )
+        .unwrap();
+    clock.set(at("2025-06-02T08:00:00Z"));
+    graph
+        .put_subject_data(
+            "pod_window",
+            "new_subject",
+            configuration_address,
+            &data("new_subject"),
+        )
+        .unwrap();
+    clock.set(at("2025-06-03T08:00:00Z"));
+    let merge = r#"{"@id": "ant://merged_subject", "http://schema.org/name": "Merged"}"#;
+    graph
+        .merge_subject_data(
+            "pod_window_other",
+            "merged_subject",
+            configuration_address,
+            merge,
+        )
+        .unwrap();
+
+    // Touching the pod again keeps the dates of the earlier subjects
+    let window = graph
+        .subjects_modified_between(
+            &at("2025-06-02T00:00:00Z"),
+            &at("2025-06-04T00:00:00Z"),
+            &SearchScope::All,
+        )
+        .unwrap();
+    assert_eq!(
+        window,
+        vec!["merged_subject".to_string(), "new_subject".to_string()]
+    );
+
+    // The end of the window is exclusive
+    let window = graph
+        .subjects_modified_between(
+            &at("2025-06-01T00:00:00Z"),
+            &at("2025-06-02T08:00:00Z"),
+            &SearchScope::All,
+        )
+        .unwrap();
+    assert_eq!(window, vec!["old_subject".to_string()]);
+
+    let window = graph
+        .subjects_modified_between(
+            &at("2025-06-01T00:00:00Z"),
+            &at("2025-06-04T00:00:00Z"),
+            &SearchScope::Pod("pod_window".to_string()),
+        )
+        .unwrap();
+    assert_eq!(
+        window,
+        vec!["new_subject".to_string(), "old_subject".to_string()]
+    );
+}
+
+#[test]
+fn test_get_subject_data_local_with_conflicts() {
+    let (mut graph, _temp_dir) = create_test_graph();
+
+    let configuration_address = "test_config_conflicts";
+    for (pod_address, scratchpad_address) in [
+        ("test_pod_conflicts_1", "scratchpad_conflicts_1"),
+        ("test_pod_conflicts_2", "scratchpad_conflicts_2"),
+        ("test_pod_conflicts_remote", "scratchpad_conflicts_remote"),
+    ] {
+        graph
+            .add_pod_entry(
+                "Conflicts Pod",
+                pod_address,
+                scratchpad_address,
+                configuration_address,
+                "test_config_scratchpad_conflicts",
+                0,
+            )
+            .unwrap();
+    }
+    // The third pod is a referenced pod, so it is not part of the local view
+    graph
+        .force_set_pod_depth("test_pod_conflicts_remote", configuration_address, 1)
+        .unwrap();
+
+    // Both local pods agree on the type but not on the name, and only one has a description
+    for (pod_address, data) in [
+        (
+            "test_pod_conflicts_1",
+            r#"{
+                "@context": {"schema": "http://schema.org/"},
+                "@id": "ant://conflicted_subject",
+                "@type": "schema:MusicRecording",
+                "schema:name": "First Name",
+                "schema:description": "Only here"
+            }"#,
+        ),
+        (
+            "test_pod_conflicts_2",
+            r#"{
+                "@context": {"schema": "http://schema.org/"},
+                "@id": "ant://conflicted_subject",
+                "@type": "schema:MusicRecording",
+                "schema:name": "Second Name"
+            }"#,
+        ),
+        (
+            "test_pod_conflicts_remote",
+            r#"{
+                "@context": {"schema": "http://schema.org/"},
+                "@id": "ant://conflicted_subject",
+                "@type": "schema:MusicRecording",
+                "schema:name": "Remote Name"
+            }"#,
+        ),
+    ] {
+        graph
+            .put_subject_data(
+                pod_address,
+                "conflicted_subject",
+                configuration_address,
+                data,
+            )
+            .unwrap();
+    }
+
+    let view = graph
+        .get_subject_data_local_with_conflicts("conflicted_subject")
+        .unwrap();
+    assert_eq!(view.subject, "conflicted_subject");
+    assert_eq!(
+        view.pods,
+        vec![
+            "test_pod_conflicts_1".to_string(),
+            "test_pod_conflicts_2".to_string()
+        ]
+    );
+
+    let property = |predicate: &str| {
+        view.properties
+            .iter()
+            .find(|property| property.predicate == predicate)
+            .unwrap()
+    };
+
+    let name = property("http://schema.org/name");
+    assert!(name.conflict);
+    assert_eq!(name.values.len(), 2);
+    assert_eq!(name.values[0].value, "First Name");
+    assert_eq!(
+        name.values[0].pods,
+        vec!["test_pod_conflicts_1".to_string()]
+    );
+    assert_eq!(name.values[1].value, "Second Name");
+    assert_eq!(
+        name.values[1].pods,
+        vec!["test_pod_conflicts_2".to_string()]
+    );
+
+    let type_property = property("http://www.w3.org/1999/02/22-rdf-syntax-ns#type");
+    assert!(!type_property.conflict);
+    assert_eq!(type_property.values.len(), 1);
+    assert_eq!(type_property.values[0].pods.len(), 2);
+
+    // A predicate stated by only one pod is not a disagreement
+    assert!(!property("http://schema.org/description").conflict);
+
+    let conflicts: Vec<&str> = view
+        .conflicts()
+        .map(|property| property.predicate.as_str())
+        .collect();
+    assert_eq!(conflicts, vec!["http://schema.org/name"]);
+
+    // A subject that no local pod describes gives an empty view
+    let view = graph
+        .get_subject_data_local_with_conflicts("unknown_subject")
+        .unwrap();
+    assert!(view.pods.is_empty());
+    assert!(view.properties.is_empty());
+}
+
+#[test]
+fn test_search_pagination() {
+    let (graph, _temp_dir) = create_test_graph();
+
+    let pod_iri = "ant://test_pod_paging";
+    for i in 0..30 {
+        let subject_iri = format!("ant://paged_subject_{i:02}");
+        graph
+            .put_quad(
+                &subject_iri,
+                "http://www.w3.org/1999/02/22-rdf-syntax-ns#type",
+                "http://schema.org/MediaObject",
+                Some(pod_iri),
+            )
+            .unwrap();
+        graph
+            .put_quad(
+                &subject_iri,
+                "http://schema.org/name",
+                &format!("Paged Item {i:02}"),
+                Some(pod_iri),
+            )
+            .unwrap();
+    }
+
+    let rows = |json: String| -> Vec<(String, String)> {
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        parsed["results"]["bindings"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|binding| {
+                (
+                    binding["subject"]["value"].as_str().unwrap().to_string(),
+                    binding["object"]["value"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                )
+            })
+            .collect()
+    };
+    let assert_pages = |first: Vec<(String, String)>,
+                        second: Vec<(String, String)>,
+                        all: Vec<(String, String)>| {
+        assert_eq!(first.len(), 10);
+        assert_eq!(second.len(), 10);
+        assert!(first.iter().all(|row| !second.contains(row)));
+        // The two pages are the first twenty results in order
+        let paged: Vec<(String, String)> = first.into_iter().chain(second).collect();
+        assert_eq!(paged, all[..20].to_vec());
+    };
+
+    // Type search returns one row per subject
+    let all = rows(
+        graph
+            .search_by_type("http://schema.org/MediaObject", None, None)
+            .unwrap(),
+    );
+    assert_eq!(all.len(), 30);
+    assert_pages(
+        rows(
+            graph
+                .search_by_type("http://schema.org/MediaObject", Some(10), Some(0))
+                .unwrap(),
+        ),
+        rows(
+            graph
+                .search_by_type("http://schema.org/MediaObject", Some(10), Some(10))
+                .unwrap(),
+        ),
+        all.clone(),
+    );
+    let subjects: Vec<&str> = all.iter().map(|(subject, _)| subject.as_str()).collect();
+    assert_eq!(subjects[0], "ant://paged_subject_00");
+    assert_eq!(subjects[29], "ant://paged_subject_29");
+
+    // Predicate search
+    let all = rows(
+        graph
+            .search_by_predicate("http://schema.org/name", None, None)
+            .unwrap(),
+    );
+    assert_eq!(all.len(), 30);
+    assert_pages(
+        rows(
+            graph
+                .search_by_predicate("http://schema.org/name", Some(10), Some(0))
+                .unwrap(),
+        ),
+        rows(
+            graph
+                .search_by_predicate("http://schema.org/name", Some(10), Some(10))
+                .unwrap(),
+        ),
+        all,
+    );
+
+    // Text search pages through the rows of every matching subject
+    let all = rows(graph.search_content("paged item", None, None).unwrap());
+    assert_eq!(all.len(), 60);
+    assert_pages(
+        rows(
+            graph
+                .search_content("paged item", Some(10), Some(0))
+                .unwrap(),
+        ),
+        rows(
+            graph
+                .search_content("paged item", Some(10), Some(10))
+                .unwrap(),
+        ),
+        all,
+    );
+
+    // An offset past the end gives an empty page
+    assert!(
+        rows(
+            graph
+                .search_by_type("http://schema.org/MediaObject", Some(10), Some(30))
+                .unwrap()
+        )
+        .is_empty()
+    );
+}
+
+#[test]
+fn test_construct_query() {
+    use colonylib::graph::RdfFormat;
+    use oxigraph::model::{GraphNameRef, LiteralRef, NamedNode, QuadRef};
+    use oxigraph::store::Store;
+
+    let (mut graph, _temp_dir) = create_test_graph();
+
+    let pod_address = "test_pod_construct";
+    let configuration_address = "test_config_construct";
+    graph
+        .add_pod_entry(
+            "Construct Pod",
+            pod_address,
+            "scratchpad_construct",
+            configuration_address,
+            "test_config_scratchpad_construct",
+            0,
+        )
+        .unwrap();
+    for (subject, name) in [
+        ("construct_subject_1", "First Song"),
+        ("construct_subject_2", "Second Song"),
+    ] {
+        graph
+            .put_subject_data(
+                pod_address,
+                subject,
+                configuration_address,
+                &format!(
+                    r#"{{"@context": {{"schema": "http://schema.org/"}}, "@id": "ant://{subject}", "@type": "schema:MusicRecording", "schema:name": "{name}"}}"#
+                ),
+            )
+            .unwrap();
+    }
+
+    let sparql = format!(
+        "CONSTRUCT {{ ?subject <http://schema.org/name> ?name }} WHERE {{ GRAPH <ant://{pod_address}> {{ ?subject <http://schema.org/name> ?name }} }}"
+    );
+    let turtle = graph.construct_query(&sparql, RdfFormat::Turtle).unwrap();
+
+    // Parse the Turtle output back in and check it holds exactly the constructed triples
+    let store = Store::new().unwrap();
+    store
+        .load_from_reader(RdfFormat::Turtle, turtle.as_slice())
+        .unwrap();
+    assert_eq!(store.len().unwrap(), 2);
+    let name = NamedNode::new("http://schema.org/name").unwrap();
+    for (subject, value) in [
+        ("construct_subject_1", "First Song"),
+        ("construct_subject_2", "Second Song"),
+    ] {
+        let subject = NamedNode::new(format!("ant://{subject}")).unwrap();
+        assert!(
+            store
+                .contains(QuadRef::new(
+                    &subject,
+                    &name,
+                    LiteralRef::new_simple_literal(value),
+                    GraphNameRef::DefaultGraph,
+                ))
+                .unwrap()
+        );
+    }
+
+    // The same triples can be written as N-Triples, one per line
+    let ntriples = graph.construct_query(&sparql, RdfFormat::NTriples).unwrap();
+    assert_eq!(String::from_utf8(ntriples).unwrap().lines().count(), 2);
+
+    // SELECT queries don't produce RDF
+    assert!(matches!(
+        graph.construct_query("SELECT ?s WHERE { ?s ?p ?o }", RdfFormat::Turtle),
+        Err(colonylib::graph::Error::InvalidInput(_))
+    ));
+}
+
+#[test]
+fn test_search_content_modes() {
+    use colonylib::graph::TextMatchMode;
+
+    let (graph, _temp_dir) = create_test_graph();
+
+    let pod_iri = "ant://test_pod_modes";
+    for (subject, name) in [
+        ("ant://mode_subject_1", "Important document"),
+        ("ant://mode_subject_2", "Not important at all"),
+        ("ant://mode_subject_3", "Café Crème"),
+        ("ant://mode_subject_4", "Plain cafe"),
+    ] {
+        graph
+            .put_quad(subject, "http://schema.org/name", name, Some(pod_iri))
+            .unwrap();
+    }
+
+    let subjects = |json: String| -> Vec<String> {
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let mut subjects: Vec<String> = parsed["results"]["bindings"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|binding| binding["subject"]["value"].as_str().unwrap().to_string())
+            .collect();
+        subjects.sort();
+        subjects.dedup();
+        subjects
+    };
+
+    // The anchor only matches values that start with the word
+    let anchored = graph
+        .search_content_with_mode("^Important", TextMatchMode::Regex, None, None, false)
+        .unwrap();
+    assert_eq!(subjects(anchored), vec!["ant://mode_subject_1".to_string()]);
+    let contains = graph
+        .search_content_with_mode("important", TextMatchMode::Contains, None, None, false)
+        .unwrap();
+    assert_eq!(
+        subjects(contains),
+        vec![
+            "ant://mode_subject_1".to_string(),
+            "ant://mode_subject_2".to_string()
+        ]
+    );
+
+    // Fuzzy matching ignores accents in both the data and the search text
+    let fuzzy = graph
+        .search_content_with_mode("cafe creme", TextMatchMode::Fuzzy, None, None, false)
+        .unwrap();
+    assert_eq!(
+        subjects(fuzzy),
+        vec![
+            "ant://mode_subject_3".to_string(),
+            "ant://mode_subject_4".to_string()
+        ]
+    );
+    let fuzzy = graph
+        .search_content_with_mode("CRÉME", TextMatchMode::Fuzzy, None, None, false)
+        .unwrap();
+    assert_eq!(subjects(fuzzy), vec!["ant://mode_subject_3".to_string()]);
+    let contains = graph
+        .search_content_with_mode("creme", TextMatchMode::Contains, None, None, false)
+        .unwrap();
+    assert!(subjects(contains).is_empty());
+
+    // A pattern that doesn't compile is an error, not a panic or an empty result
+    assert!(matches!(
+        graph.search_content_with_mode("(unclosed", TextMatchMode::Regex, None, None, false),
+        Err(colonylib::graph::Error::Regex(_))
+    ));
+}
+
+#[test]
+fn test_export_import_all() {
+    use colonylib::graph::RdfFormat;
+
+    let (mut graph, _temp_dir) = create_test_graph();
+
+    let configuration_address = "test_config_export";
+    for i in 1..=3 {
+        graph
+            .add_pod_entry(
+                &format!("Export Pod {i}"),
+                &format!("export_pod_{i}"),
+                &format!("export_scratchpad_{i}"),
+                configuration_address,
+                "test_config_scratchpad_export",
+                0,
+            )
+            .unwrap();
+    }
+    let pods = graph.get_my_pods(configuration_address).unwrap();
+    for i in 1..=3 {
+        assert!(pods.contains(&format!("export_pod_{i}")));
+    }
+
+    let mut backup = Vec::new();
+    graph.export_all(&mut backup, RdfFormat::TriG).unwrap();
+
+    // Restoring into an empty graph gives back the same pods, in the same named graphs
+    let (mut restored, _restored_temp_dir) = create_test_graph();
+    restored
+        .import_all(backup.as_slice(), RdfFormat::TriG)
+        .unwrap();
+    assert_eq!(restored.get_my_pods(configuration_address).unwrap(), pods);
+    for i in 1..=3 {
+        assert_eq!(
+            restored
+                .get_pod_graph_data(&format!("export_pod_{i}"))
+                .unwrap(),
+            graph
+                .get_pod_graph_data(&format!("export_pod_{i}"))
+                .unwrap()
+        );
+    }
+
+    // Formats without named graphs are rejected
+    let mut turtle = Vec::new();
+    assert!(matches!(
+        graph.export_all(&mut turtle, RdfFormat::Turtle),
+        Err(colonylib::graph::Error::InvalidInput(_))
+    ));
+    assert!(
+        restored
+            .import_all(backup.as_slice(), RdfFormat::Turtle)
+            .is_err()
+    );
+}
+
+#[test]
+fn test_list_named_graphs() {
+    let (mut graph, _temp_dir) = create_test_graph();
+    assert!(graph.list_named_graphs().unwrap().is_empty());
+
+    let configuration_address = "test_config_graphs";
+    for pod_address in ["graphs_pod_a", "graphs_pod_b"] {
+        graph
+            .add_pod_entry(
+                pod_address,
+                pod_address,
+                &format!("{pod_address}_scratchpad"),
+                configuration_address,
+                "test_config_scratchpad_graphs",
+                0,
+            )
+            .unwrap();
+    }
+
+    let graphs = graph.list_named_graphs().unwrap();
+    assert_eq!(
+        graphs,
+        vec![
+            "graphs_pod_a".to_string(),
+            "graphs_pod_b".to_string(),
+            configuration_address.to_string(),
+        ]
+    );
+    assert_eq!(graph.reader().list_named_graphs().unwrap(), graphs);
+}
+
+#[test]
+fn test_get_subject_data_jsonld() {
+    use colonylib::graph::RdfFormat;
+    use oxigraph::model::{LiteralRef, NamedNodeRef, Term};
+    use oxigraph::store::Store;
+    use oxjsonld::{JsonLdProfile, JsonLdProfileSet};
+
+    let (mut graph, _temp_dir) = create_test_graph();
+
+    let pod_address = "test_pod_jsonld";
+    let configuration_address = "test_config_jsonld";
+    let subject_address = "jsonld_subject";
+    graph
+        .add_pod_entry(
+            "JSON-LD Pod",
+            pod_address,
+            "scratchpad_jsonld",
+            configuration_address,
+            "test_config_scratchpad_jsonld",
+            0,
+        )
+        .unwrap();
+    graph
+        .put_subject_data(
+            pod_address,
+            subject_address,
+            configuration_address,
+            r#"{
+                "@context": {"schema": "http://schema.org/"},
+                "@type": "schema:Book",
+                "@id": "ant://jsonld_subject",
+                "schema:name": "The Colony Handbook",
+                "schema:author": {"@type": "schema:Person", "schema:name": "Ada Writer"}
+            }"#,
+        )
+        .unwrap();
+
+    let jsonld = graph.get_subject_data_jsonld(subject_address).unwrap();
+    let document: serde_json::Value = serde_json::from_str(&jsonld).unwrap();
+    assert!(document.is_object() || document.is_array());
+
+    // Reading the document back gives the same name and author
+    let mut profile = JsonLdProfileSet::empty();
+    profile |= JsonLdProfile::Compacted;
+    let store = Store::new().unwrap();
+    store
+        .load_from_reader(RdfFormat::JsonLd { profile }, jsonld.as_bytes())
+        .unwrap();
+    let subject = NamedNodeRef::new("ant://jsonld_subject").unwrap();
+    let name = NamedNodeRef::new("http://schema.org/name").unwrap();
+    let author = NamedNodeRef::new("http://schema.org/author").unwrap();
+    assert!(
+        store
+            .quads_for_pattern(
+                Some(subject.into()),
+                Some(name),
+                Some(LiteralRef::new_simple_literal("The Colony Handbook").into()),
+                None,
+            )
+            .next()
+            .is_some()
+    );
+    let author_node = store
+        .quads_for_pattern(Some(subject.into()), Some(author), None, None)
+        .next()
+        .unwrap()
+        .unwrap()
+        .object;
+    let author_node = match author_node {
+        Term::BlankNode(node) => node,
+        other => panic!("Author should be a nested object, got {other}"),
+    };
+    assert!(
+        store
+            .quads_for_pattern(
+                Some(author_node.as_ref().into()),
+                Some(name),
+                Some(LiteralRef::new_simple_literal("Ada Writer").into()),
+                None,
+            )
+            .next()
+            .is_some()
+    );
+    assert_eq!(
+        graph
+            .reader()
+            .get_subject_data_jsonld(subject_address)
+            .unwrap(),
+        jsonld
+    );
+}
+
+#[test]
+fn test_put_subject_data_jsonld_checks() {
+    let (mut graph, _temp_dir) = create_test_graph();
+
+    let pod_address = "test_pod_checks";
+    let configuration_address = "test_config_checks";
+    graph
+        .add_pod_entry(
+            "Checks Pod",
+            pod_address,
+            "scratchpad_checks",
+            configuration_address,
+            "test_config_scratchpad_checks",
+            0,
+        )
+        .unwrap();
+
+    // Without @context, full IRIs still work and undefined terms are skipped without an error
+    graph
+        .put_subject_data(
+            pod_address,
+            "checks_subject_1",
+            configuration_address,
+            r#"{
+                "@id": "ant://checks_subject_1",
+                "http://schema.org/name": "No Context",
+                "undefined_term": "ignored"
+            }"#,
+        )
+        .unwrap();
+    let data = graph.get_subject_data("checks_subject_1").unwrap();
+    assert!(data.contains("No Context"));
+    assert!(!data.contains("ignored"));
+
+    // Malformed JSON reports where it broke and leaves the pod untouched
+    let before = graph.get_pod_graph_data(pod_address).unwrap();
+    let error = graph
+        .put_subject_data(
+            pod_address,
+            "checks_subject_2",
+            configuration_address,
+            "{\n  \"@context\": {\"schema\": \"http://schema.org/\"},\n  \"schema:name\": \"Broken\",,\n}",
+        )
+        .unwrap_err();
+    assert!(matches!(error, colonylib::graph::Error::InvalidInput(_)));
+    assert!(error.to_string().contains("checks_subject_2"));
+    assert_eq!(graph.get_pod_graph_data(pod_address).unwrap(), before);
+
+    // Named graphs are rejected with a specific message
+    let error = graph
+        .put_subject_data(
+            pod_address,
+            "checks_subject_3",
+            configuration_address,
+            r#"{
+                "@context": {"schema": "http://schema.org/"},
+                "@id": "ant://some_other_graph",
+                "@graph": [
+                    {"@id": "ant://checks_subject_3", "schema:name": "In a named graph"}
+                ]
+            }"#,
+        )
+        .unwrap_err();
+    assert!(matches!(error, colonylib::graph::Error::InvalidInput(_)));
+    assert!(error.to_string().contains("named graph"));
+    assert!(
+        !graph
+            .get_subject_data("checks_subject_3")
+            .unwrap()
+            .contains("In a named graph")
+    );
+}
+
+#[test]
+fn test_ask_query() {
+    let (mut graph, _temp_dir) = create_test_graph();
+
+    let pod_address = "test_pod_ask";
+    let configuration_address = "test_config_ask";
+    graph
+        .add_pod_entry(
+            "Ask Pod",
+            pod_address,
+            "scratchpad_ask",
+            configuration_address,
+            "test_config_scratchpad_ask",
+            0,
+        )
+        .unwrap();
+    graph
+        .put_subject_data(
+            pod_address,
+            "ask_subject",
+            configuration_address,
+            r#"{"@context": {"schema": "http://schema.org/"}, "@id": "ant://ask_subject", "schema:name": "Asked For"}"#,
+        )
+        .unwrap();
+
+    assert!(
+        graph
+            .ask_query("ASK { GRAPH ?g { <ant://ask_subject> ?p ?o } }")
+            .unwrap()
+    );
+    assert!(
+        !graph
+            .ask_query("ASK { GRAPH ?g { <ant://missing_subject> ?p ?o } }")
+            .unwrap()
+    );
+    assert!(
+        graph
+            .reader()
+            .ask_query("ASK { GRAPH ?g { ?s <http://schema.org/name> \"Asked For\" } }")
+            .unwrap()
+    );
+
+    // Other query forms are not answered with a boolean
+    assert!(matches!(
+        graph.ask_query("SELECT ?s WHERE { GRAPH ?g { ?s ?p ?o } }"),
+        Err(colonylib::graph::Error::InvalidInput(_))
+    ));
+}
+
+#[test]
+fn test_search_in_pods() {
+    use colonylib::graph::TextMatchMode;
+
+    let (mut graph, _temp_dir) = create_test_graph();
+
+    let configuration_address = "test_pods_search_config";
+    let first_pod = "test_first_collection";
+    let second_pod = "test_second_collection";
+    for (name, pod, scratchpad, config_scratchpad) in [
+        (
+            "First Collection",
+            first_pod,
+            "first_scratchpad",
+            "config_scratchpad_1",
+        ),
+        (
+            "Second Collection",
+            second_pod,
+            "second_scratchpad",
+            "config_scratchpad_2",
+        ),
+    ] {
+        graph
+            .add_pod_entry(
+                name,
+                pod,
+                scratchpad,
+                configuration_address,
+                config_scratchpad,
+                0,
+            )
+            .unwrap();
+    }
+
+    for (pod, subject) in [(first_pod, "first_subject"), (second_pod, "second_subject")] {
+        let data = format!(
+            r#"{{
+                "@context": {{"schema": "http://schema.org/"}},
+                "@id": "ant://{subject}",
+                "@type": "schema:MusicRecording",
+                "schema:name": "Shared Tune"
+            }}"#
+        );
+        graph
+            .put_subject_data(pod, subject, configuration_address, &data)
+            .unwrap();
+    }
+
+    let subjects = |json: String| -> Vec<String> {
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let mut subjects: Vec<String> = parsed["results"]["bindings"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|binding| binding["subject"]["value"].as_str())
+            .map(|subject| subject.to_string())
+            .collect();
+        subjects.dedup();
+        subjects
+    };
+    let only_first = vec![first_pod.to_string()];
+
+    // Without a pod restriction both subjects are found
+    let all = subjects(
+        graph
+            .search_content_in_pods("shared", TextMatchMode::Contains, None, None, false, &[])
+            .unwrap(),
+    );
+    assert!(all.contains(&"ant://first_subject".to_string()));
+    assert!(all.contains(&"ant://second_subject".to_string()));
+
+    // Restricting to one pod only returns that pod's subject
+    let text = subjects(
+        graph
+            .search_content_in_pods(
+                "shared",
+                TextMatchMode::Contains,
+                None,
+                None,
+                false,
+                &only_first,
+            )
+            .unwrap(),
+    );
+    assert_eq!(text, vec!["ant://first_subject".to_string()]);
+
+    let exact = subjects(
+        graph
+            .search_exact_in_pods("Shared Tune", None, None, None, false, &only_first)
+            .unwrap(),
+    );
+    assert_eq!(exact, vec!["ant://first_subject".to_string()]);
+
+    let by_type = subjects(
+        graph
+            .search_by_type_in_pods("http://schema.org/MusicRecording", None, None, &only_first)
+            .unwrap(),
+    );
+    assert_eq!(by_type, vec!["ant://first_subject".to_string()]);
+
+    let by_predicate = subjects(
+        graph
+            .reader()
+            .search_by_predicate_in_pods("http://schema.org/name", None, None, &only_first)
+            .unwrap(),
+    );
+    assert_eq!(by_predicate, vec!["ant://first_subject".to_string()]);
+
+    // Pod addresses may also be given as IRIs
+    let by_iri = subjects(
+        graph
+            .search_by_type_in_pods(
+                "http://schema.org/MusicRecording",
+                None,
+                None,
+                &[format!("ant://{second_pod}")],
+            )
+            .unwrap(),
+    );
+    assert_eq!(by_iri, vec!["ant://second_subject".to_string()]);
+}
+
+#[test]
+fn test_remove_subject_data() {
+    let (mut graph, _temp_dir) = create_test_graph();
+
+    let pod_address = "test_pod_remove";
+    let configuration_address = "test_config_remove";
+    graph
+        .add_pod_entry(
+            "Remove Pod",
+            pod_address,
+            "scratchpad_remove",
+            configuration_address,
+            "test_config_scratchpad_remove",
+            0,
+        )
+        .unwrap();
+    for subject in ["kept_subject", "removed_subject"] {
+        let data = format!(
+            r#"{{"@context": {{"schema": "http://schema.org/"}}, "@id": "ant://{subject}", "schema:name": "{subject}"}}"#
+        );
+        graph
+            .put_subject_data(pod_address, subject, configuration_address, &data)
+            .unwrap();
+    }
+
+    let (pod_data, _configuration) = graph
+        .remove_subject_data(pod_address, "removed_subject", configuration_address)
+        .unwrap();
+    assert!(
+        !String::from_utf8(pod_data)
+            .unwrap()
+            .contains("removed_subject")
+    );
+
+    let subjects = graph.get_pod_subjects(pod_address).unwrap();
+    assert!(subjects.contains(&"kept_subject".to_string()));
+    assert!(!subjects.contains(&"removed_subject".to_string()));
+
+    // A subject without data in the pod is an error
+    assert!(
+        graph
+            .remove_subject_data(pod_address, "removed_subject", configuration_address)
+            .is_err()
+    );
+}
@@ -483,3 +483,205 @@ fn test_update_list_comparison_with_free_addresses() {
     assert!(update_list.pods[pod_address1].contains(&scratchpad_address2.to_string()));
     assert!(update_list.pods[pod_address2].contains(&scratchpad_address3.to_string()));
 }
+
+#[test]
+fn test_remove_from_update_list() {
+    let (datastore, _temp_dir) = create_test_datastore();
+
+    // Two pods and two removals pending upload
+    datastore.append_update_list("uploaded_pod").unwrap();
+    datastore.append_update_list("pending_pod").unwrap();
+    datastore
+        .append_removal_list("removed_pointer", "pointer")
+        .unwrap();
+    datastore
+        .append_removal_list("pending_scratchpad", "scratchpad")
+        .unwrap();
+
+    // Only part of the upload made it to the network
+    let finished: std::collections::HashSet<String> = ["uploaded_pod", "removed_pointer"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    datastore.remove_from_update_list(&finished).unwrap();
+
+    let update_list = datastore.get_update_list().unwrap();
+    assert!(!update_list.pods.contains_key("uploaded_pod"));
+    assert!(update_list.pods.contains_key("pending_pod"));
+    assert!(update_list.remove.pointers.is_empty());
+    assert_eq!(
+        update_list.remove.scratchpads,
+        vec!["pending_scratchpad".to_string()]
+    );
+}
+
+#[test]
+fn test_append_update_list_with_scratchpads() {
+    let (datastore, _temp_dir) = create_test_datastore();
+
+    let pod_address = "queued_pod";
+    let scratchpads = vec!["scratchpad_0".to_string(), "scratchpad_1".to_string()];
+
+    // A pod that was marked for removal is queued for upload again with its scratchpads
+    datastore
+        .append_removal_list(pod_address, "pointer")
+        .unwrap();
+    datastore
+        .append_removal_list("scratchpad_1", "scratchpad")
+        .unwrap();
+    datastore
+        .append_update_list_with_scratchpads(pod_address, &scratchpads)
+        .unwrap();
+
+    let update_list = datastore.get_update_list().unwrap();
+    assert_eq!(update_list.pods[pod_address], scratchpads);
+    assert!(update_list.remove.pointers.is_empty());
+    assert!(update_list.remove.scratchpads.is_empty());
+
+    // Queuing the pod again without scratchpads keeps the recorded set
+    datastore.append_update_list(pod_address).unwrap();
+    let update_list = datastore.get_update_list().unwrap();
+    assert_eq!(update_list.pods[pod_address], scratchpads);
+}
+
+#[test]
+fn test_scratchpad_changes_after_queuing() {
+    let (datastore, _temp_dir) = create_test_datastore();
+
+    let pod_address = "changing_pod";
+    datastore
+        .append_update_list_with_scratchpads(
+            pod_address,
+            &["scratchpad_0".to_string(), "scratchpad_1".to_string()],
+        )
+        .unwrap();
+
+    // The pod shrinks to a single scratchpad after it was queued
+    datastore
+        .append_removal_list("scratchpad_1", "scratchpad")
+        .unwrap();
+    let update_list = datastore.get_update_list().unwrap();
+    assert_eq!(
+        update_list.pods[pod_address],
+        vec!["scratchpad_0".to_string()]
+    );
+    assert_eq!(
+        update_list.remove.scratchpads,
+        vec!["scratchpad_1".to_string()]
+    );
+
+    // Then grows again into a new scratchpad
+    datastore
+        .add_scratchpad_to_pod(pod_address, "scratchpad_2")
+        .unwrap();
+    let update_list = datastore.get_update_list().unwrap();
+    assert_eq!(
+        update_list.pods[pod_address],
+        vec!["scratchpad_0".to_string(), "scratchpad_2".to_string()]
+    );
+
+    // Re-queuing with the current scratchpad set replaces the recorded one exactly
+    let current = vec!["scratchpad_2".to_string(), "scratchpad_1".to_string()];
+    datastore
+        .append_update_list_with_scratchpads(pod_address, &current)
+        .unwrap();
+    let update_list = datastore.get_update_list().unwrap();
+    assert_eq!(update_list.pods[pod_address], current);
+    assert!(update_list.remove.scratchpads.is_empty());
+}
+
+#[test]
+fn test_get_pending_removals() {
+    let (datastore, _temp_dir) = create_test_datastore();
+
+    let removals = datastore.get_pending_removals().unwrap();
+    assert!(removals.pointers.is_empty());
+    assert!(removals.scratchpads.is_empty());
+
+    // A shrinking pod queues only a scratchpad, a removed pod queues its pointer too
+    datastore
+        .append_update_list_with_scratchpads("kept_pod", &["kept_scratchpad".to_string()])
+        .unwrap();
+    datastore
+        .append_removal_list("shrunk_scratchpad", "scratchpad")
+        .unwrap();
+    datastore
+        .append_removal_list("removed_pod", "pointer")
+        .unwrap();
+    datastore
+        .append_removal_list("removed_scratchpad", "scratchpad")
+        .unwrap();
+
+    let removals = datastore.get_pending_removals().unwrap();
+    assert_eq!(removals.pointers, vec!["removed_pod".to_string()]);
+    assert_eq!(
+        removals.scratchpads,
+        vec![
+            "shrunk_scratchpad".to_string(),
+            "removed_scratchpad".to_string()
+        ]
+    );
+
+    // Uploads are not reported as removals
+    assert!(
+        !removals
+            .scratchpads
+            .contains(&"kept_scratchpad".to_string())
+    );
+}
+
+#[test]
+fn test_replace_update_list() {
+    let (datastore, _temp_dir) = create_test_datastore();
+
+    datastore
+        .append_update_list_with_scratchpads("pod", &["scratchpad".to_string()])
+        .unwrap();
+    datastore
+        .append_removal_list("removed_pod", "pointer")
+        .unwrap();
+    let saved = datastore.get_update_list().unwrap();
+
+    datastore.clear_update_list().unwrap();
+    assert!(datastore.get_update_list().unwrap().pods.is_empty());
+
+    // Restoring the copy brings back both uploads and removals
+    datastore.replace_update_list(&saved).unwrap();
+    let restored = datastore.get_update_list().unwrap();
+    assert_eq!(restored.pods, saved.pods);
+    assert_eq!(restored.remove.pointers, vec!["removed_pod".to_string()]);
+}
+
+#[test]
+fn test_known_new_addresses() {
+    let (datastore, _temp_dir) = create_test_datastore();
+
+    // Update lists written before known_new existed still parse
+    fs::write(
+        datastore.get_update_list_path(),
+        r#"{"remove":{"pointers":[],"scratchpads":[]},"pods":{"pod":[]}}"#,
+    )
+    .unwrap();
+    assert!(datastore.get_update_list().unwrap().known_new.is_empty());
+
+    datastore.mark_known_new("new_pointer").unwrap();
+    datastore.mark_known_new("new_scratchpad").unwrap();
+    datastore.mark_known_new("new_pointer").unwrap();
+    assert_eq!(
+        datastore.get_update_list().unwrap().known_new,
+        vec!["new_pointer".to_string(), "new_scratchpad".to_string()]
+    );
+
+    // Uploaded and removed addresses are no longer known to be new
+    let uploaded: std::collections::HashSet<String> =
+        ["new_pointer".to_string()].into_iter().collect();
+    datastore.remove_from_update_list(&uploaded).unwrap();
+    datastore
+        .append_removal_list("new_scratchpad", "scratchpad")
+        .unwrap();
+    assert!(datastore.get_update_list().unwrap().known_new.is_empty());
+
+    datastore.mark_known_new("another").unwrap();
+    datastore.clear_update_list().unwrap();
+    assert!(datastore.get_update_list().unwrap().known_new.is_empty());
+}
@@ -16,6 +16,74 @@ fn test_key_store_from_mnemonic() {
     assert_eq!(key_store.get_pointers().len(), 2);
 }
 
+#[test]
+fn test_key_store_from_mnemonic_with_passphrase() {
+    let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    let plain = KeyStore::from_mnemonic(mnemonic).unwrap();
+    let empty_passphrase = KeyStore::from_mnemonic_with_passphrase(mnemonic, "").unwrap();
+    let protected = KeyStore::from_mnemonic_with_passphrase(mnemonic, "TREZOR").unwrap();
+    let other = KeyStore::from_mnemonic_with_passphrase(mnemonic, "another passphrase").unwrap();
+
+    // An empty passphrase derives the same keys as the mnemonic alone
+    assert_eq!(
+        plain.get_configuration_address().unwrap(),
+        empty_passphrase.get_configuration_address().unwrap()
+    );
+
+    // Each passphrase derives a different pointer address at index 0
+    let addresses = [
+        plain.get_address_at_index(0).unwrap(),
+        protected.get_address_at_index(0).unwrap(),
+        other.get_address_at_index(0).unwrap(),
+    ];
+    assert_ne!(addresses[0], addresses[1]);
+    assert_ne!(addresses[0], addresses[2]);
+    assert_ne!(addresses[1], addresses[2]);
+
+    // The seed phrase itself is stored without the passphrase
+    assert_eq!(protected.get_seed_phrase(), mnemonic);
+}
+
+#[test]
+fn test_key_store_verify_configuration_address() {
+    let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    let key_store = KeyStore::from_mnemonic(mnemonic).unwrap();
+    let configuration_address = key_store.get_configuration_address().unwrap();
+
+    assert!(
+        key_store
+            .verify_configuration_address(&configuration_address)
+            .unwrap()
+    );
+    assert!(
+        key_store
+            .verify_configuration_address(&format!(
+                " ant://{} ",
+                configuration_address.to_uppercase()
+            ))
+            .unwrap()
+    );
+
+    // A restored key store derives the same address, a different mnemonic doesn't
+    let restored = KeyStore::from_mnemonic(mnemonic).unwrap();
+    assert!(
+        restored
+            .verify_configuration_address(&configuration_address)
+            .unwrap()
+    );
+    let random_address = KeyStore::from_mnemonic(
+        "legal winner thank year wave sausage worth useful legal winner thank yellow",
+    )
+    .unwrap()
+    .get_configuration_address()
+    .unwrap();
+    assert!(
+        !key_store
+            .verify_configuration_address(&random_address)
+            .unwrap()
+    );
+}
+
 #[test]
 fn test_key_store_to_and_from_file() {
     let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
@@ -39,6 +107,98 @@ fn test_key_store_to_and_from_file() {
     );
 }
 
+#[test]
+fn test_key_store_index_of_address() {
+    let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    let mut key_store = KeyStore::from_mnemonic(mnemonic).unwrap();
+
+    // The configuration pointer and scratchpad take indices 0 and 1
+    for _ in 0..4 {
+        key_store.add_pointer_key().unwrap();
+        key_store.add_scratchpad_key().unwrap();
+    }
+    assert_eq!(key_store.get_num_keys(), 10);
+
+    for i in 0..10 {
+        let address = key_store.get_address_at_index(i).unwrap();
+        assert_eq!(key_store.index_of_address(&address), Some(i));
+    }
+
+    // Freed keys keep their index
+    let address = key_store.get_address_at_index(4).unwrap();
+    key_store.remove_pointer_key(&address).ok();
+    key_store.remove_scratchpad_key(&address).ok();
+    assert_eq!(key_store.index_of_address(&address), Some(4));
+
+    // Addresses the key store doesn't hold have no index
+    let unknown = key_store.get_address_at_index(100).unwrap();
+    assert_eq!(key_store.index_of_address(&unknown), None);
+    assert_eq!(key_store.index_of_address("not hex"), None);
+
+    // The map is rebuilt when the key store is read back from a file
+    let mut file = std::io::Cursor::new(Vec::new());
+    key_store.to_file(&mut file, "test_password").unwrap();
+    file.set_position(0);
+    let loaded_key_store = KeyStore::from_file(&mut file, "test_password").unwrap();
+    for i in 0..10 {
+        let address = loaded_key_store.get_address_at_index(i).unwrap();
+        assert_eq!(loaded_key_store.index_of_address(&address), Some(i));
+    }
+}
+
+#[test]
+fn test_key_store_change_password() {
+    let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    let key_store = KeyStore::from_mnemonic(mnemonic).unwrap();
+
+    let mut file = std::io::Cursor::new(Vec::new());
+    key_store.to_file(&mut file, "old_password").unwrap();
+    file.set_position(0);
+
+    // The wrong old password is rejected and nothing is written
+    let mut updated = std::io::Cursor::new(Vec::new());
+    assert!(
+        key_store
+            .change_password(&mut file, &mut updated, "wrong_password", "new_password")
+            .is_err()
+    );
+    assert!(updated.get_ref().is_empty());
+
+    file.set_position(0);
+    key_store
+        .change_password(&mut file, &mut updated, "old_password", "new_password")
+        .unwrap();
+
+    // The updated key store only opens with the new password
+    updated.set_position(0);
+    assert!(KeyStore::from_file(&mut updated, "old_password").is_err());
+    updated.set_position(0);
+    let loaded_key_store = KeyStore::from_file(&mut updated, "new_password").unwrap();
+    assert_eq!(loaded_key_store.get_seed_phrase(), mnemonic);
+
+    // A key store file of another key store is rejected
+    let other_key_store = KeyStore::from_mnemonic(
+        "legal winner thank year wave sausage worth useful legal winner thank yellow",
+    )
+    .unwrap();
+    let mut other_file = std::io::Cursor::new(Vec::new());
+    other_key_store
+        .to_file(&mut other_file, "old_password")
+        .unwrap();
+    other_file.set_position(0);
+    let mut other_updated = std::io::Cursor::new(Vec::new());
+    assert!(
+        key_store
+            .change_password(
+                &mut other_file,
+                &mut other_updated,
+                "old_password",
+                "new_password"
+            )
+            .is_err()
+    );
+}
+
 #[test]
 fn test_wallet_key_operations() {
     let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
@@ -251,3 +411,187 @@ fn test_specific_ethereum_key() {
     // (This is a test key from Hardhat/Anvil)
     assert_eq!(eth_address, "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266");
 }
+
+#[test]
+fn test_wallet_checksum_address() {
+    let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    let mut key_store = KeyStore::from_mnemonic(mnemonic).unwrap();
+
+    // Hardhat/Anvil test key with its well-known checksummed address
+    let ethereum_key = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+    key_store.add_wallet_key("test_eth", ethereum_key).unwrap();
+
+    assert_eq!(
+        key_store.get_wallet_checksum_address("test_eth").unwrap(),
+        "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266"
+    );
+    assert!(key_store.get_wallet_checksum_address("missing").is_err());
+
+    // Reference vectors from EIP-55
+    for address in [
+        "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+        "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+        "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+        "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+    ] {
+        assert_eq!(KeyStore::checksum_address(&address.to_lowercase()), address);
+    }
+}
+
+#[test]
+fn test_validate_wallet_key() {
+    // Too short
+    assert!(KeyStore::validate_wallet_key("1234").is_err());
+    assert!(KeyStore::validate_wallet_key("").is_err());
+
+    // Not hex
+    assert!(
+        KeyStore::validate_wallet_key(
+            "zz0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
+        )
+        .is_err()
+    );
+
+    // Valid, with or without the 0x prefix and surrounding whitespace
+    let ethereum_key = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+    let expected_address = "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266";
+    assert_eq!(
+        KeyStore::validate_wallet_key(ethereum_key).unwrap(),
+        expected_address
+    );
+    assert_eq!(
+        KeyStore::validate_wallet_key(&format!(" 0x{ethereum_key}\n")).unwrap(),
+        expected_address
+    );
+
+    // Validation matches what add_wallet_key stores
+    let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    let mut key_store = KeyStore::from_mnemonic(mnemonic).unwrap();
+    assert!(key_store.add_wallet_key("short", "1234").is_err());
+    assert!(key_store.get_wallet_keys().is_empty());
+    key_store.add_wallet_key("main", ethereum_key).unwrap();
+    assert_eq!(
+        key_store.get_wallet_address("main").unwrap(),
+        expected_address
+    );
+}
+
+#[test]
+fn test_add_next_keys_skip_free_keys() {
+    let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    let mut key_store = KeyStore::from_mnemonic(mnemonic).unwrap();
+
+    key_store.add_free_pointer_key().unwrap();
+    key_store.add_free_scratchpad_key().unwrap();
+
+    // The next keys are derived after the free keys, never at an index already handed out
+    let next_index = key_store.get_num_derived_keys();
+    assert_eq!(next_index, key_store.get_num_keys() + 2);
+    let (pointer_address, _) = key_store.add_next_pointer_key().unwrap();
+    assert_eq!(
+        pointer_address,
+        key_store.get_address_at_index(next_index).unwrap()
+    );
+    let (scratchpad_address, _) = key_store.add_next_scratchpad_key().unwrap();
+    assert_eq!(
+        scratchpad_address,
+        key_store.get_address_at_index(next_index + 1).unwrap()
+    );
+    assert_eq!(key_store.get_free_pointers().len(), 1);
+    assert_eq!(key_store.get_free_scratchpads().len(), 1);
+
+    // add_pointer_key still reuses the free key first
+    key_store.add_pointer_key().unwrap();
+    assert!(key_store.get_free_pointers().is_empty());
+    assert_eq!(key_store.get_num_derived_keys(), next_index + 2);
+}
+
+#[test]
+fn test_abandon_key() {
+    let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    let mut key_store = KeyStore::from_mnemonic(mnemonic).unwrap();
+
+    let (pointer_address, _) = key_store.add_pointer_key().unwrap();
+    let (scratchpad_address, _) = key_store.add_scratchpad_key().unwrap();
+    let num_keys = key_store.get_num_keys();
+
+    key_store.abandon_key(&pointer_address).unwrap();
+    key_store.abandon_key(&scratchpad_address).unwrap();
+    assert!(!key_store.get_pointers().contains_key(&pointer_address));
+    assert!(
+        !key_store
+            .get_scratchpads()
+            .contains_key(&scratchpad_address)
+    );
+    assert!(key_store.get_bad_keys().contains_key(&pointer_address));
+    assert!(key_store.get_bad_keys().contains_key(&scratchpad_address));
+
+    // Abandoned keys still count towards the derivation index and are never handed out again
+    assert_eq!(key_store.get_num_keys(), num_keys);
+    let (next_address, _) = key_store.add_pointer_key().unwrap();
+    assert_ne!(next_address, pointer_address);
+    assert_ne!(next_address, scratchpad_address);
+
+    // Unknown keys are rejected
+    assert!(key_store.abandon_key(&pointer_address).is_err());
+}
+
+#[test]
+fn test_derivation_map() {
+    use colonylib::key::KeyKind;
+
+    let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    let mut key_store = KeyStore::from_mnemonic(mnemonic).unwrap();
+
+    let (pointer_address, _) = key_store.add_pointer_key().unwrap();
+    let (scratchpad_address, _) = key_store.add_scratchpad_key().unwrap();
+    key_store.add_bad_key().unwrap();
+    key_store.remove_pointer_key(&pointer_address).unwrap();
+
+    let map = key_store.derivation_map().unwrap();
+    let kinds: Vec<KeyKind> = map.iter().map(|(_, _, kind)| *kind).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            KeyKind::Configuration,
+            KeyKind::Configuration,
+            KeyKind::FreePointer,
+            KeyKind::Scratchpad,
+            KeyKind::Bad,
+        ]
+    );
+    for (index, address, _) in &map {
+        assert_eq!(address, &key_store.get_address_at_index(*index).unwrap());
+    }
+    assert_eq!(map[0].1, key_store.get_configuration_address().unwrap());
+    assert_eq!(map[2].1, pointer_address);
+    assert_eq!(map[3].1, scratchpad_address);
+}
+
+#[test]
+fn test_export_public_manifest() {
+    let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    let mut key_store = KeyStore::from_mnemonic(mnemonic).unwrap();
+
+    let (first_pointer, first_key) = key_store.add_pointer_key().unwrap();
+    let (second_pointer, second_key) = key_store.add_pointer_key().unwrap();
+    let (removed_pointer, _) = key_store.add_pointer_key().unwrap();
+    let (scratchpad_address, _) = key_store.add_scratchpad_key().unwrap();
+    key_store.remove_pointer_key(&removed_pointer).unwrap();
+
+    let manifest = key_store.export_public_manifest();
+    assert_eq!(
+        manifest.configuration_address,
+        key_store.get_configuration_address().unwrap()
+    );
+    let mut expected = vec![first_pointer, second_pointer];
+    expected.sort();
+    assert_eq!(manifest.pod_addresses, expected);
+
+    // Nothing secret or private to the pods leaks into the shared form
+    let json = serde_json::to_string(&manifest).unwrap();
+    assert!(!json.contains(&first_key));
+    assert!(!json.contains(&second_key));
+    assert!(!json.contains(&scratchpad_address));
+    assert!(!json.contains(&key_store.get_configuration_scratchpad_address().unwrap()));
+}
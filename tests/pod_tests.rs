@@ -1,6 +1,6 @@
 mod common;
 use colonylib::DataStore;
-use common::create_test_components;
+use common::{create_test_client, create_test_components, create_test_pod_manager};
 
 #[test]
 fn test_get_pods_at_depth() {
@@ -274,7 +274,7 @@ fn test_search_functionality() {
         .unwrap();
 
     // Test search directly on graph (without network calls)
-    let search_results = graph.search_content("important", Some(10)).unwrap();
+    let search_results = graph.search_content("important", Some(10), None).unwrap();
     let parsed_results: serde_json::Value = serde_json::from_str(&search_results).unwrap();
 
     // Verify results structure
@@ -284,7 +284,7 @@ fn test_search_functionality() {
 
     // Test type search
     let type_results = graph
-        .search_by_type("http://schema.org/MediaObject", Some(10))
+        .search_by_type("http://schema.org/MediaObject", Some(10), None)
         .unwrap();
     let parsed_type_results: serde_json::Value = serde_json::from_str(&type_results).unwrap();
     let type_bindings = parsed_type_results["results"]["bindings"]
@@ -294,7 +294,11 @@ fn test_search_functionality() {
 
     // Test predicate search
     let predicate_results = graph
-        .search_by_predicate("ant://colonylib/vocabulary/0.1/predicate#name", Some(10))
+        .search_by_predicate(
+            "ant://colonylib/vocabulary/0.1/predicate#name",
+            Some(10),
+            None,
+        )
         .unwrap();
     let parsed_predicate_results: serde_json::Value =
         serde_json::from_str(&predicate_results).unwrap();
@@ -331,7 +335,7 @@ fn test_structured_search_queries() {
         .unwrap();
 
     // Test text search directly on graph
-    let text_results = graph.search_content("video", Some(10)).unwrap();
+    let text_results = graph.search_content("video", Some(10), None).unwrap();
     let parsed_text_results: serde_json::Value = serde_json::from_str(&text_results).unwrap();
     let text_bindings = parsed_text_results["results"]["bindings"]
         .as_array()
@@ -340,7 +344,7 @@ fn test_structured_search_queries() {
 
     // Test type search directly on graph
     let type_results = graph
-        .search_by_type("http://schema.org/MediaObject", Some(10))
+        .search_by_type("http://schema.org/MediaObject", Some(10), None)
         .unwrap();
     let parsed_type_results: serde_json::Value = serde_json::from_str(&type_results).unwrap();
     let type_bindings = parsed_type_results["results"]["bindings"]
@@ -350,7 +354,11 @@ fn test_structured_search_queries() {
 
     // Test predicate search directly on graph
     let predicate_results = graph
-        .search_by_predicate("ant://colonylib/vocabulary/0.1/predicate#name", Some(10))
+        .search_by_predicate(
+            "ant://colonylib/vocabulary/0.1/predicate#name",
+            Some(10),
+            None,
+        )
         .unwrap();
     let parsed_predicate_results: serde_json::Value =
         serde_json::from_str(&predicate_results).unwrap();
@@ -386,7 +394,7 @@ fn test_search_error_handling() {
     let (_data_store, _key_store, graph, _temp_dir) = create_test_components();
 
     // Test search with empty text (should return empty array)
-    let empty_results = graph.search_content("", Some(10)).unwrap();
+    let empty_results = graph.search_content("", Some(10), None).unwrap();
     assert_eq!(
         empty_results, "[]",
         "Empty search should return empty array"
@@ -394,7 +402,7 @@ fn test_search_error_handling() {
 
     // Test search with non-existent text
     let no_results = graph
-        .search_content("nonexistent_text_12345", Some(10))
+        .search_content("nonexistent_text_12345", Some(10), None)
         .unwrap();
     let parsed_no_results: serde_json::Value = serde_json::from_str(&no_results).unwrap();
     let no_bindings = parsed_no_results["results"]["bindings"].as_array().unwrap();
@@ -402,7 +410,7 @@ fn test_search_error_handling() {
 
     // Test search by non-existent type
     let no_type_results = graph
-        .search_by_type("http://example.com/NonExistentType", Some(10))
+        .search_by_type("http://example.com/NonExistentType", Some(10), None)
         .unwrap();
     let parsed_no_type_results: serde_json::Value = serde_json::from_str(&no_type_results).unwrap();
     let no_type_bindings = parsed_no_type_results["results"]["bindings"]
@@ -412,7 +420,7 @@ fn test_search_error_handling() {
 
     // Test search by non-existent predicate
     let no_pred_results = graph
-        .search_by_predicate("http://example.com/nonexistent", Some(10))
+        .search_by_predicate("http://example.com/nonexistent", Some(10), None)
         .unwrap();
     let parsed_no_pred_results: serde_json::Value = serde_json::from_str(&no_pred_results).unwrap();
     let no_pred_bindings = parsed_no_pred_results["results"]["bindings"]
@@ -589,6 +597,116 @@ fn test_data_splitting_helper_functions() {
     println!("Created {} chunks from large line", chunks_large.len());
 }
 
+// NOTE: this test can only be run if there is a local testnet running, so ignoring by default
+#[ignore]
+#[test]
+fn test_split_data_into_chunks_multibyte() {
+    use autonomi::{Client, Wallet};
+    use colonylib::PodManager;
+
+    let (mut data_store, mut key_store, mut graph, _temp_dir) = create_test_components();
+
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let (client, wallet) = rt.block_on(async {
+        let client = Client::init_local()
+            .await
+            .expect("Failed to create test client");
+        let evm_network = client.evm_network().clone();
+        let private_key = "0x1234567890123456789012345678901234567890123456789012345678901234";
+        let wallet = Wallet::new_from_private_key(evm_network, private_key)
+            .expect("Failed to create test wallet");
+        (client, wallet)
+    });
+
+    let pod_manager = rt.block_on(async {
+        PodManager::new(client, &wallet, &mut data_store, &mut key_store, &mut graph)
+            .await
+            .expect("Failed to create PodManager")
+    });
+
+    // Short lines plus one long line of 3 and 4 byte characters, none of which line up with
+    // the chunk boundaries
+    let chunk_size = 100;
+    let data = format!(
+        "<ant://s1> <http://schema.org/name> \"日本語のテキスト\" .\n{}\n<ant://s2> <http://schema.org/name> \"🦀🚀✨\" .\n",
+        "漢字🦀".repeat(40)
+    );
+
+    let chunks = pod_manager.split_data_into_chunks(&data, chunk_size);
+    assert!(chunks.len() > 2, "Should have created multiple chunks");
+
+    for (i, chunk) in chunks.iter().enumerate() {
+        assert!(
+            chunk.len() <= chunk_size,
+            "Chunk {} exceeds size limit: {} bytes",
+            i,
+            chunk.len()
+        );
+    }
+
+    // Dropping the timestamp comment of each chunk gives back the exact original bytes
+    let reassembled: String = chunks
+        .iter()
+        .map(|chunk| chunk.split_once('\n').map(|(_, rest)| rest).unwrap_or(""))
+        .collect();
+    assert_eq!(reassembled.as_bytes(), data.as_bytes());
+}
+
+// NOTE: this test can only be run if there is a local testnet running, so ignoring by default
+#[ignore]
+#[test]
+fn test_sort_graph_data_multiline_literal() {
+    use autonomi::{Client, Wallet};
+    use colonylib::PodManager;
+    use colonylib::graph::{HAS_INDEX, HAS_MODIFIED_DATE};
+
+    let (mut data_store, mut key_store, mut graph, _temp_dir) = create_test_components();
+
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let (client, wallet) = rt.block_on(async {
+        let client = Client::init_local()
+            .await
+            .expect("Failed to create test client");
+        let evm_network = client.evm_network().clone();
+        let private_key = "0x1234567890123456789012345678901234567890123456789012345678901234";
+        let wallet = Wallet::new_from_private_key(evm_network, private_key)
+            .expect("Failed to create test wallet");
+        (client, wallet)
+    });
+
+    let pod_manager = rt.block_on(async {
+        PodManager::new(client, &wallet, &mut data_store, &mut key_store, &mut graph)
+            .await
+            .expect("Failed to create PodManager")
+    });
+
+    // The second line of the literal doesn't start with whitespace, which used to be taken as
+    // the start of a new statement
+    let test_data = format!(
+        r#"<ant://subject1> <http://schema.org/description> """First line
+<ant://not_a_subject> second line""" .
+<ant://scratchpad1> <{HAS_INDEX}> "1" .
+<ant://scratchpad0> <{HAS_INDEX}> "0" ;
+    <{HAS_MODIFIED_DATE}> "2024-01-15T10:30:45Z" .
+"#
+    );
+
+    let sorted_data = pod_manager.sort_graph_data(&test_data);
+    let lines: Vec<&str> = sorted_data.lines().collect();
+    assert_eq!(lines.len(), 4, "Each triple should be on its own line");
+
+    // Scratchpad 0 comes first with all of its triples, then scratchpad 1, then the rest
+    assert!(lines[0].starts_with("<ant://scratchpad0>"));
+    assert!(lines[0].contains(HAS_INDEX));
+    assert!(lines[1].starts_with("<ant://scratchpad0>"));
+    assert!(lines[1].contains(HAS_MODIFIED_DATE));
+    assert!(lines[2].starts_with("<ant://scratchpad1>"));
+    assert!(lines[3].starts_with("<ant://subject1>"));
+
+    // The literal is kept whole, with the newline escaped
+    assert!(lines[3].contains(r#""First line\n<ant://not_a_subject> second line""#));
+}
+
 #[tokio::test]
 async fn test_pod_manager_browse_search() {
     let (_data_store, _key_store, graph, _temp_dir) = create_test_components();
@@ -820,3 +938,1196 @@ fn test_pointer_unix_timestamp_counter() {
     println!("Current Unix timestamp for pointer: {current_time}");
     println!("Later Unix timestamp for pointer: {later_time}");
 }
+
+#[test]
+fn test_select_newest_scratchpad_with_fake_clock() {
+    use autonomi::client::scratchpad::{Scratchpad, ScratchpadAddress};
+    use autonomi::{Bytes, SecretKey};
+    use colonylib::PodManager;
+    use colonylib::clock::{Clock, FakeClock};
+
+    // Two versions of the same scratchpad, written one minute apart
+    let key = SecretKey::random();
+    let address = ScratchpadAddress::new(key.public_key());
+    let clock = FakeClock::new(
+        chrono::DateTime::parse_from_rfc3339("2025-01-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc),
+    );
+    let make_version = |clock: &FakeClock, body: &str, counter: u64| {
+        let data = Bytes::from(format!("#{}\n{body}", clock.now().to_rfc3339()));
+        let signature = key.sign(Scratchpad::bytes_for_signature(address, 0, &data, counter));
+        Scratchpad::new_with_signature(key.public_key(), 0, data, counter, signature)
+    };
+
+    let older = make_version(&clock, "older", 7);
+    clock.advance(chrono::Duration::minutes(1));
+    let newer = make_version(&clock, "newer", 3);
+
+    // The newer timestamp wins regardless of order or counter
+    for versions in [
+        vec![older.clone(), newer.clone()],
+        vec![newer.clone(), older.clone()],
+    ] {
+        let (selected, fork_info) = PodManager::select_newest_scratchpad(versions).unwrap();
+        assert_eq!(selected.encrypted_data(), newer.encrypted_data());
+        assert_eq!(fork_info.version_count, 2);
+        assert!(!fork_info.ambiguous);
+        assert_eq!(
+            fork_info.timestamps[fork_info.selected_index],
+            Some("2025-01-01T12:01:00+00:00".to_string())
+        );
+    }
+    // There is nothing to select from an empty fork
+    assert!(PodManager::select_newest_scratchpad(Vec::new()).is_none());
+}
+
+#[test]
+fn test_refresh_mode() {
+    use colonylib::pod::RefreshMode;
+
+    assert_eq!(RefreshMode::default(), RefreshMode::Always);
+
+    // Always downloads the configuration pod regardless of the counters
+    assert!(RefreshMode::Always.should_download(5, Some(5), true));
+    assert!(RefreshMode::Always.should_download(4, Some(5), true));
+    assert!(RefreshMode::Always.should_download(6, Some(5), true));
+    assert!(RefreshMode::Always.should_download(0, None, true));
+
+    // Every mode only downloads the user's pods when they are newer, or were never cached
+    for mode in [
+        RefreshMode::Always,
+        RefreshMode::CounterAware,
+        RefreshMode::TtlAware,
+    ] {
+        assert!(!mode.should_download(5, Some(5), false));
+        assert!(!mode.should_download(4, Some(5), false));
+        assert!(mode.should_download(6, Some(5), false));
+        assert!(mode.should_download(0, None, false));
+    }
+
+    // The counter-aware modes check the configuration counter too
+    for mode in [RefreshMode::CounterAware, RefreshMode::TtlAware] {
+        assert!(!mode.should_download(5, Some(5), true));
+        assert!(mode.should_download(6, Some(5), true));
+    }
+
+    // Only TtlAware skips recently refreshed pods
+    assert!(!RefreshMode::Always.uses_ttl());
+    assert!(!RefreshMode::CounterAware.uses_ttl());
+    assert!(RefreshMode::TtlAware.uses_ttl());
+}
+
+#[test]
+fn test_refresh_conflict_policy() {
+    use colonylib::pod::RefreshConflictPolicy;
+
+    assert_eq!(
+        RefreshConflictPolicy::default(),
+        RefreshConflictPolicy::PreferLocal
+    );
+
+    // Pods without local changes are always downloaded
+    for policy in [
+        RefreshConflictPolicy::PreferLocal,
+        RefreshConflictPolicy::PreferRemote,
+        RefreshConflictPolicy::Error,
+    ] {
+        assert!(policy.should_download("clean_pod", false).unwrap());
+    }
+
+    // Pending edits survive a refresh under PreferLocal
+    assert!(
+        !RefreshConflictPolicy::PreferLocal
+            .should_download("edited_pod", true)
+            .unwrap()
+    );
+    assert!(
+        RefreshConflictPolicy::PreferRemote
+            .should_download("edited_pod", true)
+            .unwrap()
+    );
+    let error = RefreshConflictPolicy::Error
+        .should_download("edited_pod", true)
+        .unwrap_err();
+    assert!(error.to_string().contains("edited_pod"));
+}
+
+#[test]
+fn test_search_results_from_sparql_json() {
+    use colonylib::pod::SearchResults;
+
+    let (_data_store, _key_store, mut graph, _temp_dir) = create_test_components();
+
+    let pod_address = "search_typed_pod";
+    let configuration_address = "search_typed_config";
+    graph
+        .add_pod_entry(
+            "Typed Search Pod",
+            pod_address,
+            "search_typed_scratchpad",
+            configuration_address,
+            "search_typed_config_scratchpad",
+            0,
+        )
+        .unwrap();
+    let data = r#"{
+        "@context": {"schema": "http://schema.org/"},
+        "@id": "ant://typed_subject",
+        "schema:name": "Moonlight Sonata",
+        "schema:description": "Piano sonata"
+    }"#;
+    graph
+        .put_subject_data(pod_address, "typed_subject", configuration_address, data)
+        .unwrap();
+
+    let json = graph.search_content("moonlight", Some(50), None).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let results = SearchResults::from_sparql_json(&json, "2024-01-01T00:00:00+00:00");
+
+    assert!(results.result_count > 0);
+    assert_eq!(results.result_count, results.results.len());
+    assert_eq!(results.pods_found, vec![pod_address.to_string()]);
+    assert_eq!(results.timestamp, "2024-01-01T00:00:00+00:00");
+
+    let name_hit = results
+        .results
+        .iter()
+        .find(|hit| hit.predicate.as_deref() == Some("http://schema.org/name"))
+        .unwrap();
+    assert_eq!(name_hit.subject, "ant://typed_subject");
+    assert_eq!(name_hit.object.as_deref(), Some("Moonlight Sonata"));
+    assert_eq!(name_hit.pod.as_deref(), Some(pod_address));
+    assert_eq!(name_hit.depth, Some(0));
+    assert_eq!(name_hit.match_count, Some(1));
+
+    // Bindings without a subject are skipped and missing results parse as empty
+    let json = serde_json::json!({
+        "head": {"vars": ["name"]},
+        "results": {"bindings": [{"name": {"type": "literal", "value": "orphan"}}]}
+    });
+    let results = SearchResults::from_sparql_json(&json, "");
+    assert!(results.results.is_empty());
+    assert_eq!(results.result_count, 0);
+    assert!(results.pods_found.is_empty());
+    assert!(
+        SearchResults::from_sparql_json(&serde_json::Value::Null, "")
+            .results
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_search_ranking_from_sparql_json() {
+    use colonylib::graph::UNKNOWN_DEPTH_RANK;
+    use colonylib::pod::SearchRanking;
+
+    let (_data_store, _key_store, mut graph, _temp_dir) = create_test_components();
+
+    let pod_address = "ranking_pod";
+    let configuration_address = "ranking_config";
+    graph
+        .add_pod_entry(
+            "Ranking Pod",
+            pod_address,
+            "ranking_scratchpad",
+            configuration_address,
+            "ranking_config_scratchpad",
+            0,
+        )
+        .unwrap();
+    let data = r#"{
+        "@context": {"schema": "http://schema.org/"},
+        "@id": "ant://ranked_subject",
+        "schema:name": "Moonlight Sonata"
+    }"#;
+    graph
+        .put_subject_data(pod_address, "ranked_subject", configuration_address, data)
+        .unwrap();
+
+    let json = graph
+        .search_content("moonlight sonata", Some(50), None)
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let ranking = SearchRanking::from_sparql_json(&json);
+
+    assert!(!ranking.is_empty());
+    let ranks: Vec<usize> = ranking.iter().map(|r| r.rank).collect();
+    assert_eq!(ranks, (1..=ranking.len()).collect::<Vec<_>>());
+    // The name matches both terms so it is ranked first
+    assert_eq!(ranking[0].subject, "ant://ranked_subject");
+    assert_eq!(ranking[0].match_count, 2);
+    assert_eq!(ranking[0].pod.as_deref(), Some(pod_address));
+    assert_eq!(ranking[0].depth, Some(0));
+    assert_eq!(ranking[0].sort_depth, 0);
+    assert!(
+        ranking
+            .windows(2)
+            .all(|w| w[0].match_count >= w[1].match_count)
+    );
+
+    // Results from pods without a known depth are ranked after every real depth
+    let json = serde_json::json!({
+        "head": {"vars": ["subject", "match_count"]},
+        "results": {"bindings": [{
+            "subject": {"type": "uri", "value": "ant://remote_subject"},
+            "match_count": {"type": "literal", "value": "1"}
+        }]}
+    });
+    let ranking = SearchRanking::from_sparql_json(&json);
+    assert_eq!(ranking.len(), 1);
+    assert_eq!(ranking[0].depth, None);
+    assert_eq!(ranking[0].sort_depth, UNKNOWN_DEPTH_RANK);
+    assert!(SearchRanking::from_sparql_json(&serde_json::Value::Null).is_empty());
+}
+
+#[test]
+fn test_csv_mapping_to_subjects() {
+    use colonylib::pod::CsvMapping;
+
+    let (_data_store, _key_store, mut graph, _temp_dir) = create_test_components();
+
+    let pod_address = "csv_pod";
+    let configuration_address = "csv_config";
+    graph
+        .add_pod_entry(
+            "CSV Pod",
+            pod_address,
+            "csv_scratchpad",
+            configuration_address,
+            "csv_config_scratchpad",
+            0,
+        )
+        .unwrap();
+
+    let csv = "address,title,artist,kind\r\n\
+               ant://csv_subject_1,\"Sonata, No. 14\",Beethoven,\r\n\
+               \r\n\
+               csv_subject_2,\"The \"\"Moonlight\"\"\",,http://schema.org/CreativeWork\r\n";
+    let mapping = CsvMapping {
+        subject_column: "address".to_string(),
+        predicates: vec![
+            ("title".to_string(), "http://schema.org/name".to_string()),
+            (
+                "artist".to_string(),
+                "http://schema.org/byArtist".to_string(),
+            ),
+        ],
+        type_uri: Some("http://schema.org/MusicRecording".to_string()),
+        type_column: Some("kind".to_string()),
+    };
+
+    let subjects = mapping.to_subjects(csv).unwrap();
+    assert_eq!(subjects.len(), 2);
+    assert_eq!(subjects[0].0, "csv_subject_1");
+    assert_eq!(subjects[1].0, "csv_subject_2");
+
+    graph
+        .put_subjects_data(pod_address, configuration_address, &subjects)
+        .unwrap();
+    let values_of = |subject: &str, predicate: &str| -> Vec<String> {
+        let data: serde_json::Value =
+            serde_json::from_str(&graph.get_subject_data(subject).unwrap()).unwrap();
+        data["results"]["bindings"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|b| b["predicate"]["value"] == predicate)
+            .map(|b| b["object"]["value"].as_str().unwrap().to_string())
+            .collect()
+    };
+    assert_eq!(
+        values_of("csv_subject_1", "http://schema.org/name"),
+        vec!["Sonata, No. 14"]
+    );
+    assert_eq!(
+        values_of("csv_subject_1", "http://schema.org/byArtist"),
+        vec!["Beethoven"]
+    );
+    assert_eq!(
+        values_of("csv_subject_2", "http://schema.org/name"),
+        vec!["The \"Moonlight\""]
+    );
+    assert!(values_of("csv_subject_2", "http://schema.org/byArtist").is_empty());
+    assert_eq!(
+        graph.get_subject_types("csv_subject_1").unwrap(),
+        vec!["http://schema.org/MusicRecording".to_string()]
+    );
+    assert_eq!(
+        graph.get_subject_types("csv_subject_2").unwrap(),
+        vec!["http://schema.org/CreativeWork".to_string()]
+    );
+
+    // Missing columns, missing subjects and broken quoting are rejected
+    let bad_mapping = CsvMapping {
+        subject_column: "id".to_string(),
+        ..Default::default()
+    };
+    assert!(bad_mapping.to_subjects(csv).is_err());
+    let title_mapping = CsvMapping {
+        subject_column: "address".to_string(),
+        predicates: vec![("title".to_string(), "http://schema.org/name".to_string())],
+        ..Default::default()
+    };
+    assert!(
+        title_mapping
+            .to_subjects("address,title\n,No subject\n")
+            .is_err()
+    );
+    assert!(
+        title_mapping
+            .to_subjects("address,title\nsubject,\"unterminated\n")
+            .is_err()
+    );
+    assert!(title_mapping.to_subjects("").unwrap().is_empty());
+}
+
+#[test]
+fn test_pod_manager_config_serde() {
+    use colonylib::pod::{
+        DEFAULT_MAX_CONCURRENCY, DEFAULT_RETRY_ATTEMPTS, DEFAULT_RETRY_BASE_DELAY,
+        DEFAULT_SCRATCHPAD_SIZE_LIMIT, PodManagerConfig, RefreshConflictPolicy, RefreshMode,
+    };
+    use std::time::Duration;
+
+    let config = PodManagerConfig::default();
+    assert_eq!(config.refresh_mode, RefreshMode::Always);
+    assert_eq!(config.refresh_ttl, Duration::ZERO);
+    assert!(!config.defer_configuration);
+    assert!(!config.show_configuration_pod);
+    assert_eq!(
+        config.refresh_conflict_policy,
+        RefreshConflictPolicy::PreferLocal
+    );
+    assert_eq!(config.max_concurrency, DEFAULT_MAX_CONCURRENCY);
+    assert_eq!(config.retry_attempts, DEFAULT_RETRY_ATTEMPTS);
+    assert_eq!(config.retry_base_delay, DEFAULT_RETRY_BASE_DELAY);
+    assert_eq!(config.scratchpad_size_limit, DEFAULT_SCRATCHPAD_SIZE_LIMIT);
+
+    let config = PodManagerConfig {
+        refresh_mode: RefreshMode::TtlAware,
+        refresh_ttl: Duration::from_secs(300),
+        defer_configuration: true,
+        show_configuration_pod: true,
+        refresh_conflict_policy: RefreshConflictPolicy::Error,
+        history_depth: 3,
+        max_concurrency: 8,
+        retry_attempts: 5,
+        retry_base_delay: Duration::from_millis(250),
+        scratchpad_size_limit: 1024 * 1024,
+    };
+    let json = serde_json::to_string(&config).unwrap();
+    let restored: PodManagerConfig = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, config);
+
+    // Settings saved without some fields fall back to the defaults
+    let restored: PodManagerConfig =
+        serde_json::from_str(r#"{"refresh_mode": "CounterAware"}"#).unwrap();
+    assert_eq!(
+        restored,
+        PodManagerConfig {
+            refresh_mode: RefreshMode::CounterAware,
+            ..PodManagerConfig::default()
+        }
+    );
+}
+
+// NOTE: this test can only be run if there is a local testnet running, so ignoring by default
+#[ignore]
+#[test]
+fn test_estimate_upload_cost() {
+    let (mut data_store, mut key_store, mut graph, _temp_dir) = create_test_components();
+
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        let (client, wallet) = create_test_client().await;
+        let mut pod_manager =
+            create_test_pod_manager(client, &wallet, &mut data_store, &mut key_store, &mut graph)
+                .await;
+
+        // Upload a first pod so the configuration pod already exists on the network
+        pod_manager.add_pod("First Pod").await.unwrap();
+        pod_manager.upload_all().await.unwrap();
+
+        // A fresh pod needs one new pointer and one new scratchpad, the configuration pod
+        // is only updated
+        pod_manager.add_pod("Estimated Pod").await.unwrap();
+        let estimate = pod_manager.estimate_upload_cost().await.unwrap();
+        assert_eq!(estimate["new_pointers"], 1);
+        assert_eq!(estimate["new_scratchpads"], 1);
+        assert_eq!(estimate["updated_pointers"], 1);
+        assert!(estimate["bytes"].as_u64().unwrap() > 0);
+
+        pod_manager.upload_all().await.unwrap();
+        let estimate = pod_manager.estimate_upload_cost().await.unwrap();
+        assert_eq!(estimate["new_pointers"], 0);
+        assert_eq!(estimate["new_scratchpads"], 0);
+    });
+}
+
+// NOTE: this test can only be run if there is a local testnet running, so ignoring by default
+#[ignore]
+#[test]
+fn test_upload_all_dry_run() {
+    use futures::StreamExt;
+    use std::collections::HashSet;
+
+    let (mut data_store, mut key_store, mut graph, _temp_dir) = create_test_components();
+
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        let (client, wallet) = create_test_client().await;
+        let mut pod_manager =
+            create_test_pod_manager(client, &wallet, &mut data_store, &mut key_store, &mut graph)
+                .await;
+
+        pod_manager.add_pod("Dry Run Pod").await.unwrap();
+        let update_list = pod_manager.get_update_list().unwrap();
+
+        // The dry run leaves the update list as it was
+        let plan = pod_manager.upload_all_dry_run().await.unwrap();
+        assert_eq!(pod_manager.get_update_list().unwrap(), update_list);
+        let planned: HashSet<(String, String)> = plan["operations"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|operation| {
+                (
+                    operation["address"].as_str().unwrap().to_string(),
+                    operation["action"].as_str().unwrap().to_string(),
+                )
+            })
+            .collect();
+        assert!(!planned.is_empty());
+
+        // The real upload performs exactly the planned operations
+        let outcomes: Vec<_> = pod_manager.upload_all_stream().collect().await;
+        let performed: HashSet<(String, String)> = outcomes
+            .into_iter()
+            .map(|outcome| {
+                let outcome = outcome.unwrap();
+                let action = serde_json::to_value(outcome.action).unwrap();
+                (outcome.address, action.as_str().unwrap().to_lowercase())
+            })
+            .collect();
+        assert_eq!(planned, performed);
+    });
+}
+
+// NOTE: this test can only be run if there is a local testnet running, so ignoring by default
+#[ignore]
+#[test]
+fn test_max_concurrency_multi_scratchpad_pod() {
+    let (mut data_store, mut key_store, mut graph, _temp_dir) = create_test_components();
+    let (mut fresh_data_store, mut fresh_key_store, mut fresh_graph, _fresh_temp_dir) =
+        create_test_components();
+
+    // Large enough to need more than one 4MB scratchpad
+    let description = "colonylib ".repeat(500_000);
+    let subject_address = "c859818c623ce4fc0899c2ab43061b19caa0b0598eec35ef309dbe50c8af8d59";
+
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        let (client, wallet) = create_test_client().await;
+        let mut pod_manager = create_test_pod_manager(
+            client.clone(),
+            &wallet,
+            &mut data_store,
+            &mut key_store,
+            &mut graph,
+        )
+        .await;
+
+        pod_manager.set_max_concurrency(0);
+        assert_eq!(pod_manager.max_concurrency(), 1);
+
+        let (pod_address, _) = pod_manager.add_pod("Large Pod").await.unwrap();
+        let metadata = serde_json::json!({
+            "@context": {"schema": "http://schema.org/"},
+            "@type": "schema:Dataset",
+            "schema:description": description,
+        })
+        .to_string();
+        pod_manager
+            .put_subject_data(&pod_address, subject_address, &metadata)
+            .await
+            .unwrap();
+
+        let plan = pod_manager.upload_all_dry_run().await.unwrap();
+        let scratchpads = plan["operations"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|operation| operation["kind"] == "scratchpad")
+            .count();
+        assert!(scratchpads > 2, "Pod should span several scratchpads");
+
+        // Upload one operation at a time
+        pod_manager.upload_all().await.unwrap();
+
+        // Download everything into an empty cache, again one operation at a time
+        let mut fresh_pod_manager = create_test_pod_manager(
+            client,
+            &wallet,
+            &mut fresh_data_store,
+            &mut fresh_key_store,
+            &mut fresh_graph,
+        )
+        .await;
+        fresh_pod_manager.set_max_concurrency(1);
+        fresh_pod_manager.refresh_cache(false).await.unwrap();
+
+        let subject_data = fresh_pod_manager
+            .get_subject_data(subject_address)
+            .await
+            .unwrap();
+        assert!(subject_data.contains(description.trim_end()));
+    });
+}
+
+// NOTE: this test can only be run if there is a local testnet running, so ignoring by default
+#[ignore]
+#[test]
+fn test_refresh_progress_callback() {
+    use colonylib::pod::{RefreshPhase, RefreshProgress};
+    use std::sync::{Arc, Mutex};
+
+    let (mut data_store, mut key_store, mut graph, _temp_dir) = create_test_components();
+
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        let (client, wallet) = create_test_client().await;
+        let mut pod_manager =
+            create_test_pod_manager(client, &wallet, &mut data_store, &mut key_store, &mut graph)
+                .await;
+
+        pod_manager.add_pod("Progress Pod").await.unwrap();
+        pod_manager.upload_all().await.unwrap();
+
+        let events: Arc<Mutex<Vec<RefreshProgress>>> = Arc::new(Mutex::new(Vec::new()));
+        let collected = events.clone();
+        pod_manager.set_progress_callback(Box::new(move |progress| {
+            collected.lock().unwrap().push(progress);
+        }));
+        pod_manager.refresh_ref(1).await.unwrap();
+
+        let events = events.lock().unwrap();
+        for phase in [
+            RefreshPhase::Configuration,
+            RefreshPhase::Pointers,
+            RefreshPhase::Scratchpads,
+            RefreshPhase::References,
+        ] {
+            let phase_events: Vec<&RefreshProgress> =
+                events.iter().filter(|event| event.phase == phase).collect();
+            assert!(!phase_events.is_empty(), "No progress for {phase:?}");
+
+            // Counts only go up within a phase and never pass the total
+            for pair in phase_events.windows(2) {
+                assert!(pair[0].pods_processed <= pair[1].pods_processed);
+                assert!(pair[0].current_depth <= pair[1].current_depth);
+            }
+            for event in &phase_events {
+                assert!(event.pods_processed <= event.pods_total);
+            }
+            let last = phase_events.last().unwrap();
+            assert_eq!(last.pods_processed, last.pods_total);
+        }
+
+        // The pointer phase reports every pointer fetched
+        let pointer_events = events
+            .iter()
+            .filter(|event| event.phase == RefreshPhase::Pointers)
+            .count();
+        assert!(pointer_events > 1);
+    });
+}
+
+// NOTE: this test can only be run if there is a local testnet running, so ignoring by default
+#[ignore]
+#[test]
+fn test_upload_all_report() {
+    let (mut data_store, mut key_store, mut graph, _temp_dir) = create_test_components();
+
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        let (client, wallet) = create_test_client().await;
+        let mut pod_manager =
+            create_test_pod_manager(client, &wallet, &mut data_store, &mut key_store, &mut graph)
+                .await;
+
+        // Upload a first pod so the configuration pod already exists on the network
+        pod_manager.add_pod("First Pod").await.unwrap();
+        pod_manager.upload_all().await.unwrap();
+
+        // A new pod creates its pointer and scratchpad and updates the configuration pod
+        let (pod_address, _) = pod_manager.add_pod("Reported Pod").await.unwrap();
+        let report = pod_manager.upload_all_report().await.unwrap();
+        assert_eq!(report.created_pointers, 1);
+        assert_eq!(report.created_scratchpads, 1);
+        assert_eq!(report.updated_pointers, 1);
+        assert_eq!(report.updated_scratchpads, 1);
+        assert_eq!(report.removed, 0);
+        assert!(report.total_cost.parse::<u128>().unwrap() > 0);
+
+        // Editing the metadata only updates existing objects
+        let subject_address = "c859818c623ce4fc0899c2ab43061b19caa0b0598eec35ef309dbe50c8af8d59";
+        let metadata = r#"{
+            "@context": {"schema": "http://schema.org/"},
+            "@type": "schema:Dataset",
+            "schema:name": "Reported Data"
+        }"#;
+        pod_manager
+            .put_subject_data(&pod_address, subject_address, metadata)
+            .await
+            .unwrap();
+        let report = pod_manager.upload_all_report().await.unwrap();
+        assert_eq!(report.created_pointers, 0);
+        assert_eq!(report.created_scratchpads, 0);
+        assert!(report.updated_pointers >= 1);
+        assert!(report.updated_scratchpads >= 1);
+        assert_eq!(report.removed, 0);
+    });
+}
+
+// NOTE: this test can only be run if there is a local testnet running, so ignoring by default
+#[ignore]
+#[test]
+fn test_remove_subject_data() {
+    let (mut data_store, mut key_store, mut graph, _temp_dir) = create_test_components();
+    let kept_subject = "c859818c623ce4fc0899c2ab43061b19caa0b0598eec35ef309dbe50c8af8d59";
+    let removed_subject = "a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f90";
+
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        let (client, wallet) = create_test_client().await;
+        let mut pod_manager =
+            create_test_pod_manager(client, &wallet, &mut data_store, &mut key_store, &mut graph)
+                .await;
+
+        let (pod_address, _) = pod_manager.add_pod("Removal Pod").await.unwrap();
+        for subject_address in [kept_subject, removed_subject] {
+            let metadata = serde_json::json!({
+                "@context": {"schema": "http://schema.org/"},
+                "@type": "schema:MediaObject",
+                "schema:name": format!("Subject {subject_address}"),
+            })
+            .to_string();
+            pod_manager
+                .put_subject_data(&pod_address, subject_address, &metadata)
+                .await
+                .unwrap();
+        }
+        let subjects = pod_manager.list_pod_subjects(&pod_address).unwrap();
+        assert!(subjects.contains(&removed_subject.to_string()));
+
+        pod_manager
+            .remove_subject_data(&pod_address, removed_subject)
+            .await
+            .unwrap();
+
+        let subjects = pod_manager.list_pod_subjects(&pod_address).unwrap();
+        assert!(!subjects.contains(&removed_subject.to_string()));
+        assert!(subjects.contains(&kept_subject.to_string()));
+
+        // Removing it again fails, as the pod no longer has data for it
+        assert!(
+            pod_manager
+                .remove_subject_data(&pod_address, removed_subject)
+                .await
+                .is_err()
+        );
+    });
+}
+
+// NOTE: this test can only be run if there is a local testnet running, so ignoring by default
+#[ignore]
+#[test]
+fn test_gc_orphan_files_scratchpads_only() {
+    let (mut data_store, mut key_store, mut graph, _temp_dir) = create_test_components();
+    let orphan_address = "0f0e0d0c0b0a09080706050403020100f0e0d0c0b0a090807060504030201000";
+    let configuration_scratchpad = key_store.get_configuration_scratchpad_address().unwrap();
+
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        let (client, wallet) = create_test_client().await;
+        let mut pod_manager =
+            create_test_pod_manager(client, &wallet, &mut data_store, &mut key_store, &mut graph)
+                .await;
+
+        let (pod_address, _) = pod_manager.add_pod("GC Pod").await.unwrap();
+        let pod_scratchpads = pod_manager.graph.get_pod_scratchpads(&pod_address).unwrap();
+        assert!(!pod_scratchpads.is_empty());
+
+        // Plant a scratchpad file that no pod refers to
+        pod_manager
+            .data_store
+            .create_scratchpad_file(orphan_address)
+            .unwrap();
+
+        // A dry run only reports the orphan
+        let orphan_path = pod_manager
+            .data_store
+            .get_scratchpads_dir()
+            .join(orphan_address);
+        let orphans = pod_manager.gc_orphan_files(true, true).unwrap();
+        assert_eq!(orphans, vec![orphan_path.clone()]);
+        assert!(orphan_path.exists());
+
+        let removed = pod_manager.gc_orphan_files(false, true).unwrap();
+        assert_eq!(removed, vec![orphan_path]);
+
+        let remaining = pod_manager.data_store.list_scratchpad_files().unwrap();
+        assert!(!remaining.contains(&orphan_address.to_string()));
+        assert!(remaining.contains(&configuration_scratchpad));
+        for scratchpad in pod_scratchpads {
+            assert!(remaining.contains(&scratchpad));
+        }
+        assert!(
+            pod_manager
+                .data_store
+                .address_is_pointer(&pod_address)
+                .unwrap()
+        );
+
+        // Nothing is left to collect
+        assert!(pod_manager.gc_orphan_files(false, true).unwrap().is_empty());
+    });
+}
+
+// NOTE: this test can only be run if there is a local testnet running, so ignoring by default
+#[ignore]
+#[test]
+fn test_get_wallet_balance() {
+    let (mut data_store, mut key_store, mut graph, _temp_dir) = create_test_components();
+
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        let (client, wallet) = create_test_client().await;
+        let pod_manager =
+            create_test_pod_manager(client, &wallet, &mut data_store, &mut key_store, &mut graph)
+                .await;
+
+        // The local testnet key is funded with both tokens and gas
+        let balance = pod_manager.get_wallet_balance().await.unwrap();
+        let token_balance: u128 = balance.token_balance.parse().unwrap();
+        let gas_balance: u128 = balance.gas_balance.parse().unwrap();
+        assert!(token_balance > 0);
+        assert!(gas_balance > 0);
+    });
+}
+
+// NOTE: this test can only be run if there is a local testnet running, so ignoring by default
+#[ignore]
+#[test]
+fn test_put_subjects_data_batch() {
+    let (mut data_store, mut key_store, mut graph, _temp_dir) = create_test_components();
+
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        let (client, wallet) = create_test_client().await;
+        let mut pod_manager =
+            create_test_pod_manager(client, &wallet, &mut data_store, &mut key_store, &mut graph)
+                .await;
+
+        let (pod_address, _) = pod_manager.add_pod("Batch Pod").await.unwrap();
+        let entries: Vec<(String, String)> = (0..100)
+            .map(|i| {
+                let subject_address = format!("{i:064x}");
+                let metadata = serde_json::json!({
+                    "@context": {"schema": "http://schema.org/"},
+                    "@type": "schema:MediaObject",
+                    "schema:name": format!("Batch Subject {i}"),
+                })
+                .to_string();
+                (subject_address, metadata)
+            })
+            .collect();
+        pod_manager
+            .put_subjects_data(&pod_address, entries.clone())
+            .await
+            .unwrap();
+
+        let subjects = pod_manager.list_pod_subjects(&pod_address).unwrap();
+        let scratchpad_data: String = pod_manager
+            .graph
+            .get_pod_scratchpads(&pod_address)
+            .unwrap()
+            .iter()
+            .map(|address| pod_manager.data_store.get_scratchpad_data(address).unwrap())
+            .collect();
+        for (subject_address, _) in &entries {
+            assert!(subjects.contains(subject_address));
+            assert!(scratchpad_data.contains(subject_address.as_str()));
+        }
+
+        // The pod is queued for upload
+        let update_list = pod_manager.data_store.get_update_list().unwrap();
+        assert!(update_list.pods.contains_key(&pod_address));
+    });
+}
+
+// NOTE: this test can only be run if there is a local testnet running, so ignoring by default
+#[ignore]
+#[test]
+fn test_process_pod_data_queues_changed_scratchpads() {
+    let (mut data_store, mut key_store, mut graph, _temp_dir) = create_test_components();
+
+    // Large enough to need several 4MB scratchpads
+    let description = "colonylib ".repeat(1_000_000);
+    let large_subject = "c859818c623ce4fc0899c2ab43061b19caa0b0598eec35ef309dbe50c8af8d59";
+    let small_subject = "a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f90";
+
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        let (client, wallet) = create_test_client().await;
+        let mut pod_manager =
+            create_test_pod_manager(client, &wallet, &mut data_store, &mut key_store, &mut graph)
+                .await;
+
+        let (pod_address, _) = pod_manager.add_pod("Large Pod").await.unwrap();
+        let entries = vec![
+            (
+                large_subject.to_string(),
+                serde_json::json!({
+                    "@context": {"schema": "http://schema.org/"},
+                    "@type": "schema:Dataset",
+                    "schema:description": description,
+                })
+                .to_string(),
+            ),
+            (
+                small_subject.to_string(),
+                serde_json::json!({
+                    "@context": {"schema": "http://schema.org/"},
+                    "@type": "schema:MediaObject",
+                    "schema:name": "Version One",
+                })
+                .to_string(),
+            ),
+        ];
+        pod_manager
+            .put_subjects_data(&pod_address, entries)
+            .await
+            .unwrap();
+        pod_manager.upload_all().await.unwrap();
+
+        let pod_scratchpads = pod_manager.graph.get_pod_scratchpads(&pod_address).unwrap();
+        assert!(
+            pod_scratchpads.len() > 2,
+            "Pod should span several scratchpads"
+        );
+
+        // Edit the small subject without changing the size of its data
+        let metadata = serde_json::json!({
+            "@context": {"schema": "http://schema.org/"},
+            "@type": "schema:MediaObject",
+            "schema:name": "Version Two",
+        })
+        .to_string();
+        pod_manager
+            .put_subject_data(&pod_address, small_subject, &metadata)
+            .await
+            .unwrap();
+
+        // Only the scratchpads whose data changed are queued for upload
+        let update_list = pod_manager.data_store.get_update_list().unwrap();
+        let queued = update_list.pods.get(&pod_address).unwrap();
+        assert!(!queued.is_empty());
+        assert!(queued.len() < pod_scratchpads.len());
+        let holding_subject = pod_scratchpads
+            .iter()
+            .find(|address| {
+                pod_manager
+                    .data_store
+                    .get_scratchpad_data(address)
+                    .unwrap()
+                    .contains("Version Two")
+            })
+            .unwrap();
+        assert!(queued.contains(holding_subject));
+    });
+}
+
+// NOTE: this test can only be run if there is a local testnet running, so ignoring by default
+#[ignore]
+#[test]
+fn test_scratchpad_size_limit() {
+    let (mut data_store, mut key_store, mut graph, _temp_dir) = create_test_components();
+    let subject_address = "c859818c623ce4fc0899c2ab43061b19caa0b0598eec35ef309dbe50c8af8d59";
+
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        let (client, wallet) = create_test_client().await;
+        let mut pod_manager =
+            create_test_pod_manager(client, &wallet, &mut data_store, &mut key_store, &mut graph)
+                .await;
+
+        // Limits below the minimum are clamped
+        pod_manager.set_scratchpad_size_limit(0);
+        assert_eq!(pod_manager.scratchpad_size_limit(), 64);
+
+        pod_manager.set_scratchpad_size_limit(1024);
+        assert_eq!(pod_manager.scratchpad_size_limit(), 1024);
+
+        // The limit is saved with the settings, and a limit of 0 is rejected when restoring them
+        let mut config = pod_manager.get_config();
+        assert_eq!(config.scratchpad_size_limit, 1024);
+        config.scratchpad_size_limit = 0;
+        assert!(pod_manager.apply_config(&config).is_err());
+        assert_eq!(pod_manager.scratchpad_size_limit(), 1024);
+
+        let (pod_address, _) = pod_manager.add_pod("Chunked Pod").await.unwrap();
+
+        // Three properties of 700 bytes each can't share a 1KB scratchpad
+        let metadata = serde_json::json!({
+            "@context": {"schema": "http://schema.org/"},
+            "@type": "schema:Dataset",
+            "schema:alternateName": "a".repeat(700),
+            "schema:description": "d".repeat(700),
+            "schema:headline": "h".repeat(700),
+        })
+        .to_string();
+        pod_manager
+            .put_subject_data(&pod_address, subject_address, &metadata)
+            .await
+            .unwrap();
+
+        let scratchpads = pod_manager.graph.get_pod_scratchpads(&pod_address).unwrap();
+        // Chunks are assigned to scratchpads in index order
+        assert!(
+            scratchpads.len() >= 3,
+            "Expected at least three scratchpads, found {}",
+            scratchpads.len()
+        );
+        for scratchpad in &scratchpads {
+            let data = pod_manager
+                .data_store
+                .get_scratchpad_data(scratchpad)
+                .unwrap();
+            assert!(data.len() <= 1024);
+        }
+    });
+}
+
+// NOTE: this test can only be run if there is a local testnet running, so ignoring by default
+#[ignore]
+#[test]
+fn test_refresh_cache_counter_aware_and_forced() {
+    use colonylib::pod::RefreshMode;
+
+    let (mut data_store, mut key_store, mut graph, _temp_dir) = create_test_components();
+
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        let (client, wallet) = create_test_client().await;
+        let mut pod_manager =
+            create_test_pod_manager(client, &wallet, &mut data_store, &mut key_store, &mut graph)
+                .await;
+
+        let (pod_address, _) = pod_manager.add_pod("Refresh Pod").await.unwrap();
+        pod_manager.upload_all().await.unwrap();
+
+        let scratchpad_address = pod_manager
+            .graph
+            .get_pod_scratchpads(&pod_address)
+            .unwrap()
+            .remove(0);
+        let uploaded = pod_manager
+            .data_store
+            .get_scratchpad_data(&scratchpad_address)
+            .unwrap();
+
+        // Change the cached copy behind the pod manager's back. The pointer counter on the
+        // network is unchanged, so a counter-aware refresh doesn't download the pod again
+        let stale = format!("{uploaded}# stale\n");
+        pod_manager
+            .data_store
+            .update_scratchpad_data(&scratchpad_address, &stale)
+            .unwrap();
+        pod_manager.set_refresh_mode(RefreshMode::CounterAware);
+        pod_manager.refresh_cache(false).await.unwrap();
+        assert_eq!(
+            pod_manager
+                .data_store
+                .get_scratchpad_data(&scratchpad_address)
+                .unwrap(),
+            stale
+        );
+
+        // A forced refresh ignores the counters and restores the network copy
+        pod_manager.refresh_cache(true).await.unwrap();
+        assert_ne!(
+            pod_manager
+                .data_store
+                .get_scratchpad_data(&scratchpad_address)
+                .unwrap(),
+            stale
+        );
+    });
+}
+
+// NOTE: this test can only be run if there is a local testnet running, so ignoring by default
+#[ignore]
+#[test]
+fn test_refresh_ref_cancellation() {
+    use colonylib::KeyStore;
+    use colonylib::pod::RefreshPhase;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    let (mut data_store, mut key_store, mut graph, _temp_dir) = create_test_components();
+    let (mut other_data_store, _, mut other_graph, _other_temp_dir) = create_test_components();
+    let mut other_key_store = KeyStore::from_mnemonic(
+        "legal winner thank year wave sausage worth useful legal winner thank yellow",
+    )
+    .unwrap();
+
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        let (client, wallet) = create_test_client().await;
+
+        // Another user publishes a chain of pods: near -> far
+        let (near_pod, far_pod) = {
+            let mut other_manager = create_test_pod_manager(
+                client.clone(),
+                &wallet,
+                &mut other_data_store,
+                &mut other_key_store,
+                &mut other_graph,
+            )
+            .await;
+            let (far_pod, _) = other_manager.add_pod("Far Pod").await.unwrap();
+            let (near_pod, _) = other_manager.add_pod("Near Pod").await.unwrap();
+            other_manager
+                .add_pod_ref(&near_pod, &far_pod)
+                .await
+                .unwrap();
+            other_manager.upload_all().await.unwrap();
+            (near_pod, far_pod)
+        };
+
+        let mut pod_manager =
+            create_test_pod_manager(client, &wallet, &mut data_store, &mut key_store, &mut graph)
+                .await;
+        let (my_pod, _) = pod_manager.add_pod("My Pod").await.unwrap();
+        pod_manager.add_pod_ref(&my_pod, &near_pod).await.unwrap();
+        pod_manager.upload_all().await.unwrap();
+
+        // Cancel as soon as references start being followed, so only the first batch of
+        // referenced pods is downloaded
+        let token = Arc::new(AtomicBool::new(false));
+        pod_manager.set_cancellation_token(token.clone());
+        let stop = token.clone();
+        pod_manager.set_progress_callback(Box::new(move |progress| {
+            if progress.phase == RefreshPhase::References {
+                stop.store(true, Ordering::SeqCst);
+            }
+        }));
+
+        let error = pod_manager.refresh_ref(0).await.unwrap_err();
+        assert!(error.to_string().contains("cancelled"));
+
+        // The referenced pod was stored, the pod it references was never downloaded
+        assert!(pod_manager.graph.check_pod_exists(&near_pod).is_ok());
+        assert!(pod_manager.graph.check_pod_exists(&far_pod).is_err());
+
+        // The flag is left set, so the next refresh stops right away until it is cleared
+        assert!(token.load(Ordering::SeqCst));
+        pod_manager.clear_progress_callback();
+        assert!(pod_manager.refresh_ref(0).await.is_err());
+        token.store(false, Ordering::SeqCst);
+        pod_manager.refresh_ref(0).await.unwrap();
+        assert!(pod_manager.graph.check_pod_exists(&far_pod).is_ok());
+    });
+}
+
+// NOTE: this test can only be run if there is a local testnet running, so ignoring by default
+#[ignore]
+#[test]
+fn test_discard_all_pending() {
+    let (mut data_store, mut key_store, mut graph, _temp_dir) = create_test_components();
+
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        let (client, wallet) = create_test_client().await;
+        let mut pod_manager =
+            create_test_pod_manager(client, &wallet, &mut data_store, &mut key_store, &mut graph)
+                .await;
+        pod_manager.set_history_depth(2).unwrap();
+
+        let subject_address = format!("{:064x}", 1);
+        let subject = |name: &str| {
+            serde_json::json!({
+                "@context": {"schema": "http://schema.org/"},
+                "@type": "schema:MediaObject",
+                "schema:name": name,
+            })
+            .to_string()
+        };
+        let unsaved_subject_address = format!("{:064x}", 2);
+        let (pod_address, _) = pod_manager.add_pod("Saved Pod").await.unwrap();
+        pod_manager
+            .put_subject_data(&pod_address, &subject_address, &subject("Saved Name"))
+            .await
+            .unwrap();
+        let (unsaved_address, _) = pod_manager.add_pod("Unsaved Pod").await.unwrap();
+        pod_manager
+            .put_subject_data(
+                &unsaved_address,
+                &unsaved_subject_address,
+                &subject("Unsaved Name"),
+            )
+            .await
+            .unwrap();
+        pod_manager.upload_all().await.unwrap();
+
+        // Edit one pod without pod history, so no version of it is saved
+        pod_manager.set_history_depth(0).unwrap();
+        pod_manager
+            .put_subject_data(
+                &unsaved_address,
+                &unsaved_subject_address,
+                &subject("Unsaved Edit"),
+            )
+            .await
+            .unwrap();
+        pod_manager.set_history_depth(2).unwrap();
+
+        // Edit the other uploaded pod and create a pod that is never uploaded
+        pod_manager
+            .put_subject_data(&pod_address, &subject_address, &subject("Edited Name"))
+            .await
+            .unwrap();
+        let (draft_address, _) = pod_manager.add_pod("Draft Pod").await.unwrap();
+
+        // Both pods are restored from the local store, without the network. The pod without a
+        // saved version can only come back from the network, so it is not reverted
+        let reverted = pod_manager.discard_all_pending(false).await.unwrap();
+        assert_eq!(reverted, 2);
+        let subject_data = pod_manager
+            .get_subject_data(&subject_address)
+            .await
+            .unwrap();
+        assert!(subject_data.contains("Saved Name"));
+        assert!(!subject_data.contains("Edited Name"));
+        assert!(
+            !pod_manager
+                .data_store
+                .address_is_pointer(&draft_address)
+                .unwrap()
+        );
+
+        // The pod that was not reverted keeps its pending changes
+        let subject_data = pod_manager
+            .get_subject_data(&unsaved_subject_address)
+            .await
+            .unwrap();
+        assert!(subject_data.contains("Unsaved Edit"));
+        let update_list = pod_manager.data_store.get_update_list().unwrap();
+        assert!(update_list.pods.contains_key(&unsaved_address));
+        assert!(!update_list.pods.contains_key(&pod_address));
+        assert!(!update_list.pods.contains_key(&draft_address));
+        assert!(update_list.remove.pointers.is_empty());
+    });
+}